@@ -0,0 +1,364 @@
+use crate::{
+    algebra_error::{AlgebraError, AlgebraResult},
+    bernstein_basis::BernsteinBasis,
+    efloat::EFloat64,
+    factorial::binomial_coefficient,
+    monomial_polynom::MonomialPolynom,
+};
+
+// Roots are isolated until their enclosures are this narrow.
+const ROOT_PRECISION: f64 = 1e-12;
+// If clipping shrinks an interval to less than this fraction, it is clipped again instead of split.
+const CLIP_RATIO: f64 = 0.5;
+
+// Polynomial sum_i c_i * B_i,n(t) in Bernstein form, where B_i,n are the Bernstein basis polynomials of degree n on [0, 1].
+#[derive(Debug, Clone)]
+pub struct BernsteinPolynomial {
+    pub coefficients: Vec<EFloat64>,
+}
+
+// Sign of an interval, or 0 if it contains zero.
+fn sign(x: EFloat64) -> i32 {
+    if x.lower_bound > 0.0 {
+        1
+    } else if x.upper_bound < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+// Part [lo, hi] of [0, 1] where the convex hull of the control points (i / n, c_i) meets zero, or None if it does not.
+// The hull meets zero between its leftmost and rightmost crossing, which are on edges between vertices of opposite sign.
+fn clip(coefficients: &[EFloat64]) -> Option<(f64, f64)> {
+    let n = coefficients.len() - 1;
+    let t = |i: usize| EFloat64::from(i as f64 / n as f64);
+    let mut lo = f64::INFINITY;
+    let mut hi = f64::NEG_INFINITY;
+    for (i, c) in coefficients.iter().enumerate() {
+        let s = sign(*c);
+        if s == 0 {
+            lo = lo.min(t(i).lower_bound);
+            hi = hi.max(t(i).upper_bound);
+            continue;
+        }
+        for (j, d) in coefficients.iter().enumerate() {
+            if sign(*d) != -s {
+                continue;
+            }
+            // The edge from i to j crosses zero at t_i + (t_j - t_i) * c_i / (c_i - c_j).
+            let x = t(i) + (t(j) - t(i)) * (*c / (*c - *d)).unwrap();
+            lo = lo.min(x.lower_bound);
+            hi = hi.max(x.upper_bound);
+        }
+    }
+    match lo <= hi {
+        true => Some((lo.max(0.0), hi.min(1.0))),
+        false => None,
+    }
+}
+
+// Sorts the enclosures and merges the ones that overlap.
+fn merge(mut roots: Vec<EFloat64>) -> Vec<EFloat64> {
+    roots.sort_by(|a, b| a.lower_bound.total_cmp(&b.lower_bound));
+    let mut result = Vec::<EFloat64>::new();
+    for root in roots {
+        match result.last_mut() {
+            Some(last) if root.lower_bound <= last.upper_bound => {
+                *last = EFloat64::new(last.upper_bound.max(root.upper_bound), last.lower_bound);
+            }
+            _ => result.push(root),
+        }
+    }
+    result
+}
+
+impl BernsteinPolynomial {
+    pub fn new(coefficients: Vec<EFloat64>) -> Self {
+        assert!(!coefficients.is_empty(), "Polynomial needs a coefficient");
+        Self { coefficients }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    // Evaluates the polynomial with the de Casteljau algorithm.
+    pub fn eval(&self, t: EFloat64) -> EFloat64 {
+        self.blossom(&vec![t; self.degree()])
+    }
+
+    // Blossom of the polynomial, i.e. de Casteljau with a different parameter in every step. Needs degree many parameters.
+    fn blossom(&self, ts: &[EFloat64]) -> EFloat64 {
+        let mut b = self.coefficients.clone();
+        for (k, t) in ts.iter().enumerate() {
+            for i in 0..b.len() - k - 1 {
+                b[i] = (EFloat64::one() - *t) * b[i] + *t * b[i + 1];
+            }
+        }
+        b[0]
+    }
+
+    // The same polynomial with one more coefficient.
+    pub fn elevate_degree(&self) -> Self {
+        let n = self.degree() + 1;
+        let c = &self.coefficients;
+        let coefficients = (0..=n)
+            .map(|i| {
+                let alpha = EFloat64::from(i as f64 / n as f64);
+                match i {
+                    0 => c[0],
+                    _ if i == n => c[n - 1],
+                    _ => alpha * c[i - 1] + (EFloat64::one() - alpha) * c[i],
+                }
+            })
+            .collect();
+        Self::new(coefficients)
+    }
+
+    // Splits the polynomial at t into the polynomials on [0, t] and [t, 1], both reparameterized to [0, 1].
+    pub fn subdivide(&self, t: EFloat64) -> (Self, Self) {
+        let n = self.degree();
+        let mut b = self.coefficients.clone();
+        let mut left = vec![b[0]];
+        let mut right = vec![b[n]];
+        for k in 1..=n {
+            for i in 0..=n - k {
+                b[i] = (EFloat64::one() - t) * b[i] + t * b[i + 1];
+            }
+            left.push(b[0]);
+            right.push(b[n - k]);
+        }
+        right.reverse();
+        (Self::new(left), Self::new(right))
+    }
+
+    // The polynomial on [start, end], reparameterized to [0, 1].
+    pub fn restrict(&self, start: EFloat64, end: EFloat64) -> Self {
+        let n = self.degree();
+        let coefficients = (0..=n)
+            .map(|i| {
+                let mut ts = vec![start; n - i];
+                ts.extend(vec![end; i]);
+                self.blossom(&ts)
+            })
+            .collect();
+        Self::new(coefficients)
+    }
+
+    pub fn derivative(&self) -> Self {
+        let n = self.degree();
+        if n == 0 {
+            return Self::new(vec![EFloat64::zero()]);
+        }
+        let factor = EFloat64::from(n as f64);
+        Self::new(
+            self.coefficients
+                .windows(2)
+                .map(|c| (c[1] - c[0]) * factor)
+                .collect(),
+        )
+    }
+
+    pub fn to_monomial_polynom(&self) -> MonomialPolynom {
+        let n = self.degree();
+        let mut result = MonomialPolynom::new(vec![]);
+        for (i, c) in self.coefficients.iter().enumerate() {
+            result = &result + &(&BernsteinBasis::new(i, n).to_monomial_polynom() * *c);
+        }
+        result
+    }
+
+    // Bernstein form of the polynomial on [0, 1], with the degree of the polynomial.
+    pub fn from_monomial_polynom(polynom: &MonomialPolynom) -> Self {
+        let a = &polynom.monomials;
+        if a.is_empty() {
+            return Self::new(vec![EFloat64::zero()]);
+        }
+        let n = a.len() - 1;
+        // b_k = sum_{i <= k} (k over i) / (n over i) * a_i
+        let coefficients = (0..=n)
+            .map(|k| {
+                let mut b = EFloat64::zero();
+                for (i, a) in a.iter().enumerate().take(k + 1) {
+                    let factor = (EFloat64::from(binomial_coefficient(k, i) as f64)
+                        / EFloat64::from(binomial_coefficient(n, i) as f64))
+                    .unwrap();
+                    b = b + factor * *a;
+                }
+                b
+            })
+            .collect();
+        Self::new(coefficients)
+    }
+
+    // Finds enclosures of all roots in [0, 1]. Every root is contained in exactly one of the returned, disjoint enclosures.
+    pub fn roots(&self) -> AlgebraResult<Vec<EFloat64>> {
+        self.roots_in(0.0, 1.0)
+    }
+
+    // Finds enclosures of all roots in [start, end], which has to be part of [0, 1].
+    // Roots are isolated by Bezier clipping: only the part of the interval where the convex hull of the coefficients contains zero can contain roots.
+    // If clipping does not shrink the interval enough, e.g. because it contains several roots, it is split in half.
+    pub fn roots_in(&self, start: f64, end: f64) -> AlgebraResult<Vec<EFloat64>> {
+        if !(0.0 <= start && start <= end && end <= 1.0) {
+            return Err(AlgebraError::new(format!(
+                "Interval [{}, {}] is not part of [0, 1]",
+                start, end
+            )));
+        }
+        if self
+            .coefficients
+            .iter()
+            .all(|c| c.lower_bound == 0.0 && c.upper_bound == 0.0)
+        {
+            return Err(AlgebraError::new(
+                "Zero polynomial has infinitely many roots".to_string(),
+            ));
+        }
+        let mut roots = Vec::new();
+        self.isolate(start, end, &mut roots);
+        Ok(merge(roots))
+    }
+
+    fn isolate(&self, start: f64, end: f64, roots: &mut Vec<EFloat64>) {
+        let part = self.restrict(EFloat64::from(start), EFloat64::from(end));
+        if part.degree() == 0 {
+            if sign(part.coefficients[0]) == 0 {
+                roots.push(EFloat64::new(end, start));
+            }
+            return;
+        }
+        let Some((lo, hi)) = clip(&part.coefficients) else {
+            return;
+        };
+        let (a, b) = (EFloat64::from(start), EFloat64::from(end));
+        let lo = (a + (b - a) * EFloat64::from(lo)).lower_bound.max(start);
+        let hi = (a + (b - a) * EFloat64::from(hi)).upper_bound.min(end);
+        // The interval cannot be narrowed further if all coefficients are too close to zero.
+        let undecidable = part.coefficients.iter().all(|c| sign(*c) == 0);
+        if undecidable {
+            roots.push(EFloat64::new(hi, lo));
+            return;
+        }
+        // The hull can touch zero where the polynomial does not, e.g. at a zero coefficient.
+        if hi - lo <= ROOT_PRECISION {
+            let t = EFloat64::new(hi, lo);
+            if sign(self.eval(t)) == 0 {
+                roots.push(t);
+            }
+            return;
+        }
+        if hi - lo <= CLIP_RATIO * (end - start) {
+            self.isolate(lo, hi, roots);
+            return;
+        }
+        let mid = (lo + hi) / 2.0;
+        self.isolate(lo, mid, roots);
+        self.isolate(mid, hi, roots);
+    }
+}
+
+impl PartialEq for BernsteinPolynomial {
+    fn eq(&self, other: &Self) -> bool {
+        self.coefficients.len() == other.coefficients.len()
+            && self
+                .coefficients
+                .iter()
+                .zip(other.coefficients.iter())
+                .all(|(a, b)| a == b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (t - 0.2) (t - 0.5) (t - 0.7)
+    fn cubic() -> MonomialPolynom {
+        MonomialPolynom::new(vec![
+            EFloat64::from(-0.07),
+            EFloat64::from(0.59),
+            EFloat64::from(-1.4),
+            EFloat64::one(),
+        ])
+    }
+
+    #[test]
+    fn test_bernstein_polynomial_conversion() {
+        let monomial = cubic();
+        let bernstein = BernsteinPolynomial::from_monomial_polynom(&monomial);
+        assert_eq!(bernstein.degree(), 3);
+        for t in [0.0, 0.15, 0.43456, 0.6373, 1.0] {
+            let t = EFloat64::from(t);
+            assert_eq!(bernstein.eval(t), monomial.eval(t));
+            assert_eq!(bernstein.elevate_degree().eval(t), monomial.eval(t));
+        }
+        assert_eq!(bernstein.to_monomial_polynom(), monomial);
+        assert_eq!(
+            bernstein.derivative().to_monomial_polynom(),
+            MonomialPolynom::new(vec![
+                EFloat64::from(0.59),
+                EFloat64::from(-2.8),
+                EFloat64::from(3.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_bernstein_polynomial_subdivide() {
+        let bernstein = BernsteinPolynomial::from_monomial_polynom(&cubic());
+        let (left, right) = bernstein.subdivide(EFloat64::from(0.25));
+        let restricted = bernstein.restrict(EFloat64::from(0.25), EFloat64::one());
+        assert_eq!(right, restricted);
+        for t in [0.0, 0.3, 1.0] {
+            let s = EFloat64::from(t);
+            assert_eq!(left.eval(s), bernstein.eval(EFloat64::from(0.25 * t)));
+            assert_eq!(
+                right.eval(s),
+                bernstein.eval(EFloat64::from(0.25 + 0.75 * t))
+            );
+        }
+    }
+
+    #[test]
+    fn test_bernstein_polynomial_roots() {
+        let bernstein = BernsteinPolynomial::from_monomial_polynom(&cubic());
+        let roots = bernstein.roots().unwrap();
+        assert_eq!(roots.len(), 3);
+        for (root, expected) in roots.iter().zip([0.2, 0.5, 0.7]) {
+            assert!(*root == expected);
+            assert!(root.upper_bound - root.lower_bound < 1e-9);
+        }
+        assert_eq!(bernstein.roots_in(0.3, 0.6).unwrap().len(), 1);
+        assert!(bernstein.roots_in(0.8, 1.0).unwrap().is_empty());
+
+        // A double root at 0.5 and no root at all.
+        let square = MonomialPolynom::new(vec![
+            EFloat64::from(0.25),
+            EFloat64::from(-1.0),
+            EFloat64::one(),
+        ]);
+        let roots = BernsteinPolynomial::from_monomial_polynom(&square)
+            .roots()
+            .unwrap();
+        assert_eq!(roots.len(), 1);
+        assert!(roots[0] == 0.5);
+        let positive = &square + &MonomialPolynom::new(vec![EFloat64::from(0.01)]);
+        assert!(BernsteinPolynomial::from_monomial_polynom(&positive)
+            .roots()
+            .unwrap()
+            .is_empty());
+        // A zero coefficient does not make a root.
+        let bernstein = BernsteinPolynomial::new(vec![
+            EFloat64::from(-5.0),
+            EFloat64::zero(),
+            -EFloat64::one(),
+        ]);
+        assert!(bernstein.roots().unwrap().is_empty());
+
+        assert!(BernsteinPolynomial::new(vec![EFloat64::zero(); 3])
+            .roots()
+            .is_err());
+    }
+}
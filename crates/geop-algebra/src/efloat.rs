@@ -15,6 +15,29 @@ pub struct EFloat64 {
 }
 
 const TWO_PI: f64 = 2.0 * PI;
+// Error of the transcendental functions of the standard library in ulps, with a margin.
+const ULPS: usize = 2;
+
+// Moves x up by the given number of ulps.
+fn up(x: f64, ulps: usize) -> f64 {
+    (0..ulps).fold(x, |x, _| x.next_after(f64::INFINITY))
+}
+
+// Moves x down by the given number of ulps.
+fn down(x: f64, ulps: usize) -> f64 {
+    (0..ulps).fold(x, |x, _| x.next_after(f64::NEG_INFINITY))
+}
+
+// Whether [lower, upper] might contain a + 2 k PI for some integer k.
+// The check is padded relative to the magnitude of the bounds, because 2 k PI is not exact for large k.
+fn contains_periodic(lower: f64, upper: f64, a: f64) -> bool {
+    let slack = 4.0 * f64::EPSILON * lower.abs().max(upper.abs()).max(1.0);
+    let k = ((lower - a) / TWO_PI).floor();
+    (0..3).any(|i| {
+        let x = a + (k + i as f64) * TWO_PI;
+        lower - slack <= x && x <= upper + slack
+    })
+}
 
 impl EFloat64 {
     pub fn new(upper_bound: f64, lower_bound: f64) -> Self {
@@ -50,113 +73,153 @@ impl EFloat64 {
         EFloat64::new(2.0, 2.0)
     }
 
+    // Midpoint of the interval. Iterative methods continue from it, so that the intervals do not grow with every step.
+    pub fn mid(&self) -> f64 {
+        (self.upper_bound + self.lower_bound) / 2.0
+    }
+
+    // The constants of the standard library are rounded down, so the intervals reach up by one ulp.
     pub fn half_pi() -> Self {
-        EFloat64::new(std::f64::consts::FRAC_PI_2, std::f64::consts::FRAC_PI_2)
+        EFloat64::new(up(PI / 2.0, 1), PI / 2.0)
     }
 
     pub fn pi() -> Self {
-        EFloat64::new(PI, PI)
+        EFloat64::new(up(PI, 1), PI)
     }
 
     pub fn two_pi() -> Self {
-        EFloat64::new(2.0 * PI, 2.0 * PI)
+        EFloat64::new(up(TWO_PI, 1), TWO_PI)
     }
 
     pub fn sqrt(&self) -> Option<Self> {
         if self.upper_bound < 0.0 {
             return None;
         }
+        // sqrt is correctly rounded, so one ulp is enough.
         Some(EFloat64::new(
-            self.upper_bound.sqrt() + 1E-15,
-            self.lower_bound.max(0.0).sqrt() - 1E-15,
+            up(self.upper_bound.sqrt(), 1),
+            down(self.lower_bound.max(0.0).sqrt(), 1).max(0.0),
         ))
     }
 
-    // TODO: Add extremal values if max or min values are in the range.
     pub fn sin(&self) -> Self {
-        let upper_bound = ((self.upper_bound % TWO_PI) + TWO_PI) % TWO_PI;
-        let mut lower_bound = ((self.lower_bound % TWO_PI) + TWO_PI) % TWO_PI;
-        if lower_bound > upper_bound {
-            lower_bound -= TWO_PI;
-        }
-        let s_u = self.upper_bound.sin();
-        let s_l = self.lower_bound.sin();
+        // Maxima are at PI / 2 + 2 k PI and minima at 3 PI / 2 + 2 k PI.
+        self.periodic(f64::sin, PI / 2.0, 3.0 * PI / 2.0)
+    }
+
+    pub fn cos(&self) -> Self {
+        // Maxima are at 2 k PI and minima at PI + 2 k PI.
+        self.periodic(f64::cos, 0.0, PI)
+    }
 
-        // Check if PI/2 and -PI/2 are in the range.
-        if lower_bound <= -PI / 2.0 && upper_bound >= PI / 2.0 {
+    // Encloses a function with period 2 PI, which is monotonic between its maximum and minimum of 1 and -1.
+    fn periodic(&self, f: fn(f64) -> f64, maximum: f64, minimum: f64) -> Self {
+        if self.upper_bound - self.lower_bound >= TWO_PI {
             return EFloat64::new(1.0, -1.0);
         }
+        let (a, b) = (f(self.lower_bound), f(self.upper_bound));
+        let upper_bound = match contains_periodic(self.lower_bound, self.upper_bound, maximum) {
+            true => 1.0,
+            false => up(a.max(b), ULPS).min(1.0),
+        };
+        let lower_bound = match contains_periodic(self.lower_bound, self.upper_bound, minimum) {
+            true => -1.0,
+            false => down(a.min(b), ULPS).max(-1.0),
+        };
+        EFloat64::new(upper_bound, lower_bound)
+    }
 
-        // PI/2 is in the range.
-        if lower_bound <= PI / 2.0 && upper_bound >= PI / 2.0 {
-            return EFloat64::new(1.0, s_u.min(s_l) - 1E-15);
-        }
-        // Check if 3PI/2 is in the range.
-        if lower_bound <= 3.0 * PI / 2.0 && upper_bound >= 3.0 * PI / 2.0 {
-            return EFloat64::new(s_u.max(s_l) + 1E-15, -1.0);
-        }
-        // Check if -PI/2 is in the range.
-        if lower_bound <= -PI / 2.0 && upper_bound >= -PI / 2.0 {
-            return EFloat64::new(1.0, s_u.min(s_l) - 1E-15);
-        }
-        // Check if -3PI/2 is in the range.
-        if lower_bound <= -3.0 * PI / 2.0 && upper_bound >= -3.0 * PI / 2.0 {
-            return EFloat64::new(s_u.max(s_l) + 1E-15, -1.0);
+    // Fails if the interval contains a pole at PI / 2 + k PI.
+    pub fn tan(&self) -> Option<Self> {
+        let (l, u) = (self.lower_bound, self.upper_bound);
+        if u - l >= PI || contains_periodic(l, u, PI / 2.0) || contains_periodic(l, u, -PI / 2.0) {
+            return None;
         }
+        Some(EFloat64::new(up(u.tan(), ULPS), down(l.tan(), ULPS)))
+    }
 
-        EFloat64::new(s_u.max(s_l) + 1E-15, s_u.min(s_l) - 1E-15)
+    // Values outside of [-1, 1] are ignored, since they are usually caused by rounding errors. Fails if the whole interval is outside.
+    pub fn asin(&self) -> Option<Self> {
+        let (l, u) = self.clamp_unit()?;
+        Some(EFloat64::new(
+            up(u.asin(), ULPS).min(up(PI / 2.0, 1)),
+            down(l.asin(), ULPS).max(down(-PI / 2.0, 1)),
+        ))
     }
 
-    pub fn cos(&self) -> Self {
-        let upper_bound = ((self.upper_bound % TWO_PI) + TWO_PI) % TWO_PI;
-        let mut lower_bound = ((self.lower_bound % TWO_PI) + TWO_PI) % TWO_PI;
-        if lower_bound > upper_bound {
-            lower_bound -= TWO_PI;
-        }
+    // Values outside of [-1, 1] are ignored, since they are usually caused by rounding errors. Fails if the whole interval is outside.
+    pub fn acos(&self) -> Option<Self> {
+        let (l, u) = self.clamp_unit()?;
+        Some(EFloat64::new(
+            up(l.acos(), ULPS).min(up(PI, 1)),
+            down(u.acos(), ULPS).max(0.0),
+        ))
+    }
 
-        let s_u = self.upper_bound.cos();
-        let s_l = self.lower_bound.cos();
-        // Check if 0 and PI are in the range.
-        if lower_bound <= 0.0 && upper_bound >= PI {
-            return EFloat64::new(1.0, -1.0);
-        }
-        // Check if 0 an -PI are in the range.
-        if lower_bound <= -PI && upper_bound >= 0.0 {
-            return EFloat64::new(1.0, -1.0);
+    fn clamp_unit(&self) -> Option<(f64, f64)> {
+        if self.upper_bound < -1.0 || self.lower_bound > 1.0 {
+            return None;
         }
+        Some((self.lower_bound.max(-1.0), self.upper_bound.min(1.0)))
+    }
 
-        // Check if 0 is in the range.
-        if lower_bound <= 0.0 && upper_bound >= 0.0 {
-            return EFloat64::new(1.0, s_u.min(s_l) - 1E-15);
-        }
-        // Check if PI is in the range.
-        if lower_bound <= PI && upper_bound >= PI {
-            return EFloat64::new(s_u.max(s_l) + 1E-15, -1.0);
+    pub fn atan(&self) -> Self {
+        EFloat64::new(
+            up(self.upper_bound.atan(), ULPS),
+            down(self.lower_bound.atan(), ULPS),
+        )
+    }
+
+    // Overflows to an infinite upper bound like powu, while the lower bound stays finite.
+    pub fn exp(&self) -> Self {
+        Self {
+            upper_bound: up(self.upper_bound.exp(), ULPS),
+            lower_bound: down(self.lower_bound.exp(), ULPS).clamp(0.0, f64::MAX),
         }
-        // Check if -PI is in the range.
-        if lower_bound <= -PI && upper_bound >= -PI {
-            return EFloat64::new(s_u.max(s_l) + 1E-15, -1.0);
+    }
+
+    // Fails if the interval contains numbers that are not positive.
+    pub fn ln(&self) -> Option<Self> {
+        if self.lower_bound <= 0.0 {
+            return None;
         }
+        Some(EFloat64::new(
+            up(self.upper_bound.ln(), ULPS),
+            down(self.lower_bound.ln(), ULPS),
+        ))
+    }
 
-        EFloat64::new(s_u.max(s_l) + 1E-15, s_u.min(s_l) - 1E-15)
+    // Overflows to infinite bounds like exp.
+    pub fn sinh(&self) -> Self {
+        Self {
+            upper_bound: up(self.upper_bound.sinh(), ULPS).max(f64::MIN),
+            lower_bound: down(self.lower_bound.sinh(), ULPS).min(f64::MAX),
+        }
     }
 
-    pub fn acos(&self) -> Self {
-        let s_u = self.upper_bound.acos();
-        let s_l = self.lower_bound.acos();
-        EFloat64::new(s_u.max(s_l) + 1E-15, s_u.min(s_l) - 1E-15)
+    // Overflows to an infinite upper bound like exp.
+    pub fn cosh(&self) -> Self {
+        let abs = self.abs();
+        Self {
+            upper_bound: up(abs.upper_bound.cosh(), ULPS),
+            lower_bound: down(abs.lower_bound.cosh(), ULPS).clamp(1.0, f64::MAX),
+        }
     }
 
     pub fn square(&self) -> EFloat64 {
-        let s_u = self.upper_bound * self.upper_bound;
-        let s_l = self.lower_bound * self.lower_bound;
+        let abs = self.abs();
         EFloat64::new(
-            s_u.max(s_l).next_after(f64::INFINITY),
-            s_u.min(s_l).next_after(f64::NEG_INFINITY),
+            up(abs.upper_bound * abs.upper_bound, 1),
+            down(abs.lower_bound * abs.lower_bound, 1).max(0.0),
         )
     }
 
     pub fn atan2(&self, x: EFloat64) -> EFloat64 {
+        // Around the origin, every angle is possible.
+        if *self == 0.0 && x == 0.0 {
+            return EFloat64::new(up(PI, 1), down(-PI, 1));
+        }
+        // The extremal angles of a box are at its corners.
         let a1 = self.lower_bound.atan2(x.lower_bound);
         let a2 = self.lower_bound.atan2(x.upper_bound);
         let a3 = self.upper_bound.atan2(x.lower_bound);
@@ -167,7 +230,7 @@ impl EFloat64 {
 
         // This is for the case that atan2 wraps around.
         if upper_bound - lower_bound <= PI {
-            return EFloat64::new(upper_bound, lower_bound);
+            return EFloat64::new(up(upper_bound, ULPS), down(lower_bound, ULPS));
         }
 
         let a1 = (a1 + TWO_PI) % TWO_PI;
@@ -176,8 +239,8 @@ impl EFloat64 {
         let a4 = (a4 + TWO_PI) % TWO_PI;
 
         EFloat64::new(
-            a1.max(a2).max(a3).max(a4) + 1E-15,
-            a1.min(a2).min(a3).min(a4) - 1E-15,
+            up(a1.max(a2).max(a3).max(a4), ULPS + 2),
+            down(a1.min(a2).min(a3).min(a4), ULPS + 2),
         )
     }
 
@@ -189,22 +252,48 @@ impl EFloat64 {
             return -*self;
         }
         Self {
-            upper_bound: (-self.lower_bound)
-                .max(self.upper_bound)
-                .next_after(f64::INFINITY),
+            upper_bound: (-self.lower_bound).max(self.upper_bound),
             lower_bound: 0.0,
         }
     }
 
+    // Panics for negative powers of intervals that contain zero.
     pub fn powi(&self, n: i32) -> Self {
-        let s_u = self.upper_bound.powi(n);
-        let s_l = self.lower_bound.powi(n);
+        let power = self.powu(n.unsigned_abs());
+        match n < 0 {
+            true => (EFloat64::one() / power).expect("Negative power of zero"),
+            false => power,
+        }
+    }
+
+    fn powu(&self, n: u32) -> Self {
+        let (l, u) = match n % 2 {
+            0 => (self.abs().lower_bound, self.abs().upper_bound),
+            _ => (self.lower_bound, self.upper_bound),
+        };
+        let pow = |x: f64| match i32::try_from(n) {
+            Ok(n) => x.powi(n),
+            Err(_) => x.powf(n as f64),
+        };
+        // powi multiplies repeatedly, so the relative error grows with n.
+        let error = (n as f64 + 1.0) * f64::EPSILON;
+        // Powers that overflow give an infinite outer bound, while the inner bound stays finite.
+        let (lower, upper) = (pow(l).min(f64::MAX), pow(u).max(f64::MIN));
+        let lower_bound = (lower - lower.abs() * error).next_after(f64::NEG_INFINITY);
         Self {
-            upper_bound: s_u.next_after(f64::INFINITY),
-            lower_bound: s_l.next_after(f64::NEG_INFINITY),
+            upper_bound: (upper + upper.abs() * error).next_after(f64::INFINITY),
+            lower_bound: match n % 2 {
+                0 => lower_bound.max(0.0),
+                _ => lower_bound,
+            },
         }
     }
 
+    // Real powers of positive numbers. Fails if the interval contains numbers that are not positive.
+    pub fn powf(&self, exponent: EFloat64) -> Option<Self> {
+        Some((self.ln()? * exponent).exp())
+    }
+
     pub fn max(self, other: Self) -> Self {
         Self {
             upper_bound: self.upper_bound.max(other.upper_bound),
@@ -329,7 +418,7 @@ impl Display for EFloat64 {
         //     (self.upper_bound + self.lower_bound) / 2.0,
         //     (self.upper_bound - self.lower_bound) / 2.0
         // )
-        write!(f, "{:.2e}", (self.upper_bound + self.lower_bound) / 2.0)
+        write!(f, "{:.2e}", self.mid())
     }
 }
 
@@ -352,4 +441,59 @@ mod tests {
         let b = EFloat64::from(8.0);
         assert!((b.sqrt().unwrap() * a.sqrt().unwrap()) == 4.0);
     }
+
+    #[test]
+    fn test_efloat_trigonometry() {
+        // Extremal values inside the interval.
+        let sin = EFloat64::new(2.0, 1.0).sin();
+        assert_eq!(sin.upper_bound, 1.0);
+        assert!(sin == 1.0_f64.sin() && sin > 0.84);
+        let cos = EFloat64::new(4.0, 3.0).cos();
+        assert_eq!(cos.lower_bound, -1.0);
+        // Large arguments keep valid enclosures.
+        let x = 1e10;
+        assert!(EFloat64::from(x).sin() == x.sin());
+        assert!(EFloat64::pi().sin() == 0.0);
+        assert!(EFloat64::half_pi().cos() == 0.0);
+        assert!(EFloat64::new(1.6, 1.5).tan().is_none());
+        assert!(EFloat64::from(1.0).tan().unwrap() == 1.0_f64.tan());
+        assert!(EFloat64::from(1.0).atan() * EFloat64::from(4.0) == PI);
+        assert!(EFloat64::from(1.0 + 1e-16).acos().unwrap() == 0.0);
+        assert!(EFloat64::from(0.5).asin().unwrap() == PI / 6.0);
+        assert!(EFloat64::from(2.0).asin().is_none());
+        assert!(EFloat64::new(-1.5, -2.0).acos().is_none());
+    }
+
+    #[test]
+    fn test_efloat_exponential() {
+        let x = EFloat64::from(1e5);
+        assert!(x.ln().unwrap().exp() == 1e5);
+        assert!(EFloat64::zero().ln().is_none());
+        assert!(EFloat64::from(2.0).powf(EFloat64::from(0.5)).unwrap() == 2.0_f64.sqrt());
+        assert!(EFloat64::from(3.0).cosh().square() - EFloat64::from(3.0).sinh().square() == 1.0);
+        let square = EFloat64::new(1.0, -2.0).powi(2);
+        assert_eq!(square.lower_bound, 0.0);
+        assert!(square == 4.0);
+        assert!(EFloat64::from(2.0).powi(-2) == 0.25);
+        assert!(EFloat64::from(1.0).powi(i32::MIN) == 1.0);
+        assert!(EFloat64::from(-1.0).powi(i32::MIN) == 1.0);
+        assert!(EFloat64::from(1.0 + 1e-9).powi(1 << 20) == (1.0 + 1e-9_f64).powi(1 << 20));
+    }
+
+    #[test]
+    fn test_efloat_overflow() {
+        // Results beyond f64::MAX get an infinite bound, the other bound stays finite.
+        let x = EFloat64::from(711.0);
+        for y in [x.exp(), x.cosh(), x.sinh(), x.powi(200)] {
+            assert_eq!(y.upper_bound, f64::INFINITY);
+            assert!(y.lower_bound.is_finite() && y.lower_bound > 1e300);
+        }
+        let y = (-x).sinh();
+        assert_eq!(y.lower_bound, f64::NEG_INFINITY);
+        assert!(y.upper_bound.is_finite() && y.upper_bound < -1e300);
+        // Only the part of the interval that overflows is lost.
+        let y = EFloat64::new(711.0, 1.0).exp();
+        assert!(y.lower_bound <= 1.0_f64.exp() && y.lower_bound > 2.7);
+        assert!((-x).exp() >= 0.0);
+    }
 }
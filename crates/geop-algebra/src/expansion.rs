@@ -0,0 +1,135 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+// a + b = s + e exactly, see Shewchuk, "Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates".
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let b_virtual = s - a;
+    let a_virtual = s - b_virtual;
+    (s, (a - a_virtual) + (b - b_virtual))
+}
+
+// a * b = p + e exactly.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+// Exact sum of floating point numbers, which do not overlap and are sorted by increasing magnitude.
+// Sums, differences and products of expansions are exact, as long as nothing overflows or underflows.
+#[derive(Debug, Clone)]
+pub struct Expansion {
+    pub components: Vec<f64>,
+}
+
+impl Expansion {
+    pub fn from(value: f64) -> Self {
+        assert!(value.is_finite());
+        Self {
+            components: vec![value],
+        }
+        .compress()
+    }
+
+    pub fn zero() -> Self {
+        Self { components: vec![] }
+    }
+
+    fn compress(mut self) -> Self {
+        self.components.retain(|c| *c != 0.0);
+        self
+    }
+
+    // Adds a number, keeping the components non overlapping.
+    fn grow(&self, b: f64) -> Self {
+        let mut q = b;
+        let mut components = Vec::with_capacity(self.components.len() + 1);
+        for e in self.components.iter() {
+            let (sum, error) = two_sum(q, *e);
+            components.push(error);
+            q = sum;
+        }
+        components.push(q);
+        Self { components }.compress()
+    }
+
+    // Sign of the exact value, which is the sign of the largest component.
+    pub fn sign(&self) -> Ordering {
+        match self.components.last() {
+            Some(c) => c.total_cmp(&0.0),
+            None => Ordering::Equal,
+        }
+    }
+
+    // Approximation of the exact value.
+    pub fn estimate(&self) -> f64 {
+        self.components.iter().sum()
+    }
+}
+
+impl Neg for &Expansion {
+    type Output = Expansion;
+
+    fn neg(self) -> Expansion {
+        Expansion {
+            components: self.components.iter().map(|c| -c).collect(),
+        }
+    }
+}
+
+impl Add for &Expansion {
+    type Output = Expansion;
+
+    fn add(self, other: &Expansion) -> Expansion {
+        other
+            .components
+            .iter()
+            .fold(self.clone(), |sum, c| sum.grow(*c))
+    }
+}
+
+impl Sub for &Expansion {
+    type Output = Expansion;
+
+    fn sub(self, other: &Expansion) -> Expansion {
+        self + &(-other)
+    }
+}
+
+impl Mul for &Expansion {
+    type Output = Expansion;
+
+    fn mul(self, other: &Expansion) -> Expansion {
+        let mut result = Expansion::zero();
+        for a in self.components.iter() {
+            for b in other.components.iter() {
+                let (p, e) = two_product(*a, *b);
+                result = result.grow(e).grow(p);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expansion() {
+        // (1 + 1e-30) - 1 is not zero.
+        let a = &Expansion::from(1.0) + &Expansion::from(1e-30);
+        let b = &a - &Expansion::from(1.0);
+        assert_eq!(b.sign(), Ordering::Greater);
+        assert_eq!(b.estimate(), 1e-30);
+        // (a + b)^2 - a^2 - 2ab - b^2 = 0 for numbers where the floating point evaluation does not cancel.
+        let (x, y) = (Expansion::from(0.1), Expansion::from(1e17 + 2.0));
+        let sum = &x + &y;
+        let square = &sum * &sum;
+        let two = Expansion::from(2.0);
+        let rest = &(&(&square - &(&x * &x)) - &(&two * &(&x * &y))) - &(&y * &y);
+        assert_eq!(rest.sign(), Ordering::Equal);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{efloat::EFloat64, matrix::Matrix};
+
+// Boxes that are processed before the remaining ones are given up as possible roots.
+const MAX_BOXES: usize = 10000;
+// A Krawczyk step that shrinks the box less than this fraction is followed by bisection.
+const MIN_SHRINK: f64 = 0.25;
+
+fn width(x: &[EFloat64]) -> f64 {
+    x.iter()
+        .map(|x| x.upper_bound - x.lower_bound)
+        .fold(0.0, f64::max)
+}
+
+fn intersect(a: EFloat64, b: EFloat64) -> Option<EFloat64> {
+    let lower_bound = a.lower_bound.max(b.lower_bound);
+    let upper_bound = a.upper_bound.min(b.upper_bound);
+    match lower_bound <= upper_bound {
+        true => Some(EFloat64::new(upper_bound, lower_bound)),
+        false => None,
+    }
+}
+
+// Result of solving f(x) = 0 on a box. Every root in the domain lies in one of the unique or possible boxes.
+// The empty boxes are only the parts of the domain that were discarded as a whole. Parts cut away by the Krawczyk operator are not recorded.
+#[derive(Debug, Clone, Default)]
+pub struct IntervalNewtonSolution {
+    // Boxes that contain exactly one root each.
+    pub unique: Vec<Vec<EFloat64>>,
+    // Boxes that might contain roots, but are too small to be decided, e.g. around multiple roots.
+    pub possible: Vec<Vec<EFloat64>>,
+    // Boxes that contain no roots.
+    pub empty: Vec<Vec<EFloat64>>,
+}
+
+enum Krawczyk {
+    Empty,
+    Unique(Vec<EFloat64>),
+    Shrunk(Vec<EFloat64>),
+    Singular,
+}
+
+// K(X) = m - Y f(m) + (I - Y J(X)) (X - m), where m is the midpoint of X and Y approximates the inverse of J(m).
+// Every root in X is in K(X), and if K(X) is in the interior of X, X contains exactly one root.
+fn krawczyk<F, J>(f: &F, jacobian: &J, x: &[EFloat64]) -> Krawczyk
+where
+    F: Fn(&[EFloat64]) -> Vec<EFloat64>,
+    J: Fn(&[EFloat64]) -> Matrix,
+{
+    let n = x.len();
+    let m: Vec<EFloat64> = x.iter().map(|x| EFloat64::from((*x).mid())).collect();
+    let y = match jacobian(&m).inverse() {
+        Ok(inverse) => Matrix::from_f64(
+            inverse
+                .entries
+                .iter()
+                .map(|r| r.iter().map(|x| (*x).mid()).collect())
+                .collect(),
+        ),
+        Err(_) => return Krawczyk::Singular,
+    };
+    let step = y.mul_vector(&f(&m));
+    let contraction = &Matrix::identity(n) - &(&y * &jacobian(x));
+    let offset: Vec<EFloat64> = x.iter().zip(m.iter()).map(|(x, m)| *x - *m).collect();
+    let k: Vec<EFloat64> = contraction
+        .mul_vector(&offset)
+        .iter()
+        .zip(m.iter().zip(step.iter()))
+        .map(|(c, (m, s))| *m - *s + *c)
+        .collect();
+
+    let interior = k
+        .iter()
+        .zip(x.iter())
+        .all(|(k, x)| k.lower_bound > x.lower_bound && k.upper_bound < x.upper_bound);
+    let shrunk: Option<Vec<EFloat64>> = k
+        .iter()
+        .zip(x.iter())
+        .map(|(k, x)| intersect(*k, *x))
+        .collect();
+    match (shrunk, interior) {
+        (None, _) => Krawczyk::Empty,
+        (Some(k), true) => Krawczyk::Unique(k),
+        (Some(k), false) => Krawczyk::Shrunk(k),
+    }
+}
+
+// Splits the box in half along its widest side.
+fn bisect(x: &[EFloat64]) -> (Vec<EFloat64>, Vec<EFloat64>) {
+    let (i, _) = x
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| {
+            (a.upper_bound - a.lower_bound).total_cmp(&(b.upper_bound - b.lower_bound))
+        })
+        .unwrap();
+    let mid = x[i].mid();
+    let (mut left, mut right) = (x.to_vec(), x.to_vec());
+    left[i] = EFloat64::new(mid, x[i].lower_bound);
+    right[i] = EFloat64::new(x[i].upper_bound, mid);
+    (left, right)
+}
+
+// Finds all roots of f: R^n -> R^n in the domain with the Krawczyk operator and bisection.
+// The jacobian has to enclose the derivatives of f over the whole box it is called with. Roots are narrowed down to boxes of the given width.
+pub fn interval_newton<F, J>(
+    f: F,
+    jacobian: J,
+    domain: Vec<EFloat64>,
+    precision: f64,
+) -> IntervalNewtonSolution
+where
+    F: Fn(&[EFloat64]) -> Vec<EFloat64>,
+    J: Fn(&[EFloat64]) -> Matrix,
+{
+    let mut solution = IntervalNewtonSolution::default();
+    let mut boxes = vec![domain];
+    let mut processed = 0;
+    while let Some(x) = boxes.pop() {
+        processed += 1;
+        if processed > MAX_BOXES {
+            solution.possible.push(x);
+            continue;
+        }
+        // A component of f that cannot be zero excludes the box.
+        if f(&x).iter().any(|y| *y != 0.0) {
+            solution.empty.push(x);
+            continue;
+        }
+        match krawczyk(&f, &jacobian, &x) {
+            Krawczyk::Empty => solution.empty.push(x),
+            Krawczyk::Unique(mut k) => {
+                // Contract until the box is small or stops shrinking.
+                while width(&k) > precision {
+                    match krawczyk(&f, &jacobian, &k) {
+                        Krawczyk::Unique(next) | Krawczyk::Shrunk(next)
+                            if width(&next) < width(&k) =>
+                        {
+                            k = next
+                        }
+                        _ => break,
+                    }
+                }
+                solution.unique.push(k);
+            }
+            Krawczyk::Shrunk(k) if width(&k) <= precision => solution.possible.push(k),
+            Krawczyk::Shrunk(k) if width(&k) <= (1.0 - MIN_SHRINK) * width(&x) => boxes.push(k),
+            Krawczyk::Shrunk(_) | Krawczyk::Singular if width(&x) <= precision => {
+                solution.possible.push(x)
+            }
+            Krawczyk::Shrunk(_) | Krawczyk::Singular => {
+                let (left, right) = bisect(&x);
+                boxes.push(left);
+                boxes.push(right);
+            }
+        }
+    }
+    solution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_newton_circle_line() {
+        // x^2 + y^2 = 1 and x = y
+        let f = |x: &[EFloat64]| vec![x[0].square() + x[1].square() - EFloat64::one(), x[0] - x[1]];
+        let jacobian = |x: &[EFloat64]| {
+            Matrix::new(vec![
+                vec![EFloat64::two() * x[0], EFloat64::two() * x[1]],
+                vec![EFloat64::one(), -EFloat64::one()],
+            ])
+        };
+        let domain = vec![EFloat64::new(2.0, -2.0), EFloat64::new(1.5, -2.5)];
+        let solution = interval_newton(f, jacobian, domain, 1e-12);
+        assert_eq!(solution.unique.len(), 2);
+        assert!(solution.possible.is_empty());
+        let root = std::f64::consts::FRAC_1_SQRT_2;
+        for x in solution.unique.iter() {
+            assert!(x[0] == root || x[0] == -root);
+            assert!(width(x) <= 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_interval_newton_empty_and_double() {
+        let domain = vec![EFloat64::new(1.0, -1.0)];
+        // x^2 + 1 has no roots.
+        let solution = interval_newton(
+            |x: &[EFloat64]| vec![x[0].square() + EFloat64::one()],
+            |x: &[EFloat64]| Matrix::new(vec![vec![EFloat64::two() * x[0]]]),
+            domain.clone(),
+            1e-9,
+        );
+        assert!(solution.unique.is_empty() && solution.possible.is_empty());
+        assert!(!solution.empty.is_empty());
+        // x^2 has a double root, which cannot be certified.
+        let solution = interval_newton(
+            |x: &[EFloat64]| vec![x[0].square()],
+            |x: &[EFloat64]| Matrix::new(vec![vec![EFloat64::two() * x[0]]]),
+            domain,
+            1e-9,
+        );
+        assert!(solution.unique.is_empty());
+        assert!(!solution.possible.is_empty());
+        assert!(solution.possible.iter().all(|x| x[0] == 0.0));
+    }
+}
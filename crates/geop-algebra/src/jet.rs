@@ -0,0 +1,379 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{
+    algebra_error::{AlgebraError, AlgebraResult},
+    efloat::EFloat64,
+};
+
+// Value of a function of n variables together with enclosures of its first and second partial derivatives.
+// Evaluating an expression on jets of the variables gives the derivatives of the expression by forward mode automatic differentiation.
+#[derive(Debug, Clone)]
+pub struct Jet {
+    pub value: EFloat64,
+    pub gradient: Vec<EFloat64>,
+    // hessian[i][j] is the derivative by the i-th and the j-th variable.
+    pub hessian: Vec<Vec<EFloat64>>,
+}
+
+impl Jet {
+    pub fn constant(value: EFloat64, variables: usize) -> Self {
+        Self {
+            value,
+            gradient: vec![EFloat64::zero(); variables],
+            hessian: vec![vec![EFloat64::zero(); variables]; variables],
+        }
+    }
+
+    // The index-th of n variables at the given value.
+    pub fn variable(value: EFloat64, index: usize, variables: usize) -> Self {
+        let mut result = Self::constant(value, variables);
+        result.gradient[index] = EFloat64::one();
+        result
+    }
+
+    // One variable for each value.
+    pub fn variables(values: &[EFloat64]) -> Vec<Self> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| Self::variable(*v, i, values.len()))
+            .collect()
+    }
+
+    pub fn variables_count(&self) -> usize {
+        self.gradient.len()
+    }
+
+    // Derivative by the only variable, and the second derivative.
+    pub fn derivative(&self) -> EFloat64 {
+        self.gradient[0]
+    }
+
+    pub fn second_derivative(&self) -> EFloat64 {
+        self.hessian[0][0]
+    }
+
+    // g(self) for a function g with the value f = g(v), the first derivative df = g'(v) and the second derivative ddf = g''(v).
+    fn chain(&self, f: EFloat64, df: EFloat64, ddf: EFloat64) -> Self {
+        let n = self.variables_count();
+        Self {
+            value: f,
+            gradient: self.gradient.iter().map(|g| df * *g).collect(),
+            hessian: (0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| {
+                            df * self.hessian[i][j] + ddf * self.gradient[i] * self.gradient[j]
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    pub fn recip(&self) -> AlgebraResult<Self> {
+        let r = (EFloat64::one() / self.value)?;
+        Ok(self.chain(r, -r.square(), EFloat64::two() * r.powi(3)))
+    }
+
+    pub fn square(&self) -> Self {
+        self.chain(
+            self.value.square(),
+            EFloat64::two() * self.value,
+            EFloat64::two(),
+        )
+    }
+
+    pub fn powi(&self, n: i32) -> Self {
+        let e = EFloat64::from(n as f64);
+        let ddf = match n {
+            0 | 1 => EFloat64::zero(),
+            _ => e * EFloat64::from((n - 1) as f64) * self.value.powi(n - 2),
+        };
+        let df = match n {
+            0 => EFloat64::zero(),
+            _ => e * self.value.powi(n - 1),
+        };
+        self.chain(self.value.powi(n), df, ddf)
+    }
+
+    // Fails if the value is not positive, since the derivative is not bounded at zero.
+    pub fn sqrt(&self) -> AlgebraResult<Self> {
+        if self.value.lower_bound <= 0.0 {
+            return Err("Square root is only differentiable for positive numbers".into());
+        }
+        let s = self.value.sqrt().unwrap();
+        let df = (EFloat64::from(0.5) / s)?;
+        let ddf = (EFloat64::from(-0.25) / (s * self.value))?;
+        Ok(self.chain(s, df, ddf))
+    }
+
+    pub fn sin(&self) -> Self {
+        let (s, c) = (self.value.sin(), self.value.cos());
+        self.chain(s, c, -s)
+    }
+
+    pub fn cos(&self) -> Self {
+        let (s, c) = (self.value.sin(), self.value.cos());
+        self.chain(c, -s, -c)
+    }
+
+    pub fn tan(&self) -> AlgebraResult<Self> {
+        let t = self
+            .value
+            .tan()
+            .ok_or(AlgebraError::new("Tangent has a pole".to_string()))?;
+        let df = EFloat64::one() + t.square();
+        Ok(self.chain(t, df, EFloat64::two() * t * df))
+    }
+
+    // Fails at -1 and 1, where the derivatives are not bounded.
+    pub fn asin(&self) -> AlgebraResult<Self> {
+        let d = (EFloat64::one() - self.value.square())
+            .sqrt()
+            .ok_or(AlgebraError::new("Arcus sine is not defined".to_string()))?;
+        let df = (EFloat64::one() / d)?;
+        let ddf = self.value * df.powi(3);
+        let value = self
+            .value
+            .asin()
+            .ok_or(AlgebraError::new("Arcus sine is not defined".to_string()))?;
+        Ok(self.chain(value, df, ddf))
+    }
+
+    pub fn acos(&self) -> AlgebraResult<Self> {
+        let asin = self.asin()?;
+        let mut result = -&asin;
+        result.value = self
+            .value
+            .acos()
+            .ok_or(AlgebraError::new("Arcus cosine is not defined".to_string()))?;
+        Ok(result)
+    }
+
+    pub fn atan(&self) -> Self {
+        let df = (EFloat64::one() / (EFloat64::one() + self.value.square())).unwrap();
+        let ddf = -EFloat64::two() * self.value * df.square();
+        self.chain(self.value.atan(), df, ddf)
+    }
+
+    pub fn exp(&self) -> Self {
+        let e = self.value.exp();
+        self.chain(e, e, e)
+    }
+
+    pub fn ln(&self) -> AlgebraResult<Self> {
+        let l = self.value.ln().ok_or(AlgebraError::new(
+            "Logarithm of non positive number".to_string(),
+        ))?;
+        let df = (EFloat64::one() / self.value)?;
+        Ok(self.chain(l, df, -df.square()))
+    }
+
+    pub fn sinh(&self) -> Self {
+        let (s, c) = (self.value.sinh(), self.value.cosh());
+        self.chain(s, c, s)
+    }
+
+    pub fn cosh(&self) -> Self {
+        let (s, c) = (self.value.sinh(), self.value.cosh());
+        self.chain(c, s, c)
+    }
+
+    // Real powers of positive numbers.
+    pub fn powf(&self, exponent: &Jet) -> AlgebraResult<Self> {
+        Ok((&self.ln()? * exponent).exp())
+    }
+}
+
+impl Neg for &Jet {
+    type Output = Jet;
+
+    fn neg(self) -> Jet {
+        self * -EFloat64::one()
+    }
+}
+
+impl Add for &Jet {
+    type Output = Jet;
+
+    fn add(self, other: &Jet) -> Jet {
+        assert!(self.variables_count() == other.variables_count());
+        Jet {
+            value: self.value + other.value,
+            gradient: self
+                .gradient
+                .iter()
+                .zip(other.gradient.iter())
+                .map(|(a, b)| *a + *b)
+                .collect(),
+            hessian: self
+                .hessian
+                .iter()
+                .zip(other.hessian.iter())
+                .map(|(a, b)| a.iter().zip(b.iter()).map(|(a, b)| *a + *b).collect())
+                .collect(),
+        }
+    }
+}
+
+impl Sub for &Jet {
+    type Output = Jet;
+
+    fn sub(self, other: &Jet) -> Jet {
+        self + &(-other)
+    }
+}
+
+impl Mul for &Jet {
+    type Output = Jet;
+
+    // (a b)'' = a'' b + a' b'^T + b' a'^T + a b''
+    fn mul(self, other: &Jet) -> Jet {
+        assert!(self.variables_count() == other.variables_count());
+        let n = self.variables_count();
+        let (a, b) = (self, other);
+        Jet {
+            value: a.value * b.value,
+            gradient: (0..n)
+                .map(|i| a.gradient[i] * b.value + a.value * b.gradient[i])
+                .collect(),
+            hessian: (0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| {
+                            a.hessian[i][j] * b.value
+                                + a.gradient[i] * b.gradient[j]
+                                + b.gradient[i] * a.gradient[j]
+                                + a.value * b.hessian[i][j]
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Div for &Jet {
+    type Output = AlgebraResult<Jet>;
+
+    fn div(self, other: &Jet) -> AlgebraResult<Jet> {
+        Ok(Mul::mul(self, &other.recip()?))
+    }
+}
+
+impl Add<EFloat64> for &Jet {
+    type Output = Jet;
+
+    fn add(self, other: EFloat64) -> Jet {
+        Jet {
+            value: self.value + other,
+            ..self.clone()
+        }
+    }
+}
+
+impl Sub<EFloat64> for &Jet {
+    type Output = Jet;
+
+    fn sub(self, other: EFloat64) -> Jet {
+        self + -other
+    }
+}
+
+impl Mul<EFloat64> for &Jet {
+    type Output = Jet;
+
+    fn mul(self, other: EFloat64) -> Jet {
+        Jet {
+            value: self.value * other,
+            gradient: self.gradient.iter().map(|g| *g * other).collect(),
+            hessian: self
+                .hessian
+                .iter()
+                .map(|r| r.iter().map(|h| *h * other).collect())
+                .collect(),
+        }
+    }
+}
+
+impl Div<EFloat64> for &Jet {
+    type Output = AlgebraResult<Jet>;
+
+    fn div(self, other: EFloat64) -> AlgebraResult<Jet> {
+        Ok(Mul::mul(self, (EFloat64::one() / other)?))
+    }
+}
+
+// The same operations on owned jets.
+macro_rules! forward_owned {
+    ($trait:ident, $method:ident, $output:ty) => {
+        impl $trait for Jet {
+            type Output = $output;
+
+            fn $method(self, other: Jet) -> $output {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $trait<EFloat64> for Jet {
+            type Output = $output;
+
+            fn $method(self, other: EFloat64) -> $output {
+                (&self).$method(other)
+            }
+        }
+    };
+}
+
+forward_owned!(Add, add, Jet);
+forward_owned!(Sub, sub, Jet);
+forward_owned!(Mul, mul, Jet);
+forward_owned!(Div, div, AlgebraResult<Jet>);
+
+impl Neg for Jet {
+    type Output = Jet;
+
+    fn neg(self) -> Jet {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jet_univariate() {
+        // f(x) = x^2 sin(x), f' = 2x sin(x) + x^2 cos(x), f'' = (2 - x^2) sin(x) + 4x cos(x)
+        let x = 1.3_f64;
+        let jet = Jet::variable(EFloat64::from(x), 0, 1);
+        let f = jet.square() * jet.sin();
+        assert!(f.value == x * x * x.sin());
+        assert!(f.derivative() == 2.0 * x * x.sin() + x * x * x.cos());
+        assert!(f.second_derivative() == (2.0 - x * x) * x.sin() + 4.0 * x * x.cos());
+
+        // g(x) = ln(x) / x, g' = (1 - ln(x)) / x^2, g'' = (2 ln(x) - 3) / x^3
+        let g = (jet.ln().unwrap() / jet.clone()).unwrap();
+        assert!(g.derivative() == (1.0 - x.ln()) / (x * x));
+        assert!(g.second_derivative() == (2.0 * x.ln() - 3.0) / (x * x * x));
+
+        let h = jet.powf(&Jet::constant(EFloat64::from(2.5), 1)).unwrap();
+        assert!(h.derivative() == 2.5 * x.powf(1.5));
+        assert!(Jet::variable(EFloat64::zero(), 0, 1).sqrt().is_err());
+    }
+
+    #[test]
+    fn test_jet_multivariate() {
+        // f(x, y) = x y + exp(x) cos(y)
+        let (x, y) = (0.4_f64, -0.7_f64);
+        let v = Jet::variables(&[EFloat64::from(x), EFloat64::from(y)]);
+        let f = &v[0] * &v[1] + v[0].exp() * v[1].cos();
+        assert!(f.gradient[0] == y + x.exp() * y.cos());
+        assert!(f.gradient[1] == x - x.exp() * y.sin());
+        assert!(f.hessian[0][0] == x.exp() * y.cos());
+        assert!(f.hessian[0][1] == 1.0 - x.exp() * y.sin());
+        assert!(f.hessian[1][0] == f.hessian[0][1]);
+        assert!(f.hessian[1][1] == -x.exp() * y.cos());
+    }
+}
@@ -1,8 +1,14 @@
 pub mod algebra_error;
 pub mod bernstein_basis;
+pub mod bernstein_polynomial;
 pub mod efloat;
+pub mod expansion;
 pub mod factorial;
+pub mod interval_newton;
+pub mod jet;
+pub mod matrix;
 pub mod monomial_polynom;
+pub mod predicates;
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
@@ -0,0 +1,510 @@
+use std::fmt::Display;
+
+use crate::{
+    algebra_error::{AlgebraError, AlgebraResult},
+    efloat::EFloat64,
+};
+
+// Sweeps of the Jacobi eigenvalue algorithm, after which it gives up.
+const MAX_JACOBI_SWEEPS: usize = 64;
+
+fn norm(v: &[EFloat64]) -> EFloat64 {
+    let mut sum = EFloat64::zero();
+    for x in v {
+        sum = sum + x.square();
+    }
+    sum.sqrt().unwrap()
+}
+
+// Matrix of intervals, stored as rows. Vectors are plain slices of intervals.
+#[derive(Debug, Clone)]
+pub struct Matrix {
+    pub entries: Vec<Vec<EFloat64>>,
+}
+
+// Lower and upper triangular matrix in one, with the rows swapped by the permutation.
+struct LuDecomposition {
+    lu: Vec<Vec<EFloat64>>,
+    permutation: Vec<usize>,
+    swaps: usize,
+}
+
+impl LuDecomposition {
+    fn solve(&self, b: &[EFloat64]) -> AlgebraResult<Vec<EFloat64>> {
+        let n = self.lu.len();
+        let mut y: Vec<EFloat64> = self.permutation.iter().map(|i| b[*i]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                y[i] = y[i] - self.lu[i][j] * y[j];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                y[i] = y[i] - self.lu[i][j] * y[j];
+            }
+            y[i] = (y[i] / self.lu[i][i])?;
+        }
+        Ok(y)
+    }
+}
+
+impl Matrix {
+    pub fn new(entries: Vec<Vec<EFloat64>>) -> Self {
+        assert!(!entries.is_empty(), "Matrix needs a row");
+        assert!(
+            entries.iter().all(|r| r.len() == entries[0].len()),
+            "Rows must have the same length"
+        );
+        Self { entries }
+    }
+
+    pub fn from_f64(entries: Vec<Vec<f64>>) -> Self {
+        Self::new(
+            entries
+                .into_iter()
+                .map(|r| r.into_iter().map(EFloat64::from).collect())
+                .collect(),
+        )
+    }
+
+    pub fn zeros(rows: usize, columns: usize) -> Self {
+        Self::new(vec![vec![EFloat64::zero(); columns]; rows])
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut result = Self::zeros(n, n);
+        for (i, row) in result.entries.iter_mut().enumerate() {
+            row[i] = EFloat64::one();
+        }
+        result
+    }
+
+    pub fn rows(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn columns(&self) -> usize {
+        self.entries[0].len()
+    }
+
+    pub fn column(&self, j: usize) -> Vec<EFloat64> {
+        self.entries.iter().map(|r| r[j]).collect()
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::new((0..self.columns()).map(|j| self.column(j)).collect())
+    }
+
+    pub fn mul_vector(&self, v: &[EFloat64]) -> Vec<EFloat64> {
+        assert!(v.len() == self.columns(), "Vector has the wrong length");
+        self.entries
+            .iter()
+            .map(|r| {
+                r.iter()
+                    .zip(v.iter())
+                    .fold(EFloat64::zero(), |sum, (a, b)| sum + *a * *b)
+            })
+            .collect()
+    }
+
+    // Gaussian elimination with partial pivoting. The pivot is the entry furthest away from zero, which fails if all candidates contain zero.
+    fn lu(&self) -> AlgebraResult<LuDecomposition> {
+        if self.rows() != self.columns() {
+            return Err("Matrix must be square".into());
+        }
+        let n = self.rows();
+        let mut lu = self.entries.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut swaps = 0;
+        for k in 0..n {
+            let pivot = (k..n)
+                .max_by(|i, j| {
+                    let (a, b) = (lu[*i][k].abs().lower_bound, lu[*j][k].abs().lower_bound);
+                    a.total_cmp(&b)
+                })
+                .unwrap();
+            if lu[pivot][k].abs().lower_bound == 0.0 {
+                return Err("Matrix is singular".into());
+            }
+            if pivot != k {
+                lu.swap(pivot, k);
+                permutation.swap(pivot, k);
+                swaps += 1;
+            }
+            let pivot_row = lu[k].clone();
+            for row in lu[k + 1..].iter_mut() {
+                let factor = (row[k] / pivot_row[k])?;
+                row[k] = factor;
+                for (x, p) in row[k + 1..].iter_mut().zip(pivot_row[k + 1..].iter()) {
+                    *x = *x - factor * *p;
+                }
+            }
+        }
+        Ok(LuDecomposition {
+            lu,
+            permutation,
+            swaps,
+        })
+    }
+
+    // Solves A x = b for a square, regular matrix A.
+    pub fn solve(&self, b: &[EFloat64]) -> AlgebraResult<Vec<EFloat64>> {
+        assert!(b.len() == self.rows(), "Vector has the wrong length");
+        self.lu()?.solve(b)
+    }
+
+    // Minimizes |A x - b| with Householder reflections, for matrices with at least as many rows as columns and full rank.
+    pub fn solve_least_squares(&self, b: &[EFloat64]) -> AlgebraResult<Vec<EFloat64>> {
+        let (m, n) = (self.rows(), self.columns());
+        assert!(b.len() == m, "Vector has the wrong length");
+        if m < n {
+            return Err("Matrix must not have more columns than rows".into());
+        }
+        let mut a = self.entries.clone();
+        let mut b = b.to_vec();
+        for k in 0..n {
+            let column: Vec<EFloat64> = a[k..].iter().map(|r| r[k]).collect();
+            let length = norm(&column);
+            if length.lower_bound <= 0.0 {
+                return Err("Matrix does not have full rank".into());
+            }
+            // Reflect the column onto -sign(a_kk) |column| e_k, which avoids cancellation.
+            let alpha = match a[k][k].mid() > 0.0 {
+                true => -length,
+                false => length,
+            };
+            let mut v = column;
+            v[0] = v[0] - alpha;
+            let v_norm_sq = v.iter().fold(EFloat64::zero(), |s, x| s + x.square());
+            let reflect = |x: &mut [EFloat64]| -> AlgebraResult<()> {
+                let dot = v
+                    .iter()
+                    .zip(x.iter())
+                    .fold(EFloat64::zero(), |s, (v, x)| s + *v * *x);
+                let factor = (EFloat64::two() * dot / v_norm_sq)?;
+                for (x, v) in x.iter_mut().zip(v.iter()) {
+                    *x = *x - factor * *v;
+                }
+                Ok(())
+            };
+            for j in k..n {
+                let mut column: Vec<EFloat64> = a[k..].iter().map(|r| r[j]).collect();
+                reflect(&mut column)?;
+                for (r, x) in a[k..].iter_mut().zip(column) {
+                    r[j] = x;
+                }
+            }
+            reflect(&mut b[k..])?;
+        }
+        let mut x = vec![EFloat64::zero(); n];
+        for i in (0..n).rev() {
+            let mut sum = b[i];
+            for j in i + 1..n {
+                sum = sum - a[i][j] * x[j];
+            }
+            x[i] = (sum / a[i][i])?;
+        }
+        Ok(x)
+    }
+
+    // Uses Laplace expansion if elimination fails because the matrix is close to singular.
+    pub fn determinant(&self) -> EFloat64 {
+        assert!(self.rows() == self.columns(), "Matrix must be square");
+        match self.lu() {
+            Ok(lu) => {
+                let mut det = match lu.swaps % 2 {
+                    0 => EFloat64::one(),
+                    _ => -EFloat64::one(),
+                };
+                for (i, row) in lu.lu.iter().enumerate() {
+                    det = det * row[i];
+                }
+                det
+            }
+            Err(_) => self.laplace(),
+        }
+    }
+
+    fn laplace(&self) -> EFloat64 {
+        let n = self.rows();
+        if n == 1 {
+            return self.entries[0][0];
+        }
+        let mut det = EFloat64::zero();
+        for j in 0..n {
+            let minor = Matrix::new(
+                self.entries[1..]
+                    .iter()
+                    .map(|r| {
+                        r.iter()
+                            .enumerate()
+                            .filter(|(k, _)| *k != j)
+                            .map(|(_, x)| *x)
+                            .collect()
+                    })
+                    .collect(),
+            );
+            let term = self.entries[0][j] * minor.laplace();
+            det = match j % 2 {
+                0 => det + term,
+                _ => det - term,
+            };
+        }
+        det
+    }
+
+    pub fn inverse(&self) -> AlgebraResult<Matrix> {
+        let lu = self.lu()?;
+        let n = self.rows();
+        let columns = (0..n)
+            .map(|j| lu.solve(&Matrix::identity(n).column(j)))
+            .collect::<AlgebraResult<Vec<_>>>()?;
+        Ok(Matrix::new(columns).transpose())
+    }
+
+    // Eigenvalues of a symmetric matrix in ascending order, with the eigenvectors as columns.
+    // The eigenvectors are approximated with the Jacobi algorithm. Each eigenvalue is enclosed by the residual bound |A x - l x| / |x|, which holds for symmetric matrices.
+    pub fn symmetric_eigen(&self) -> AlgebraResult<(Vec<EFloat64>, Matrix)> {
+        let n = self.rows();
+        if n != self.columns() {
+            return Err("Matrix must be square".into());
+        }
+        for i in 0..n {
+            for j in 0..i {
+                if self.entries[i][j] != self.entries[j][i] {
+                    return Err("Matrix must be symmetric".into());
+                }
+            }
+        }
+        let mut a: Vec<Vec<f64>> = self
+            .entries
+            .iter()
+            .map(|r| r.iter().map(|x| (*x).mid()).collect())
+            .collect();
+        let mut v: Vec<Vec<f64>> = (0..n)
+            .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+            .collect();
+        let frobenius: f64 = a.iter().flatten().map(|x| x * x).sum();
+        let mut converged = false;
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            let off: f64 = (0..n)
+                .flat_map(|i| (0..i).map(move |j| (i, j)))
+                .map(|(i, j)| a[i][j] * a[i][j])
+                .sum();
+            if off <= f64::EPSILON * f64::EPSILON * frobenius {
+                converged = true;
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[p][q] == 0.0 {
+                        continue;
+                    }
+                    // Rotation that removes a_pq, see Numerical Recipes 11.1.
+                    let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                    let t = match theta.abs() > 1e150 {
+                        true => 0.5 / theta,
+                        false => theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt()),
+                    };
+                    let c = 1.0 / (t * t + 1.0).sqrt();
+                    let s = t * c;
+                    let rotate_columns = |m: &mut Vec<Vec<f64>>| {
+                        for row in m.iter_mut() {
+                            let (x, y) = (row[p], row[q]);
+                            row[p] = c * x - s * y;
+                            row[q] = s * x + c * y;
+                        }
+                    };
+                    rotate_columns(&mut a);
+                    rotate_columns(&mut v);
+                    let (row_p, row_q) = (a[p].clone(), a[q].clone());
+                    for (k, (x, y)) in row_p.iter().zip(row_q.iter()).enumerate() {
+                        a[p][k] = c * x - s * y;
+                        a[q][k] = s * x + c * y;
+                    }
+                }
+            }
+        }
+        if !converged {
+            return Err(AlgebraError::new(format!(
+                "Jacobi algorithm did not converge in {} sweeps",
+                MAX_JACOBI_SWEEPS
+            )));
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|i, j| a[*i][*i].total_cmp(&a[*j][*j]));
+        let vectors = Matrix::from_f64(
+            (0..n)
+                .map(|i| order.iter().map(|k| v[i][*k]).collect())
+                .collect(),
+        );
+        let values = order
+            .iter()
+            .enumerate()
+            .map(|(column, k)| {
+                let x = vectors.column(column);
+                let lambda = EFloat64::from(a[*k][*k]);
+                let residual: Vec<EFloat64> = self
+                    .mul_vector(&x)
+                    .iter()
+                    .zip(x.iter())
+                    .map(|(ax, x)| *ax - lambda * *x)
+                    .collect();
+                let bound = (norm(&residual) / norm(&x))?.upper_bound;
+                Ok(lambda + EFloat64::new(bound, -bound))
+            })
+            .collect::<AlgebraResult<Vec<_>>>()?;
+        Ok((values, vectors))
+    }
+}
+
+impl std::ops::Add for &Matrix {
+    type Output = Matrix;
+
+    fn add(self, other: &Matrix) -> Matrix {
+        assert!(self.rows() == other.rows() && self.columns() == other.columns());
+        Matrix::new(
+            self.entries
+                .iter()
+                .zip(other.entries.iter())
+                .map(|(a, b)| a.iter().zip(b.iter()).map(|(a, b)| *a + *b).collect())
+                .collect(),
+        )
+    }
+}
+
+impl std::ops::Sub for &Matrix {
+    type Output = Matrix;
+
+    fn sub(self, other: &Matrix) -> Matrix {
+        assert!(self.rows() == other.rows() && self.columns() == other.columns());
+        Matrix::new(
+            self.entries
+                .iter()
+                .zip(other.entries.iter())
+                .map(|(a, b)| a.iter().zip(b.iter()).map(|(a, b)| *a - *b).collect())
+                .collect(),
+        )
+    }
+}
+
+impl std::ops::Mul for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, other: &Matrix) -> Matrix {
+        assert!(self.columns() == other.rows(), "Matrix sizes do not match");
+        let columns: Vec<Vec<EFloat64>> = (0..other.columns())
+            .map(|j| self.mul_vector(&other.column(j)))
+            .collect();
+        Matrix::new(columns).transpose()
+    }
+}
+
+impl std::ops::Mul<EFloat64> for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, other: EFloat64) -> Matrix {
+        Matrix::new(
+            self.entries
+                .iter()
+                .map(|r| r.iter().map(|x| *x * other).collect())
+                .collect(),
+        )
+    }
+}
+
+impl PartialEq for Matrix {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows() == other.rows()
+            && self.columns() == other.columns()
+            && self
+                .entries
+                .iter()
+                .zip(other.entries.iter())
+                .all(|(a, b)| a.iter().zip(b.iter()).all(|(a, b)| a == b))
+    }
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.entries.iter() {
+            for x in row.iter() {
+                write!(f, "{} ", x)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Matrix {
+        Matrix::from_f64(vec![
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+            vec![3.0, 0.0, 1.0],
+        ])
+    }
+
+    #[test]
+    fn test_matrix_solve() {
+        let a = example();
+        let x = vec![
+            EFloat64::from(1.0),
+            EFloat64::from(-2.0),
+            EFloat64::from(0.5),
+        ];
+        let b = a.mul_vector(&x);
+        for (y, x) in a.solve(&b).unwrap().iter().zip(x.iter()) {
+            assert!(y == x);
+        }
+        for (y, x) in a.solve_least_squares(&b).unwrap().iter().zip(x.iter()) {
+            assert!(y == x);
+        }
+        assert!(a.determinant() == -5.0);
+        assert!(&a * &a.inverse().unwrap() == Matrix::identity(3));
+        let singular = Matrix::from_f64(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+        assert!(singular.inverse().is_err());
+        assert!(singular.determinant() == 0.0);
+    }
+
+    #[test]
+    fn test_matrix_least_squares() {
+        // Line through (0, 1), (1, 2) and (2, 4).
+        let a = Matrix::from_f64(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]);
+        let b = vec![
+            EFloat64::from(1.0),
+            EFloat64::from(2.0),
+            EFloat64::from(4.0),
+        ];
+        let x = a.solve_least_squares(&b).unwrap();
+        assert!(x[0] == 5.0 / 6.0);
+        assert!(x[1] == 1.5);
+    }
+
+    #[test]
+    fn test_matrix_symmetric_eigen() {
+        let a = Matrix::from_f64(vec![
+            vec![2.0, 1.0, 0.0],
+            vec![1.0, 2.0, 0.0],
+            vec![0.0, 0.0, 5.0],
+        ]);
+        let (values, vectors) = a.symmetric_eigen().unwrap();
+        for (value, expected) in values.iter().zip([1.0, 3.0, 5.0]) {
+            assert!(*value == expected);
+            assert!(value.upper_bound - value.lower_bound < 1e-12);
+        }
+        // A V = V L
+        for (j, value) in values.iter().enumerate() {
+            let x = vectors.column(j);
+            for (ax, x) in a.mul_vector(&x).iter().zip(x.iter()) {
+                assert!(*ax == *value * *x);
+            }
+        }
+        assert!(example().symmetric_eigen().is_err());
+    }
+}
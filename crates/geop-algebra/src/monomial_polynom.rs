@@ -1,5 +1,6 @@
 use crate::{
     algebra_error::{AlgebraError, AlgebraResult},
+    bernstein_polynomial::BernsteinPolynomial,
     efloat::EFloat64,
 };
 
@@ -50,6 +51,31 @@ impl MonomialPolynom {
         }
         result
     }
+
+    // Finds enclosures of all roots in [start, end]. Every root is contained in exactly one of the returned, disjoint enclosures.
+    pub fn roots_in(&self, start: f64, end: f64) -> AlgebraResult<Vec<EFloat64>> {
+        if start > end {
+            return Err(AlgebraError::new(format!(
+                "Interval [{}, {}] is empty",
+                start, end
+            )));
+        }
+        // q(t) = p(start + (end - start) t) has its roots in [0, 1].
+        let (a, b) = (EFloat64::from(start), EFloat64::from(end));
+        let x = MonomialPolynom::new(vec![a, b - a]);
+        let mut q = MonomialPolynom::new(vec![]);
+        for m in self.monomials.iter().rev() {
+            q = &(&q * &x) + &MonomialPolynom::from_factor(*m);
+        }
+        let roots = BernsteinPolynomial::from_monomial_polynom(&q).roots()?;
+        Ok(roots
+            .into_iter()
+            .map(|t| {
+                let x = a + (b - a) * t;
+                EFloat64::new(x.upper_bound.min(end), x.lower_bound.max(start))
+            })
+            .collect())
+    }
 }
 
 impl PartialEq for MonomialPolynom {
@@ -199,4 +225,21 @@ mod tests {
         assert!(remainder.is_zero());
         assert_eq!(result, *p1);
     }
+
+    #[test]
+    fn test_monomial_polynom_roots() {
+        // (x + 1) (x - 2) (x - 3)
+        let p = MonomialPolynom::new(vec![
+            EFloat64::from(6.0),
+            EFloat64::from(1.0),
+            EFloat64::from(-4.0),
+            EFloat64::from(1.0),
+        ]);
+        let roots = p.roots_in(-2.0, 4.0).unwrap();
+        assert_eq!(roots.len(), 3);
+        for (root, expected) in roots.iter().zip([-1.0, 2.0, 3.0]) {
+            assert!(*root == expected);
+        }
+        assert_eq!(p.roots_in(0.0, 2.5).unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,167 @@
+use std::cmp::Ordering;
+
+use crate::{efloat::EFloat64, expansion::Expansion};
+
+// The predicates decide on which side of something a point is, where any consistent answer for a degenerate configuration is fine.
+// They are not meant for equality or incidence, e.g. of points or of parallel lines. For these, values that overlap within their error bounds have to be equal, and an exact answer for the midpoints would split them.
+
+// Half an ulp of 1, the relative rounding error of a single operation.
+const EPSILON: f64 = f64::EPSILON / 2.0;
+// Error bounds of the floating point evaluation, relative to the permanent of the determinant, from Shewchuk's predicates.
+const ORIENT2D_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const ORIENT3D_BOUND: f64 = (7.0 + 56.0 * EPSILON) * EPSILON;
+const INCIRCLE_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+// Sign of the interval if it is decided, otherwise the exact answer.
+pub fn sign(x: EFloat64, exact: impl FnOnce() -> Ordering) -> Ordering {
+    if x.lower_bound > 0.0 {
+        Ordering::Greater
+    } else if x.upper_bound < 0.0 {
+        Ordering::Less
+    } else {
+        exact()
+    }
+}
+
+// Sign of the floating point estimate if it is larger than its error bound, otherwise the exact sign.
+fn adaptive(estimate: f64, bound: f64, exact: impl FnOnce() -> Expansion) -> Ordering {
+    if estimate.abs() > bound {
+        return estimate.total_cmp(&0.0);
+    }
+    exact().sign()
+}
+
+fn e(x: f64) -> Expansion {
+    Expansion::from(x)
+}
+
+fn det2(a: &Expansion, b: &Expansion, c: &Expansion, d: &Expansion) -> Expansion {
+    &(a * d) - &(b * c)
+}
+
+fn det3(m: [[&Expansion; 3]; 3]) -> Expansion {
+    let a = m[0][0] * &det2(m[1][1], m[1][2], m[2][1], m[2][2]);
+    let b = m[0][1] * &det2(m[1][0], m[1][2], m[2][0], m[2][2]);
+    let c = m[0][2] * &det2(m[1][0], m[1][1], m[2][0], m[2][1]);
+    &(&a - &b) + &c
+}
+
+// Sign of det(a - c, b - c), which is positive if a, b, c are counter clockwise.
+pub fn orient2d(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> Ordering {
+    let left = (a[0] - c[0]) * (b[1] - c[1]);
+    let right = (a[1] - c[1]) * (b[0] - c[0]);
+    let bound = ORIENT2D_BOUND * (left.abs() + right.abs());
+    adaptive(left - right, bound, || {
+        let d = |p: [f64; 2], i: usize| &e(p[i]) - &e(c[i]);
+        det2(&d(a, 0), &d(a, 1), &d(b, 0), &d(b, 1))
+    })
+}
+
+// Sign of det(a - d, b - d, c - d), which is positive if d is below the plane through a, b, c, when they are counter clockwise seen from above.
+pub fn orient3d(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]) -> Ordering {
+    let m: Vec<[f64; 3]> = [a, b, c]
+        .iter()
+        .map(|p| [p[0] - d[0], p[1] - d[1], p[2] - d[2]])
+        .collect();
+    let minor =
+        |i: usize, j: usize, k: usize| m[i][j] * m[(i + 1) % 3][k] - m[i][k] * m[(i + 1) % 3][j];
+    let estimate = m[2][0] * minor(0, 1, 2) + m[2][1] * minor(0, 2, 0) + m[2][2] * minor(0, 0, 1);
+    let permanent = |i: usize, j: usize, k: usize| {
+        (m[i][j] * m[(i + 1) % 3][k]).abs() + (m[i][k] * m[(i + 1) % 3][j]).abs()
+    };
+    let bound = ORIENT3D_BOUND
+        * (m[2][0].abs() * permanent(0, 1, 2)
+            + m[2][1].abs() * permanent(0, 2, 0)
+            + m[2][2].abs() * permanent(0, 0, 1));
+    adaptive(estimate, bound, || {
+        let rows: Vec<Vec<Expansion>> = [a, b, c]
+            .iter()
+            .map(|p| (0..3).map(|i| &e(p[i]) - &e(d[i])).collect())
+            .collect();
+        det3([
+            [&rows[0][0], &rows[0][1], &rows[0][2]],
+            [&rows[1][0], &rows[1][1], &rows[1][2]],
+            [&rows[2][0], &rows[2][1], &rows[2][2]],
+        ])
+    })
+}
+
+// Positive if d is inside the circle through a, b, c, when they are counter clockwise.
+pub fn incircle(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> Ordering {
+    let m: Vec<[f64; 3]> = [a, b, c]
+        .iter()
+        .map(|p| {
+            let (x, y) = (p[0] - d[0], p[1] - d[1]);
+            [x, y, x * x + y * y]
+        })
+        .collect();
+    let minor = |i: usize, j: usize| m[i][0] * m[j][1] - m[j][0] * m[i][1];
+    let estimate = m[0][2] * minor(1, 2) + m[1][2] * minor(2, 0) + m[2][2] * minor(0, 1);
+    let permanent = |i: usize, j: usize| (m[i][0] * m[j][1]).abs() + (m[j][0] * m[i][1]).abs();
+    let bound = INCIRCLE_BOUND
+        * (m[0][2] * permanent(1, 2) + m[1][2] * permanent(2, 0) + m[2][2] * permanent(0, 1));
+    adaptive(estimate, bound, || {
+        let rows: Vec<Vec<Expansion>> = [a, b, c]
+            .iter()
+            .map(|p| {
+                let (x, y) = (&e(p[0]) - &e(d[0]), &e(p[1]) - &e(d[1]));
+                let lift = &(&x * &x) + &(&y * &y);
+                vec![x, y, lift]
+            })
+            .collect();
+        det3([
+            [&rows[0][0], &rows[0][1], &rows[0][2]],
+            [&rows[1][0], &rows[1][1], &rows[1][2]],
+            [&rows[2][0], &rows[2][1], &rows[2][2]],
+        ])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orient2d() {
+        assert_eq!(
+            orient2d([0.0, 0.0], [1.0, 0.0], [0.0, 1.0]),
+            Ordering::Greater
+        );
+        assert_eq!(orient2d([0.0, 0.0], [0.0, 1.0], [1.0, 0.0]), Ordering::Less);
+        // Points on a line that floating point evaluation gets wrong.
+        assert_eq!(
+            orient2d([0.5, 0.5], [12.0, 12.0], [24.0, 24.0]),
+            Ordering::Equal
+        );
+        let tiny = 0.5 + f64::EPSILON;
+        assert_eq!(
+            orient2d([tiny, 0.5], [12.0, 12.0], [24.0, 24.0]),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_orient3d_and_incircle() {
+        let (a, b, c) = ([0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        assert_eq!(orient3d(a, b, c, [0.3, 0.3, -1.0]), Ordering::Greater);
+        assert_eq!(orient3d(a, b, c, [0.3, 0.3, 1e-300]), Ordering::Less);
+        assert_eq!(orient3d(a, b, c, [0.1, 0.7, 0.0]), Ordering::Equal);
+        // Four points on the unit circle, and points just inside and outside.
+        let (a, b, c) = ([1.0, 0.0], [0.0, 1.0], [-1.0, 0.0]);
+        assert_eq!(incircle(a, b, c, [0.0, -1.0]), Ordering::Equal);
+        assert_eq!(incircle(a, b, c, [0.0, -1.0 + 1e-16]), Ordering::Greater);
+        assert_eq!(incircle(a, b, c, [0.0, -1.0 - 1e-15]), Ordering::Less);
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(
+            sign(EFloat64::from(1.0), || Ordering::Less),
+            Ordering::Greater
+        );
+        assert_eq!(
+            sign(EFloat64::new(1.0, -1.0), || Ordering::Less),
+            Ordering::Less
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use geop_topology::{
     contains::face_point::{face_point_contains, FacePointContains},
-    topology::{edge::Edge, face::Face},
+    topology::{contour::ContourTangent, edge::Edge, face::Face},
 };
 
 #[derive(Clone, Debug, PartialEq)]
@@ -27,14 +27,27 @@ pub fn face_edge_contains(face: &Face, edge: &Edge) -> FaceEdgeContains {
             true => FaceEdgeContains::OnBorderSameDir,
             false => FaceEdgeContains::OnBorderOppositeDir,
         },
-        FacePointContains::OnPoint(_) => match face
-            .boundary_tangent(p)
-            .expect_on_edge()
-            .dot(edge.tangent(p))
-            > 0.0
-        {
-            true => FaceEdgeContains::OnBorderSameDir,
-            false => FaceEdgeContains::OnBorderOppositeDir,
+        FacePointContains::OnPoint(_) => match face.boundary_tangent(p) {
+            ContourTangent::OnEdge(tangent) => match tangent.dot(edge.tangent(p)) > 0.0 {
+                true => FaceEdgeContains::OnBorderSameDir,
+                false => FaceEdgeContains::OnBorderOppositeDir,
+            },
+            // The midpoint of the edge is a corner of the face. If the corner is straight and the edge runs along it, it is on the border. Otherwise the edge passes through the corner.
+            tangent @ ContourTangent::OnCorner(_, _) => {
+                let (&tangent_in, &tangent_out) = tangent.expect_on_corner();
+                let edge_tangent = edge.tangent(p);
+                if tangent_in.is_parallel(edge_tangent) && tangent_out.is_parallel(edge_tangent) {
+                    match tangent_out.dot(edge_tangent) > 0.0 {
+                        true => FaceEdgeContains::OnBorderSameDir,
+                        false => FaceEdgeContains::OnBorderOppositeDir,
+                    }
+                } else {
+                    match tangent.is_inside(face.normal(p), edge_tangent) {
+                        true => FaceEdgeContains::Inside,
+                        false => FaceEdgeContains::Outside,
+                    }
+                }
+            }
         },
         FacePointContains::NotOnSurface => FaceEdgeContains::NotSameSurface,
     }
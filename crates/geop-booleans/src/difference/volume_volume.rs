@@ -0,0 +1,111 @@
+use crate::remesh::volume::{normalize_volumes, volume_remesh, volume_split, VolumeSplit};
+use geop_topology::topology::volume::Volume;
+
+pub fn volume_volume_difference(volume_self: &Volume, volume_other: &Volume) -> Vec<Volume> {
+    let faces = volume_split(volume_self, volume_other)
+        .drain(..)
+        .filter_map(|mode| match mode {
+            VolumeSplit::AinB(_) => None,
+            VolumeSplit::AonBSameSide(_) => None,
+            VolumeSplit::AonBOpSide(face) => Some(VolumeSplit::AonBOpSide(face)),
+            VolumeSplit::AoutB(face) => Some(VolumeSplit::AoutB(face)),
+            // The parts of the other volume that are inside become boundary, with normals pointing the other way.
+            VolumeSplit::BinA(face) => Some(VolumeSplit::BinA(face.flip())),
+            VolumeSplit::BonASameSide(_) => None,
+            VolumeSplit::BonAOpSide(_) => None,
+            VolumeSplit::BoutA(_) => None,
+        })
+        .collect::<Vec<VolumeSplit>>();
+
+    let shells = volume_remesh(faces);
+    normalize_volumes(shells)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_algebra::efloat::EFloat64;
+    use geop_geometry::{
+        point::Point,
+        surfaces::{plane::Plane, surface::Surface},
+    };
+    use geop_topology::{
+        contains::volume_point::{volume_point_contains, VolumePointContains},
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+        topology::{contour::Contour, face::Face},
+    };
+
+    use super::*;
+
+    fn cube(size: f64) -> Volume {
+        let size = EFloat64::from(size);
+        primitive_cube(size, size, size)
+    }
+
+    // A cylinder of radius 0.5 along the z axis, from z = -2 to z = 2.
+    fn cylinder() -> Volume {
+        let basis = Point::from_f64(0.0, 0.0, -2.0);
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                basis,
+                -Point::unit_z(),
+                EFloat64::from(0.5),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                basis,
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        extrude(disk, Point::from_f64(0.0, 0.0, 4.0))
+    }
+
+    fn contains(volume: &Volume, point: Point) -> bool {
+        matches!(
+            volume_point_contains(volume, point),
+            VolumePointContains::Inside
+        )
+    }
+
+    #[test]
+    fn test_cube_minus_cylinder() {
+        let volumes = volume_volume_difference(&cube(2.0), &cylinder());
+        assert_eq!(volumes.len(), 1);
+        let volume = &volumes[0];
+        assert!(volume.cavities.is_empty());
+        assert!(contains(volume, Point::from_f64(0.8, 0.8, 0.1)));
+        assert!(contains(volume, Point::from_f64(-0.6, 0.1, 0.7)));
+        assert!(!contains(volume, Point::from_f64(0.1, 0.2, 0.3)));
+        assert!(!contains(volume, Point::from_f64(0.1, 0.2, 1.5)));
+    }
+
+    #[test]
+    fn test_nested_cavities() {
+        // Carving a cube out of the center of a bigger one leaves a cavity.
+        let volumes = volume_volume_difference(&cube(4.0), &cube(2.0));
+        assert_eq!(volumes.len(), 1);
+        let hollow = &volumes[0];
+        assert_eq!(hollow.cavities.len(), 1);
+        assert!(contains(hollow, Point::from_f64(1.5, 0.1, 0.2)));
+        assert!(!contains(hollow, Point::from_f64(0.1, 0.2, 0.3)));
+
+        // Carving the hollow cube out of an even bigger one leaves a cavity with a solid core in it.
+        let volumes = volume_volume_difference(&cube(6.0), hollow);
+        assert_eq!(volumes.len(), 2);
+        let core = volumes
+            .iter()
+            .find(|volume| volume.cavities.is_empty())
+            .unwrap();
+        let shell = volumes
+            .iter()
+            .find(|volume| volume.cavities.len() == 1)
+            .unwrap();
+        assert!(contains(core, Point::from_f64(0.1, 0.2, 0.3)));
+        assert!(!contains(core, Point::from_f64(1.5, 0.1, 0.2)));
+        assert!(contains(shell, Point::from_f64(2.5, 0.1, 0.2)));
+        assert!(!contains(shell, Point::from_f64(1.5, 0.1, 0.2)));
+        assert!(!contains(shell, Point::from_f64(0.1, 0.2, 0.3)));
+    }
+}
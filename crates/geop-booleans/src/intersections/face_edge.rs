@@ -3,11 +3,16 @@ use geop_geometry::{
         curve_surface_intersection, CurveSurfaceIntersection,
     },
     curves::CurveLike,
+    efloat::EFloat64,
     point::Point,
+    HORIZON_DIST,
 };
 
 use geop_topology::{
-    contains::face_point::{face_point_contains, FacePointContains},
+    contains::{
+        edge_point::{edge_point_contains, EdgePointContains},
+        face_point::{face_point_contains, FacePointContains},
+    },
     topology::{edge::Edge, face::Face},
 };
 
@@ -55,15 +60,45 @@ pub fn face_edge_intersection(face: &Face, edge: &Edge) -> FaceEdgeIntersection
             points.push(None); // Add a None to the end to close the loop
 
             let mut edges = Vec::<Edge>::new();
+            let bounded = points.iter().any(|p| p.is_some());
             for (p1, p2) in points.iter().zip(points.iter().skip(1)) {
+                // Two open ends only span the whole curve if there are no other points on it.
+                if bounded && p1.is_none() && p2.is_none() {
+                    continue;
+                }
                 let m = curve.get_midpoint(*p1, *p2).unwrap();
-                if face_point_contains(face, m) == FacePointContains::Inside {
+                if face_point_contains(face, m) == FacePointContains::Inside
+                    && edge_point_contains(edge, m) == EdgePointContains::Inside
+                {
                     edges.push(Edge::new(*p1, *p2, curve.clone()));
                 }
             }
 
             return FaceEdgeIntersection::Edges(edges);
         }
+        // Unbounded ends of the edge are cut off at the horizon.
+        CurveSurfaceIntersection::InfiniteDiscretePoints(arrays) => FaceEdgeIntersection::Points(
+            arrays
+                .iter()
+                .flat_map(|array| {
+                    let horizon =
+                        array.extend_dir.normalize().unwrap() * EFloat64::from(HORIZON_DIST);
+                    match (edge.start, edge.end) {
+                        (Some(start), Some(end)) => array.points_between(start, end),
+                        (Some(p), None) | (None, Some(p)) => {
+                            array.points_between(p - horizon, p + horizon)
+                        }
+                        (None, None) => {
+                            array.points_between(array.basis - horizon, array.basis + horizon)
+                        }
+                    }
+                })
+                .filter(|p| {
+                    edge_point_contains(edge, *p) != EdgePointContains::Outside
+                        && face_point_contains(face, *p) == FacePointContains::Inside
+                })
+                .collect(),
+        ),
         CurveSurfaceIntersection::None => FaceEdgeIntersection::None,
     }
 }
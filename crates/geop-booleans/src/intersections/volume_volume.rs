@@ -0,0 +1,83 @@
+use crate::remesh::volume::{normalize_volumes, volume_remesh, volume_split, VolumeSplit};
+use geop_topology::topology::volume::Volume;
+
+pub fn volume_volume_intersection(volume_self: &Volume, volume_other: &Volume) -> Vec<Volume> {
+    let faces = volume_split(volume_self, volume_other)
+        .drain(..)
+        .filter(|mode| match mode {
+            VolumeSplit::AinB(_) => true,
+            VolumeSplit::AonBSameSide(_) => true,
+            VolumeSplit::AonBOpSide(_) => false,
+            VolumeSplit::AoutB(_) => false,
+            VolumeSplit::BinA(_) => true,
+            VolumeSplit::BonASameSide(_) => false,
+            VolumeSplit::BonAOpSide(_) => false,
+            VolumeSplit::BoutA(_) => false,
+        })
+        .collect::<Vec<VolumeSplit>>();
+
+    let shells = volume_remesh(faces);
+    normalize_volumes(shells)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+    use geop_geometry::{point::Point, transforms::Transform};
+    use geop_topology::{
+        contains::volume_point::{volume_point_contains, VolumePointContains},
+        primitive_objects::volumes::cube::primitive_cube,
+    };
+
+    use super::*;
+
+    // A cube of the given size, centered at the origin and moved by the offset.
+    fn cube(size: f64, offset: Point) -> Volume {
+        let size = EFloat64::from(size);
+        primitive_cube(size, size, size).transform(Transform::from_translation(offset))
+    }
+
+    fn contains(volumes: &[Volume], point: Point) -> bool {
+        volumes.iter().any(|volume| {
+            matches!(
+                volume_point_contains(volume, point),
+                VolumePointContains::Inside
+            )
+        })
+    }
+
+    #[test]
+    fn test_intersection_overlapping() {
+        let volumes = volume_volume_intersection(
+            &cube(2.0, Point::zero()),
+            &cube(2.0, Point::from_f64(1.0, 0.5, 0.25)),
+        );
+        assert_eq!(volumes.len(), 1);
+        assert!(volumes[0].cavities.is_empty());
+        assert!(contains(&volumes, Point::from_f64(0.5, 0.5, 0.5)));
+        assert!(contains(&volumes, Point::from_f64(0.1, -0.4, -0.7)));
+        assert!(!contains(&volumes, Point::from_f64(-0.5, 0.5, 0.5)));
+        assert!(!contains(&volumes, Point::from_f64(1.5, 0.5, 0.5)));
+    }
+
+    #[test]
+    fn test_intersection_touching() {
+        // Cubes sharing only a face have no volume in common.
+        let volumes = volume_volume_intersection(
+            &cube(2.0, Point::zero()),
+            &cube(2.0, Point::from_f64(2.0, 0.0, 0.0)),
+        );
+        assert!(!contains(&volumes, Point::from_f64(0.9, 0.2, 0.3)));
+        assert!(!contains(&volumes, Point::from_f64(1.1, 0.2, 0.3)));
+    }
+
+    #[test]
+    fn test_intersection_inside() {
+        let volumes =
+            volume_volume_intersection(&cube(4.0, Point::zero()), &cube(2.0, Point::zero()));
+        assert_eq!(volumes.len(), 1);
+        assert!(volumes[0].cavities.is_empty());
+        assert!(contains(&volumes, Point::from_f64(0.1, 0.2, 0.3)));
+        assert!(!contains(&volumes, Point::from_f64(1.5, 0.2, 0.3)));
+    }
+}
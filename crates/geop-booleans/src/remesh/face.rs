@@ -57,9 +57,6 @@ pub enum FaceSplit {
 
 pub fn face_split(face_self: &Face, face_other: &Face) -> Vec<FaceSplit> {
     assert!(face_self.surface == face_other.surface);
-    println!("face_split");
-    println!("Face_self {:}", face_self);
-    println!("Face_other {:}", face_other);
 
     // debug_data::add_face(face_self.clone(), DebugColor::Red);
     // debug_data::add_face(face_other.clone(), DebugColor::Blue);
@@ -96,18 +93,10 @@ pub fn face_split(face_self: &Face, face_other: &Face) -> Vec<FaceSplit> {
         )
         .collect();
 
-    for edge in res.iter() {
-        println!("Edge: {:?}", edge);
-    }
-
     res
 }
 
 pub fn face_remesh(mut edges_intermediate: Vec<FaceSplit>) -> Vec<Contour> {
-    println!("face_remesh");
-    for edge in edges_intermediate.iter() {
-        println!("Edge: {:?}", edge);
-    }
     let mut edges = edges_intermediate
         .drain(..)
         .map(|e| match e {
@@ -126,14 +115,6 @@ pub fn face_remesh(mut edges_intermediate: Vec<FaceSplit>) -> Vec<Contour> {
     while let Some(current_edge) = edges.pop() {
         let mut new_contour = vec![current_edge];
         loop {
-            println!("new_contour");
-            for edge in new_contour.iter() {
-                println!("Edge: {:?}", edge);
-            }
-            println!("edges");
-            for edge in edges.iter() {
-                println!("Edge: {:?}", edge);
-            }
             let next_i = edges.iter().position(|edge| {
                 edge.start == new_contour[new_contour.len() - 1].end
                     || edge.end == new_contour[new_contour.len() - 1].end
@@ -229,8 +210,6 @@ impl Display for ContourHierarchy {
 }
 
 pub fn normalize_faces(contours: Vec<Contour>, surface: Rc<Surface>) -> Vec<Face> {
-    println!("normalize_faces");
-    println!("Contours: {}", contours.len());
     let mut hierarchies = Vec::<ContourHierarchy>::new();
     'outer_loop: for contour in contours.iter() {
         for h in hierarchies.iter_mut() {
@@ -250,20 +229,5 @@ pub fn normalize_faces(contours: Vec<Contour>, surface: Rc<Surface>) -> Vec<Face
     for h in hierarchies.iter() {
         faces.extend(h.as_faces(surface.clone()));
     }
-
-    println!("Hierarchies: {}", hierarchies.len());
-    for h in hierarchies.iter() {
-        println!("Hierarchy: {}", h);
-    }
-    // Now build a hierarchy of Contours
-    let mut faces = Vec::<Face>::new();
-    for h in hierarchies.iter() {
-        faces.extend(h.as_faces(surface.clone()));
-    }
-
-    println!("Faces: {}", faces.len());
-    for face in faces.iter() {
-        println!("Face: {}", face);
-    }
     faces
 }
@@ -3,7 +3,16 @@ use crate::{
     intersections::face_face::{face_face_intersection, FaceFaceIntersection},
     split_if_necessary::edge_split_face::split_faces_by_edges_if_necessary,
 };
-use geop_topology::topology::{edge::Edge, face::Face, volume::Volume};
+use geop_geometry::{
+    bounding_box::BoundingBox, curves::CurveLike, point::Point, surfaces::surface::Surface,
+};
+use geop_topology::{
+    contains::{
+        edge_point::{edge_point_contains, EdgePointContains},
+        volume_point::{volume_point_contains, VolumePointContains},
+    },
+    topology::{edge::Edge, face::Face, shell::Shell, volume::Volume},
+};
 
 // Points are ignored for now.
 pub fn volume_split_edges(volume_self: &Volume, volume_other: &Volume) -> Vec<Edge> {
@@ -79,3 +88,262 @@ pub fn volume_split(volume_self: &Volume, volume_other: &Volume) -> Vec<VolumeSp
         )
         .collect()
 }
+
+// Two faces are connected if an edge of one of them lies on an edge of the other.
+fn faces_share_edge(face_self: &Face, face_other: &Face) -> bool {
+    let edges_other = face_other.all_edges();
+    face_self.all_edges().iter().any(|edge| {
+        let m = edge.get_midpoint();
+        edges_other
+            .iter()
+            .any(|e| edge_point_contains(e, m) != EdgePointContains::Outside)
+    })
+}
+
+pub fn volume_remesh(mut faces_intermediate: Vec<VolumeSplit>) -> Vec<Shell> {
+    let mut faces = faces_intermediate
+        .drain(..)
+        .map(|f| match f {
+            VolumeSplit::AinB(face) => face,
+            VolumeSplit::AonBSameSide(face) => face,
+            VolumeSplit::AonBOpSide(face) => face,
+            VolumeSplit::AoutB(face) => face,
+            VolumeSplit::BinA(face) => face,
+            VolumeSplit::BonASameSide(face) => face,
+            VolumeSplit::BonAOpSide(face) => face,
+            VolumeSplit::BoutA(face) => face,
+        })
+        .collect::<Vec<Face>>();
+    // Now find all the shells
+    let mut shells = Vec::<Shell>::new();
+    while let Some(current_face) = faces.pop() {
+        let mut new_shell = vec![current_face];
+        let mut i = 0;
+        while i < new_shell.len() {
+            while let Some(next_i) = faces
+                .iter()
+                .position(|face| faces_share_edge(&new_shell[i], face))
+            {
+                new_shell.push(faces.remove(next_i));
+            }
+            i += 1;
+        }
+        shells.push(Shell::new(new_shell));
+    }
+
+    shells
+}
+
+// Checks if the shell lies inside of the volume bounded by the other shell.
+fn shell_inside_shell(shell: &Shell, boundary: &Shell) -> bool {
+    matches!(
+        volume_point_contains(
+            &Volume::new(boundary.clone(), vec![]),
+            shell.faces[0].inner_point(),
+        ),
+        VolumePointContains::Inside
+    )
+}
+
+// Box around the shell. Faces on closed surfaces can bulge past their edges, so the whole surface is added for them. Faces on the other surfaces lie within the hull of their edges.
+fn shell_bounding_box(shell: &Shell) -> BoundingBox {
+    let p = shell.faces[0].inner_point();
+    let mut bounding_box = BoundingBox::new(p, p);
+    let mut add_box = |other: BoundingBox| {
+        bounding_box.add_point(other.min);
+        bounding_box.add_point(other.max);
+    };
+    for face in shell.faces.iter() {
+        match face.surface.as_ref() {
+            Surface::Sphere(sphere) => {
+                let extent = Point::new(sphere.radius, sphere.radius, sphere.radius);
+                add_box(BoundingBox::new(
+                    sphere.basis - extent,
+                    sphere.basis + extent,
+                ));
+            }
+            Surface::Torus(torus) => {
+                let radius = torus.major_radius + torus.minor_radius;
+                let extent = Point::new(radius, radius, radius);
+                add_box(BoundingBox::new(torus.basis - extent, torus.basis + extent));
+            }
+            Surface::Ellipsoid(ellipsoid) => {
+                let [a, b, c] = ellipsoid.radii;
+                let extent = Point::new(
+                    Point::new(a.x, b.x, c.x).norm(),
+                    Point::new(a.y, b.y, c.y).norm(),
+                    Point::new(a.z, b.z, c.z).norm(),
+                );
+                add_box(BoundingBox::new(
+                    ellipsoid.basis - extent,
+                    ellipsoid.basis + extent,
+                ));
+            }
+            Surface::Nurbs(nurbs) => add_box(nurbs.get_bounding_box()),
+            _ => {}
+        }
+        // The edges of a closed shell are bounded.
+        for edge in face.all_edges() {
+            add_box(edge.curve.get_bounding_box(edge.start, edge.end).unwrap());
+        }
+    }
+    bounding_box
+}
+
+// Checks if the normals of the shell point away from the region it encloses. This is the case for the boundary of a volume, but not for its cavities.
+fn shell_is_outer(shell: &Shell) -> bool {
+    // A point beyond the bounding box is outside of the region.
+    let bounding_box = shell_bounding_box(shell);
+    let p = shell.faces[0].inner_point();
+    let far = p + Point::unit_x() * (bounding_box.max.x - p.x + bounding_box.max_size());
+    matches!(
+        volume_point_contains(&Volume::new(shell.clone(), vec![]), far),
+        VolumePointContains::Outside
+    )
+}
+
+// The shell with normals pointing away from the region it encloses, regardless of whether it is a boundary or a cavity.
+fn shell_region(shell: &Shell) -> Shell {
+    match shell_is_outer(shell) {
+        true => shell.clone(),
+        false => shell.flip(),
+    }
+}
+
+pub struct ShellHierarchy {
+    pub shell: Shell,
+    pub children: Vec<ShellHierarchy>,
+}
+
+impl ShellHierarchy {
+    // Add shell to this hierarchy if it is inside the region enclosed by this shell. Shells have to be consumed from the outside in, since a shell that encloses an already consumed one is not re-parented.
+    pub fn consume_shell_if_inside(&mut self, shell: Shell) -> Option<Shell> {
+        if !shell_inside_shell(&shell, &shell_region(&self.shell)) {
+            return Some(shell);
+        }
+        let mut shell = shell;
+        for child in self.children.iter_mut() {
+            match child.consume_shell_if_inside(shell) {
+                Some(s) => shell = s,
+                None => return None,
+            }
+        }
+        self.children.push(ShellHierarchy {
+            shell,
+            children: Vec::new(),
+        });
+        None
+    }
+
+    pub fn as_volumes(&self) -> Vec<Volume> {
+        let mut volumes = Vec::<Volume>::new();
+        let mut volume = Volume::new(self.shell.clone(), vec![]);
+        for child in self.children.iter() {
+            volume.cavities.push(child.shell.clone());
+            for child2 in child.children.iter() {
+                volumes.extend(child2.as_volumes());
+            }
+        }
+        volumes.push(volume);
+        volumes
+    }
+}
+
+pub fn normalize_volumes(shells: Vec<Shell>) -> Vec<Volume> {
+    // Sort the shells by the number of shells enclosing them, so that every shell is consumed after the shells around it.
+    let regions = shells.iter().map(shell_region).collect::<Vec<Shell>>();
+    let mut shells = shells
+        .into_iter()
+        .enumerate()
+        .map(|(i, shell)| {
+            let depth = regions
+                .iter()
+                .enumerate()
+                .filter(|(j, region)| i != *j && shell_inside_shell(&shell, region))
+                .count();
+            (depth, shell)
+        })
+        .collect::<Vec<(usize, Shell)>>();
+    shells.sort_by_key(|(depth, _)| *depth);
+
+    let mut hierarchies = Vec::<ShellHierarchy>::new();
+    'outer_loop: for (_, shell) in shells.into_iter() {
+        let mut shell = shell;
+        for h in hierarchies.iter_mut() {
+            match h.consume_shell_if_inside(shell) {
+                Some(s) => shell = s,
+                None => continue 'outer_loop,
+            }
+        }
+        hierarchies.push(ShellHierarchy {
+            shell,
+            children: Vec::new(),
+        });
+    }
+
+    let mut volumes = Vec::<Volume>::new();
+    for h in hierarchies.iter() {
+        volumes.extend(h.as_volumes());
+    }
+    volumes
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+    use geop_geometry::transforms::Transform;
+    use geop_topology::primitive_objects::volumes::cube::primitive_cube;
+
+    use super::*;
+
+    fn cube_shell(size: f64) -> Shell {
+        let size = EFloat64::from(size);
+        primitive_cube(size, size, size).boundary
+    }
+
+    #[test]
+    fn test_normalize_volumes_nested() {
+        // A solid with a cavity, which in turn contains a smaller solid.
+        let outer = cube_shell(4.0);
+        let cavity = cube_shell(2.0).flip();
+        let island = cube_shell(1.0);
+
+        let orders = [
+            vec![outer.clone(), cavity.clone(), island.clone()],
+            vec![island.clone(), cavity.clone(), outer.clone()],
+            vec![cavity.clone(), island.clone(), outer.clone()],
+            vec![island.clone(), outer.clone(), cavity.clone()],
+        ];
+        for shells in orders {
+            let mut volumes = normalize_volumes(shells);
+            volumes.sort_by_key(|volume| volume.cavities.len());
+            assert_eq!(volumes.len(), 2);
+            assert_eq!(volumes[0].cavities.len(), 0);
+            assert_eq!(volumes[1].cavities.len(), 1);
+            assert!(shell_is_outer(&volumes[0].boundary));
+            assert!(shell_is_outer(&volumes[1].boundary));
+            assert!(!shell_is_outer(&volumes[1].cavities[0]));
+        }
+    }
+
+    #[test]
+    fn test_normalize_volumes_separate() {
+        let shells = vec![
+            cube_shell(1.0),
+            cube_shell(1.0).transform(Transform::from_translation(Point::from_f64(3.0, 0.0, 0.0))),
+        ];
+        let volumes = normalize_volumes(shells);
+        assert_eq!(volumes.len(), 2);
+        assert!(volumes.iter().all(|volume| volume.cavities.is_empty()));
+    }
+
+    #[test]
+    fn test_shell_is_outer_large() {
+        // Much larger than any fixed distance would have to be.
+        let shell = cube_shell(1e8);
+        assert!(shell_is_outer(&shell));
+        assert!(!shell_is_outer(&shell.flip()));
+        let shell = shell.transform(Transform::from_translation(Point::from_f64(-3e8, 0.0, 0.0)));
+        assert!(shell_is_outer(&shell));
+    }
+}
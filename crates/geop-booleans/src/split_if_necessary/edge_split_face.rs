@@ -3,8 +3,13 @@
 //     split_if_necessary::point_split_edge::split_contours_by_points_if_necessary,
 // };
 
+use geop_geometry::point::Point;
 use geop_topology::{
-    contains::{contour_point::contour_point_contains, edge_point::EdgePointContains},
+    contains::{
+        contour_point::contour_point_contains,
+        edge_point::EdgePointContains,
+        face_point::{face_point_contains, FacePointContains},
+    },
     topology::{contour::Contour, edge::Edge, face::Face},
 };
 
@@ -15,28 +20,35 @@ use crate::{
 
 use super::point_split_edge::split_contours_by_points_if_necessary;
 
-pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
-    match face_edge_contains(face, edge) {
+// Splits a face along a path of connected edges, which has to lie inside the face.
+pub fn split_face_by_path_if_necessary(face: &Face, path: &[Edge]) -> Vec<Face> {
+    let start = path[0].start;
+    let end = path[path.len() - 1].end;
+    let path_flipped = path.iter().rev().map(|e| e.flip()).collect::<Vec<Edge>>();
+    match face_edge_contains(face, &path[0]) {
         FaceEdgeContains::Inside => {
-            if edge.start.is_none() || edge.end.is_none() {
+            if start.is_none() && end.is_none() {
+                return split_face_by_loop(face, path, &path_flipped);
+            }
+            if start.is_none() || end.is_none() {
                 todo!("Not yet implemented. Edge should have start and end points");
             }
 
-            let split_points = vec![edge.start.unwrap(), edge.end.unwrap()];
+            let split_points = vec![start.unwrap(), end.unwrap()];
 
             let contours = face.boundaries.clone();
             let contours = split_contours_by_points_if_necessary(contours, &split_points);
             let start_contour =
                 contours
                     .iter()
-                    .find(|&c| match contour_point_contains(c, edge.start.unwrap()) {
+                    .find(|&c| match contour_point_contains(c, start.unwrap()) {
                         EdgePointContains::OnPoint(_) => true,
                         _ => false,
                     });
             let end_contour =
                 contours
                     .iter()
-                    .find(|&c| match contour_point_contains(c, edge.end.unwrap()) {
+                    .find(|&c| match contour_point_contains(c, end.unwrap()) {
                         EdgePointContains::OnPoint(_) => true,
                         _ => false,
                     });
@@ -45,24 +57,14 @@ pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
                 Option::Some(start_contour) => match end_contour {
                     Option::Some(end_contour) => {
                         if std::ptr::eq(start_contour, end_contour) {
-                            println!("Start contour");
-                            for e in start_contour.edges.iter() {
-                                println!("{}", e);
-                            }
                             // Make it 2 contours
                             let mut edges =
-                                start_contour.get_subcurve(edge.end.unwrap(), edge.start.unwrap());
-                            println!("Edges");
-                            for e in edges.iter() {
-                                println!("{:}", e);
-                            }
-                            println!("Edge");
-                            edges.push(edge.clone());
-                            println!("{:}", edge);
+                                start_contour.get_subcurve(end.unwrap(), start.unwrap());
+                            edges.extend(path.iter().cloned());
                             new_contours.push(Contour::new(edges));
                             let mut edges =
-                                start_contour.get_subcurve(edge.start.unwrap(), edge.end.unwrap());
-                            edges.push(edge.flip());
+                                start_contour.get_subcurve(start.unwrap(), end.unwrap());
+                            edges.extend(path_flipped.iter().cloned());
                             new_contours.push(Contour::new(edges));
                             // Push the rest of the contours
                             for contour in contours.iter() {
@@ -72,11 +74,10 @@ pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
                             }
                         } else {
                             // Make it 1 contour
-                            let mut edges =
-                                start_contour.get_subcurve_single_point(edge.start.unwrap());
-                            edges.push(edge.clone());
-                            edges.extend(end_contour.get_subcurve_single_point(edge.end.unwrap()));
-                            edges.push(edge.flip());
+                            let mut edges = start_contour.get_subcurve_single_point(start.unwrap());
+                            edges.extend(path.iter().cloned());
+                            edges.extend(end_contour.get_subcurve_single_point(end.unwrap()));
+                            edges.extend(path_flipped.iter().cloned());
                             new_contours.push(Contour::new(edges));
                             // Push the rest of the contours
                             for contour in contours.iter() {
@@ -90,10 +91,9 @@ pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
                     }
                     Option::None => {
                         // Make it 1 contour
-                        let mut edges =
-                            start_contour.get_subcurve_single_point(edge.start.unwrap());
-                        edges.push(edge.clone());
-                        edges.push(edge.flip());
+                        let mut edges = start_contour.get_subcurve_single_point(start.unwrap());
+                        edges.extend(path.iter().cloned());
+                        edges.extend(path_flipped.iter().cloned());
                         new_contours.push(Contour::new(edges));
                         // Push the rest of the contours
                         for contour in contours.iter() {
@@ -106,9 +106,9 @@ pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
                 Option::None => match end_contour {
                     Option::Some(end_contour) => {
                         // Make it 1 contour
-                        let mut edges = end_contour.get_subcurve_single_point(edge.end.unwrap());
-                        edges.push(edge.flip());
-                        edges.push(edge.clone());
+                        let mut edges = end_contour.get_subcurve_single_point(end.unwrap());
+                        edges.extend(path_flipped.iter().cloned());
+                        edges.extend(path.iter().cloned());
                         new_contours.push(Contour::new(edges));
                         // Push the rest of the contours
                         for contour in contours.iter() {
@@ -119,7 +119,8 @@ pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
                     }
                     Option::None => {
                         // Make it 1 contour
-                        let edges = vec![edge.clone(), edge.flip()];
+                        let mut edges = path.to_vec();
+                        edges.extend(path_flipped.iter().cloned());
                         new_contours.push(Contour::new(edges));
                         // Push the rest of the contours
                         for contour in contours.iter() {
@@ -145,12 +146,106 @@ pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
     }
 }
 
+// Splits a face along a closed path inside of it. Every contour of the face ends up in the part on its side of the path.
+fn split_face_by_loop(face: &Face, path: &[Edge], path_flipped: &[Edge]) -> Vec<Face> {
+    // The loop alone does not need to bound a valid face, e.g. on a cylinder, so it is only used for the side test.
+    let left = Face {
+        boundaries: vec![Contour::new(path.to_vec())],
+        surface: face.surface.clone(),
+    };
+    let mut contours_left = Vec::<Contour>::new();
+    let mut contours_right = Vec::<Contour>::new();
+    for contour in face.boundaries.iter() {
+        match face_point_contains(&left, contour.edges[0].get_midpoint()) {
+            FacePointContains::Inside => contours_left.push(contour.clone()),
+            _ => contours_right.push(contour.clone()),
+        }
+    }
+    contours_left.push(Contour::new(path.to_vec()));
+    contours_right.push(Contour::new(path_flipped.to_vec()));
+    vec![
+        Face::new(contours_left, face.surface.clone()),
+        Face::new(contours_right, face.surface.clone()),
+    ]
+}
+
+pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
+    split_face_by_path_if_necessary(face, std::slice::from_ref(edge))
+}
+
+// Connects the edges that lie inside the face to paths. Edges are only joined at points inside the face that are shared by exactly two edges, so every path ends on the boundary of the face or at a branching point.
+fn face_inner_paths(face: &Face, edges: &[Edge]) -> Vec<Vec<Edge>> {
+    let mut inner = Vec::<Edge>::new();
+    for edge in edges {
+        if face_edge_contains(face, edge) == FaceEdgeContains::Inside && !inner.contains(edge) {
+            inner.push(edge.clone());
+        }
+    }
+
+    let all_inner = inner.clone();
+    let is_joint = |p: Option<Point>| -> bool {
+        match p {
+            Some(p) => {
+                face_point_contains(face, p) == FacePointContains::Inside
+                    && all_inner
+                        .iter()
+                        .filter(|e| e.start == Some(p) || e.end == Some(p))
+                        .count()
+                        == 2
+            }
+            None => false,
+        }
+    };
+
+    let mut paths = Vec::<Vec<Edge>>::new();
+    while let Some(edge) = inner.pop() {
+        let mut path = vec![edge];
+        loop {
+            let end = path[path.len() - 1].end;
+            if !is_joint(end) {
+                break;
+            }
+            match inner.iter().position(|e| e.start == end || e.end == end) {
+                Some(i) => {
+                    let next = inner.remove(i);
+                    match next.start == end {
+                        true => path.push(next),
+                        false => path.push(next.flip()),
+                    }
+                }
+                None => break,
+            }
+        }
+        loop {
+            let start = path[0].start;
+            if !is_joint(start) {
+                break;
+            }
+            match inner
+                .iter()
+                .position(|e| e.start == start || e.end == start)
+            {
+                Some(i) => {
+                    let prev = inner.remove(i);
+                    match prev.end == start {
+                        true => path.insert(0, prev),
+                        false => path.insert(0, prev.flip()),
+                    }
+                }
+                None => break,
+            }
+        }
+        paths.push(path);
+    }
+    paths
+}
+
 pub fn split_face_by_edges_if_necessary(face: &Face, edges: &[Edge]) -> Vec<Face> {
     let mut result = vec![face.clone()];
-    for c in edges {
+    for path in face_inner_paths(face, edges) {
         let mut new_result = Vec::<Face>::new();
         for face in result.iter() {
-            new_result.extend(split_face_by_edge_if_necessary(face, c));
+            new_result.extend(split_face_by_path_if_necessary(face, &path));
         }
         result = new_result;
     }
@@ -21,10 +21,22 @@ pub fn circle_line_intersection(circle: &Circle, line: &Line) -> CircleLineInter
         return CircleLineIntersection::None;
     }
 
-    assert!(
-        circle.normal.is_perpendicular(line.direction),
-        "3D circle-line intersection is not implemented yet"
-    );
+    // The line crosses the plane of the circle in one point.
+    if !circle.normal.is_perpendicular(line.direction) {
+        let t = ((circle.basis - line.basis).dot(circle.normal)
+            / line.direction.dot(circle.normal))
+        .unwrap();
+        let point = line.basis + line.direction * t;
+        if ((point - circle.basis).norm() - circle.radius.norm()) == 0.0 {
+            return CircleLineIntersection::OnePoint(point);
+        }
+        return CircleLineIntersection::None;
+    }
+
+    // The line is parallel to the plane of the circle, but not in it.
+    if (line.basis - circle.basis).dot(circle.normal) != 0.0 {
+        return CircleLineIntersection::None;
+    }
 
     let v = circle.basis - line.basis;
     let dir = line.direction.normalize().unwrap();
@@ -71,5 +83,22 @@ mod tests {
             }
             _ => panic!("Expected two point intersection"),
         }
+
+        // A line through the circle at an angle.
+        let l = Line::new(
+            Point::from_f64(1.0, -1.0, -1.0),
+            Point::from_f64(0.0, 1.0, 1.0).normalize().unwrap(),
+        )
+        .unwrap();
+        match circle_line_intersection(&c, &l) {
+            CircleLineIntersection::OnePoint(p) => assert_eq!(p, Point::from_f64(1.0, 0.0, 0.0)),
+            i => panic!("Expected one point intersection, got {:?}", i),
+        }
+        // A line parallel to the plane of the circle.
+        let l = Line::new(Point::from_f64(0.0, 0.0, 1.0), Point::unit_x()).unwrap();
+        assert!(matches!(
+            circle_line_intersection(&c, &l),
+            CircleLineIntersection::None
+        ));
     }
 }
@@ -1,12 +1,24 @@
-use crate::{curves::curve::Curve, point::Point};
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{
+        circle::Circle, curve::Curve, ellipse::Ellipse, helix::Helix, line::Line, nurbs::Nurbs,
+    },
+    point::Point,
+};
 
 use super::{
     circle_circle::{circle_circle_intersection, CircleCircleIntersection},
     circle_line::{circle_line_intersection, CircleLineIntersection},
+    ellipse_circle::{ellipse_circle_intersection, EllipseCircleIntersection},
     ellipse_ellipse::{ellipse_ellipse_intersection, EllipseEllipseIntersection},
+    ellipse_line::{ellipse_line_intersection, EllipseLineIntersection},
     helix_circle::{helix_circle_intersection, HelixCircleIntersection},
+    helix_ellipse::helix_ellipse_intersection,
+    helix_helix::{helix_helix_intersection, HelixHelixIntersection},
     helix_line::{helix_line_intersection, HelixLineIntersection},
     line_line::{line_line_intersection, LineLineIntersection},
+    nurbs_curve::nurbs_curve_intersection,
 };
 
 // This represents an infinite point array, [basis + n * extend_dir | n in Z]. This is for example used for the helix-line intersection.
@@ -16,6 +28,21 @@ pub struct PointArray {
     pub extend_dir: Point,
 }
 
+impl PointArray {
+    // The points of the array from start to end, where both are projected onto the array.
+    pub fn points_between(&self, start: Point, end: Point) -> Vec<Point> {
+        let index = |p: Point| {
+            let i = ((p - self.basis).dot(self.extend_dir) / self.extend_dir.norm_sq()).unwrap();
+            i.mid()
+        };
+        let (i, j) = (index(start), index(end));
+        let (first, last) = ((i.min(j) - 1e-9).ceil(), (i.max(j) + 1e-9).floor());
+        (first as i64..=last as i64)
+            .map(|n| self.basis + self.extend_dir * EFloat64::from(n as f64))
+            .collect()
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum CurveCurveIntersection {
     None,
@@ -24,6 +51,68 @@ pub enum CurveCurveIntersection {
     Curve(Curve),
 }
 
+fn ellipse_line(ellipse: &Ellipse, line: &Line) -> CurveCurveIntersection {
+    match ellipse_line_intersection(ellipse, line) {
+        EllipseLineIntersection::None => CurveCurveIntersection::None,
+        EllipseLineIntersection::OnePoint(p) => CurveCurveIntersection::FinitePoints(vec![p]),
+        EllipseLineIntersection::TwoPoint(p1, p2) => {
+            CurveCurveIntersection::FinitePoints(vec![p1, p2])
+        }
+    }
+}
+
+fn ellipse_circle(ellipse: &Ellipse, circle: &Circle) -> CurveCurveIntersection {
+    match ellipse_circle_intersection(ellipse, circle) {
+        EllipseCircleIntersection::Circle(c) => CurveCurveIntersection::Curve(Curve::Circle(c)),
+        EllipseCircleIntersection::OnePoint(p0) => CurveCurveIntersection::FinitePoints(vec![p0]),
+        EllipseCircleIntersection::TwoPoint(p0, p1) => {
+            CurveCurveIntersection::FinitePoints(vec![p0, p1])
+        }
+        EllipseCircleIntersection::ThreePoint(p0, p1, p2) => {
+            CurveCurveIntersection::FinitePoints(vec![p0, p1, p2])
+        }
+        EllipseCircleIntersection::FourPoint(p0, p1, p2, p3) => {
+            CurveCurveIntersection::FinitePoints(vec![p0, p1, p2, p3])
+        }
+        EllipseCircleIntersection::None => CurveCurveIntersection::None,
+    }
+}
+
+fn helix_line(helix: &Helix, line: &Line) -> CurveCurveIntersection {
+    match helix_line_intersection(helix, line) {
+        HelixLineIntersection::PointArray(point_array) => {
+            CurveCurveIntersection::InfiniteDiscretePoints(point_array)
+        }
+        HelixLineIntersection::TwoPoint(p, q) => CurveCurveIntersection::FinitePoints(vec![p, q]),
+        HelixLineIntersection::OnePoint(p) => CurveCurveIntersection::FinitePoints(vec![p]),
+        HelixLineIntersection::None => CurveCurveIntersection::None,
+    }
+}
+
+fn helix_circle(helix: &Helix, circle: &Circle) -> CurveCurveIntersection {
+    match helix_circle_intersection(helix, circle) {
+        HelixCircleIntersection::Points(points) => CurveCurveIntersection::FinitePoints(points),
+        HelixCircleIntersection::OnePoint(p) => CurveCurveIntersection::FinitePoints(vec![p]),
+        HelixCircleIntersection::None => CurveCurveIntersection::None,
+    }
+}
+
+fn helix_ellipse(helix: &Helix, ellipse: &Ellipse) -> CurveCurveIntersection {
+    let points = helix_ellipse_intersection(helix, ellipse);
+    if points.is_empty() {
+        return CurveCurveIntersection::None;
+    }
+    CurveCurveIntersection::FinitePoints(points)
+}
+
+fn nurbs_curve(nurbs: &Nurbs, other: &Curve) -> CurveCurveIntersection {
+    let points = nurbs_curve_intersection(nurbs, other);
+    if points.is_empty() {
+        return CurveCurveIntersection::None;
+    }
+    CurveCurveIntersection::FinitePoints(points)
+}
+
 pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveCurveIntersection {
     match edge_self {
         Curve::Line(line) => match edge_other {
@@ -41,15 +130,9 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                     CurveCurveIntersection::FinitePoints(vec![p1, p2])
                 }
             },
-            Curve::Ellipse(_) => todo!("Implement this"),
-            Curve::Helix(helix) => match helix_line_intersection(helix, line) {
-                HelixLineIntersection::PointArray(point_array) => {
-                    CurveCurveIntersection::InfiniteDiscretePoints(point_array)
-                }
-                HelixLineIntersection::TwoPoint(p) => CurveCurveIntersection::FinitePoints(vec![p]),
-                HelixLineIntersection::OnePoint(p) => CurveCurveIntersection::FinitePoints(vec![p]),
-                HelixLineIntersection::None => CurveCurveIntersection::None,
-            },
+            Curve::Ellipse(ellipse) => ellipse_line(ellipse, line),
+            Curve::Helix(helix) => helix_line(helix, line),
+            Curve::Nurbs(nurbs) => nurbs_curve(nurbs, edge_self),
         },
         Curve::Circle(circle) => match edge_other {
             Curve::Line(other_line) => match circle_line_intersection(circle, other_line) {
@@ -73,20 +156,13 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                     CurveCurveIntersection::Curve(Curve::Circle(c))
                 }
             },
-            Curve::Ellipse(_) => todo!("Implement this"),
-            Curve::Helix(helix) => match helix_circle_intersection(helix, circle) {
-                HelixCircleIntersection::TwoPoints(p1, p2) => {
-                    CurveCurveIntersection::FinitePoints(vec![p1, p2])
-                }
-                HelixCircleIntersection::OnePoint(p) => {
-                    CurveCurveIntersection::FinitePoints(vec![p])
-                }
-                HelixCircleIntersection::None => CurveCurveIntersection::None,
-            },
+            Curve::Ellipse(ellipse) => ellipse_circle(ellipse, circle),
+            Curve::Helix(helix) => helix_circle(helix, circle),
+            Curve::Nurbs(nurbs) => nurbs_curve(nurbs, edge_self),
         },
         Curve::Ellipse(ellipse) => match edge_other {
-            Curve::Line(_) => todo!("Implement this"),
-            Curve::Circle(_) => todo!("Implement this"),
+            Curve::Line(line) => ellipse_line(ellipse, line),
+            Curve::Circle(circle) => ellipse_circle(ellipse, circle),
             Curve::Ellipse(other_ellipse) => {
                 match ellipse_ellipse_intersection(ellipse, other_ellipse) {
                     EllipseEllipseIntersection::Ellipse(ellipse) => {
@@ -107,8 +183,27 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                     EllipseEllipseIntersection::None => CurveCurveIntersection::None,
                 }
             }
-            Curve::Helix(_) => todo!("Implement this"),
+            Curve::Helix(helix) => helix_ellipse(helix, ellipse),
+            Curve::Nurbs(nurbs) => nurbs_curve(nurbs, edge_self),
+        },
+        Curve::Helix(helix) => match edge_other {
+            Curve::Line(line) => helix_line(helix, line),
+            Curve::Circle(circle) => helix_circle(helix, circle),
+            Curve::Ellipse(ellipse) => helix_ellipse(helix, ellipse),
+            Curve::Helix(other_helix) => match helix_helix_intersection(helix, other_helix) {
+                HelixHelixIntersection::Helix(helix) => {
+                    CurveCurveIntersection::Curve(Curve::Helix(helix))
+                }
+                HelixHelixIntersection::PointArray(point_array) => {
+                    CurveCurveIntersection::InfiniteDiscretePoints(point_array)
+                }
+                HelixHelixIntersection::Points(points) => {
+                    CurveCurveIntersection::FinitePoints(points)
+                }
+                HelixHelixIntersection::None => CurveCurveIntersection::None,
+            },
+            Curve::Nurbs(nurbs) => nurbs_curve(nurbs, edge_self),
         },
-        Curve::Helix(_) => todo!("Implement this"),
+        Curve::Nurbs(nurbs) => nurbs_curve(nurbs, edge_other),
     }
 }
@@ -0,0 +1,102 @@
+use crate::{
+    curves::{circle::Circle, ellipse::Ellipse, CurveLike},
+    point::Point,
+};
+
+#[derive(Debug)]
+pub enum EllipseCircleIntersection {
+    Circle(Circle),
+    OnePoint(Point),
+    TwoPoint(Point, Point),
+    ThreePoint(Point, Point, Point),
+    FourPoint(Point, Point, Point, Point),
+    None,
+}
+
+pub fn ellipse_circle_intersection(
+    ellipse: &Ellipse,
+    circle: &Circle,
+) -> EllipseCircleIntersection {
+    // The points of the ellipse in the plane of the circle.
+    let points = match ellipse.implicit_intersection(circle.basis, |e| e.dot(circle.normal)) {
+        Some(points) => points.into_iter().filter(|p| circle.on_curve(*p)).collect(),
+        // If they are in the same plane, the circle is where the ellipse meets the sphere around it.
+        None => {
+            let radius_sq = circle.radius.norm_sq();
+            match ellipse.implicit_intersection(circle.basis, |e| {
+                &e.norm_sq() - &(&(&e.w * &e.w) * radius_sq)
+            }) {
+                Some(points) => points,
+                None => return EllipseCircleIntersection::Circle(circle.clone()),
+            }
+        }
+    };
+    match points.len() {
+        0 => EllipseCircleIntersection::None,
+        1 => EllipseCircleIntersection::OnePoint(points[0]),
+        2 => EllipseCircleIntersection::TwoPoint(points[0], points[1]),
+        3 => EllipseCircleIntersection::ThreePoint(points[0], points[1], points[2]),
+        4 => EllipseCircleIntersection::FourPoint(points[0], points[1], points[2], points[3]),
+        _ => panic!("Unexpected number of intersection points"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use super::*;
+
+    #[test]
+    fn test_ellipse_circle_intersection() {
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        // A circle between the radii crosses the ellipse four times.
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::from(1.5));
+        match ellipse_circle_intersection(&ellipse, &circle) {
+            EllipseCircleIntersection::FourPoint(p0, p1, p2, p3) => {
+                for p in [p0, p1, p2, p3] {
+                    assert!(ellipse.on_curve(p) && circle.on_curve(p));
+                }
+            }
+            i => panic!("Expected four point intersection, got {:?}", i),
+        }
+        // The inscribed circle touches the ellipse at the ends of the minor axis.
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::one());
+        match ellipse_circle_intersection(&ellipse, &circle) {
+            EllipseCircleIntersection::TwoPoint(p0, p1) => {
+                assert!(p0 == Point::unit_y() || p0 == -Point::unit_y());
+                assert!(p1 == Point::unit_y() || p1 == -Point::unit_y());
+            }
+            i => panic!("Expected two point intersection, got {:?}", i),
+        }
+        // A circle standing upright on the major axis.
+        let circle = Circle::new(
+            Point::from_f64(2.0, 0.0, 1.0),
+            Point::unit_y(),
+            EFloat64::one(),
+        );
+        match ellipse_circle_intersection(&ellipse, &circle) {
+            EllipseCircleIntersection::OnePoint(p) => {
+                assert_eq!(p, Point::from_f64(2.0, 0.0, 0.0))
+            }
+            i => panic!("Expected one point intersection, got {:?}", i),
+        }
+        // An ellipse with equal radii is a circle.
+        let round = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::one());
+        assert!(matches!(
+            ellipse_circle_intersection(&round, &circle),
+            EllipseCircleIntersection::Circle(_)
+        ));
+    }
+}
@@ -1,6 +1,10 @@
-use crate::{curves::ellipse::Ellipse, point::Point};
+use geop_algebra::{efloat::EFloat64, interval_newton::interval_newton, matrix::Matrix};
 
-use super::numerical::curve_curve_intersection_numerical;
+use crate::{
+    curves::{ellipse::Ellipse, CurveLike},
+    point::Point,
+    tolerance::Tolerance,
+};
 
 pub enum EllipseEllipseIntersection {
     Ellipse(Ellipse),
@@ -11,6 +15,80 @@ pub enum EllipseEllipseIntersection {
     None,
 }
 
+fn point_at(ellipse: &Ellipse, angle: EFloat64) -> Point {
+    ellipse.basis + ellipse.major_radius * angle.cos() + ellipse.minor_radius * angle.sin()
+}
+
+fn tangent_at(ellipse: &Ellipse, angle: EFloat64) -> Point {
+    ellipse.minor_radius * angle.cos() - ellipse.major_radius * angle.sin()
+}
+
+// Merges boxes that are closer than the gap into their hull.
+fn merge_boxes(boxes: &[Vec<EFloat64>], gap: f64) -> Vec<Vec<EFloat64>> {
+    let touch = |a: &[EFloat64], b: &[EFloat64]| {
+        a.iter().zip(b.iter()).all(|(a, b)| {
+            a.lower_bound <= b.upper_bound + gap && b.lower_bound <= a.upper_bound + gap
+        })
+    };
+    let mut merged: Vec<Vec<EFloat64>> = Vec::new();
+    for x in boxes {
+        let mut hull = x.clone();
+        while let Some(i) = merged.iter().position(|m| touch(m, &hull)) {
+            let m = merged.remove(i);
+            hull = hull
+                .iter()
+                .zip(m.iter())
+                .map(|(a, b)| {
+                    EFloat64::new(
+                        a.upper_bound.max(b.upper_bound),
+                        a.lower_bound.min(b.lower_bound),
+                    )
+                })
+                .collect();
+        }
+        merged.push(hull);
+    }
+    merged
+}
+
+// Ellipses in the same plane meet where the difference of their points vanishes in the coordinates of the plane.
+// These roots are enclosed in the angles of both ellipses with the Krawczyk operator.
+fn coplanar_ellipse_intersection(ellipse_self: &Ellipse, ellipse_other: &Ellipse) -> Vec<Point> {
+    let (u, v) = (ellipse_other.major_radius, ellipse_other.minor_radius);
+    let f = |x: &[EFloat64]| {
+        let d = point_at(ellipse_self, x[0]) - point_at(ellipse_other, x[1]);
+        vec![d.dot(u), d.dot(v)]
+    };
+    let jacobian = |x: &[EFloat64]| {
+        let d_self = tangent_at(ellipse_self, x[0]);
+        let d_other = -tangent_at(ellipse_other, x[1]);
+        Matrix::new(vec![
+            vec![d_self.dot(u), d_other.dot(u)],
+            vec![d_self.dot(v), d_other.dot(v)],
+        ])
+    };
+    let two_pi = EFloat64::two_pi().upper_bound;
+    let domain = vec![EFloat64::new(two_pi, 0.0); 2];
+    let precision = Tolerance::current().parametric * two_pi;
+    let solution = interval_newton(f, jacobian, domain, precision);
+
+    // Touching ellipses leave clusters of undecided boxes. The double root is only determined up to about the square root of the precision, so closer clusters are merged to one point.
+    // Roots at angle 0 are found again at 2 pi, which gives overlapping points.
+    let mut points: Vec<Point> = Vec::new();
+    for x in solution
+        .unique
+        .iter()
+        .cloned()
+        .chain(merge_boxes(&solution.possible, precision.sqrt()))
+    {
+        let p = point_at(ellipse_self, x[0]);
+        if !points.contains(&p) {
+            points.push(p);
+        }
+    }
+    points
+}
+
 pub fn ellipse_ellipse_intersection(
     ellipse_self: &Ellipse,
     ellipse_other: &Ellipse,
@@ -19,7 +97,16 @@ pub fn ellipse_ellipse_intersection(
         return EllipseEllipseIntersection::Ellipse(ellipse_self.clone());
     }
 
-    let intersection_points = curve_curve_intersection_numerical(ellipse_self, ellipse_other);
+    // The points of the ellipse in the plane of the other one.
+    let intersection_points = match ellipse_self
+        .implicit_intersection(ellipse_other.basis, |e| e.dot(ellipse_other.normal))
+    {
+        Some(points) => points
+            .into_iter()
+            .filter(|p| ellipse_other.on_curve(*p))
+            .collect(),
+        None => coplanar_ellipse_intersection(ellipse_self, ellipse_other),
+    };
     match intersection_points.len() {
         0 => EllipseEllipseIntersection::None,
         1 => EllipseEllipseIntersection::OnePoint(intersection_points[0]),
@@ -38,3 +125,66 @@ pub fn ellipse_ellipse_intersection(
         _ => panic!("Unexpected number of intersection points"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(intersection: EllipseEllipseIntersection) -> Vec<Point> {
+        match intersection {
+            EllipseEllipseIntersection::Ellipse(_) => panic!("Expected points"),
+            EllipseEllipseIntersection::OnePoint(p1) => vec![p1],
+            EllipseEllipseIntersection::TwoPoint(p1, p2) => vec![p1, p2],
+            EllipseEllipseIntersection::ThreePoint(p1, p2, p3) => vec![p1, p2, p3],
+            EllipseEllipseIntersection::FourPoint(p1, p2, p3, p4) => vec![p1, p2, p3, p4],
+            EllipseEllipseIntersection::None => vec![],
+        }
+    }
+
+    #[test]
+    fn test_ellipse_ellipse_intersection() {
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+        );
+        // Rotated by 90 degrees, the ellipses cross where |x| = |y| = 2 / sqrt(5).
+        let rotated = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(0.0, 2.0, 0.0),
+            -Point::unit_x(),
+        );
+        let points_coplanar = points(ellipse_ellipse_intersection(&ellipse, &rotated));
+        assert_eq!(points_coplanar.len(), 4);
+        let c = 2.0 / 5.0f64.sqrt();
+        for p in points_coplanar {
+            assert!(p.x.abs() == c && p.y.abs() == c && p.z == 0.0);
+        }
+
+        // Shifted along the major axis, the ellipses touch at (2, 0, 0) and cross twice.
+        let shifted = Ellipse::new(
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::unit_z(),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        let points_touching = points(ellipse_ellipse_intersection(&ellipse, &shifted));
+        assert_eq!(points_touching.len(), 3);
+        assert!(points_touching
+            .iter()
+            .any(|p| (*p - Point::from_f64(2.0, 0.0, 0.0)).norm() < 1e-6));
+
+        // An ellipse standing upright on the x axis crosses the other one in the plane y = 0.
+        let upright = Ellipse::new(
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::unit_y(),
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::from_f64(1.0, 0.0, 0.0),
+        );
+        let points_upright = points(ellipse_ellipse_intersection(&ellipse, &upright));
+        assert_eq!(points_upright.len(), 1);
+        assert_eq!(points_upright[0], Point::from_f64(2.0, 0.0, 0.0));
+    }
+}
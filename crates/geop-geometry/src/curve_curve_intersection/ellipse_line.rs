@@ -0,0 +1,89 @@
+use crate::{
+    curves::{ellipse::Ellipse, line::Line, CurveLike},
+    point::Point,
+    tolerance::Tolerance,
+};
+
+#[derive(Debug)]
+pub enum EllipseLineIntersection {
+    TwoPoint(Point, Point),
+    OnePoint(Point),
+    None,
+}
+
+pub fn ellipse_line_intersection(ellipse: &Ellipse, line: &Line) -> EllipseLineIntersection {
+    // The line crosses the plane of the ellipse in one point.
+    if !ellipse.normal.is_perpendicular(line.direction) {
+        let t = ((ellipse.basis - line.basis).dot(ellipse.normal)
+            / line.direction.dot(ellipse.normal))
+        .unwrap();
+        let point = line.basis + line.direction * t;
+        if ellipse.on_curve(point) {
+            return EllipseLineIntersection::OnePoint(point);
+        }
+        return EllipseLineIntersection::None;
+    }
+
+    // The line is parallel to the plane of the ellipse, but not in it.
+    if !Tolerance::current().is_zero_length((line.basis - ellipse.basis).dot(ellipse.normal)) {
+        return EllipseLineIntersection::None;
+    }
+
+    // In the plane, the line is where the distance along the normal of the line vanishes.
+    let normal = ellipse.normal.cross(line.direction);
+    let mut points = ellipse
+        .implicit_intersection(line.basis, |e| e.dot(normal))
+        .unwrap();
+    points.sort_by(|a, b| {
+        let a = (*a - line.basis).dot(line.direction);
+        let b = (*b - line.basis).dot(line.direction);
+        a.lower_bound.total_cmp(&b.lower_bound)
+    });
+    match points.len() {
+        0 => EllipseLineIntersection::None,
+        1 => EllipseLineIntersection::OnePoint(points[0]),
+        2 => EllipseLineIntersection::TwoPoint(points[0], points[1]),
+        _ => panic!("Unexpected number of intersection points"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ellipse_line_intersection() {
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        // A line through the center along the major axis.
+        let line = Line::new(Point::from_f64(-5.0, 0.0, 0.0), Point::unit_x()).unwrap();
+        match ellipse_line_intersection(&ellipse, &line) {
+            EllipseLineIntersection::TwoPoint(p1, p2) => {
+                assert_eq!(p1, Point::from_f64(-2.0, 0.0, 0.0));
+                assert_eq!(p2, Point::from_f64(2.0, 0.0, 0.0));
+            }
+            i => panic!("Expected two point intersection, got {:?}", i),
+        }
+        // A tangent line at the end of the minor axis.
+        let line = Line::new(Point::from_f64(0.0, 1.0, 0.0), Point::unit_x()).unwrap();
+        match ellipse_line_intersection(&ellipse, &line) {
+            EllipseLineIntersection::OnePoint(p) => assert_eq!(p, Point::from_f64(0.0, 1.0, 0.0)),
+            i => panic!("Expected one point intersection, got {:?}", i),
+        }
+        // A line crossing the plane on the ellipse and one outside of it.
+        let line = Line::new(Point::from_f64(2.0, 0.0, -1.0), Point::unit_z()).unwrap();
+        assert!(matches!(
+            ellipse_line_intersection(&ellipse, &line),
+            EllipseLineIntersection::OnePoint(_)
+        ));
+        let line = Line::new(Point::from_f64(0.0, 1.5, 0.0), Point::unit_x()).unwrap();
+        assert!(matches!(
+            ellipse_line_intersection(&ellipse, &line),
+            EllipseLineIntersection::None
+        ));
+    }
+}
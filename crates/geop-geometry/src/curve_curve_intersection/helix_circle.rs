@@ -1,10 +1,12 @@
 use crate::{
-    curves::{circle::Circle, helix::Helix},
+    curves::{circle::Circle, ellipse::Ellipse, helix::Helix},
     point::Point,
 };
 
+use super::helix_ellipse::helix_ellipse_intersection;
+
 pub enum HelixCircleIntersection {
-    TwoPoints(Point, Point),
+    Points(Vec<Point>),
     OnePoint(Point),
     None,
 }
@@ -22,5 +24,48 @@ pub fn helix_circle_intersection(helix: &Helix, circle: &Circle) -> HelixCircleI
         }
     }
 
-    todo!()
+    // Otherwise the circle is an ellipse with equal radii.
+    let ellipse = Ellipse::new(
+        circle.basis,
+        circle.normal,
+        circle.radius,
+        circle.normal.cross(circle.radius),
+    );
+    match helix_ellipse_intersection(helix, &ellipse).as_slice() {
+        [] => HelixCircleIntersection::None,
+        [p] => HelixCircleIntersection::OnePoint(*p),
+        points => HelixCircleIntersection::Points(points.to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use super::*;
+
+    #[test]
+    fn test_helix_circle_intersection() {
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        // In the plane x = 1 / 2 the helix is at y = sqrt(3) / 2 for z = 1 / 6 + n and at y = -sqrt(3) / 2 for z = -1 / 6 + n.
+        // A circle in this plane through the points closest to its center.
+        let circle = Circle::new(
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::unit_x(),
+            EFloat64::from(28.0f64.sqrt() / 6.0),
+        );
+        match helix_circle_intersection(&helix, &circle) {
+            HelixCircleIntersection::Points(points) => {
+                assert_eq!(points.len(), 2);
+                let y = 3.0f64.sqrt() / 2.0;
+                for p in [
+                    Point::from_f64(0.5, y, 1.0 / 6.0),
+                    Point::from_f64(0.5, -y, -1.0 / 6.0),
+                ] {
+                    assert!(points.iter().any(|q| (*q - p).norm() < 1e-9));
+                }
+            }
+            _ => panic!("Expected points"),
+        }
+    }
 }
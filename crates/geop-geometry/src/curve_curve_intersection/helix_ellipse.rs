@@ -0,0 +1,79 @@
+use crate::{
+    curve_surface_intersection::helix_plane::{helix_plane_intersection, HelixPlaneIntersection},
+    curves::{ellipse::Ellipse, helix::Helix, CurveLike},
+    point::Point,
+    surfaces::plane::Plane,
+    tolerance::Tolerance,
+};
+
+// Whether a point that was found numerically is on the curve, up to the precision of the root finding.
+pub(super) fn on_curve_numerical(curve: &dyn CurveLike, p: Point) -> bool {
+    let tolerance = Tolerance::current();
+    Tolerance {
+        linear: tolerance.linear.max(tolerance.numerical),
+        ..tolerance
+    }
+    .apply(|| curve.on_curve(p))
+}
+
+// The intersections are the points where the helix crosses the plane of the ellipse, and which are on the ellipse.
+pub fn helix_ellipse_intersection(helix: &Helix, ellipse: &Ellipse) -> Vec<Point> {
+    let plane = Plane::new(ellipse.basis, ellipse.major_radius, ellipse.minor_radius);
+    let candidates = match helix_plane_intersection(helix, &plane) {
+        HelixPlaneIntersection::Points(points) => points,
+        // The ellipse only meets the few points of each array within its bounding sphere.
+        HelixPlaneIntersection::PointArrays(arrays) => {
+            let extend = ellipse.major_radius.norm() + ellipse.minor_radius.norm();
+            arrays
+                .iter()
+                .flat_map(|array| {
+                    let direction = array.extend_dir.normalize().unwrap();
+                    let closest =
+                        array.basis + direction * (ellipse.basis - array.basis).dot(direction);
+                    array.points_between(closest - direction * extend, closest + direction * extend)
+                })
+                .collect()
+        }
+        HelixPlaneIntersection::None => Vec::new(),
+    };
+    candidates
+        .into_iter()
+        .filter(|p| on_curve_numerical(ellipse, *p))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use super::*;
+
+    #[test]
+    fn test_helix_ellipse_intersection() {
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        // In the plane x = 1 / 2 the helix is at y = sqrt(3) / 2 for z = 1 / 6 + n and at y = -sqrt(3) / 2 for z = -1 / 6 + n.
+        let ellipse = Ellipse::new(
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::unit_x(),
+            Point::unit_y(),
+            Point::from_f64(0.0, 0.0, 1.0 / 3.0),
+        );
+        let points = helix_ellipse_intersection(&helix, &ellipse);
+        assert_eq!(points.len(), 2);
+        for p in points {
+            assert!(helix.on_curve(p));
+            assert!((p.y * p.z - EFloat64::from(3.0f64.sqrt() / 12.0)).abs() < 1e-9);
+        }
+
+        // Across the axis, the ellipse is crossed where the helix passes through the plane.
+        let ellipse = Ellipse::new(
+            Point::from_f64(0.0, 0.0, 0.25),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+        );
+        let points = helix_ellipse_intersection(&helix, &ellipse);
+        assert_eq!(points.len(), 1);
+        assert!((points[0] - Point::from_f64(0.0, 1.0, 0.25)).norm() < 1e-9);
+    }
+}
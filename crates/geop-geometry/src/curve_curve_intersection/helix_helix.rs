@@ -0,0 +1,195 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curve_surface_intersection::helix_cylinder::{
+        helix_cylinder_intersection, HelixCylinderIntersection,
+    },
+    curves::{helix::Helix, CurveLike},
+    point::Point,
+    surfaces::cylinder::Cylinder,
+    tolerance::Tolerance,
+    HORIZON_DIST,
+};
+
+use super::{curve_curve::PointArray, helix_ellipse::on_curve_numerical};
+
+pub enum HelixHelixIntersection {
+    Helix(Helix),
+    PointArray(PointArray),
+    Points(Vec<Point>),
+    None,
+}
+
+// Turns per length along the axis, signed by the winding.
+fn turns_per_length(helix: &Helix) -> f64 {
+    let turns = 1.0 / helix.pitch.norm().mid();
+    match helix.right_winding {
+        true => turns,
+        false => -turns,
+    }
+}
+
+pub fn helix_helix_intersection(helix: &Helix, other: &Helix) -> HelixHelixIntersection {
+    let cylinder = Cylinder::new(other.basis, other.pitch, other.radius.norm(), true);
+    if !helix.pitch.is_parallel(other.pitch) {
+        return match helix_cylinder_intersection(helix, &cylinder) {
+            HelixCylinderIntersection::Points(points) => {
+                let points: Vec<Point> = points
+                    .into_iter()
+                    .filter(|p| on_curve_numerical(other, *p))
+                    .collect();
+                match points.is_empty() {
+                    true => HelixHelixIntersection::None,
+                    false => HelixHelixIntersection::Points(points),
+                }
+            }
+            _ => HelixHelixIntersection::None,
+        };
+    }
+
+    let axis = helix.pitch.normalize().unwrap();
+    let offset = other.basis - helix.basis;
+    let (turns, other_turns) = (turns_per_length(helix), turns_per_length(other));
+    let same_turns = (turns - other_turns).abs() <= 1e-12 * turns.abs();
+
+    // Coaxial helices with the same radius meet where their angles around the axis agree.
+    if Tolerance::current().is_zero_length((offset - axis * offset.dot(axis)).norm()) {
+        if !Tolerance::current().is_zero_length(helix.radius.norm() - other.radius.norm()) {
+            return HelixHelixIntersection::None;
+        }
+        if same_turns {
+            return match other.on_curve(helix.point_at_pitch(EFloat64::zero())) {
+                true => HelixHelixIntersection::Helix(helix.clone()),
+                false => HelixHelixIntersection::None,
+            };
+        }
+        // The angle of the helix at height z is 2 pi * turns * z, and that of the other 2 pi * other_turns * (z - height) + phase.
+        let phase = helix.radius.angle2(other.radius, axis).unwrap().mid();
+        let height = offset.dot(axis).mid();
+        let z =
+            (phase / (2.0 * std::f64::consts::PI) - other_turns * height) / (turns - other_turns);
+        let t = EFloat64::from(z) / helix.pitch.norm();
+        return HelixHelixIntersection::PointArray(PointArray {
+            basis: helix.point_at_pitch(t.unwrap()),
+            extend_dir: axis * EFloat64::from(1.0 / (turns - other_turns).abs()),
+        });
+    }
+
+    // With different pitches, the helix meets the other cylinder in point arrays, on which the other helix only reaches some of the points. These are not periodic in general, so they are listed up to the horizon.
+    if !same_turns {
+        let points: Vec<Point> = match helix_cylinder_intersection(helix, &cylinder) {
+            HelixCylinderIntersection::PointArrays(arrays) => arrays
+                .iter()
+                .flat_map(|array| {
+                    let direction = array.extend_dir.normalize().unwrap();
+                    let closest =
+                        array.basis + direction * (helix.basis - array.basis).dot(direction);
+                    let horizon = direction * EFloat64::from(HORIZON_DIST);
+                    array.points_between(closest - horizon, closest + horizon)
+                })
+                .filter(|p| on_curve_numerical(other, *p))
+                .collect(),
+            _ => Vec::new(),
+        };
+        return match points.is_empty() {
+            true => HelixHelixIntersection::None,
+            false => HelixHelixIntersection::Points(points),
+        };
+    }
+
+    // Parallel helices with the same pitch repeat their relative position every turn.
+    match helix_cylinder_intersection(helix, &cylinder) {
+        HelixCylinderIntersection::PointArrays(arrays) => arrays
+            .into_iter()
+            .find(|array| on_curve_numerical(other, array.basis))
+            .map_or(HelixHelixIntersection::None, |array| {
+                HelixHelixIntersection::PointArray(array)
+            }),
+        _ => HelixHelixIntersection::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helix() -> Helix {
+        Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true)
+    }
+
+    #[test]
+    fn test_helix_helix_intersection() {
+        let shifted = Helix::new(Point::unit_z(), Point::unit_z(), Point::unit_x(), true);
+        assert!(matches!(
+            helix_helix_intersection(&helix(), &shifted),
+            HelixHelixIntersection::Helix(_)
+        ));
+
+        // With half the speed, the coaxial helix meets the other every second height.
+        let slow = Helix::new(
+            Point::zero(),
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_x(),
+            true,
+        );
+        match helix_helix_intersection(&helix(), &slow) {
+            HelixHelixIntersection::PointArray(array) => {
+                assert!((array.basis - Point::unit_x()).norm() < 1e-9);
+                assert!((array.extend_dir - Point::from_f64(0.0, 0.0, 2.0)).norm() < 1e-9);
+            }
+            _ => panic!("Expected a point array"),
+        }
+
+        // A parallel helix through the axis, turned by 60 degrees, meets the helix once every turn.
+        let parallel = Helix::new(
+            Point::unit_x(),
+            Point::unit_z(),
+            Point::from_f64(0.5, 3.0f64.sqrt() / 2.0, 0.0),
+            true,
+        );
+        match helix_helix_intersection(&helix(), &parallel) {
+            HelixHelixIntersection::PointArray(array) => {
+                let expected = Point::from_f64(0.5, 3.0f64.sqrt() / 2.0, 1.0 / 6.0);
+                assert!((array.basis - expected).norm() < 1e-9);
+                assert_eq!(array.extend_dir, Point::unit_z());
+            }
+            _ => panic!("Expected a point array"),
+        }
+
+        // A parallel helix through the axis with twice the pitch meets the helix every second turn on both sides.
+        let y = 3.0f64.sqrt() / 2.0;
+        let steep = Helix::new(
+            Point::from_f64(1.0, 0.0, 1.0 / 6.0),
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::from_f64(-0.5, y, 0.0),
+            true,
+        );
+        match helix_helix_intersection(&helix(), &steep) {
+            HelixHelixIntersection::Points(points) => {
+                let found = |p: Point| points.iter().any(|q| (*q - p).norm() < 1e-9);
+                assert!(found(Point::from_f64(0.5, y, 1.0 / 6.0)));
+                assert!(found(Point::from_f64(0.5, y, 13.0 / 6.0)));
+                assert!(found(Point::from_f64(0.5, -y, 5.0 / 6.0)));
+                assert!(!found(Point::from_f64(0.5, y, 7.0 / 6.0)));
+            }
+            _ => panic!("Expected points"),
+        }
+
+        // A helix across the axis, which starts on the other.
+        let across = Helix::new(
+            Point::from_f64(1.0, 0.0, -0.5),
+            Point::unit_x(),
+            Point::from_f64(0.0, 0.0, 0.5),
+            true,
+        );
+        match helix_helix_intersection(&helix(), &across) {
+            HelixHelixIntersection::Points(points) => {
+                assert!(points.iter().any(|p| (*p - Point::unit_x()).norm() < 1e-9));
+                for p in points {
+                    assert!(on_curve_numerical(&across, p));
+                }
+            }
+            _ => panic!("Expected finitely many points"),
+        }
+    }
+}
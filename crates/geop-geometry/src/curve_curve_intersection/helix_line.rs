@@ -1,13 +1,15 @@
 use crate::{
+    curve_surface_intersection::helix_plane::{helix_plane_intersection, HelixPlaneIntersection},
     curves::{helix::Helix, line::Line, CurveLike},
     point::Point,
+    surfaces::plane::Plane,
 };
 
-use super::curve_curve::PointArray;
+use super::{curve_curve::PointArray, helix_ellipse::on_curve_numerical};
 
 pub enum HelixLineIntersection {
     PointArray(PointArray),
-    TwoPoint(Point),
+    TwoPoint(Point, Point),
     OnePoint(Point),
     None,
 }
@@ -38,5 +40,45 @@ pub fn helix_line_intersection(helix: &Helix, line: &Line) -> HelixLineIntersect
         }
         return HelixLineIntersection::None;
     }
-    todo!("Implement this")
+
+    // Otherwise the line crosses the cylinder of the helix at most twice. The intersections are the points where the helix crosses a plane through the line that is not parallel to the axis, and which are on the line.
+    let normal = line.direction.cross(line.direction.cross(helix.pitch));
+    let plane = Plane::new(line.basis, line.direction, normal.cross(line.direction));
+    let mut points: Vec<Point> = match helix_plane_intersection(helix, &plane) {
+        HelixPlaneIntersection::Points(points) => points,
+        _ => Vec::new(),
+    };
+    points.retain(|p| on_curve_numerical(line, *p));
+    points.dedup();
+    match points.as_slice() {
+        [] => HelixLineIntersection::None,
+        [p] => HelixLineIntersection::OnePoint(*p),
+        [p, q, ..] => HelixLineIntersection::TwoPoint(*p, *q),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helix_line_intersection() {
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        // A line through the helix at half a turn apart.
+        let (p, q) = (Point::unit_x(), Point::from_f64(-1.0, 0.0, 0.5));
+        let line = Line::new(p, (q - p).normalize().unwrap()).unwrap();
+        match helix_line_intersection(&helix, &line) {
+            HelixLineIntersection::TwoPoint(a, b) => {
+                assert!((a == p && b == q) || (a == q && b == p));
+            }
+            _ => panic!("Expected two points"),
+        }
+        // The second crossing with the cylinder misses the helix.
+        let r = Point::from_f64(-1.0, 0.0, 0.6);
+        let line = Line::new(p, (r - p).normalize().unwrap()).unwrap();
+        match helix_line_intersection(&helix, &line) {
+            HelixLineIntersection::OnePoint(a) => assert_eq!(a, p),
+            _ => panic!("Expected one point"),
+        }
+    }
 }
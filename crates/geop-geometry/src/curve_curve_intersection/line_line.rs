@@ -7,6 +7,7 @@ pub enum LineLineIntersection {
     None,
 }
 
+// Whether the lines are parallel or meet is an incidence, so it is decided within the error bounds and not by the exact predicates.
 pub fn line_line_intersection(a: &Line, b: &Line) -> LineLineIntersection {
     let v1 = a.direction;
     let v2 = b.direction;
@@ -2,8 +2,12 @@
 pub mod circle_circle;
 pub mod circle_line;
 pub mod curve_curve;
+pub mod ellipse_circle;
 pub mod ellipse_ellipse;
+pub mod ellipse_line;
 pub mod helix_circle;
+pub mod helix_ellipse;
+pub mod helix_helix;
 pub mod helix_line;
 pub mod line_line;
-pub mod numerical;
+pub mod nurbs_curve;
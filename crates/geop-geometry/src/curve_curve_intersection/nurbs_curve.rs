@@ -0,0 +1,242 @@
+use geop_algebra::{efloat::EFloat64, interval_newton::interval_newton, matrix::Matrix};
+
+use crate::{
+    curves::{curve::Curve, nurbs::Nurbs, CurveLike},
+    point::Point,
+    tolerance::Tolerance,
+};
+
+const MAX_DEPTH: u32 = 64;
+const REFINEMENT_ITERATIONS: usize = 16;
+// The parameter boxes are split into at most this many parts per side to isolate an intersection.
+const ISOLATION_STEPS: f64 = 16.0;
+
+// Parameters of the curve within the given distance around p, restricted to the knot span of p.
+fn parameter_box(curve: &Nurbs, p: Point, distance: f64) -> EFloat64 {
+    let t = curve.parameter(p);
+    let (start, end) = curve.span(t);
+    let speed = curve.derivative_at(t).norm().mid();
+    let width = distance / speed.max(f64::MIN_POSITIVE);
+    let t = t.mid();
+    EFloat64::new(
+        (t + width).min(end.lower_bound),
+        (t - width).max(start.upper_bound),
+    )
+}
+
+// Encloses the intersection close to p with the Krawczyk operator, where the other curve is represented by a nurbs around p.
+// At a crossing, the parameters of both curves are a simple root of the gradient of half the squared distance between them.
+// Returns None if the intersection cannot be certified, e.g. for touching curves or helices, which have no nurbs representation.
+fn enclose_intersection(nurbs: &Nurbs, other: &Curve, p: Point, distance: f64) -> Option<Point> {
+    let q = other.project(p);
+    let t = nurbs.parameter(p);
+    let (span_start, span_end) = nurbs.span(t);
+    let span = (span_end - span_start).mid();
+    let local = match other {
+        Curve::Nurbs(other) => other.clone(),
+        // The piece of the other curve is about as long as the knot span of the nurbs, so that both parameters have a similar scale. Arcs are kept below a radian.
+        _ => {
+            let mut length = nurbs.derivative_at(t).norm().mid() * span / 2.0;
+            if let Ok(curvature) = other.curvature(q) {
+                if curvature.mid() > 0.0 {
+                    length = length.min(0.5 / curvature.mid());
+                }
+            }
+            let start = other.point_at_length(q, EFloat64::from(-length)).ok()?;
+            let end = other.point_at_length(q, EFloat64::from(length)).ok()?;
+            Nurbs::from_curve(other, Some(start), Some(end)).ok()?
+        }
+    };
+
+    let f = |x: &[EFloat64]| {
+        let (n, m) = (nurbs.derivatives_at(x[0], 1), local.derivatives_at(x[1], 1));
+        let r = n[0] - m[0];
+        vec![r.dot(n[1]), r.dot(m[1])]
+    };
+    let jacobian = |x: &[EFloat64]| {
+        let (n, m) = (nurbs.derivatives_at(x[0], 2), local.derivatives_at(x[1], 2));
+        let r = n[0] - m[0];
+        Matrix::new(vec![
+            vec![n[1].dot(n[1]) + r.dot(n[2]), -m[1].dot(n[1])],
+            vec![n[1].dot(m[1]), -m[1].dot(m[1]) + r.dot(m[2])],
+        ])
+    };
+    let domain = vec![
+        parameter_box(nurbs, p, distance),
+        parameter_box(&local, q, distance),
+    ];
+    // The root is isolated on a coarse grid first, which gives up quickly if it cannot be certified, and then contracted.
+    let width = domain
+        .iter()
+        .map(|x| x.upper_bound - x.lower_bound)
+        .fold(0.0, f64::max);
+    let isolated = interval_newton(f, jacobian, domain, width / ISOLATION_STEPS);
+    if isolated.unique.len() != 1 || !isolated.possible.is_empty() {
+        return None;
+    }
+    let solution = interval_newton(
+        f,
+        jacobian,
+        isolated.unique[0].clone(),
+        Tolerance::current().parametric * span,
+    );
+    match solution.unique.as_slice() {
+        [x] => {
+            let p = nurbs.point_at(x[0]);
+            // The closest points of curves that miss each other are no intersection.
+            match p == local.point_at(x[1]) {
+                true => Some(p),
+                false => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// The nurbs lies inside the bounding box of its control points. Returns the center and radius of the bounding sphere of this box.
+fn bounding_sphere(nurbs: &Nurbs) -> (Point, f64) {
+    let mut min = nurbs.control_points[0];
+    let mut max = nurbs.control_points[0];
+    for p in nurbs.control_points.iter() {
+        min = Point::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+        max = Point::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+    }
+    (
+        (min + max) * EFloat64::from(0.5),
+        (max - min).norm().mid() / 2.0,
+    )
+}
+
+fn nurbs_curve_intersection_iteration(
+    nurbs: &Nurbs,
+    other: &Curve,
+    t0: f64,
+    t1: f64,
+    deepness: u32,
+    precision: f64,
+    result: &mut Vec<Point>,
+) {
+    // The part of the nurbs between t0 and t1 is discarded if its bounding sphere misses the other curve.
+    let (center, radius) = bounding_sphere(&nurbs.subcurve(EFloat64::from(t0), EFloat64::from(t1)));
+    let closest = other.project(center);
+    if (closest - center).norm().mid() > radius + precision {
+        return;
+    }
+
+    if radius > precision && deepness < MAX_DEPTH {
+        let t_mid = (t0 + t1) / 2.0;
+        nurbs_curve_intersection_iteration(
+            nurbs,
+            other,
+            t0,
+            t_mid,
+            deepness + 1,
+            precision,
+            result,
+        );
+        nurbs_curve_intersection_iteration(
+            nurbs,
+            other,
+            t_mid,
+            t1,
+            deepness + 1,
+            precision,
+            result,
+        );
+        return;
+    }
+
+    // Refine the candidate by projecting back and forth between the two curves.
+    let mut p = nurbs.point_at(EFloat64::from((t0 + t1) / 2.0));
+    for _ in 0..REFINEMENT_ITERATIONS {
+        p = nurbs.project(other.project(p));
+    }
+    if (other.project(p) - p).norm().mid() > precision {
+        return;
+    }
+    if !result
+        .iter()
+        .any(|q| (*q - p).norm().mid() <= 10.0 * precision)
+    {
+        result.push(enclose_intersection(nurbs, other, p, radius.max(precision)).unwrap_or(p));
+    }
+}
+
+// Finds the intersection points of a nurbs curve with any other curve by subdividing the nurbs. The curves have to intersect in a finite number of points.
+pub fn nurbs_curve_intersection(nurbs: &Nurbs, other: &Curve) -> Vec<Point> {
+    let (start, end) = nurbs.domain();
+    let (center, radius) = bounding_sphere(nurbs);
+    // Intersections are found up to the numerical tolerance, relative to the size of the nurbs and its distance from the origin, or the linear tolerance if that is larger.
+    // Below the distance from the origin the coordinates are only rounding noise, so a tiny curve far away is not refined further than a big one.
+    let tolerance = Tolerance::current();
+    let scale = 2.0 * radius + center.norm().mid();
+    let precision = (scale * tolerance.numerical).max(tolerance.linear);
+    let mut result = Vec::new();
+    nurbs_curve_intersection_iteration(
+        nurbs,
+        other,
+        start.mid(),
+        end.mid(),
+        0,
+        precision,
+        &mut result,
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curves::line::Line, transforms::Transform};
+
+    #[test]
+    fn test_nurbs_line_intersection() {
+        let nurbs = Nurbs::new_uniform_clamped(
+            2,
+            vec![
+                Point::from_f64(0.0, 0.0, 0.0),
+                Point::from_f64(1.0, 2.0, 0.0),
+                Point::from_f64(2.0, 0.0, 0.0),
+            ],
+        )
+        .unwrap();
+        // The parabola reaches y = 1 at x = 1 and crosses y = 0.5 twice.
+        let line = Line::new(Point::from_f64(0.0, 0.5, 0.0), Point::unit_x()).unwrap();
+        let points = nurbs_curve_intersection(&nurbs, &Curve::Line(line.clone()));
+        assert_eq!(points.len(), 2);
+        for p in points {
+            assert!(line.on_curve(p));
+            assert!(nurbs.on_curve(p));
+            // The points are enclosed, so they match the exact solution x = 1 +- sqrt(1 / 2).
+            assert!((p.x - EFloat64::one()).abs() == 0.5f64.sqrt());
+        }
+
+        let line = Line::new(Point::from_f64(0.0, 2.0, 0.0), Point::unit_x()).unwrap();
+        assert!(nurbs_curve_intersection(&nurbs, &Curve::Line(line)).is_empty());
+    }
+
+    #[test]
+    fn test_nurbs_line_intersection_scaled() {
+        // The precision follows the size of the curve, so tiny and huge models behave the same.
+        for scale in [1e-6, 1e6] {
+            let transform = Transform::from_scale(Point::from_f64(scale, scale, scale));
+            let nurbs = Nurbs::new_uniform_clamped(
+                2,
+                vec![
+                    Point::from_f64(0.0, 0.0, 0.0),
+                    Point::from_f64(1.0, 2.0, 0.0),
+                    Point::from_f64(2.0, 0.0, 0.0),
+                ],
+            )
+            .unwrap()
+            .transform(transform);
+            let line = Line::new(Point::from_f64(0.0, 0.5 * scale, 0.0), Point::unit_x()).unwrap();
+            let points = nurbs_curve_intersection(&nurbs, &Curve::Line(line));
+            assert_eq!(points.len(), 2);
+            for p in points {
+                let x = p.x.mid() / scale;
+                assert!(((x - 1.0).abs() - 0.5f64.sqrt()).abs() < 1e-6);
+            }
+        }
+    }
+}
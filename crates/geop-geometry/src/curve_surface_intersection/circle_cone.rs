@@ -0,0 +1,123 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{curves::circle::Circle, point::Point, surfaces::cone::Cone, tolerance::Tolerance};
+
+use super::find_angle_roots;
+
+const SAMPLES: usize = 64;
+
+pub enum CircleConeIntersection {
+    Circle(Circle),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn circle_cone_intersection(circle: &Circle, cone: &Cone) -> CircleConeIntersection {
+    let precision = Tolerance::current().numerical;
+    let d = cone.extend_dir;
+    if circle.normal.is_parallel(d) {
+        let offset = circle.basis - cone.basis;
+        let height = offset.dot(d);
+        if (offset - height * d).norm() == 0.0 {
+            let tan = (cone.half_angle.sin() / cone.half_angle.cos()).unwrap();
+            if height > 0.0 && (circle.radius.norm() - height * tan) == 0.0 {
+                return CircleConeIntersection::Circle(circle.clone());
+            }
+            return CircleConeIntersection::None;
+        }
+    }
+
+    // The signed distance to the cone along its normal is h cos - r sin. It is negative below the apex, so only the nappe of the cone has roots.
+    let dir_cross = circle.normal.cross(circle.radius);
+    let point_at = |phi: f64| {
+        let phi = EFloat64::from(phi);
+        circle.basis + circle.radius * phi.cos() + dir_cross * phi.sin()
+    };
+    let (sin, cos) = (cone.half_angle.sin(), cone.half_angle.cos());
+    let f = |phi: f64| {
+        let p = point_at(phi) - cone.basis;
+        let height = p.dot(d);
+        let radius = (p - height * d).norm();
+        let distance = height * cos - radius * sin;
+        distance.mid()
+    };
+    let points: Vec<Point> = find_angle_roots(f, SAMPLES, precision)
+        .into_iter()
+        .map(point_at)
+        .collect();
+    if points.is_empty() {
+        return CircleConeIntersection::None;
+    }
+    CircleConeIntersection::Points(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::SurfaceLike;
+
+    fn cone() -> Cone {
+        Cone::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(std::f64::consts::FRAC_PI_4),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_circle_cone_intersection_circle() {
+        let circle = Circle::new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_z(),
+            EFloat64::two(),
+        );
+        assert!(matches!(
+            circle_cone_intersection(&circle, &cone()),
+            CircleConeIntersection::Circle(_)
+        ));
+
+        let circle = Circle::new(
+            Point::from_f64(0.0, 0.0, -2.0),
+            Point::unit_z(),
+            EFloat64::two(),
+        );
+        assert!(matches!(
+            circle_cone_intersection(&circle, &cone()),
+            CircleConeIntersection::None
+        ));
+    }
+
+    #[test]
+    fn test_circle_cone_intersection_points() {
+        // Circle in the xz plane around (2, 0, 1) that reaches the cone at x = z.
+        let circle = Circle::new(
+            Point::from_f64(2.0, 0.0, 1.0),
+            Point::unit_y(),
+            EFloat64::one(),
+        );
+        match circle_cone_intersection(&circle, &cone()) {
+            CircleConeIntersection::Points(points) => {
+                assert_eq!(points.len(), 2);
+                for p in points {
+                    assert!((cone().project(p) - p).norm() < 1e-9);
+                }
+            }
+            _ => panic!("Should be points!"),
+        }
+
+        // The circle touches the cone at (1, 0, 1).
+        let circle = Circle::new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            EFloat64::from(2.0f64.sqrt()),
+        );
+        match circle_cone_intersection(&circle, &cone()) {
+            CircleConeIntersection::Points(points) => {
+                assert_eq!(points.len(), 1);
+                assert!((points[0] - Point::from_f64(1.0, 0.0, 1.0)).norm() < 1e-6);
+            }
+            _ => panic!("Should be a point!"),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{curves::circle::Circle, point::Point, surfaces::torus::Torus, tolerance::Tolerance};
+
+use super::{find_angle_roots, line_torus::torus_distance};
+
+const SAMPLES: usize = 128;
+
+pub enum CircleTorusIntersection {
+    Circle(Circle),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn circle_torus_intersection(circle: &Circle, torus: &Torus) -> CircleTorusIntersection {
+    let precision = Tolerance::current().numerical;
+    let offset = circle.basis - torus.basis;
+    let height = offset.dot(torus.normal);
+    let radial = offset - height * torus.normal;
+
+    // Circles around the axis of the torus
+    if circle.normal.is_parallel(torus.normal) && radial.norm() == 0.0 {
+        let tube = (circle.radius.norm() - torus.major_radius).square() + height.square();
+        if (tube - torus.minor_radius.square()) == 0.0 {
+            return CircleTorusIntersection::Circle(circle.clone());
+        }
+    }
+
+    // Circles around the tube of the torus
+    if height == 0.0
+        && (radial.norm() - torus.major_radius) == 0.0
+        && circle.normal.is_parallel(torus.normal.cross(radial))
+        && (circle.radius.norm() - torus.minor_radius) == 0.0
+    {
+        return CircleTorusIntersection::Circle(circle.clone());
+    }
+
+    let dir_cross = circle.normal.cross(circle.radius);
+    let point_at = |phi: f64| {
+        let phi = EFloat64::from(phi);
+        circle.basis + circle.radius * phi.cos() + dir_cross * phi.sin()
+    };
+    let points: Vec<Point> = find_angle_roots(
+        |phi| torus_distance(torus, point_at(phi)),
+        SAMPLES,
+        precision,
+    )
+    .into_iter()
+    .map(point_at)
+    .collect();
+    if points.is_empty() {
+        return CircleTorusIntersection::None;
+    }
+    CircleTorusIntersection::Points(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn torus() -> Torus {
+        Torus::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(3.0),
+            EFloat64::one(),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_circle_torus_intersection_circle() {
+        let circle = Circle::new(
+            Point::from_f64(0.0, 0.0, 1.0),
+            Point::unit_z(),
+            EFloat64::from(3.0),
+        );
+        assert!(matches!(
+            circle_torus_intersection(&circle, &torus()),
+            CircleTorusIntersection::Circle(_)
+        ));
+
+        let circle = Circle::new(
+            Point::from_f64(3.0, 0.0, 0.0),
+            Point::unit_y(),
+            EFloat64::one(),
+        );
+        assert!(matches!(
+            circle_torus_intersection(&circle, &torus()),
+            CircleTorusIntersection::Circle(_)
+        ));
+
+        let circle = Circle::new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_z(),
+            EFloat64::from(3.0),
+        );
+        assert!(matches!(
+            circle_torus_intersection(&circle, &torus()),
+            CircleTorusIntersection::None
+        ));
+    }
+
+    #[test]
+    fn test_circle_torus_intersection_points() {
+        // A circle in the xz plane passes through both sides of the tube.
+        let circle = Circle::new(Point::zero(), Point::unit_y(), EFloat64::from(3.0));
+        match circle_torus_intersection(&circle, &torus()) {
+            CircleTorusIntersection::Points(points) => {
+                assert_eq!(points.len(), 4);
+                for p in points {
+                    assert!(torus_distance(&torus(), p).abs() < 1e-9);
+                }
+            }
+            _ => panic!("Should be points!"),
+        }
+    }
+}
@@ -1,17 +1,54 @@
-use crate::{curves::curve::Curve, point::Point, surfaces::surface::Surface};
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curve_curve_intersection::curve_curve::PointArray,
+    curves::{curve::Curve, nurbs::Nurbs, CurveLike},
+    geometry_error::GeometryResult,
+    point::Point,
+    surfaces::{
+        cylinder::Cylinder, ellipsoid::Ellipsoid, elliptic_cylinder::EllipticCylinder,
+        nurbs_surface::NurbsSurface, sphere::Sphere, surface::Surface, SurfaceLike,
+    },
+    transforms::Transform,
+    HORIZON_DIST,
+};
 
 use super::{
+    circle_cone::{circle_cone_intersection, CircleConeIntersection},
     circle_cylinder::{circle_cylinder_intersection, CircleCylinderIntersection},
     circle_plane::{circle_plane_intersection, CirclePlaneIntersection},
     circle_sphere::{circle_sphere_intersection, CircleSphereIntersection},
+    circle_torus::{circle_torus_intersection, CircleTorusIntersection},
+    ellipse_cone::{ellipse_cone_intersection, EllipseConeIntersection},
+    ellipse_cylinder::{ellipse_cylinder_intersection, EllipseCylinderIntersection},
+    ellipse_plane::{ellipse_plane_intersection, EllipsePlaneIntersection},
+    ellipse_sphere::{ellipse_sphere_intersection, EllipseSphereIntersection},
+    ellipse_torus::{ellipse_torus_intersection, EllipseTorusIntersection},
+    helix_cone::helix_cone_intersection,
+    helix_cylinder::{helix_cylinder_intersection, HelixCylinderIntersection},
+    helix_ellipsoid::helix_ellipsoid_intersection,
+    helix_elliptic_cylinder::{
+        helix_elliptic_cylinder_intersection, HelixEllipticCylinderIntersection,
+    },
+    helix_plane::{helix_plane_intersection, HelixPlaneIntersection},
+    helix_sphere::helix_sphere_intersection,
+    helix_torus::helix_torus_intersection,
+    line_cone::{line_cone_intersection, LineConeIntersection},
     line_cylinder::{line_cylinder_intersection, CylinderLineIntersection},
     line_plane::{line_plane_intersection, LinePlaneIntersection},
     line_sphere::{line_sphere_intersection, LineSphereIntersection},
+    line_torus::line_torus_intersection,
+    nurbs::{
+        curve_nurbs_surface_intersection, line_on_nurbs_surface, nurbs_curve_on_surface,
+        nurbs_curve_surface_intersection,
+    },
 };
 
 pub enum CurveSurfaceIntersection {
     None,
     Points(Vec<Point>),
+    // Periodic intersections, e.g. of a helix with a plane along its axis.
+    InfiniteDiscretePoints(Vec<PointArray>),
     Curve(Curve),
 }
 
@@ -38,6 +75,97 @@ impl CurveSurfaceIntersection {
     }
 }
 
+fn points(points: Vec<Point>) -> CurveSurfaceIntersection {
+    if points.is_empty() {
+        return CurveSurfaceIntersection::None;
+    }
+    CurveSurfaceIntersection::Points(points)
+}
+
+// A closed curve lies on the surface if its exact nurbs representation does. Otherwise the surface is subdivided, which does not terminate quickly for curves on the surface.
+fn closed_curve_nurbs_surface(
+    curve: &Curve,
+    inner: &dyn CurveLike,
+    nurbs: &NurbsSurface,
+) -> CurveSurfaceIntersection {
+    match Nurbs::from_curve(curve, None, None) {
+        Ok(exact) if nurbs_curve_on_surface(&exact, nurbs) => {
+            CurveSurfaceIntersection::Curve(curve.clone())
+        }
+        _ => points(curve_nurbs_surface_intersection(inner, nurbs)),
+    }
+}
+
+// Intersects the curve with the surface that is mapped onto the other one by frame, by mapping the curve back and the intersections forward again.
+fn through_frame(
+    curve: &Curve,
+    frame: Transform,
+    surface: &Surface,
+) -> GeometryResult<CurveSurfaceIntersection> {
+    let inverse = frame.inverse()?;
+    Ok(
+        match curve_surface_intersection(&curve.transform(inverse), surface) {
+            CurveSurfaceIntersection::None => CurveSurfaceIntersection::None,
+            CurveSurfaceIntersection::Points(points) => {
+                CurveSurfaceIntersection::Points(points.into_iter().map(|p| frame * p).collect())
+            }
+            CurveSurfaceIntersection::InfiniteDiscretePoints(arrays) => {
+                CurveSurfaceIntersection::InfiniteDiscretePoints(
+                    arrays
+                        .into_iter()
+                        .map(|array| PointArray {
+                            basis: frame * array.basis,
+                            extend_dir: frame * (array.extend_dir + array.basis)
+                                - frame * array.basis,
+                        })
+                        .collect(),
+                )
+            }
+            CurveSurfaceIntersection::Curve(curve) => {
+                CurveSurfaceIntersection::Curve(curve.transform(frame))
+            }
+        },
+    )
+}
+
+// Without a frame to map back, the curve is subdivided as a nurbs, with lines cut at the horizon.
+fn nurbs_curve_surface(curve: &Curve, surface: &dyn SurfaceLike) -> CurveSurfaceIntersection {
+    let exact = match curve {
+        Curve::Line(line) => {
+            let horizon = line.direction * EFloat64::from(HORIZON_DIST);
+            Nurbs::from_curve(
+                curve,
+                Some(line.basis - horizon),
+                Some(line.basis + horizon),
+            )
+        }
+        _ => Nurbs::from_curve(curve, None, None),
+    };
+    match exact {
+        Ok(exact) => points(nurbs_curve_surface_intersection(&exact, surface)),
+        Err(_) => CurveSurfaceIntersection::None,
+    }
+}
+
+// The ellipsoid is the image of the unit sphere.
+fn curve_ellipsoid(curve: &Curve, ellipsoid: &Ellipsoid) -> CurveSurfaceIntersection {
+    let sphere = Sphere::new(Point::zero(), EFloat64::one(), ellipsoid.normal_outwards);
+    through_frame(curve, ellipsoid.frame(), &Surface::Sphere(sphere))
+        .unwrap_or_else(|_| nurbs_curve_surface(curve, ellipsoid))
+}
+
+// The elliptic cylinder is the image of the cylinder of radius one around the z axis.
+fn curve_elliptic_cylinder(curve: &Curve, cylinder: &EllipticCylinder) -> CurveSurfaceIntersection {
+    let unit = Cylinder::new(
+        Point::zero(),
+        Point::unit_z(),
+        EFloat64::one(),
+        cylinder.normal_outwards,
+    );
+    through_frame(curve, cylinder.frame(), &Surface::Cylinder(unit))
+        .unwrap_or_else(|_| nurbs_curve_surface(curve, cylinder))
+}
+
 pub fn curve_surface_intersection(curve: &Curve, surface: &Surface) -> CurveSurfaceIntersection {
     match curve {
         Curve::Line(line) => match surface {
@@ -71,6 +199,23 @@ pub fn curve_surface_intersection(curve: &Curve, surface: &Surface) -> CurveSurf
                 }
                 CylinderLineIntersection::None => CurveSurfaceIntersection::None,
             },
+            Surface::Cone(cone) => match line_cone_intersection(line, cone) {
+                LineConeIntersection::Line(line) => {
+                    CurveSurfaceIntersection::Curve(Curve::Line(line))
+                }
+                LineConeIntersection::TwoPoints(point1, point2) => {
+                    CurveSurfaceIntersection::Points(vec![point1, point2])
+                }
+                LineConeIntersection::Point(point) => CurveSurfaceIntersection::Points(vec![point]),
+                LineConeIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Torus(torus) => points(line_torus_intersection(line, torus)),
+            Surface::Ellipsoid(ellipsoid) => curve_ellipsoid(curve, ellipsoid),
+            Surface::EllipticCylinder(cylinder) => curve_elliptic_cylinder(curve, cylinder),
+            Surface::Nurbs(nurbs) => match line_on_nurbs_surface(line, nurbs) {
+                true => CurveSurfaceIntersection::Curve(curve.clone()),
+                false => points(curve_nurbs_surface_intersection(line, nurbs)),
+            },
         },
         Curve::Circle(circle) => match surface {
             Surface::Plane(plane) => match circle_plane_intersection(circle, plane) {
@@ -107,8 +252,113 @@ pub fn curve_surface_intersection(curve: &Curve, surface: &Surface) -> CurveSurf
                 }
                 CircleCylinderIntersection::None => CurveSurfaceIntersection::None,
             },
+            Surface::Cone(cone) => match circle_cone_intersection(circle, cone) {
+                CircleConeIntersection::Circle(circle) => {
+                    CurveSurfaceIntersection::Curve(Curve::Circle(circle))
+                }
+                CircleConeIntersection::Points(points) => CurveSurfaceIntersection::Points(points),
+                CircleConeIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Torus(torus) => match circle_torus_intersection(circle, torus) {
+                CircleTorusIntersection::Circle(circle) => {
+                    CurveSurfaceIntersection::Curve(Curve::Circle(circle))
+                }
+                CircleTorusIntersection::Points(points) => CurveSurfaceIntersection::Points(points),
+                CircleTorusIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Ellipsoid(ellipsoid) => curve_ellipsoid(curve, ellipsoid),
+            Surface::EllipticCylinder(cylinder) => curve_elliptic_cylinder(curve, cylinder),
+            Surface::Nurbs(nurbs) => closed_curve_nurbs_surface(curve, circle, nurbs),
+        },
+        Curve::Ellipse(ellipse) => match surface {
+            Surface::Plane(plane) => match ellipse_plane_intersection(ellipse, plane) {
+                EllipsePlaneIntersection::None => CurveSurfaceIntersection::None,
+                EllipsePlaneIntersection::TwoPoints(p1, p2) => {
+                    CurveSurfaceIntersection::Points(vec![p1, p2])
+                }
+                EllipsePlaneIntersection::OnePoint(p) => CurveSurfaceIntersection::Points(vec![p]),
+                EllipsePlaneIntersection::Ellipse(ellipse) => {
+                    CurveSurfaceIntersection::Curve(Curve::Ellipse(ellipse))
+                }
+            },
+            Surface::Sphere(sphere) => match ellipse_sphere_intersection(ellipse, sphere) {
+                EllipseSphereIntersection::Ellipse(ellipse) => {
+                    CurveSurfaceIntersection::Curve(Curve::Ellipse(ellipse))
+                }
+                EllipseSphereIntersection::Points(points) => {
+                    CurveSurfaceIntersection::Points(points)
+                }
+                EllipseSphereIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Cylinder(cylinder) => match ellipse_cylinder_intersection(ellipse, cylinder) {
+                EllipseCylinderIntersection::Ellipse(ellipse) => {
+                    CurveSurfaceIntersection::Curve(Curve::Ellipse(ellipse))
+                }
+                EllipseCylinderIntersection::Points(points) => {
+                    CurveSurfaceIntersection::Points(points)
+                }
+                EllipseCylinderIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Cone(cone) => match ellipse_cone_intersection(ellipse, cone) {
+                EllipseConeIntersection::Ellipse(ellipse) => {
+                    CurveSurfaceIntersection::Curve(Curve::Ellipse(ellipse))
+                }
+                EllipseConeIntersection::Points(points) => CurveSurfaceIntersection::Points(points),
+                EllipseConeIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Torus(torus) => match ellipse_torus_intersection(ellipse, torus) {
+                EllipseTorusIntersection::Ellipse(ellipse) => {
+                    CurveSurfaceIntersection::Curve(Curve::Ellipse(ellipse))
+                }
+                EllipseTorusIntersection::Points(points) => {
+                    CurveSurfaceIntersection::Points(points)
+                }
+                EllipseTorusIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Ellipsoid(ellipsoid) => curve_ellipsoid(curve, ellipsoid),
+            Surface::EllipticCylinder(cylinder) => curve_elliptic_cylinder(curve, cylinder),
+            Surface::Nurbs(nurbs) => closed_curve_nurbs_surface(curve, ellipse, nurbs),
+        },
+        Curve::Helix(helix) => match surface {
+            Surface::Plane(plane) => match helix_plane_intersection(helix, plane) {
+                HelixPlaneIntersection::PointArrays(arrays) => {
+                    CurveSurfaceIntersection::InfiniteDiscretePoints(arrays)
+                }
+                HelixPlaneIntersection::Points(points) => CurveSurfaceIntersection::Points(points),
+                HelixPlaneIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Sphere(sphere) => points(helix_sphere_intersection(helix, sphere)),
+            Surface::Cylinder(cylinder) => match helix_cylinder_intersection(helix, cylinder) {
+                HelixCylinderIntersection::Helix(helix) => {
+                    CurveSurfaceIntersection::Curve(Curve::Helix(helix))
+                }
+                HelixCylinderIntersection::PointArrays(arrays) => {
+                    CurveSurfaceIntersection::InfiniteDiscretePoints(arrays)
+                }
+                HelixCylinderIntersection::Points(points) => {
+                    CurveSurfaceIntersection::Points(points)
+                }
+                HelixCylinderIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Cone(cone) => points(helix_cone_intersection(helix, cone)),
+            Surface::Torus(torus) => points(helix_torus_intersection(helix, torus)),
+            Surface::Ellipsoid(ellipsoid) => points(helix_ellipsoid_intersection(helix, ellipsoid)),
+            Surface::EllipticCylinder(cylinder) => {
+                match helix_elliptic_cylinder_intersection(helix, cylinder) {
+                    HelixEllipticCylinderIntersection::PointArrays(arrays) => {
+                        CurveSurfaceIntersection::InfiniteDiscretePoints(arrays)
+                    }
+                    HelixEllipticCylinderIntersection::Points(points) => {
+                        CurveSurfaceIntersection::Points(points)
+                    }
+                    HelixEllipticCylinderIntersection::None => CurveSurfaceIntersection::None,
+                }
+            }
+            Surface::Nurbs(nurbs) => points(curve_nurbs_surface_intersection(helix, nurbs)),
+        },
+        Curve::Nurbs(nurbs) => match nurbs_curve_on_surface(nurbs, surface) {
+            true => CurveSurfaceIntersection::Curve(curve.clone()),
+            false => points(nurbs_curve_surface_intersection(nurbs, surface)),
         },
-        Curve::Ellipse(_) => todo!("Implement this"),
-        Curve::Helix(_) => todo!("Implement this"),
     }
 }
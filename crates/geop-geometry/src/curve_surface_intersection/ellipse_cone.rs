@@ -0,0 +1,33 @@
+use crate::{
+    curves::ellipse::Ellipse,
+    point::Point,
+    surfaces::{cone::Cone, SurfaceLike},
+};
+
+pub enum EllipseConeIntersection {
+    Ellipse(Ellipse),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn ellipse_cone_intersection(ellipse: &Ellipse, cone: &Cone) -> EllipseConeIntersection {
+    // (q . d)^2 - |q|^2 cos^2 vanishes on the cone and its mirror image behind the apex, which is filtered out afterwards.
+    let d = cone.extend_dir;
+    let cos_sq = cone.half_angle.cos().square();
+    match ellipse.implicit_intersection(cone.basis, |e| {
+        let height = e.dot(d);
+        &(&height * &height) - &(&e.norm_sq() * cos_sq)
+    }) {
+        None => match cone.on_surface(ellipse.basis + ellipse.major_radius) {
+            true => EllipseConeIntersection::Ellipse(ellipse.clone()),
+            false => EllipseConeIntersection::None,
+        },
+        Some(points) => {
+            let points: Vec<Point> = points.into_iter().filter(|p| cone.on_surface(*p)).collect();
+            match points.is_empty() {
+                true => EllipseConeIntersection::None,
+                false => EllipseConeIntersection::Points(points),
+            }
+        }
+    }
+}
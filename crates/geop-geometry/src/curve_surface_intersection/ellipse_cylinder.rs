@@ -0,0 +1,75 @@
+use crate::{curves::ellipse::Ellipse, point::Point, surfaces::cylinder::Cylinder};
+
+pub enum EllipseCylinderIntersection {
+    Ellipse(Ellipse),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn ellipse_cylinder_intersection(
+    ellipse: &Ellipse,
+    cylinder: &Cylinder,
+) -> EllipseCylinderIntersection {
+    // |q|^2 - (q . d)^2 - r^2, which is the squared distance to the axis minus the squared radius.
+    let d = cylinder.extend_dir;
+    let radius_sq = cylinder.radius.norm_sq();
+    match ellipse.implicit_intersection(cylinder.basis, |e| {
+        let height = e.dot(d);
+        &(&e.norm_sq() - &(&height * &height)) - &(&(&e.w * &e.w) * radius_sq)
+    }) {
+        None => EllipseCylinderIntersection::Ellipse(ellipse.clone()),
+        Some(points) if points.is_empty() => EllipseCylinderIntersection::None,
+        Some(points) => EllipseCylinderIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use crate::{curves::CurveLike, surfaces::SurfaceLike};
+
+    use super::*;
+
+    #[test]
+    fn test_ellipse_cylinder_intersection() {
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+        // The section of the cylinder by a plane at 45 degrees lies on the cylinder.
+        let section = Ellipse::new(
+            Point::zero(),
+            Point::from_f64(-1.0, 0.0, 1.0).normalize().unwrap(),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::unit_y(),
+        );
+        assert!(matches!(
+            ellipse_cylinder_intersection(&section, &cylinder),
+            EllipseCylinderIntersection::Ellipse(_)
+        ));
+        // A flat ellipse reaching out of the cylinder along x.
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 0.5, 0.0),
+        );
+        match ellipse_cylinder_intersection(&ellipse, &cylinder) {
+            EllipseCylinderIntersection::Points(points) => {
+                assert_eq!(points.len(), 4);
+                for p in points {
+                    assert!(ellipse.on_curve(p) && cylinder.on_surface(p));
+                }
+            }
+            _ => panic!("Intersection should be four points"),
+        }
+        let small = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::from_f64(0.0, 0.25, 0.0),
+        );
+        assert!(matches!(
+            ellipse_cylinder_intersection(&small, &cylinder),
+            EllipseCylinderIntersection::None
+        ));
+    }
+}
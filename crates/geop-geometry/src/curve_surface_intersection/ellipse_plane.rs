@@ -0,0 +1,73 @@
+use crate::{curves::ellipse::Ellipse, point::Point, surfaces::plane::Plane};
+
+pub enum EllipsePlaneIntersection {
+    None,
+    TwoPoints(Point, Point),
+    OnePoint(Point),
+    Ellipse(Ellipse),
+}
+
+pub fn ellipse_plane_intersection(ellipse: &Ellipse, plane: &Plane) -> EllipsePlaneIntersection {
+    let normal = plane.u_slope.cross(plane.v_slope);
+    match ellipse.implicit_intersection(plane.basis, |e| e.dot(normal)) {
+        None => EllipsePlaneIntersection::Ellipse(ellipse.clone()),
+        Some(points) => match points.len() {
+            0 => EllipsePlaneIntersection::None,
+            1 => EllipsePlaneIntersection::OnePoint(points[0]),
+            2 => EllipsePlaneIntersection::TwoPoints(points[0], points[1]),
+            _ => panic!("Unexpected number of intersection points"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curves::CurveLike;
+
+    use super::*;
+
+    #[test]
+    fn test_ellipse_plane_intersection() {
+        // An ellipse tilted around its minor axis, from z = -1 to z = 1.
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::from_f64(-1.0, 0.0, 1.0).normalize().unwrap(),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::from_f64(0.0, 0.5, 0.0),
+        );
+        let plane = Plane::new(
+            Point::from_f64(0.0, 0.0, 0.5),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        match ellipse_plane_intersection(&ellipse, &plane) {
+            EllipsePlaneIntersection::TwoPoints(p1, p2) => {
+                for p in [p1, p2] {
+                    assert!(ellipse.on_curve(p));
+                    assert!(p.z == 0.5);
+                }
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+        let plane = Plane::new(
+            Point::from_f64(0.0, 0.0, 1.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        match ellipse_plane_intersection(&ellipse, &plane) {
+            EllipsePlaneIntersection::OnePoint(p) => {
+                assert_eq!(p, Point::from_f64(1.0, 0.0, 1.0))
+            }
+            _ => panic!("Intersection should be a single point"),
+        }
+        let plane = Plane::new(
+            Point::zero(),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::unit_y(),
+        );
+        assert!(matches!(
+            ellipse_plane_intersection(&ellipse, &plane),
+            EllipsePlaneIntersection::Ellipse(_)
+        ));
+    }
+}
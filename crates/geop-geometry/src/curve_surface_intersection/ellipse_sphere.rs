@@ -0,0 +1,22 @@
+use crate::{curves::ellipse::Ellipse, point::Point, surfaces::sphere::Sphere};
+
+pub enum EllipseSphereIntersection {
+    Ellipse(Ellipse),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn ellipse_sphere_intersection(
+    ellipse: &Ellipse,
+    sphere: &Sphere,
+) -> EllipseSphereIntersection {
+    // |q|^2 - r^2
+    let radius_sq = sphere.radius.square();
+    match ellipse.implicit_intersection(sphere.basis, |e| {
+        &e.norm_sq() - &(&(&e.w * &e.w) * radius_sq)
+    }) {
+        None => EllipseSphereIntersection::Ellipse(ellipse.clone()),
+        Some(points) if points.is_empty() => EllipseSphereIntersection::None,
+        Some(points) => EllipseSphereIntersection::Points(points),
+    }
+}
@@ -0,0 +1,72 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{curves::ellipse::Ellipse, point::Point, surfaces::torus::Torus};
+
+pub enum EllipseTorusIntersection {
+    Ellipse(Ellipse),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn ellipse_torus_intersection(ellipse: &Ellipse, torus: &Torus) -> EllipseTorusIntersection {
+    // (|q|^2 + R^2 - r^2)^2 - 4 R^2 (|q|^2 - (q . n)^2), which has degree 4.
+    let major_sq = torus.major_radius.square();
+    let minor_sq = torus.minor_radius.square();
+    match ellipse.implicit_intersection(torus.basis, |e| {
+        let w_sq = &e.w * &e.w;
+        let norm_sq = e.norm_sq();
+        let height = e.dot(torus.normal);
+        let a = &norm_sq + &(&w_sq * (major_sq - minor_sq));
+        let b = &(&w_sq * (EFloat64::from(4.0) * major_sq)) * &(&norm_sq - &(&height * &height));
+        &(&a * &a) - &b
+    }) {
+        None => EllipseTorusIntersection::Ellipse(ellipse.clone()),
+        Some(points) if points.is_empty() => EllipseTorusIntersection::None,
+        Some(points) => EllipseTorusIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::surfaces::SurfaceLike;
+
+    use super::*;
+
+    #[test]
+    fn test_ellipse_torus_intersection() {
+        let torus = Torus::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(2.0),
+            EFloat64::from(0.5),
+            true,
+        );
+        // A flat ellipse through the tube crosses the inner and the outer side four times each.
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(3.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        match ellipse_torus_intersection(&ellipse, &torus) {
+            EllipseTorusIntersection::Points(points) => {
+                assert_eq!(points.len(), 8);
+                for p in points {
+                    assert!(torus.on_surface(p));
+                }
+            }
+            _ => panic!("Intersection should be eight points"),
+        }
+        // The outer equator is a circle on the torus.
+        let equator = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.5, 0.0, 0.0),
+            Point::from_f64(0.0, 2.5, 0.0),
+        );
+        assert!(matches!(
+            ellipse_torus_intersection(&equator, &torus),
+            EllipseTorusIntersection::Ellipse(_)
+        ));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    curves::helix::Helix, point::Point, surfaces::cone::Cone, tolerance::Tolerance, HORIZON_DIST,
+};
+
+use super::find_helix_roots;
+
+// Returns the turns of the helix that can meet the cone.
+// The helix stays within one radius of its axis, so the axis has to come close to the cone. At a turn t the axis is at height h0 + k t and distance |w + v t| from the axis of the cone, which is tan * height on the cone.
+// The boundaries of the range solve |w + v t|^2 = (tan * (h0 + k t) +- bound)^2.
+fn turn_range(helix: &Helix, cone: &Cone) -> Option<(f64, f64)> {
+    let precision = Tolerance::current().numerical;
+    let d = cone.extend_dir;
+    let tan = (cone.half_angle.sin() / cone.half_angle.cos())
+        .unwrap()
+        .mid();
+    let offset = helix.basis - cone.basis;
+    let (h0, k) = (offset.dot(d), helix.pitch.dot(d));
+    let (w, v) = (offset - h0 * d, helix.pitch - k * d);
+    let (h0, k) = (h0.mid(), k.mid());
+    let bound = helix.radius.norm().mid() * (1.0 + tan) + precision;
+
+    let speed = helix.pitch.norm().mid();
+    let a = v.dot(v).mid() - tan * tan * k * k;
+    // The axis is parallel to a generator, so the helix may cross the cone in every turn. Only the turns up to the horizon are searched.
+    if a.abs() <= precision * speed * speed {
+        let center = -offset.dot(helix.pitch).mid() / (speed * speed);
+        let extend = (HORIZON_DIST + offset.norm().mid()) / speed;
+        return Some((center - extend, center + extend));
+    }
+
+    let mut roots = Vec::new();
+    for side in [1.0, -1.0] {
+        let e = tan * h0 + side * bound;
+        let b = 2.0 * (w.dot(v).mid() - tan * k * e);
+        let c = w.dot(w).mid() - e * e;
+        let discriminant = b * b - 4.0 * a * c;
+        // A double root is kept even if rounding makes the discriminant slightly negative.
+        if discriminant >= -precision * b * b {
+            let root = discriminant.max(0.0).sqrt();
+            roots.push((-b + root) / (2.0 * a));
+            roots.push((-b - root) / (2.0 * a));
+        }
+    }
+    let start = roots.iter().cloned().fold(f64::INFINITY, f64::min);
+    let end = roots.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    match start <= end {
+        true => Some((start - precision, end + precision)),
+        false => None,
+    }
+}
+
+// Apart from an axis along a generator, a helix meets a cone only in a bounded range of turns, so there are finitely many intersections. These are found numerically.
+pub fn helix_cone_intersection(helix: &Helix, cone: &Cone) -> Vec<Point> {
+    let Some((start, end)) = turn_range(helix, cone) else {
+        return Vec::new();
+    };
+    // The signed distance to the cone along its normal is h cos - r sin. It is negative below the apex, so only the nappe of the cone has roots.
+    let d = cone.extend_dir;
+    let (sin, cos) = (cone.half_angle.sin(), cone.half_angle.cos());
+    find_helix_roots(
+        helix,
+        |p| {
+            let p = p - cone.basis;
+            let height = p.dot(d);
+            let radius = (p - height * d).norm();
+            (height * cos - radius * sin).mid()
+        },
+        start,
+        end,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use super::*;
+    use crate::surfaces::SurfaceLike;
+
+    fn cone() -> Cone {
+        Cone::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(std::f64::consts::FRAC_PI_4),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_helix_cone_intersection() {
+        // The coaxial helix of radius 1 meets the cone once, at height 1.
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        let points = helix_cone_intersection(&helix, &cone());
+        assert_eq!(points.len(), 1);
+        assert!((points[0] - Point::from_f64(1.0, 0.0, 1.0)).norm() < 1e-6);
+
+        // A helix across the axis enters and leaves the cone.
+        let helix = Helix::new(
+            Point::from_f64(0.0, 0.0, 5.0),
+            Point::unit_x(),
+            Point::unit_y(),
+            true,
+        );
+        let points = helix_cone_intersection(&helix, &cone());
+        assert!(!points.is_empty());
+        for p in points {
+            assert!((cone().project(p) - p).norm() < 1e-6);
+            assert!(p.x.abs() < 6.0);
+        }
+
+        // Below the apex there is only the other nappe.
+        let helix = Helix::new(
+            Point::from_f64(0.0, 0.0, -5.0),
+            Point::unit_x(),
+            Point::unit_y(),
+            true,
+        );
+        assert!(helix_cone_intersection(&helix, &cone()).is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curve_curve_intersection::curve_curve::PointArray, curves::helix::Helix, point::Point,
+    surfaces::cylinder::Cylinder, tolerance::Tolerance,
+};
+
+use super::{find_helix_roots, helix_plane::helix_point_arrays};
+
+pub enum HelixCylinderIntersection {
+    Helix(Helix),
+    PointArrays(Vec<PointArray>),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn helix_cylinder_intersection(
+    helix: &Helix,
+    cylinder: &Cylinder,
+) -> HelixCylinderIntersection {
+    let precision = Tolerance::current().numerical;
+    let axis = cylinder.extend_dir;
+    let project = |v: Point| v - axis * axis.dot(v);
+    let radius = cylinder.radius.norm();
+    let offset = project(helix.basis - cylinder.basis);
+
+    if helix.pitch.is_parallel(axis) {
+        // A coaxial helix either winds on the cylinder or does not touch it.
+        if Tolerance::current().is_zero_length(offset.norm()) {
+            return match Tolerance::current().is_zero_length(helix.radius.norm() - radius) {
+                true => HelixCylinderIntersection::Helix(helix.clone()),
+                false => HelixCylinderIntersection::None,
+            };
+        }
+        // |offset + cos * radius + sin * dir_cross|^2 = r^2 has the same solutions in every turn.
+        let arrays = helix_point_arrays(
+            helix,
+            offset.norm_sq() + helix.radius.norm_sq() - radius.square(),
+            EFloat64::two() * offset.dot(helix.radius),
+            EFloat64::two() * offset.dot(helix.dir_cross),
+        );
+        return match arrays.is_empty() {
+            true => HelixCylinderIntersection::None,
+            false => HelixCylinderIntersection::PointArrays(arrays),
+        };
+    }
+
+    // The axis of the helix leaves the cylinder, so all intersections are in the turns where it is closer than both radii.
+    let speed = project(helix.pitch);
+    let center = -(offset.dot(speed) / speed.norm_sq()).unwrap().mid();
+    let extend = ((radius + helix.radius.norm()) / speed.norm())
+        .unwrap()
+        .mid()
+        + precision;
+    let points = find_helix_roots(
+        helix,
+        |p| (project(p - cylinder.basis).norm() - radius).mid(),
+        center - extend,
+        center + extend,
+    );
+    match points.is_empty() {
+        true => HelixCylinderIntersection::None,
+        false => HelixCylinderIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{curves::CurveLike, surfaces::SurfaceLike};
+
+    use super::*;
+
+    fn helix() -> Helix {
+        Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true)
+    }
+
+    #[test]
+    fn test_helix_cylinder_intersection() {
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+        assert!(matches!(
+            helix_cylinder_intersection(&helix(), &cylinder),
+            HelixCylinderIntersection::Helix(_)
+        ));
+
+        // A parallel cylinder through the axis of the helix is crossed twice every turn.
+        let cylinder = Cylinder::new(Point::unit_x(), Point::unit_z(), EFloat64::one(), true);
+        match helix_cylinder_intersection(&helix(), &cylinder) {
+            HelixCylinderIntersection::PointArrays(arrays) => {
+                assert_eq!(arrays.len(), 2);
+                for array in arrays {
+                    assert!(helix().on_curve(array.basis));
+                    assert!((cylinder.project(array.basis) - array.basis).norm() < 1e-9);
+                }
+            }
+            _ => panic!("Expected point arrays"),
+        }
+
+        // A thin cylinder across the axis is crossed where sin(2 pi t)^2 + t^2 = 0.16.
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_x(), EFloat64::from(0.4), true);
+        match helix_cylinder_intersection(&helix(), &cylinder) {
+            HelixCylinderIntersection::Points(points) => {
+                assert_eq!(points.len(), 2);
+                for p in points {
+                    assert!((cylinder.project(p) - p).norm() < 1e-9);
+                }
+            }
+            _ => panic!("Expected points"),
+        }
+    }
+}
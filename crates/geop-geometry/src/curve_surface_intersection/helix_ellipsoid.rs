@@ -0,0 +1,58 @@
+use crate::{
+    curves::helix::Helix, point::Point, surfaces::ellipsoid::Ellipsoid, tolerance::Tolerance,
+};
+
+use super::find_helix_roots;
+
+// A helix leaves every ellipsoid along its axis, so there are finitely many intersections. These are found numerically on the unit sphere the ellipsoid is the image of.
+pub fn helix_ellipsoid_intersection(helix: &Helix, ellipsoid: &Ellipsoid) -> Vec<Point> {
+    let precision = Tolerance::current().numerical;
+    let inverse = ellipsoid.frame().inverse().unwrap();
+    // All intersections are in the turns where the height along the axis is within the largest radius of the center.
+    let speed = helix.pitch.norm();
+    let center = ((ellipsoid.basis - helix.basis).dot(helix.pitch) / speed.square())
+        .unwrap()
+        .mid();
+    let largest = ellipsoid
+        .radii
+        .iter()
+        .map(|r| r.norm().mid())
+        .fold(0.0, f64::max);
+    let extend = largest / speed.mid() + precision;
+    find_helix_roots(
+        helix,
+        |p| (inverse * p).norm().mid() - 1.0,
+        center - extend,
+        center + extend,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use crate::surfaces::SurfaceLike;
+
+    use super::*;
+
+    #[test]
+    fn test_helix_ellipsoid_intersection() {
+        // The helix leaves the ellipsoid with the semi axes 2, 2 and 3 where 1 / 4 + t^2 / 9 = 1.
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        let ellipsoid = Ellipsoid::new(
+            Point::zero(),
+            [
+                Point::from_f64(2.0, 0.0, 0.0),
+                Point::from_f64(0.0, 2.0, 0.0),
+                Point::from_f64(0.0, 0.0, 3.0),
+            ],
+            true,
+        );
+        let points = helix_ellipsoid_intersection(&helix, &ellipsoid);
+        assert_eq!(points.len(), 2);
+        for p in points {
+            assert!((ellipsoid.project(p) - p).norm() < 1e-9);
+            assert!((p.z.abs() - EFloat64::from(27.0f64.sqrt() / 2.0)).abs() < 1e-9);
+        }
+    }
+}
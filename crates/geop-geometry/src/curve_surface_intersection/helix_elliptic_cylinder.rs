@@ -0,0 +1,117 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curve_curve_intersection::curve_curve::PointArray, curves::helix::Helix, point::Point,
+    surfaces::elliptic_cylinder::EllipticCylinder, tolerance::Tolerance,
+};
+
+use super::{find_angle_roots, find_helix_roots};
+
+const SAMPLES: usize = 64;
+
+pub enum HelixEllipticCylinderIntersection {
+    PointArrays(Vec<PointArray>),
+    Points(Vec<Point>),
+    None,
+}
+
+// The intersections are found numerically on the cylinder of radius one around the z axis, which the elliptic cylinder is the image of.
+pub fn helix_elliptic_cylinder_intersection(
+    helix: &Helix,
+    cylinder: &EllipticCylinder,
+) -> HelixEllipticCylinderIntersection {
+    let precision = Tolerance::current().numerical;
+    let inverse = cylinder.frame().inverse().unwrap();
+    let f = |p: Point| {
+        let q = inverse * p;
+        (q.x.square() + q.y.square()).sqrt().unwrap().mid() - 1.0
+    };
+
+    // The axis is parallel to the cylinder, so the helix crosses it in the same way every turn.
+    if helix.pitch.is_parallel(cylinder.extend_dir) {
+        let point_at =
+            |angle: f64| helix.point_at_pitch(EFloat64::from(angle / (2.0 * std::f64::consts::PI)));
+        let arrays: Vec<PointArray> =
+            find_angle_roots(|angle| f(point_at(angle)), SAMPLES, precision)
+                .into_iter()
+                .map(|angle| PointArray {
+                    basis: point_at(angle),
+                    extend_dir: helix.pitch,
+                })
+                .collect();
+        return match arrays.is_empty() {
+            true => HelixEllipticCylinderIntersection::None,
+            false => HelixEllipticCylinderIntersection::PointArrays(arrays),
+        };
+    }
+
+    // The axis of the helix leaves the cylinder, so all intersections are in the turns where it is closer than the largest radius and the radius of the helix.
+    let axis = cylinder.extend_dir;
+    let project = |v: Point| v - axis * axis.dot(v);
+    let offset = project(helix.basis - cylinder.basis);
+    let speed = project(helix.pitch);
+    let center = -(offset.dot(speed) / speed.norm_sq()).unwrap().mid();
+    let largest = cylinder
+        .major_radius
+        .norm()
+        .max(cylinder.minor_radius.norm());
+    let extend = ((largest + helix.radius.norm()) / speed.norm())
+        .unwrap()
+        .mid()
+        + precision;
+    let points = find_helix_roots(helix, f, center - extend, center + extend);
+    match points.is_empty() {
+        true => HelixEllipticCylinderIntersection::None,
+        false => HelixEllipticCylinderIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::surfaces::SurfaceLike;
+
+    use super::*;
+
+    fn cylinder() -> EllipticCylinder {
+        EllipticCylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 0.5, 0.0),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_helix_elliptic_cylinder_intersection() {
+        // The coaxial helix crosses the section four times every turn.
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        match helix_elliptic_cylinder_intersection(&helix, &cylinder()) {
+            HelixEllipticCylinderIntersection::PointArrays(arrays) => {
+                assert_eq!(arrays.len(), 4);
+                for array in arrays {
+                    assert!((cylinder().project(array.basis) - array.basis).norm() < 1e-9);
+                }
+            }
+            _ => panic!("Expected point arrays"),
+        }
+
+        // Across the axis, a thin helix crosses the cylinder only near its surface.
+        let helix = Helix::new(
+            Point::from_f64(-5.0, 0.0, 0.0),
+            Point::unit_x(),
+            Point::from_f64(0.0, 0.0, 0.1),
+            true,
+        );
+        match helix_elliptic_cylinder_intersection(&helix, &cylinder()) {
+            HelixEllipticCylinderIntersection::Points(points) => {
+                assert_eq!(points.len(), 2);
+                for p in points {
+                    assert!((cylinder().project(p) - p).norm() < 1e-9);
+                    assert!((p.x.abs() - EFloat64::two()).abs() < 0.05);
+                }
+            }
+            _ => panic!("Expected points"),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curve_curve_intersection::curve_curve::PointArray, curves::helix::Helix, point::Point,
+    surfaces::plane::Plane, tolerance::Tolerance,
+};
+
+use super::{enclose_helix_roots, find_angle_roots};
+
+const SAMPLES: usize = 64;
+
+pub enum HelixPlaneIntersection {
+    PointArrays(Vec<PointArray>),
+    Points(Vec<Point>),
+    None,
+}
+
+// The helix returns to the same angle after every pitch. Each angle in [0, 2 pi) with offset + cos * a + sin * b = 0 starts a point array.
+pub(super) fn helix_point_arrays(
+    helix: &Helix,
+    offset: EFloat64,
+    a: EFloat64,
+    b: EFloat64,
+) -> Vec<PointArray> {
+    let precision = Tolerance::current().numerical;
+    let (offset, a, b) = (offset.mid(), a.mid(), b.mid());
+    find_angle_roots(
+        |angle| offset + angle.cos() * a + angle.sin() * b,
+        SAMPLES,
+        precision,
+    )
+    .into_iter()
+    .map(|angle| PointArray {
+        basis: helix.point_at_pitch(EFloat64::from(angle / (2.0 * std::f64::consts::PI))),
+        extend_dir: helix.pitch,
+    })
+    .collect()
+}
+
+pub fn helix_plane_intersection(helix: &Helix, plane: &Plane) -> HelixPlaneIntersection {
+    let precision = Tolerance::current().numerical;
+    let normal = plane.u_slope.cross(plane.v_slope).normalize().unwrap();
+    let offset = (helix.basis - plane.basis).dot(normal);
+
+    // The axis is parallel to the plane, so the helix crosses it in the same way every turn.
+    if helix.pitch.is_perpendicular(normal) {
+        let arrays = helix_point_arrays(
+            helix,
+            offset,
+            helix.radius.dot(normal),
+            helix.dir_cross.dot(normal),
+        );
+        return match arrays.is_empty() {
+            true => HelixPlaneIntersection::None,
+            false => HelixPlaneIntersection::PointArrays(arrays),
+        };
+    }
+
+    // Otherwise all intersections are in the turns where the axis is within one radius of the plane.
+    let speed = helix.pitch.dot(normal).mid();
+    let extend = helix.radius.norm().mid() / speed.abs() + precision;
+    let center = -offset.mid() / speed;
+    let points = enclose_helix_roots(
+        helix,
+        |t| (helix.point_at_pitch(t) - plane.basis).dot(normal),
+        |t| helix.derivative_at_pitch(t).dot(normal),
+        center - extend,
+        center + extend,
+    );
+    match points.is_empty() {
+        true => HelixPlaneIntersection::None,
+        false => HelixPlaneIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::surfaces::SurfaceLike;
+
+    use super::*;
+
+    fn helix() -> Helix {
+        Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true)
+    }
+
+    #[test]
+    fn test_helix_plane_intersection() {
+        // A plane across the axis is crossed once.
+        let plane = Plane::new(
+            Point::from_f64(0.0, 0.0, 2.5),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        match helix_plane_intersection(&helix(), &plane) {
+            HelixPlaneIntersection::Points(points) => {
+                assert_eq!(points.len(), 1);
+                assert_eq!(points[0], Point::from_f64(-1.0, 0.0, 2.5));
+            }
+            _ => panic!("Expected one point"),
+        }
+
+        // A plane along the axis is crossed twice every turn.
+        let plane = Plane::new(
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        );
+        match helix_plane_intersection(&helix(), &plane) {
+            HelixPlaneIntersection::PointArrays(arrays) => {
+                assert_eq!(arrays.len(), 2);
+                for array in arrays {
+                    assert!((plane.project(array.basis) - array.basis).norm() < 1e-9);
+                    assert_eq!(array.extend_dir, Point::unit_z());
+                }
+            }
+            _ => panic!("Expected point arrays"),
+        }
+
+        // An oblique plane x + z = 3.25 is crossed where t + cos(2 pi t) = 3.25.
+        let plane = Plane::new(
+            Point::from_f64(3.25, 0.0, 0.0),
+            Point::unit_y(),
+            Point::from_f64(-1.0, 0.0, 1.0),
+        );
+        match helix_plane_intersection(&helix(), &plane) {
+            HelixPlaneIntersection::Points(points) => {
+                assert_eq!(points.len(), 3);
+                for p in points {
+                    assert!(p.x + p.z == 3.25);
+                }
+            }
+            _ => panic!("Expected points"),
+        }
+
+        let plane = Plane::new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        );
+        assert!(matches!(
+            helix_plane_intersection(&helix(), &plane),
+            HelixPlaneIntersection::None
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{curves::helix::Helix, point::Point, surfaces::sphere::Sphere, tolerance::Tolerance};
+
+use super::find_helix_roots;
+
+// A helix leaves every sphere along its axis, so there are finitely many intersections. These are found numerically.
+pub fn helix_sphere_intersection(helix: &Helix, sphere: &Sphere) -> Vec<Point> {
+    let precision = Tolerance::current().numerical;
+    // All intersections are in the turns where the height along the axis is within one radius of the center.
+    let speed = helix.pitch.norm();
+    let center = ((sphere.basis - helix.basis).dot(helix.pitch) / speed.square())
+        .unwrap()
+        .mid();
+    let extend = (sphere.radius / speed).unwrap().mid() + precision;
+    find_helix_roots(
+        helix,
+        |p| ((p - sphere.basis).norm() - sphere.radius).mid(),
+        center - extend,
+        center + extend,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use super::*;
+
+    #[test]
+    fn test_helix_sphere_intersection() {
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        // The helix is at distance sqrt(1 + t^2) from the origin.
+        let sphere = Sphere::new(Point::zero(), EFloat64::from(1.5), true);
+        let points = helix_sphere_intersection(&helix, &sphere);
+        assert_eq!(points.len(), 2);
+        for p in points {
+            assert!(((p - sphere.basis).norm() - sphere.radius).abs() < 1e-9);
+            assert!((p.z.abs() - EFloat64::from(1.25f64.sqrt())).abs() < 1e-9);
+        }
+
+        let sphere = Sphere::new(Point::from_f64(0.0, 0.0, 3.0), EFloat64::from(0.5), true);
+        assert!(helix_sphere_intersection(&helix, &sphere).is_empty());
+    }
+}
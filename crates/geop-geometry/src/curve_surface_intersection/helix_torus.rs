@@ -0,0 +1,46 @@
+use crate::{curves::helix::Helix, point::Point, surfaces::torus::Torus, tolerance::Tolerance};
+
+use super::{find_helix_roots, line_torus::torus_distance};
+
+// A helix leaves every torus along its axis, so there are finitely many intersections. These are found numerically.
+pub fn helix_torus_intersection(helix: &Helix, torus: &Torus) -> Vec<Point> {
+    let precision = Tolerance::current().numerical;
+    // All intersections are in the turns where the axis is within the bounding sphere of the torus and one radius of the helix.
+    let speed = helix.pitch.norm();
+    let center = ((torus.basis - helix.basis).dot(helix.pitch) / speed.square())
+        .unwrap()
+        .mid();
+    let extend = torus.major_radius + torus.minor_radius + helix.radius.norm();
+    let extend = (extend / speed).unwrap().mid() + precision;
+    find_helix_roots(
+        helix,
+        |p| torus_distance(torus, p),
+        center - extend,
+        center + extend,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use super::*;
+
+    #[test]
+    fn test_helix_torus_intersection() {
+        // The coaxial helix runs through the middle of the tube and leaves it at the height of the minor radius.
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        let torus = Torus::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::one(),
+            EFloat64::from(0.5),
+            true,
+        );
+        let points = helix_torus_intersection(&helix, &torus);
+        assert_eq!(points.len(), 2);
+        for p in points {
+            assert!((torus_distance(&torus, p)).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{curves::line::Line, point::Point, surfaces::cone::Cone};
+
+pub enum LineConeIntersection {
+    Line(Line),
+    TwoPoints(Point, Point),
+    Point(Point),
+    None,
+}
+
+pub fn line_cone_intersection(line: &Line, cone: &Cone) -> LineConeIntersection {
+    // A point p is on both nappes of the cone if ((p - a) . d)^2 = cos^2 * |p - a|^2.
+    // With p = b + v t this is a quadratic equation in t.
+    let w = line.basis - cone.basis;
+    let v = line.direction;
+    let d = cone.extend_dir;
+    let cos_sq = cone.half_angle.cos().square();
+    let a = v.dot(d).square() - cos_sq * v.dot(v);
+    let b = EFloat64::two() * (w.dot(d) * v.dot(d) - cos_sq * w.dot(v));
+    let c = w.dot(d).square() - cos_sq * w.dot(w);
+
+    // Only points in extend_dir from the apex are on the cone.
+    let on_cone = |t: EFloat64| {
+        let p = line.basis + v * t;
+        match (p - cone.basis).dot(d).upper_bound >= 0.0 {
+            true => Some(p),
+            false => None,
+        }
+    };
+
+    if a == 0.0 {
+        if b == 0.0 {
+            // The line goes through the apex along the cone. Only half of it lies on the cone.
+            if c == 0.0 {
+                return LineConeIntersection::Line(line.clone());
+            }
+            return LineConeIntersection::None;
+        }
+        return match on_cone((-c / b).unwrap()) {
+            Some(p) => LineConeIntersection::Point(p),
+            None => LineConeIntersection::None,
+        };
+    }
+
+    let discriminant = b.square() - EFloat64::from(4.0) * a * c;
+    if discriminant == 0.0 {
+        return match on_cone((-b / (EFloat64::two() * a)).unwrap()) {
+            Some(p) => LineConeIntersection::Point(p),
+            None => LineConeIntersection::None,
+        };
+    }
+    if discriminant < 0.0 {
+        return LineConeIntersection::None;
+    }
+    let root = discriminant.sqrt().unwrap();
+    let t1 = ((-b + root) / (EFloat64::two() * a)).unwrap();
+    let t2 = ((-b - root) / (EFloat64::two() * a)).unwrap();
+    match (on_cone(t1), on_cone(t2)) {
+        (Some(p1), Some(p2)) => LineConeIntersection::TwoPoints(p1, p2),
+        (Some(p), None) | (None, Some(p)) => LineConeIntersection::Point(p),
+        (None, None) => LineConeIntersection::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::SurfaceLike;
+
+    fn cone() -> Cone {
+        Cone::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(std::f64::consts::FRAC_PI_4),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_line_cone_intersection_two_points() {
+        let line = Line::new(Point::from_f64(0.0, 0.0, 2.0), Point::unit_x()).unwrap();
+        match line_cone_intersection(&line, &cone()) {
+            LineConeIntersection::TwoPoints(p1, p2) => {
+                assert_eq!(p1, Point::from_f64(-2.0, 0.0, 2.0));
+                assert_eq!(p2, Point::from_f64(2.0, 0.0, 2.0));
+            }
+            _ => panic!("Should be two points!"),
+        }
+    }
+
+    #[test]
+    fn test_line_cone_intersection_single_nappe() {
+        // The line crosses both nappes, but only one intersection is on the cone.
+        let direction = Point::from_f64(1.0, 0.0, 2.0).normalize().unwrap();
+        let line = Line::new(Point::from_f64(0.5, 0.0, 0.0), direction).unwrap();
+        let cone = cone();
+        match line_cone_intersection(&line, &cone) {
+            LineConeIntersection::Point(p) => {
+                assert!(cone.on_surface(p));
+                assert_eq!(p, Point::from_f64(1.0, 0.0, 1.0));
+            }
+            _ => panic!("Should be a point!"),
+        }
+
+        // Parallel to a generator, the line hits the cone once.
+        let direction = Point::from_f64(1.0, 0.0, 1.0).normalize().unwrap();
+        let line = Line::new(Point::from_f64(-1.0, 0.0, 0.0), direction).unwrap();
+        match line_cone_intersection(&line, &cone) {
+            LineConeIntersection::Point(p) => {
+                assert_eq!(p, Point::from_f64(-0.5, 0.0, 0.5));
+            }
+            _ => panic!("Should be a point!"),
+        }
+
+        let line = Line::new(Point::from_f64(0.0, 0.0, -1.0), Point::unit_x()).unwrap();
+        assert!(matches!(
+            line_cone_intersection(&line, &cone),
+            LineConeIntersection::None
+        ));
+    }
+
+    #[test]
+    fn test_line_cone_intersection_line() {
+        let direction = Point::from_f64(0.0, 1.0, 1.0).normalize().unwrap();
+        let line = Line::new(Point::zero(), direction).unwrap();
+        assert!(matches!(
+            line_cone_intersection(&line, &cone()),
+            LineConeIntersection::Line(_)
+        ));
+    }
+}
@@ -0,0 +1,89 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{curves::line::Line, point::Point, surfaces::torus::Torus, tolerance::Tolerance};
+
+use super::find_roots;
+
+const SAMPLES: usize = 256;
+
+// Signed distance of p to the torus. It is negative inside the tube.
+pub(super) fn torus_distance(torus: &Torus, p: Point) -> f64 {
+    let p = p - torus.basis;
+    let height = p.dot(torus.normal);
+    let radius = (p - height * torus.normal).norm();
+    let tube = ((radius - torus.major_radius).square() + height.square())
+        .sqrt()
+        .unwrap();
+    let distance = tube - torus.minor_radius;
+    distance.mid()
+}
+
+// A line intersects a torus in up to 4 points. These are the roots of a quartic, which are found numerically.
+pub fn line_torus_intersection(line: &Line, torus: &Torus) -> Vec<Point> {
+    let precision = Tolerance::current().numerical;
+    let point_at = |t: f64| line.basis + line.direction * EFloat64::from(t);
+    // All intersections are within the bounding sphere of the torus around the closest point to its center.
+    let closest = (torus.basis - line.basis).dot(line.direction);
+    let closest = closest.mid();
+    let extend = torus.major_radius + torus.minor_radius;
+    let extend = extend.upper_bound + precision;
+    find_roots(
+        |t| torus_distance(torus, point_at(t)),
+        closest - extend,
+        closest + extend,
+        SAMPLES,
+        precision,
+    )
+    .into_iter()
+    .map(point_at)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::SurfaceLike;
+
+    fn torus() -> Torus {
+        Torus::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(3.0),
+            EFloat64::one(),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_line_torus_intersection_four_points() {
+        let line = Line::new(Point::from_f64(0.0, 5.0, 0.0), Point::unit_x()).unwrap();
+        assert!(line_torus_intersection(&line, &torus()).is_empty());
+
+        let line = Line::new(Point::from_f64(0.0, 0.0, 0.0), Point::unit_x()).unwrap();
+        let points = line_torus_intersection(&line, &torus());
+        let expected = [-4.0, -2.0, 2.0, 4.0];
+        assert_eq!(points.len(), 4);
+        for (p, x) in points.iter().zip(expected) {
+            assert!((*p - Point::from_f64(x, 0.0, 0.0)).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_line_torus_intersection_tangent() {
+        // Touches the top of the tube twice.
+        let line = Line::new(Point::from_f64(0.0, 0.0, 1.0), Point::unit_y()).unwrap();
+        let points = line_torus_intersection(&line, &torus());
+        assert_eq!(points.len(), 2);
+        for p in points {
+            assert!((torus().project(p) - p).norm() < 1e-6);
+        }
+
+        // Parallel to the axis through the tube.
+        let line = Line::new(Point::from_f64(3.5, 0.0, 0.0), Point::unit_z()).unwrap();
+        let points = line_torus_intersection(&line, &torus());
+        assert_eq!(points.len(), 2);
+        for p in points {
+            assert!(torus().on_surface(p));
+        }
+    }
+}
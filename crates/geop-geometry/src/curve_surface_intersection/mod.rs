@@ -1,14 +1,36 @@
-use geop_algebra::efloat::EFloat64;
+use geop_algebra::{efloat::EFloat64, interval_newton::interval_newton, matrix::Matrix};
 
-use crate::{curves::CurveLike, point::Point, surfaces::SurfaceLike};
+use crate::{
+    curves::{helix::Helix, CurveLike},
+    point::Point,
+    surfaces::SurfaceLike,
+    tolerance::Tolerance,
+};
 
+pub mod circle_cone;
 pub mod circle_cylinder;
 pub mod circle_plane;
 pub mod circle_sphere;
+pub mod circle_torus;
 pub mod curve_surface;
+pub mod ellipse_cone;
+pub mod ellipse_cylinder;
+pub mod ellipse_plane;
+pub mod ellipse_sphere;
+pub mod ellipse_torus;
+pub mod helix_cone;
+pub mod helix_cylinder;
+pub mod helix_ellipsoid;
+pub mod helix_elliptic_cylinder;
+pub mod helix_plane;
+pub mod helix_sphere;
+pub mod helix_torus;
+pub mod line_cone;
 pub mod line_cylinder;
 pub mod line_plane;
 pub mod line_sphere;
+pub mod line_torus;
+pub mod nurbs;
 
 pub fn curve_surface_intersection_numerical(
     curve: &dyn CurveLike,
@@ -39,3 +61,140 @@ pub fn curve_surface_intersection_numerical(
 
     result
 }
+
+// Finds the roots of f in [a, b]. The interval is sampled and every sign change is refined by bisection.
+// Roots where f only touches zero are found by minimizing |f| around the samples and accepted if |f| <= tolerance there.
+pub(crate) fn find_roots(
+    f: impl Fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    samples: usize,
+    tolerance: f64,
+) -> Vec<f64> {
+    let step = (b - a) / samples as f64;
+    let xs: Vec<f64> = (0..=samples).map(|i| a + step * i as f64).collect();
+    let fs: Vec<f64> = xs.iter().map(|x| f(*x)).collect();
+
+    let mut roots: Vec<f64> = Vec::new();
+    let mut push = |root: f64| {
+        if !roots.iter().any(|r| (r - root).abs() < step / 2.0) {
+            roots.push(root);
+        }
+    };
+    for i in 0..samples {
+        if fs[i] == 0.0 {
+            push(xs[i]);
+        } else if fs[i] * fs[i + 1] < 0.0 {
+            let (mut lo, mut hi) = (xs[i], xs[i + 1]);
+            for _ in 0..64 {
+                let mid = (lo + hi) / 2.0;
+                match (f(lo) < 0.0) == (f(mid) < 0.0) {
+                    true => lo = mid,
+                    false => hi = mid,
+                }
+            }
+            push((lo + hi) / 2.0);
+        }
+    }
+    if fs[samples] == 0.0 {
+        push(xs[samples]);
+    }
+
+    // Golden section search for minima of |f| that do not change sign.
+    let ratio = (5.0f64.sqrt() - 1.0) / 2.0;
+    for i in 1..samples {
+        if fs[i].abs() > fs[i - 1].abs() || fs[i].abs() > fs[i + 1].abs() {
+            continue;
+        }
+        let (mut lo, mut hi) = (xs[i - 1], xs[i + 1]);
+        for _ in 0..64 {
+            let x1 = hi - ratio * (hi - lo);
+            let x2 = lo + ratio * (hi - lo);
+            match f(x1).abs() < f(x2).abs() {
+                true => hi = x2,
+                false => lo = x1,
+            }
+        }
+        let x = (lo + hi) / 2.0;
+        if f(x).abs() <= tolerance {
+            push(x);
+        }
+    }
+    roots.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    roots
+}
+
+// Finds the roots of a 2 pi periodic function f in [0, 2 pi).
+pub(crate) fn find_angle_roots(f: impl Fn(f64) -> f64, samples: usize, tolerance: f64) -> Vec<f64> {
+    let two_pi = 2.0 * std::f64::consts::PI;
+    let mut roots = find_roots(f, 0.0, two_pi, samples, tolerance);
+    if let (Some(first), Some(last)) = (roots.first(), roots.last()) {
+        if roots.len() > 1 && two_pi - last + first < two_pi / samples as f64 / 2.0 {
+            roots.pop();
+        }
+    }
+    roots
+}
+
+// Encloses the simple root of f close to x with the Krawczyk operator, where df encloses the derivative of f on intervals.
+// Returns None if there is no root, or more than one, within the radius around x.
+pub(crate) fn enclose_root(
+    f: impl Fn(EFloat64) -> EFloat64,
+    df: impl Fn(EFloat64) -> EFloat64,
+    x: f64,
+    radius: f64,
+) -> Option<EFloat64> {
+    let solution = interval_newton(
+        |x: &[EFloat64]| vec![f(x[0])],
+        |x: &[EFloat64]| Matrix::new(vec![vec![df(x[0])]]),
+        vec![EFloat64::new(x + radius, x - radius)],
+        Tolerance::current().parametric * radius,
+    );
+    match solution.unique.as_slice() {
+        [root] => Some(root[0]),
+        _ => None,
+    }
+}
+
+const HELIX_SAMPLES_PER_TURN: f64 = 64.0;
+
+// Finds the turns t within [start, end] where f vanishes. The interval is sampled a fixed number of times per turn.
+fn find_helix_parameters(f: impl Fn(f64) -> f64, start: f64, end: f64) -> Vec<f64> {
+    let samples = ((end - start) * HELIX_SAMPLES_PER_TURN)
+        .ceil()
+        .max(HELIX_SAMPLES_PER_TURN) as usize;
+    find_roots(f, start, end, samples, Tolerance::current().numerical)
+}
+
+// Finds the points of the helix within the turns [start, end] where f vanishes.
+pub(crate) fn find_helix_roots(
+    helix: &Helix,
+    f: impl Fn(Point) -> f64,
+    start: f64,
+    end: f64,
+) -> Vec<Point> {
+    let point_at = |t: f64| helix.point_at_pitch(EFloat64::from(t));
+    find_helix_parameters(|t| f(point_at(t)), start, end)
+        .into_iter()
+        .map(point_at)
+        .collect()
+}
+
+// Finds the points of the helix within the turns [start, end] where f vanishes, where f and its derivative df are functions of the turn.
+// Simple roots are enclosed with the Krawczyk operator, the others are approximated.
+pub(crate) fn enclose_helix_roots(
+    helix: &Helix,
+    f: impl Fn(EFloat64) -> EFloat64,
+    df: impl Fn(EFloat64) -> EFloat64,
+    start: f64,
+    end: f64,
+) -> Vec<Point> {
+    find_helix_parameters(|t| f(EFloat64::from(t)).mid(), start, end)
+        .into_iter()
+        .map(|t| {
+            let t =
+                enclose_root(&f, &df, t, 1.0 / HELIX_SAMPLES_PER_TURN).unwrap_or(EFloat64::from(t));
+            helix.point_at_pitch(t)
+        })
+        .collect()
+}
@@ -0,0 +1,265 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{line::Line, nurbs::Nurbs, CurveLike},
+    point::Point,
+    surfaces::{nurbs_surface::NurbsSurface, SurfaceLike},
+    tolerance::Tolerance,
+};
+
+const MAX_DEPTH: u32 = 64;
+const REFINEMENT_ITERATIONS: usize = 16;
+// Number of samples per knot span used to check if a curve lies on a surface.
+const ON_SURFACE_SAMPLES_PER_SPAN: usize = 4;
+// Distance relative to the size of a surface at which a line that meets it is checked to stay on it.
+const ON_SURFACE_OFFSET: f64 = 1e-3;
+
+fn push_unique(result: &mut Vec<Point>, p: Point) {
+    let precision = Tolerance::current().numerical;
+    if !result
+        .iter()
+        .any(|q| (*q - p).norm().mid() <= 10.0 * precision)
+    {
+        result.push(p);
+    }
+}
+
+// Center and radius of a sphere that contains all points.
+fn bounding_sphere<'a>(points: impl Iterator<Item = &'a Point>) -> (Point, f64) {
+    let points: Vec<&Point> = points.collect();
+    let mut min = *points[0];
+    let mut max = *points[0];
+    for p in points.iter() {
+        min = Point::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+        max = Point::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+    }
+    (
+        (min + max) * EFloat64::from(0.5),
+        (max - min).norm().mid() / 2.0,
+    )
+}
+
+fn nurbs_curve_surface_iteration(
+    nurbs: &Nurbs,
+    surface: &dyn SurfaceLike,
+    t0: f64,
+    t1: f64,
+    deepness: u32,
+    result: &mut Vec<Point>,
+) {
+    let precision = Tolerance::current().numerical;
+    let sub = nurbs.subcurve(EFloat64::from(t0), EFloat64::from(t1));
+    let (center, radius) = bounding_sphere(sub.control_points.iter());
+    if (surface.project(center) - center).norm().mid() > radius + precision {
+        return;
+    }
+
+    if radius > precision && deepness < MAX_DEPTH {
+        let t_mid = (t0 + t1) / 2.0;
+        nurbs_curve_surface_iteration(nurbs, surface, t0, t_mid, deepness + 1, result);
+        nurbs_curve_surface_iteration(nurbs, surface, t_mid, t1, deepness + 1, result);
+        return;
+    }
+
+    let mut p = nurbs.point_at(EFloat64::from((t0 + t1) / 2.0));
+    for _ in 0..REFINEMENT_ITERATIONS {
+        p = nurbs.project(surface.project(p));
+    }
+    if (surface.project(p) - p).norm().mid() <= precision {
+        push_unique(result, p);
+    }
+}
+
+// Finds the intersection points of a nurbs curve with any surface by subdividing the curve. They have to intersect in a finite number of points.
+pub fn nurbs_curve_surface_intersection(nurbs: &Nurbs, surface: &dyn SurfaceLike) -> Vec<Point> {
+    let (start, end) = nurbs.domain();
+    let mut result = Vec::new();
+    nurbs_curve_surface_iteration(nurbs, surface, start.mid(), end.mid(), 0, &mut result);
+    result
+}
+
+// Checks if the whole nurbs curve lies on the surface by testing sample points of every knot span.
+pub fn nurbs_curve_on_surface(nurbs: &Nurbs, surface: &dyn SurfaceLike) -> bool {
+    let precision = Tolerance::current().numerical;
+    (nurbs.degree..nurbs.control_points.len()).all(|i| {
+        let (a, b) = (nurbs.knots[i].mid(), nurbs.knots[i + 1].mid());
+        (0..=ON_SURFACE_SAMPLES_PER_SPAN).all(|j| {
+            if b <= a {
+                return true;
+            }
+            let t = a + (b - a) * j as f64 / ON_SURFACE_SAMPLES_PER_SPAN as f64;
+            let p = nurbs.point_at(EFloat64::from(t));
+            (surface.project(p) - p).norm().mid() <= precision
+        })
+    })
+}
+
+fn curve_nurbs_surface_iteration(
+    curve: &dyn CurveLike,
+    surface: &NurbsSurface,
+    u: (f64, f64),
+    v: (f64, f64),
+    deepness: u32,
+    max_points: usize,
+    result: &mut Vec<Point>,
+) {
+    let precision = Tolerance::current().numerical;
+    if result.len() >= max_points {
+        return;
+    }
+    let sub = surface.subsurface(
+        EFloat64::from(u.0),
+        EFloat64::from(u.1),
+        EFloat64::from(v.0),
+        EFloat64::from(v.1),
+    );
+    let (center, radius) = bounding_sphere(sub.control_points.iter().flatten());
+    if (curve.project(center) - center).norm().mid() > radius + precision {
+        return;
+    }
+
+    if radius > precision && deepness < MAX_DEPTH {
+        let u_mid = (u.0 + u.1) / 2.0;
+        let v_mid = (v.0 + v.1) / 2.0;
+        for u in [(u.0, u_mid), (u_mid, u.1)] {
+            for v in [(v.0, v_mid), (v_mid, v.1)] {
+                curve_nurbs_surface_iteration(
+                    curve,
+                    surface,
+                    u,
+                    v,
+                    deepness + 1,
+                    max_points,
+                    result,
+                );
+            }
+        }
+        return;
+    }
+
+    // Refine the candidate by projecting back and forth between the curve and the surface.
+    let (mut pu, mut pv) = ((u.0 + u.1) / 2.0, (v.0 + v.1) / 2.0);
+    let mut p = surface.point_at(EFloat64::from(pu), EFloat64::from(pv));
+    for _ in 0..REFINEMENT_ITERATIONS {
+        (pu, pv) = surface.parameter_near(curve.project(p), pu, pv);
+        p = surface.point_at(EFloat64::from(pu), EFloat64::from(pv));
+    }
+    if (curve.project(p) - p).norm().mid() <= precision {
+        push_unique(result, p);
+    }
+}
+
+// Finds up to max_points intersection points of any curve with a nurbs surface by subdividing the surface.
+fn curve_nurbs_surface_points(
+    curve: &dyn CurveLike,
+    surface: &NurbsSurface,
+    max_points: usize,
+) -> Vec<Point> {
+    let (u_start, u_end) = surface.domain_u();
+    let (v_start, v_end) = surface.domain_v();
+    let mut result = Vec::new();
+    curve_nurbs_surface_iteration(
+        curve,
+        surface,
+        (u_start.mid(), u_end.mid()),
+        (v_start.mid(), v_end.mid()),
+        0,
+        max_points,
+        &mut result,
+    );
+    result
+}
+
+// Finds the intersection points of any curve with a nurbs surface by subdividing the surface. They have to intersect in a finite number of points.
+pub fn curve_nurbs_surface_intersection(
+    curve: &dyn CurveLike,
+    surface: &NurbsSurface,
+) -> Vec<Point> {
+    curve_nurbs_surface_points(curve, surface, usize::MAX)
+}
+
+// Checks if the line lies on the surface along a piece, e.g. a ruling of a ruled surface. Subdividing would not terminate quickly in this case.
+// Around the first point where the line meets the surface, the line has to stay on the surface on at least one side.
+pub fn line_on_nurbs_surface(line: &Line, surface: &NurbsSurface) -> bool {
+    let precision = Tolerance::current().numerical;
+    let Some(p) = curve_nurbs_surface_points(line, surface, 1).pop() else {
+        return false;
+    };
+    let offset = EFloat64::from(ON_SURFACE_OFFSET * surface.get_bounding_box().max_size().mid());
+    [offset, -offset].into_iter().any(|s| {
+        let q = p + line.direction * s;
+        (surface.project(q) - q).norm().mid() <= precision
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curves::{circle::Circle, line::Line},
+        surfaces::plane::Plane,
+    };
+
+    #[test]
+    fn test_nurbs_curve_surface_intersection() {
+        let nurbs = Nurbs::new_uniform_clamped(
+            2,
+            vec![
+                Point::from_f64(0.0, 0.0, 0.0),
+                Point::from_f64(1.0, 2.0, 0.0),
+                Point::from_f64(2.0, 0.0, 0.0),
+            ],
+        )
+        .unwrap();
+        let plane = Plane::new(
+            Point::from_f64(0.0, 0.5, 0.0),
+            Point::unit_x(),
+            Point::unit_z(),
+        );
+        let points = nurbs_curve_surface_intersection(&nurbs, &plane);
+        assert_eq!(points.len(), 2);
+        assert!(!nurbs_curve_on_surface(&nurbs, &plane));
+        let on_plane = Plane::new(Point::zero(), Point::unit_x(), Point::unit_y());
+        assert!(nurbs_curve_on_surface(&nurbs, &on_plane));
+        for p in points {
+            assert!(plane.on_surface(p));
+            assert!(nurbs.on_curve(p));
+        }
+    }
+
+    #[test]
+    fn test_curve_nurbs_surface_intersection() {
+        // Bumped patch over [0, 2] x [0, 2].
+        let surface = NurbsSurface::new_uniform_clamped(
+            2,
+            2,
+            (0..3)
+                .map(|i| {
+                    (0..3)
+                        .map(|j| {
+                            let z = if i == 1 && j == 1 { 1.0 } else { 0.0 };
+                            Point::from_f64(i as f64, j as f64, z)
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap();
+
+        let line = Line::new(Point::from_f64(1.0, 1.0, 5.0), Point::unit_z()).unwrap();
+        let points = curve_nurbs_surface_intersection(&line, &surface);
+        assert_eq!(points, vec![Point::from_f64(1.0, 1.0, 0.25)]);
+
+        let circle = Circle::new(
+            Point::from_f64(5.0, 5.0, 0.0),
+            Point::unit_z(),
+            EFloat64::one(),
+        );
+        assert!(curve_nurbs_surface_intersection(&circle, &surface).is_empty());
+
+        // The bumped patch is not ruled, so no line lies on it.
+        assert!(!line_on_nurbs_surface(&line, &surface));
+        let edge = Line::new(Point::zero(), Point::unit_y()).unwrap();
+        assert!(line_on_nurbs_surface(&edge, &surface));
+    }
+}
@@ -1,10 +1,11 @@
 use geop_algebra::efloat::EFloat64;
 
 use crate::{
-    bounding_box::BoundingBox, geometry_error::GeometryResult, point::Point, transforms::Transform,
+    bounding_box::BoundingBox, geometry_error::GeometryResult, point::Point, tolerance::Tolerance,
+    transforms::Transform,
 };
 
-use super::{curve::Curve, CurveLike};
+use super::{curve::Curve, ellipse::Ellipse, CurveLike};
 
 #[derive(Debug, Clone)]
 pub struct Circle {
@@ -16,7 +17,7 @@ pub struct Circle {
 
 pub enum CircleTransform {
     Circle(Circle),
-    Ellipse(), // TODO: Implement this
+    Ellipse(Ellipse),
 }
 
 impl Circle {
@@ -41,15 +42,18 @@ impl Circle {
     }
 
     pub fn transform(&self, transform: Transform) -> CircleTransform {
+        // The radius and dir_cross are conjugate radii of the image. It is a circle if they stay orthogonal and of the same length.
         let basis = transform * self.basis;
-        let normal = transform * (self.normal + self.basis) - basis;
-        let radius = transform * (self.radius + self.basis) - basis;
-        assert!(transform.uniform_scale_factor() > 0.0, "Circle can only be transformed with uniform scaling. An extension of this method is planned to return ellipsis.");
-        CircleTransform::Circle(Circle::new(
-            basis,
-            normal.normalize().unwrap(),
-            radius.norm(),
-        ))
+        let u = transform * (self.radius + self.basis) - basis;
+        let v = transform * (self.dir_cross + self.basis) - basis;
+        if u.dot(v) == 0.0 && u.norm() == v.norm() {
+            return CircleTransform::Circle(Circle::new(
+                basis,
+                u.cross(v).normalize().unwrap(),
+                u.norm(),
+            ));
+        }
+        CircleTransform::Ellipse(Ellipse::from_conjugate(basis, u, v))
     }
 
     pub fn neg(&self) -> Circle {
@@ -61,7 +65,7 @@ impl CurveLike for Circle {
     fn transform(&self, transform: Transform) -> Curve {
         match self.transform(transform) {
             CircleTransform::Circle(circle) => Curve::Circle(circle),
-            CircleTransform::Ellipse() => todo!("Implement this"),
+            CircleTransform::Ellipse(ellipse) => Curve::Ellipse(ellipse),
         }
     }
 
@@ -74,9 +78,25 @@ impl CurveLike for Circle {
         Ok(self.normal.cross(p - self.basis).normalize().unwrap())
     }
 
+    // Every derivative by the angle turns the previous one by 90 degrees around the normal.
+    fn derivative(&self, p: Point, order: usize) -> GeometryResult<Point> {
+        assert!(self.on_curve(p));
+        if order == 0 {
+            return Ok(p);
+        }
+        let p = p - self.basis;
+        Ok(match order % 4 {
+            0 => p,
+            1 => self.normal.cross(p),
+            2 => -p,
+            _ => -self.normal.cross(p),
+        })
+    }
+
     fn on_curve(&self, p: Point) -> bool {
-        (p - self.basis).dot(self.normal) == 0.0
-            && ((p - self.basis).norm() - self.radius.norm()) == 0.0
+        let tolerance = Tolerance::current();
+        tolerance.is_zero_length((p - self.basis).dot(self.normal))
+            && tolerance.is_zero_length((p - self.basis).norm() - self.radius.norm())
     }
 
     fn distance(&self, x: Point, y: Point) -> GeometryResult<EFloat64> {
@@ -86,6 +106,27 @@ impl CurveLike for Circle {
         Ok(self.radius.norm() * angle.unwrap())
     }
 
+    fn length_between(&self, start: Point, end: Point) -> GeometryResult<EFloat64> {
+        assert!(self.on_curve(start));
+        assert!(self.on_curve(end));
+        let start = start - self.basis;
+        let end = end - self.basis;
+        let angle_start = self.dir_cross.dot(start).atan2(self.radius.dot(start));
+        let mut angle_end = self.dir_cross.dot(end).atan2(self.radius.dot(end));
+        if angle_end.upper_bound < angle_start.lower_bound {
+            angle_end = angle_end + EFloat64::two_pi();
+        }
+        Ok(self.radius.norm() * (angle_end - angle_start))
+    }
+
+    fn point_at_length(&self, start: Point, length: EFloat64) -> GeometryResult<Point> {
+        assert!(self.on_curve(start));
+        let start = start - self.basis;
+        let angle = self.dir_cross.dot(start).atan2(self.radius.dot(start));
+        let angle = angle + (length / self.radius.norm())?;
+        Ok(angle.cos() * self.radius + angle.sin() * self.dir_cross + self.basis)
+    }
+
     fn interpolate(
         &self,
         start: Option<Point>,
@@ -208,15 +249,24 @@ impl CurveLike for Circle {
     fn project(&self, p: Point) -> Point {
         let v = p - self.basis;
         let v = v - self.normal * (v.dot(self.normal));
-        v.normalize().unwrap() * self.radius.norm() + self.basis
+        match v.normalize() {
+            Ok(v) => v * self.radius.norm() + self.basis,
+            // All points of the circle are equally close to points on its axis.
+            Err(_) => self.basis + self.radius,
+        }
     }
 
+    // The box around the whole circle, which also contains every arc of it.
     fn get_bounding_box(
         &self,
-        _interval_self: Option<Point>,
-        _midpoint_self: Option<Point>,
+        _start: Option<Point>,
+        _end: Option<Point>,
     ) -> GeometryResult<BoundingBox> {
-        todo!("Implement this")
+        Ok(circle_bounding_box(
+            self.basis,
+            self.normal,
+            self.radius.norm(),
+        ))
     }
 
     fn shrink_bounding_box(
@@ -228,14 +278,159 @@ impl CurveLike for Circle {
         todo!("Implement this")
     }
 
-    fn sort(&self, _points: Vec<Option<Point>>) -> Vec<Option<Point>> {
-        todo!("Implement this")
+    fn sort(&self, points: Vec<Option<Point>>) -> Vec<Option<Point>> {
+        // Points are ordered by their angle in the direction of the circle, starting at the radius vector. Open ends go last.
+        let angle = |p: Point| {
+            let p = p - self.basis;
+            let angle = self.dir_cross.dot(p).atan2(self.radius.dot(p));
+            match angle < 0.0 {
+                true => angle + EFloat64::two_pi(),
+                false => angle,
+            }
+        };
+        let mut points = points;
+        points.sort_unstable_by(|a, b| match (a, b) {
+            (Some(a), Some(b)) => (angle(*a) - angle(*b))
+                .partial_cmp(&0.0)
+                .unwrap_or(std::cmp::Ordering::Equal),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        points
     }
 }
 
+// Box around a circle with a normalized normal. Along an axis the circle reaches as far as its radius times the sine of the angle between the axis and the normal.
+pub(crate) fn circle_bounding_box(basis: Point, normal: Point, radius: EFloat64) -> BoundingBox {
+    let extent = |n: EFloat64| radius * (EFloat64::one() - n * n).sqrt().unwrap();
+    let extent = Point::new(extent(normal.x), extent(normal.y), extent(normal.z));
+    BoundingBox::new(basis - extent, basis + extent)
+}
+
 // Implement partial eqality for Circle
 impl PartialEq for Circle {
     fn eq(&self, other: &Circle) -> bool {
         self.basis == other.basis && self.normal == other.normal && self.radius == other.radius
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::curves::line::Line;
+
+    use super::*;
+
+    #[test]
+    fn test_circle_sort() {
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::one());
+        let points = circle.sort(vec![
+            None,
+            Some(-Point::unit_y()),
+            Some(Point::unit_x()),
+            Some(-Point::unit_x()),
+            Some(Point::unit_y()),
+        ]);
+        assert_eq!(
+            points,
+            vec![
+                Some(Point::unit_x()),
+                Some(Point::unit_y()),
+                Some(-Point::unit_x()),
+                Some(-Point::unit_y()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_circle_bounding_box() {
+        // Tilted by 45 degrees around the x axis, the circle reaches its full radius only along x.
+        let normal = Point::from_f64(0.0, 1.0, 1.0).normalize().unwrap();
+        let circle = Circle::new(Point::unit_x(), normal, EFloat64::two());
+        let bounding_box = circle.get_bounding_box(None, None).unwrap();
+        let half = 2.0f64.sqrt();
+        assert_eq!(bounding_box.min, Point::from_f64(-1.0, -half, -half));
+        assert_eq!(bounding_box.max, Point::from_f64(3.0, half, half));
+        for i in 0..16 {
+            let p = circle.interpolate(None, None, i as f64 / 16.0).unwrap();
+            assert!(bounding_box.min.x <= p.x.upper_bound && p.x <= bounding_box.max.x.upper_bound);
+            assert!(bounding_box.min.y <= p.y.upper_bound && p.y <= bounding_box.max.y.upper_bound);
+            assert!(bounding_box.min.z <= p.z.upper_bound && p.z <= bounding_box.max.z.upper_bound);
+        }
+    }
+
+    #[test]
+    fn test_circle_arc_length() {
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::two());
+        let start = circle.interpolate(None, None, 0.0).unwrap();
+        let quarter = circle.interpolate(None, None, 0.25).unwrap();
+        assert_eq!(
+            circle.length_between(start, quarter).unwrap(),
+            EFloat64::pi()
+        );
+        assert_eq!(
+            circle.length_between(quarter, start).unwrap(),
+            EFloat64::from(3.0) * EFloat64::pi()
+        );
+        assert_eq!(
+            circle.point_at_length(start, EFloat64::pi()).unwrap(),
+            quarter
+        );
+    }
+
+    #[test]
+    fn test_circle_transform() {
+        let circle = Circle::new(Point::unit_z(), Point::unit_z(), EFloat64::one());
+        let rotation =
+            Transform::from_axis_angle(Point::zero(), Point::unit_x(), EFloat64::half_pi());
+        match circle.transform(rotation * Transform::from_scale(Point::from_f64(2.0, 2.0, 2.0))) {
+            CircleTransform::Circle(circle) => {
+                assert_eq!(circle.basis, Point::from_f64(0.0, -2.0, 0.0));
+                assert_eq!(circle.normal, Point::from_f64(0.0, -1.0, 0.0));
+                assert_eq!(circle.radius.norm(), EFloat64::two());
+            }
+            CircleTransform::Ellipse(_) => panic!("Expected a circle"),
+        }
+
+        // Stretching along x gives an ellipse, which still runs counter clockwise around z.
+        let p = Point::from_f64(0.0, 1.0, 1.0);
+        let tangent = circle.tangent(p).unwrap();
+        match circle.transform(Transform::from_scale(Point::from_f64(3.0, 1.0, 1.0))) {
+            CircleTransform::Ellipse(ellipse) => {
+                assert_eq!(ellipse.basis, Point::unit_z());
+                assert_eq!(ellipse.normal, Point::unit_z());
+                assert_eq!(ellipse.major_radius.norm(), EFloat64::from(3.0));
+                assert_eq!(ellipse.minor_radius.norm(), EFloat64::one());
+                assert!(ellipse.on_curve(Point::from_f64(-3.0, 0.0, 1.0)));
+                assert!(ellipse.on_curve(p));
+                assert!(ellipse.tangent(p).unwrap().dot(tangent) > 0.0);
+            }
+            CircleTransform::Circle(_) => panic!("Expected an ellipse"),
+        }
+    }
+
+    #[test]
+    fn test_circle_derivatives() {
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::two());
+        let p = Point::from_f64(2.0, 0.0, 0.0);
+        assert_eq!(
+            circle.derivative(p, 1).unwrap(),
+            Point::from_f64(0.0, 2.0, 0.0)
+        );
+        assert_eq!(circle.derivative(p, 2).unwrap(), -p);
+        assert_eq!(circle.derivative(p, 4).unwrap(), p);
+        assert_eq!(circle.curvature(p).unwrap(), EFloat64::from(0.5));
+        assert_eq!(circle.torsion(p).unwrap(), EFloat64::zero());
+        let frame = circle.frenet_frame(p).unwrap();
+        assert_eq!(frame.tangent, Point::unit_y());
+        assert_eq!(frame.normal, -Point::unit_x());
+        assert_eq!(frame.binormal, Point::unit_z());
+
+        // A line has no curvature, so its normal is undefined.
+        let line = Line::new(Point::zero(), Point::unit_x()).unwrap();
+        assert_eq!(line.curvature(p).unwrap(), EFloat64::zero());
+        assert!(line.frenet_frame(p).is_err());
+        assert!(line.torsion(p).is_err());
+    }
+}
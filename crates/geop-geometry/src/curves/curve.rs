@@ -11,6 +11,7 @@ use super::{
     ellipse::Ellipse,
     helix::Helix,
     line::Line,
+    nurbs::Nurbs,
     CurveLike,
 };
 
@@ -20,6 +21,7 @@ pub enum Curve {
     Circle(Circle),
     Ellipse(Ellipse),
     Helix(Helix),
+    Nurbs(Nurbs),
 }
 
 // This represents a curve, which can be a line or a circle.
@@ -30,10 +32,11 @@ impl CurveLike for Curve {
             Curve::Line(line) => Curve::Line(line.transform(transform)),
             Curve::Circle(circle) => match circle.transform(transform) {
                 CircleTransform::Circle(circle) => Curve::Circle(circle),
-                CircleTransform::Ellipse() => todo!("Implement this"),
+                CircleTransform::Ellipse(ellipse) => Curve::Ellipse(ellipse),
             },
             Curve::Ellipse(ellipse) => Curve::Ellipse(ellipse.transform(transform)),
-            Curve::Helix(helix) => Curve::Helix(helix.transform(transform)),
+            Curve::Helix(helix) => helix.transform(transform),
+            Curve::Nurbs(nurbs) => Curve::Nurbs(nurbs.transform(transform)),
         }
     }
 
@@ -44,6 +47,7 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => Curve::Circle(circle.neg()),
             Curve::Ellipse(ellipse) => Curve::Ellipse(ellipse.neg()),
             Curve::Helix(helix) => Curve::Helix(helix.neg()),
+            Curve::Nurbs(nurbs) => Curve::Nurbs(nurbs.neg()),
         }
     }
 
@@ -54,6 +58,18 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.tangent(p),
             Curve::Ellipse(ellipse) => ellipse.tangent(p),
             Curve::Helix(helix) => helix.tangent(p),
+            Curve::Nurbs(nurbs) => nurbs.tangent(p),
+        }
+    }
+
+    // Derivative of the given order at the point with respect to the parameter of the curve.
+    fn derivative(&self, p: Point, order: usize) -> GeometryResult<Point> {
+        match self {
+            Curve::Line(line) => line.derivative(p, order),
+            Curve::Circle(circle) => circle.derivative(p, order),
+            Curve::Ellipse(ellipse) => ellipse.derivative(p, order),
+            Curve::Helix(helix) => helix.derivative(p, order),
+            Curve::Nurbs(nurbs) => nurbs.derivative(p, order),
         }
     }
 
@@ -64,6 +80,7 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.on_curve(p),
             Curve::Ellipse(ellipse) => ellipse.on_curve(p),
             Curve::Helix(helix) => helix.on_curve(p),
+            Curve::Nurbs(nurbs) => nurbs.on_curve(p),
         }
     }
 
@@ -74,6 +91,27 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.distance(x, y),
             Curve::Ellipse(ellipse) => ellipse.distance(x, y),
             Curve::Helix(helix) => helix.distance(x, y),
+            Curve::Nurbs(nurbs) => nurbs.distance(x, y),
+        }
+    }
+
+    fn length_between(&self, start: Point, end: Point) -> GeometryResult<EFloat64> {
+        match self {
+            Curve::Line(line) => line.length_between(start, end),
+            Curve::Circle(circle) => circle.length_between(start, end),
+            Curve::Ellipse(ellipse) => ellipse.length_between(start, end),
+            Curve::Helix(helix) => helix.length_between(start, end),
+            Curve::Nurbs(nurbs) => nurbs.length_between(start, end),
+        }
+    }
+
+    fn point_at_length(&self, start: Point, length: EFloat64) -> GeometryResult<Point> {
+        match self {
+            Curve::Line(line) => line.point_at_length(start, length),
+            Curve::Circle(circle) => circle.point_at_length(start, length),
+            Curve::Ellipse(ellipse) => ellipse.point_at_length(start, length),
+            Curve::Helix(helix) => helix.point_at_length(start, length),
+            Curve::Nurbs(nurbs) => nurbs.point_at_length(start, length),
         }
     }
 
@@ -89,6 +127,7 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.interpolate(start, end, t),
             Curve::Ellipse(ellipse) => ellipse.interpolate(start, end, t),
             Curve::Helix(helix) => helix.interpolate(start, end, t),
+            Curve::Nurbs(nurbs) => nurbs.interpolate(start, end, t),
         }
     }
 
@@ -99,6 +138,7 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.between(m, start, end),
             Curve::Ellipse(ellipse) => ellipse.between(m, start, end),
             Curve::Helix(helix) => helix.between(m, start, end),
+            Curve::Nurbs(nurbs) => nurbs.between(m, start, end),
         }
     }
 
@@ -112,6 +152,7 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.get_midpoint(start, end),
             Curve::Ellipse(ellipse) => ellipse.get_midpoint(start, end),
             Curve::Helix(helix) => helix.get_midpoint(start, end),
+            Curve::Nurbs(nurbs) => nurbs.get_midpoint(start, end),
         }
     }
 
@@ -122,6 +163,7 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.project(p),
             Curve::Ellipse(ellipse) => ellipse.project(p),
             Curve::Helix(helix) => helix.project(p),
+            Curve::Nurbs(nurbs) => nurbs.project(p),
         }
     }
 
@@ -136,6 +178,7 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.get_bounding_box(start, end),
             Curve::Ellipse(ellipse) => ellipse.get_bounding_box(start, end),
             Curve::Helix(helix) => helix.get_bounding_box(start, end),
+            Curve::Nurbs(nurbs) => nurbs.get_bounding_box(start, end),
         }
     }
 
@@ -151,6 +194,7 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.shrink_bounding_box(start, end, bounding_box),
             Curve::Ellipse(ellipse) => ellipse.shrink_bounding_box(start, end, bounding_box),
             Curve::Helix(helix) => helix.shrink_bounding_box(start, end, bounding_box),
+            Curve::Nurbs(nurbs) => nurbs.shrink_bounding_box(start, end, bounding_box),
         }
     }
 
@@ -162,6 +206,7 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.sort(points),
             Curve::Ellipse(ellipse) => ellipse.sort(points),
             Curve::Helix(helix) => helix.sort(points),
+            Curve::Nurbs(nurbs) => nurbs.sort(points),
         }
     }
 }
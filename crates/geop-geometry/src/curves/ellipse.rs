@@ -1,11 +1,113 @@
-use geop_algebra::efloat::EFloat64;
+use geop_algebra::{efloat::EFloat64, monomial_polynom::MonomialPolynom};
 
 use crate::{
-    bounding_box::BoundingBox, geometry_error::GeometryResult, point::Point, transforms::Transform,
+    bounding_box::BoundingBox, geometry_error::GeometryResult, point::Point, tolerance::Tolerance,
+    transforms::Transform,
 };
 
 use super::{curve::Curve, CurveLike};
 
+// Rational parameterisation of an ellipse relative to an origin. With t = tan(angle / 2), the point at t is origin + (x(t), y(t), z(t)) / w(t).
+pub struct RationalEllipse {
+    pub x: MonomialPolynom,
+    pub y: MonomialPolynom,
+    pub z: MonomialPolynom,
+    pub w: MonomialPolynom,
+}
+
+impl RationalEllipse {
+    pub fn dot(&self, p: Point) -> MonomialPolynom {
+        &(&(&self.x * p.x) + &(&self.y * p.y)) + &(&self.z * p.z)
+    }
+
+    pub fn norm_sq(&self) -> MonomialPolynom {
+        &(&(&self.x * &self.x) + &(&self.y * &self.y)) + &(&self.z * &self.z)
+    }
+}
+
+// Length of the polyline through n + 1 points of the ellipse with semi axes a and b, evenly spaced between the angles from and to.
+fn chord_length(a: f64, b: f64, from: f64, to: f64, n: usize) -> f64 {
+    let point = |i: usize| {
+        let angle = from + (to - from) * i as f64 / n as f64;
+        (a * angle.cos(), b * angle.sin())
+    };
+    (0..n)
+        .map(|i| {
+            let ((x0, y0), (x1, y1)) = (point(i), point(i + 1));
+            (x1 - x0).hypot(y1 - y0)
+        })
+        .sum()
+}
+
+// Length of the polyline of the tangents at the same points. Each tangent runs up to the intersection with the next one.
+fn tangent_length(a: f64, b: f64, from: f64, to: f64, n: usize) -> f64 {
+    let point = |i: usize| {
+        let angle = from + (to - from) * i as f64 / n as f64;
+        let (sin, cos) = angle.sin_cos();
+        ((a * cos, b * sin), (-a * sin, b * cos))
+    };
+    (0..n)
+        .map(|i| {
+            let ((x0, y0), (tx0, ty0)) = point(i);
+            let ((x1, y1), (tx1, ty1)) = point(i + 1);
+            let (dx, dy) = (x1 - x0, y1 - y0);
+            let det = tx0 * ty1 - ty0 * tx1;
+            if det == 0.0 {
+                return dx.hypot(dy);
+            }
+            let s = (dx * ty1 - dy * tx1) / det;
+            let u = (tx0 * dy - ty0 * dx) / det;
+            s * tx0.hypot(ty0) + u * tx1.hypot(ty1)
+        })
+        .sum()
+}
+
+// Speed of the ellipse with semi axes a and b by the angle.
+fn speed(a: f64, b: f64, angle: f64) -> f64 {
+    (a * angle.sin()).hypot(b * angle.cos())
+}
+
+// Arc length of the ellipse with semi axes a and b between the angles from and to, by a composite gauss legendre rule.
+fn gauss_length(a: f64, b: f64, from: f64, to: f64) -> f64 {
+    const NODES: [(f64, f64); 5] = [
+        (0.0, 0.5688888888888889),
+        (-0.5384693101056831, 0.47862867049936647),
+        (0.5384693101056831, 0.47862867049936647),
+        (-0.906179845938664, 0.23692688505618908),
+        (0.906179845938664, 0.23692688505618908),
+    ];
+    let pieces = ((to - from).abs() * 16.0 / std::f64::consts::PI)
+        .ceil()
+        .max(1.0) as usize;
+    let h = (to - from) / pieces as f64;
+    (0..pieces)
+        .map(|i| {
+            let center = from + h * (i as f64 + 0.5);
+            NODES
+                .iter()
+                .map(|(x, w)| w * speed(a, b, center + x * h / 2.0))
+                .sum::<f64>()
+                * h
+                / 2.0
+        })
+        .sum()
+}
+
+// Orthogonal semi axes of the ellipse with the points cos * u + sin * v, which have the same orientation as u and v.
+// The major one is at the angle where |cos * u + sin * v| is largest. The minor one is measured along the orthogonal direction, so both are orthogonal up to the error bounds.
+pub(crate) fn principal_radii(u: Point, v: Point) -> (Point, Point) {
+    let normal = u.cross(v).normalize().unwrap();
+    let angle = (EFloat64::two() * u.dot(v))
+        .mid()
+        .atan2((u.norm_sq() - v.norm_sq()).mid())
+        / 2.0;
+    let angle = EFloat64::from(angle);
+    let major = u * angle.cos() + v * angle.sin();
+    let conjugate = v * angle.cos() - u * angle.sin();
+    let minor_dir = normal.cross(major).normalize().unwrap();
+    (major, minor_dir * conjugate.dot(minor_dir))
+}
+
 #[derive(Debug, Clone)]
 pub struct Ellipse {
     pub basis: Point,
@@ -37,6 +139,14 @@ impl Ellipse {
         }
     }
 
+    // The ellipse with the points basis + cos * u + sin * v for conjugate radii u and v, e.g. the image of a circle under an affine transform.
+    // It runs counter clockwise around its normal from u to v.
+    pub fn from_conjugate(basis: Point, u: Point, v: Point) -> Ellipse {
+        let normal = u.cross(v).normalize().unwrap();
+        let (major_radius, minor_radius) = principal_radii(u, v);
+        Ellipse::new(basis, normal, major_radius, minor_radius)
+    }
+
     fn transform_point_to_circle(&self, p: Point) -> Point {
         assert!(self.on_curve(p));
         let p = p - self.basis;
@@ -52,11 +162,15 @@ impl Ellipse {
     }
 
     pub fn transform(&self, transform: Transform) -> Ellipse {
+        // The radii are conjugate after the transform. The minor one is oriented such that the ellipse runs from the major to the minor radius.
+        let minor_radius = match self.major_radius.cross(self.minor_radius).dot(self.normal) > 0.0 {
+            true => self.minor_radius,
+            false => -self.minor_radius,
+        };
         let basis = transform * self.basis;
-        let normal = transform * (self.normal + self.basis) - basis;
-        let major_radius = transform * (self.major_radius + self.basis) - basis;
-        let minor_radius = transform * (self.minor_radius + self.basis) - basis;
-        Ellipse::new(basis, normal, major_radius, minor_radius)
+        let u = transform * (self.major_radius + self.basis) - basis;
+        let v = transform * (minor_radius + self.basis) - basis;
+        Ellipse::from_conjugate(basis, u, v)
     }
 
     pub fn neg(&self) -> Ellipse {
@@ -68,6 +182,109 @@ impl Ellipse {
         )
     }
 
+    // If the ellipse runs from the major to the minor radius, the angle runs forwards along the curve.
+    fn forwards(&self) -> bool {
+        self.major_radius.cross(self.minor_radius).dot(self.normal) > 0.0
+    }
+
+    // The angle of p = basis + cos * major + sin * minor, negated if the angle runs backwards. It increases along the curve.
+    fn curve_angle(&self, p: Point) -> GeometryResult<EFloat64> {
+        let p = p - self.basis;
+        let cos = (self.major_radius.dot(p) / self.major_radius.norm_sq())?;
+        let sin = (self.minor_radius.dot(p) / self.minor_radius.norm_sq())?;
+        Ok(match self.forwards() {
+            true => sin.atan2(cos),
+            false => -sin.atan2(cos),
+        })
+    }
+
+    // Bounds of the arc length between the curve angles from and from + delta.
+    // The ellipse is convex, so the arc is longer than an inscribed polyline and shorter than the polyline of its tangents.
+    // Both are refined until they are within the numerical tolerance of each other, relative to the length. The error bounds of the radii and angles are kept.
+    fn arc_length(&self, from: EFloat64, delta: EFloat64) -> EFloat64 {
+        let precision = Tolerance::current().numerical;
+        const MAX_PIECES: usize = 1 << 17;
+        let to = from + delta;
+        let (a, b) = (self.major_radius.norm(), self.minor_radius.norm());
+        if to.lower_bound <= from.upper_bound {
+            let upper = tangent_length(
+                a.upper_bound,
+                b.upper_bound,
+                from.lower_bound,
+                to.upper_bound,
+                16,
+            );
+            return EFloat64::new(upper.max(0.0), 0.0);
+        }
+        // Pieces turn by less than 90 degrees, such that the tangents of neighbouring points intersect ahead of them.
+        let mut n =
+            ((to.upper_bound - from.lower_bound) * 8.0 / std::f64::consts::PI).ceil() as usize;
+        loop {
+            let lower = chord_length(
+                a.lower_bound,
+                b.lower_bound,
+                from.upper_bound,
+                to.lower_bound,
+                n,
+            );
+            let upper = tangent_length(
+                a.upper_bound,
+                b.upper_bound,
+                from.lower_bound,
+                to.upper_bound,
+                n,
+            );
+            if upper - lower <= precision * upper || n >= MAX_PIECES {
+                // Widen by the rounding errors of the sums.
+                let rounding = upper * n as f64 * f64::EPSILON * 8.0;
+                return EFloat64::new(upper + rounding, lower - rounding);
+            }
+            n *= 2;
+        }
+    }
+
+    // Parameterisation of the half of the ellipse from -90 to 90 degrees for t in [-1, 1]. The other half is the same with negated radii.
+    fn rational(&self, origin: Point, major_radius: Point, minor_radius: Point) -> RationalEllipse {
+        let b = self.basis - origin;
+        let polynom = |b: EFloat64, major: EFloat64, minor: EFloat64| {
+            MonomialPolynom::new(vec![b + major, EFloat64::two() * minor, b - major])
+        };
+        RationalEllipse {
+            x: polynom(b.x, major_radius.x, minor_radius.x),
+            y: polynom(b.y, major_radius.y, minor_radius.y),
+            z: polynom(b.z, major_radius.z, minor_radius.z),
+            w: MonomialPolynom::new(vec![EFloat64::one(), EFloat64::zero(), EFloat64::one()]),
+        }
+    }
+
+    // Points of the ellipse where an implicit function vanishes, or none if it vanishes on the whole ellipse.
+    // f gets the parameterisation relative to origin and has to return the implicit function of (x, y, z) / w, multiplied by w to the power of its degree.
+    pub fn implicit_intersection(
+        &self,
+        origin: Point,
+        f: impl Fn(&RationalEllipse) -> MonomialPolynom,
+    ) -> Option<Vec<Point>> {
+        let mut points: Vec<Point> = Vec::new();
+        for sign in [EFloat64::one(), -EFloat64::one()] {
+            let (major_radius, minor_radius) = (self.major_radius * sign, self.minor_radius * sign);
+            let polynom = f(&self.rational(origin, major_radius, minor_radius));
+            if polynom.is_zero() {
+                return None;
+            }
+            for t in polynom.roots_in(-1.0, 1.0).unwrap() {
+                let w = EFloat64::one() + t.square();
+                let cos = ((EFloat64::one() - t.square()) / w).unwrap();
+                let sin = ((EFloat64::two() * t) / w).unwrap();
+                let point = self.basis + cos * major_radius + sin * minor_radius;
+                // The halves share the points at -90 and 90 degrees.
+                if !points.contains(&point) {
+                    points.push(point);
+                }
+            }
+        }
+        Some(points)
+    }
+
     pub fn get_extremal_points(&self) -> Vec<Point> {
         let disc_x = (self.major_radius.x * self.major_radius.x
             + self.minor_radius.x * self.minor_radius.x)
@@ -131,19 +348,51 @@ impl CurveLike for Ellipse {
     fn tangent(&self, p: Point) -> GeometryResult<Point> {
         assert!(self.on_curve(p));
         let p = p - self.basis;
-        let x = self.major_radius.dot(p) / self.major_radius.norm();
-        let y = self.minor_radius.dot(p) / self.minor_radius.norm();
-        let tangent = y.unwrap() * self.major_radius - x.unwrap() * self.minor_radius;
+        let cos = (self.major_radius.dot(p) / self.major_radius.norm_sq()).unwrap();
+        let sin = (self.minor_radius.dot(p) / self.minor_radius.norm_sq()).unwrap();
+        // The derivative by the angle, oriented counter clockwise around the normal.
+        let tangent = cos * self.minor_radius - sin * self.major_radius;
+        let tangent = match self.major_radius.cross(self.minor_radius).dot(self.normal) > 0.0 {
+            true => tangent,
+            false => -tangent,
+        };
         Ok(tangent.normalize().unwrap())
     }
 
+    // With p = cos * major + sin * minor, the derivative of order k by the angle is cos(angle + k * 90 degrees) * major + sin(angle + k * 90 degrees) * minor.
+    fn derivative(&self, p: Point, order: usize) -> GeometryResult<Point> {
+        assert!(self.on_curve(p));
+        if order == 0 {
+            return Ok(p);
+        }
+        let q = p - self.basis;
+        let mut cos = (self.major_radius.dot(q) / self.major_radius.norm_sq())?;
+        let mut sin = (self.minor_radius.dot(q) / self.minor_radius.norm_sq())?;
+        for _ in 0..order {
+            (cos, sin) = (-sin, cos);
+        }
+        let derivative = cos * self.major_radius + sin * self.minor_radius;
+        // If the ellipse runs from the minor to the major radius, the angle runs backwards.
+        let forwards = self.major_radius.cross(self.minor_radius).dot(self.normal) > 0.0;
+        Ok(match forwards || order.is_multiple_of(2) {
+            true => derivative,
+            false => -derivative,
+        })
+    }
+
     fn on_curve(&self, p: Point) -> bool {
         let p = p - self.basis;
         let x = self.major_radius.dot(p) / self.major_radius.norm_sq();
         let y = self.minor_radius.dot(p) / self.minor_radius.norm_sq();
         let x = x.unwrap();
         let y = y.unwrap();
-        (p.dot(self.normal) == 0.0) && (x * x + y * y == 1.0)
+        let tolerance = Tolerance::current();
+        // The relative radial deviation, scaled by the major radius to bound the distance.
+        let deviation = match (x * x + y * y).sqrt() {
+            Some(r) => (r - EFloat64::one()) * self.major_radius.norm(),
+            None => return false,
+        };
+        tolerance.is_zero_length(p.dot(self.normal)) && tolerance.is_zero_length(deviation)
     }
 
     fn distance(&self, x: Point, y: Point) -> GeometryResult<EFloat64> {
@@ -153,6 +402,44 @@ impl CurveLike for Ellipse {
         Ok(self.major_radius.norm() * angle)
     }
 
+    fn length_between(&self, start: Point, end: Point) -> GeometryResult<EFloat64> {
+        assert!(self.on_curve(start));
+        assert!(self.on_curve(end));
+        let angle_start = self.curve_angle(start)?;
+        let mut delta = self.curve_angle(end)? - angle_start;
+        if delta.upper_bound < 0.0 {
+            delta = delta + EFloat64::two_pi();
+        }
+        Ok(self.arc_length(angle_start, delta))
+    }
+
+    // The angle is found by newton iterations on the arc length, which is integrated numerically.
+    fn point_at_length(&self, start: Point, length: EFloat64) -> GeometryResult<Point> {
+        assert!(self.on_curve(start));
+        let (a, b) = (
+            self.major_radius.norm().mid(),
+            self.minor_radius.norm().mid(),
+        );
+        let from = (self.curve_angle(start)?).mid();
+        let circumference = gauss_length(a, b, 0.0, 2.0 * std::f64::consts::PI);
+        let length = length.mid().rem_euclid(circumference);
+
+        let mut delta = length / circumference * 2.0 * std::f64::consts::PI;
+        for _ in 0..32 {
+            let error = gauss_length(a, b, from, from + delta) - length;
+            delta -= error / speed(a, b, from + delta);
+            if error.abs() <= f64::EPSILON * circumference {
+                break;
+            }
+        }
+        let angle = EFloat64::from(from + delta);
+        let angle = match self.forwards() {
+            true => angle,
+            false => -angle,
+        };
+        Ok(angle.cos() * self.major_radius + angle.sin() * self.minor_radius + self.basis)
+    }
+
     fn interpolate(
         &self,
         start: Option<Point>,
@@ -237,7 +524,6 @@ impl CurveLike for Ellipse {
     }
 
     fn get_midpoint(&self, start: Option<Point>, end: Option<Point>) -> GeometryResult<Point> {
-        assert!(start != end);
         match (start, end) {
             (Some(start), Some(end)) => {
                 assert!(self.on_curve(start));
@@ -358,3 +644,66 @@ impl PartialEq for Ellipse {
             && self.minor_radius == other.minor_radius
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ellipse_derivatives() {
+        // The curvature is a / b^2 at the ends of the major axis and b / a^2 at the ends of the minor axis.
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        let (major, minor) = (Point::from_f64(2.0, 0.0, 0.0), Point::unit_y());
+        assert_eq!(ellipse.curvature(major).unwrap(), EFloat64::two());
+        assert_eq!(ellipse.curvature(minor).unwrap(), EFloat64::from(0.25));
+        assert_eq!(ellipse.torsion(major).unwrap(), EFloat64::zero());
+        assert_eq!(ellipse.derivative(major, 1).unwrap(), Point::unit_y());
+        assert_eq!(ellipse.derivative(minor, 2).unwrap(), -minor);
+
+        // The derivatives follow the direction of the curve, also when it runs from the minor to the major radius.
+        for ellipse in [ellipse.clone(), ellipse.neg()] {
+            let frame = ellipse.frenet_frame(major).unwrap();
+            assert_eq!(frame.tangent, ellipse.tangent(major).unwrap());
+            assert_eq!(frame.normal, -Point::unit_x());
+            assert_eq!(frame.binormal, ellipse.normal);
+        }
+    }
+
+    #[test]
+    fn test_ellipse_arc_length() {
+        // The circumference of the ellipse with semi axes 2 and 1.
+        const CIRCUMFERENCE: f64 = 9.688448220547675;
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        let (major, minor) = (Point::from_f64(2.0, 0.0, 0.0), Point::unit_y());
+        let quarter = ellipse.length_between(major, minor).unwrap();
+        assert!(quarter == CIRCUMFERENCE / 4.0);
+        assert!(quarter.upper_bound - quarter.lower_bound < 1e-8);
+        let rest = ellipse.length_between(minor, major).unwrap();
+        assert!(rest == CIRCUMFERENCE * 3.0 / 4.0);
+
+        // Walking along the curve from either direction ends at the same points.
+        for ellipse in [ellipse.clone(), ellipse.neg()] {
+            let length = ellipse.length_between(major, minor).unwrap();
+            let p = ellipse
+                .point_at_length(major, EFloat64::from(CIRCUMFERENCE / 4.0))
+                .unwrap();
+            let q = match length == CIRCUMFERENCE / 4.0 {
+                true => minor,
+                false => -minor,
+            };
+            assert!((p - q).norm() < 1e-9);
+            let p = ellipse.point_at_length(major, EFloat64::from(1.0)).unwrap();
+            assert!(ellipse.length_between(major, p).unwrap() == 1.0);
+        }
+    }
+}
@@ -3,19 +3,22 @@ use core::f64;
 use geop_algebra::efloat::EFloat64;
 
 use crate::{
-    bounding_box::BoundingBox, geometry_error::GeometryResult, point::Point, transforms::Transform,
+    bounding_box::BoundingBox,
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    transforms::Transform,
     HORIZON_DIST,
 };
 
-use super::{curve::Curve, CurveLike};
+use super::{circle::circle_bounding_box, curve::Curve, nurbs::Nurbs, CurveLike};
 
 #[derive(Debug, Clone)]
 pub struct Helix {
     pub basis: Point,
     pub pitch: Point,
     pub radius: Point,
-    dir_cross: Point,
-    right_winding: bool,
+    pub(crate) dir_cross: Point,
+    pub(crate) right_winding: bool,
 }
 
 impl Helix {
@@ -36,12 +39,70 @@ impl Helix {
         }
     }
 
-    pub fn transform(&self, transform: Transform) -> Self {
+    // Stays a helix if the image is circular. Otherwise it winds around an elliptic cylinder, which only a nurbs can follow.
+    pub fn transform(&self, transform: Transform) -> Curve {
         let basis_old = self.basis;
         let basis = transform * self.basis;
         let pitch = transform * (self.pitch + basis_old) - basis;
         let radius = transform * (self.radius + basis_old) - basis;
-        Helix::new(basis, pitch, radius, self.right_winding)
+        let dir_cross = transform * (self.dir_cross + basis_old) - basis;
+        // Otherwise the helix winds around an elliptic cylinder.
+        if !(radius.dot(dir_cross) == 0.0
+            && radius.norm() == dir_cross.norm()
+            && pitch.dot(radius) == 0.0
+            && pitch.dot(dir_cross) == 0.0)
+        {
+            return Curve::Nurbs(self.to_nurbs().transform(transform));
+        }
+        // Mirroring changes the winding.
+        let right_winding = radius.cross(dir_cross).dot(pitch) > 0.0;
+        Curve::Helix(Helix::new(basis, pitch, radius, right_winding))
+    }
+
+    // Approximation of the helix up to the horizon by quintic pieces, which match the helix and its first two derivatives at SEGMENTS_PER_TURN points a turn. The error is about 1e-11 times the radius.
+    // A helix is not rational, so there is no exact representation. Affine images of the pieces follow the image of the helix just as closely.
+    pub fn to_nurbs(&self) -> Nurbs {
+        const SEGMENTS_PER_TURN: usize = 64;
+        let turns = (HORIZON_DIST / self.pitch.norm().lower_bound).ceil() as usize;
+        let segments = 2 * turns * SEGMENTS_PER_TURN;
+        let step = 1.0 / SEGMENTS_PER_TURN as f64;
+        let parameter = |k: usize| EFloat64::from(k as f64 * step - turns as f64);
+        // Second derivative of point_at_pitch with respect to t.
+        let curvature = |t: EFloat64| {
+            let angle = EFloat64::two_pi() * t;
+            (self.radius * angle.cos() + self.dir_cross * angle.sin())
+                * -(EFloat64::two_pi() * EFloat64::two_pi())
+        };
+        let (fifth, twentieth) = (
+            EFloat64::from(step / 5.0),
+            EFloat64::from(step * step / 20.0),
+        );
+        let mut control_points = vec![self.point_at_pitch(parameter(0))];
+        let mut knots = vec![parameter(0); 6];
+        for k in 0..segments {
+            let (start, end) = (parameter(k), parameter(k + 1));
+            let (p0, d0, a0) = (
+                self.point_at_pitch(start),
+                self.derivative_at_pitch(start),
+                curvature(start),
+            );
+            let (p1, d1, a1) = (
+                self.point_at_pitch(end),
+                self.derivative_at_pitch(end),
+                curvature(end),
+            );
+            control_points.extend([
+                p0 + d0 * fifth,
+                p0 + d0 * (fifth + fifth) + a0 * twentieth,
+                p1 - d1 * (fifth + fifth) + a1 * twentieth,
+                p1 - d1 * fifth,
+                p1,
+            ]);
+            let multiplicity = if k + 1 == segments { 6 } else { 5 };
+            knots.extend(vec![end; multiplicity]);
+        }
+        let weights = vec![EFloat64::one(); control_points.len()];
+        Nurbs::new(5, knots, weights, control_points).unwrap()
     }
 
     pub fn neg(&self) -> Helix {
@@ -54,12 +115,26 @@ impl Helix {
             + self.radius * (EFloat64::two_pi() * t).cos()
             + self.dir_cross * (EFloat64::two_pi() * t).sin()
     }
+
+    // Derivative of point_at_pitch with respect to t.
+    pub fn derivative_at_pitch(&self, t: EFloat64) -> Point {
+        let angle = EFloat64::two_pi() * t;
+        self.pitch + (self.dir_cross * angle.cos() - self.radius * angle.sin()) * EFloat64::two_pi()
+    }
+
+    // The arc length of one turn, which is the hypotenuse of the circumference and the pitch.
+    pub fn turn_length(&self) -> EFloat64 {
+        let circumference = EFloat64::two_pi() * self.radius.norm();
+        (circumference.square() + self.pitch.norm_sq())
+            .sqrt()
+            .unwrap()
+    }
 }
 
 // Helix equation is r(t) = basis + t * pitch + cos(2pi * t) * radius + sin(2pi * t) * dir_cross
 impl CurveLike for Helix {
     fn transform(&self, transform: Transform) -> Curve {
-        Curve::Helix(self.transform(transform))
+        self.transform(transform)
     }
 
     fn neg(&self) -> Curve {
@@ -68,10 +143,27 @@ impl CurveLike for Helix {
 
     fn tangent(&self, p: Point) -> GeometryResult<Point> {
         assert!(self.on_curve(p));
-        Ok((self.pitch.cross(p - self.basis).normalize().unwrap()
-            + (self.pitch / EFloat64::two_pi()).unwrap())
-        .normalize()
-        .unwrap())
+        self.derivative(p, 1)?.normalize()
+    }
+
+    fn derivative(&self, p: Point, order: usize) -> GeometryResult<Point> {
+        assert!(self.on_curve(p));
+        if order == 0 {
+            return Ok(p);
+        }
+        let t = ((p - self.basis).dot(self.pitch) / self.pitch.norm_sq())?;
+        let q = p - self.basis - t * self.pitch;
+        let mut cos = (self.radius.dot(q) / self.radius.norm_sq())?;
+        let mut sin = (self.dir_cross.dot(q) / self.dir_cross.norm_sq())?;
+        for _ in 0..order {
+            (cos, sin) = (-sin, cos);
+        }
+        let derivative =
+            EFloat64::two_pi().powi(order as i32) * (cos * self.radius + sin * self.dir_cross);
+        Ok(match order {
+            1 => derivative + self.pitch,
+            _ => derivative,
+        })
     }
 
     fn on_curve(&self, p: Point) -> bool {
@@ -94,6 +186,20 @@ impl CurveLike for Helix {
         return Ok((t_x - t_y).abs() * self.radius.norm() * EFloat64::two_pi());
     }
 
+    fn length_between(&self, start: Point, end: Point) -> GeometryResult<EFloat64> {
+        assert!(self.on_curve(start));
+        assert!(self.on_curve(end));
+        let t_start = ((start - self.basis).dot(self.pitch) / self.pitch.norm_sq())?;
+        let t_end = ((end - self.basis).dot(self.pitch) / self.pitch.norm_sq())?;
+        Ok((t_end - t_start) * self.turn_length())
+    }
+
+    fn point_at_length(&self, start: Point, length: EFloat64) -> GeometryResult<Point> {
+        assert!(self.on_curve(start));
+        let t_start = ((start - self.basis).dot(self.pitch) / self.pitch.norm_sq())?;
+        Ok(self.point_at_pitch(t_start + (length / self.turn_length())?))
+    }
+
     fn interpolate(
         &self,
         start: Option<Point>,
@@ -223,12 +329,28 @@ impl CurveLike for Helix {
         todo!("Implement this")
     }
 
+    // The helix stays on a cylinder, so it is bounded by the circles of this cylinder at its ends.
     fn get_bounding_box(
         &self,
-        _interval_self: Option<Point>,
-        _midpoint_self: Option<Point>,
+        start: Option<Point>,
+        end: Option<Point>,
     ) -> GeometryResult<BoundingBox> {
-        todo!("Implement this")
+        let (start, end) = match (start, end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => {
+                return Err(GeometryError::new(
+                    "An unbounded helix has no bounding box".to_string(),
+                ))
+            }
+        };
+        let axis = self.pitch.normalize()?;
+        let radius = self.radius.norm();
+        let center = |p: Point| self.basis + axis * (p - self.basis).dot(axis);
+        let mut bounding_box = circle_bounding_box(center(start), axis, radius);
+        let end_box = circle_bounding_box(center(end), axis, radius);
+        bounding_box.add_point(end_box.min);
+        bounding_box.add_point(end_box.max);
+        Ok(bounding_box)
     }
 
     fn shrink_bounding_box(
@@ -251,3 +373,87 @@ impl PartialEq for Helix {
         self.basis == other.basis && self.pitch == other.pitch && self.radius == other.radius
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helix_arc_length() {
+        // One turn of radius 1 and pitch 1 is as long as the hypotenuse of 2 pi and 1.
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        let turn = (4.0 * std::f64::consts::PI.powi(2) + 1.0).sqrt();
+        let start = Point::unit_x();
+        let end = helix.point_at_pitch(EFloat64::from(2.5));
+        assert!(helix.length_between(start, end).unwrap() == 2.5 * turn);
+        assert!(helix.length_between(end, start).unwrap() == -2.5 * turn);
+        let p = helix
+            .point_at_length(start, EFloat64::from(2.5 * turn))
+            .unwrap();
+        assert_eq!(p, end);
+    }
+
+    #[test]
+    fn test_helix_curvature_and_torsion() {
+        // With radius r and rise c = |pitch| / 2pi per radian, the curvature is r / (r^2 + c^2) and the torsion c / (r^2 + c^2).
+        let c = 1.0 / (2.0 * std::f64::consts::PI);
+        let (curvature, torsion) = (1.0 / (1.0 + c * c), c / (1.0 + c * c));
+        let right = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        let left = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), false);
+        let p = right.point_at_pitch(EFloat64::from(0.3));
+        assert!((right.curvature(p).unwrap() - EFloat64::from(curvature)).abs() < 1e-9);
+        assert!((right.torsion(p).unwrap() - EFloat64::from(torsion)).abs() < 1e-9);
+        let p = left.point_at_pitch(EFloat64::from(0.3));
+        assert!((left.curvature(p).unwrap() - EFloat64::from(curvature)).abs() < 1e-9);
+        assert!((left.torsion(p).unwrap() + EFloat64::from(torsion)).abs() < 1e-9);
+
+        // The normal points to the axis and the frame is right handed.
+        let p = right.point_at_pitch(EFloat64::from(0.3));
+        let frame = right.frenet_frame(p).unwrap();
+        let axis = Point::new(EFloat64::zero(), EFloat64::zero(), p.z);
+        assert!((frame.normal - (axis - p)).norm() < 1e-9);
+        assert!((frame.tangent.cross(frame.normal) - frame.binormal).norm() < 1e-9);
+        assert!(right.derivative(p, 1).unwrap().dot(right.pitch) > 0.0);
+    }
+
+    #[test]
+    fn test_helix_tangent() {
+        // The tangent follows the difference of nearby points, also for radii other than 1 and both windings.
+        for right_winding in [true, false] {
+            let helix = Helix::new(
+                Point::zero(),
+                Point::from_f64(0.0, 0.0, 4.0),
+                Point::from_f64(4.0, 0.0, 0.0),
+                right_winding,
+            );
+            let h = 1e-6;
+            let difference = helix.point_at_pitch(EFloat64::from(0.3 + h))
+                - helix.point_at_pitch(EFloat64::from(0.3 - h));
+            let p = helix.point_at_pitch(EFloat64::from(0.3));
+            let tangent = helix.tangent(p).unwrap();
+            assert!((tangent - difference.normalize().unwrap()).norm() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_helix_transform() {
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        let t = EFloat64::from(0.3);
+
+        // Scaling across the axis uniformly keeps the helix.
+        let similar = Transform::from_scale(Point::from_f64(2.0, 2.0, 3.0));
+        match helix.transform(similar) {
+            Curve::Helix(image) => assert!(image.on_curve(similar * helix.point_at_pitch(t))),
+            _ => panic!("Expected a helix"),
+        }
+
+        // Otherwise the image winds around an elliptic cylinder.
+        let stretch = Transform::from_scale(Point::from_f64(2.0, 1.0, 1.0));
+        match helix.transform(stretch) {
+            Curve::Nurbs(image) => {
+                assert!((image.point_at(t) - stretch * helix.point_at_pitch(t)).norm() < 1e-9)
+            }
+            _ => panic!("Expected a nurbs"),
+        }
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
     geometry_error::{GeometryError, GeometryResult, WithContext},
     geometry_scene::GeometryScene,
     point::Point,
+    tolerance::Tolerance,
     transforms::Transform,
     HORIZON_DIST,
 };
@@ -70,10 +71,19 @@ impl CurveLike for Line {
         Ok(self.direction.clone())
     }
 
+    fn derivative(&self, p: Point, order: usize) -> GeometryResult<Point> {
+        self.assert_on_curve(p, "p")?;
+        Ok(match order {
+            0 => p,
+            1 => self.direction,
+            _ => Point::zero(),
+        })
+    }
+
     fn on_curve(&self, p: Point) -> bool {
         let v = p - self.basis;
         let v = v - self.direction * (v.dot(self.direction));
-        v.norm() == 0.0
+        Tolerance::current().is_zero_length(v.norm())
     }
 
     fn distance(&self, x: Point, y: Point) -> GeometryResult<EFloat64> {
@@ -94,6 +104,17 @@ impl CurveLike for Line {
         Ok(v.norm())
     }
 
+    fn length_between(&self, start: Point, end: Point) -> GeometryResult<EFloat64> {
+        self.assert_on_curve(start, "start")?;
+        self.assert_on_curve(end, "end")?;
+        Ok((end - start).dot(self.direction))
+    }
+
+    fn point_at_length(&self, start: Point, length: EFloat64) -> GeometryResult<Point> {
+        self.assert_on_curve(start, "start")?;
+        Ok(start + self.direction * length)
+    }
+
     fn interpolate(
         &self,
         start: Option<Point>,
@@ -240,10 +261,15 @@ impl CurveLike for Line {
 
     fn get_bounding_box(
         &self,
-        _interval_self: Option<Point>,
-        _midpoint_self: Option<Point>,
+        start: Option<Point>,
+        end: Option<Point>,
     ) -> GeometryResult<BoundingBox> {
-        todo!()
+        match (start, end) {
+            (Some(start), Some(end)) => Ok(BoundingBox::with_2_points(start, end)),
+            _ => Err(GeometryError::new(
+                "An unbounded line has no bounding box".to_string(),
+            )),
+        }
     }
 
     fn shrink_bounding_box(
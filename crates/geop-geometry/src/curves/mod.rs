@@ -12,6 +12,15 @@ pub mod circle;
 pub mod ellipse;
 pub mod helix;
 pub mod line;
+pub mod nurbs;
+
+// Orthonormal frame of a curve at a point. The normal points to the center of curvature and the binormal is tangent x normal.
+#[derive(Debug, Clone)]
+pub struct FrenetFrame {
+    pub tangent: Point,
+    pub normal: Point,
+    pub binormal: Point,
+}
 
 // CurveLike is a trait that all curves should implement.
 pub trait CurveLike {
@@ -24,12 +33,54 @@ pub trait CurveLike {
     // Normalized Tangent / Direction of the curve at the given point.
     fn tangent(&self, p: Point) -> GeometryResult<Point>;
 
+    // Derivative of the given order at the point with respect to the parameter of the curve, which runs in the direction of the curve.
+    // The parameter is the length along a line, the angle on a circle or an ellipse, the turns of a helix and the knot parameter of a nurbs curve.
+    // Order 0 is the point itself.
+    fn derivative(&self, p: Point, order: usize) -> GeometryResult<Point>;
+
+    // Curvature at the point, which is the inverse of the radius of the osculating circle.
+    fn curvature(&self, p: Point) -> GeometryResult<EFloat64> {
+        let d1 = self.derivative(p, 1)?;
+        let d2 = self.derivative(p, 2)?;
+        Ok((d1.cross(d2).norm() / d1.norm().powi(3))?)
+    }
+
+    // Torsion at the point. It is positive if the curve turns around the tangent like a right handed screw. Fails where the curvature is zero.
+    fn torsion(&self, p: Point) -> GeometryResult<EFloat64> {
+        let d1 = self.derivative(p, 1)?;
+        let d2 = self.derivative(p, 2)?;
+        let d3 = self.derivative(p, 3)?;
+        let cross = d1.cross(d2);
+        Ok((cross.dot(d3) / cross.norm_sq())?)
+    }
+
+    // Frenet frame at the point. Fails where the curvature is zero, since the normal is not defined there.
+    fn frenet_frame(&self, p: Point) -> GeometryResult<FrenetFrame> {
+        let d1 = self.derivative(p, 1)?;
+        let d2 = self.derivative(p, 2)?;
+        let tangent = d1.normalize()?;
+        let binormal = d1.cross(d2).normalize()?;
+        Ok(FrenetFrame {
+            tangent,
+            normal: binormal.cross(tangent),
+            binormal,
+        })
+    }
+
     // Checks if point is on the curve.
     fn on_curve(&self, p: Point) -> bool;
 
     // Returns the distance between x and y. Fails if x and y are not on the curve.
     fn distance(&self, x: Point, y: Point) -> GeometryResult<EFloat64>;
 
+    // Returns the arc length from start to end, running in the direction of the curve.
+    // On open curves it is negative if end lies before start. On closed curves it is in [0, circumference).
+    fn length_between(&self, start: Point, end: Point) -> GeometryResult<EFloat64>;
+
+    // Returns the point at the given arc length from start, running in the direction of the curve.
+    // On open curves negative lengths run backwards. On closed curves the length wraps around.
+    fn point_at_length(&self, start: Point, length: EFloat64) -> GeometryResult<Point>;
+
     // Interpolate between start and end at t. t is between 0 and 1.
     fn interpolate(
         &self,
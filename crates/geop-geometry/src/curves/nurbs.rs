@@ -0,0 +1,1124 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    bounding_box::BoundingBox,
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    tolerance::Tolerance,
+    transforms::Transform,
+};
+
+use super::{curve::Curve, CurveLike};
+
+// Number of samples per knot span used to find a good starting value for the projection.
+const PROJECTION_SAMPLES_PER_SPAN: usize = 8;
+const NEWTON_ITERATIONS: usize = 32;
+
+// Non-uniform rational B-spline curve of the given degree.
+// The curve is defined on [knots[degree], knots[n]] where n is the number of control points.
+#[derive(Debug, Clone)]
+pub struct Nurbs {
+    pub degree: usize,
+    pub knots: Vec<EFloat64>,
+    pub weights: Vec<EFloat64>,
+    pub control_points: Vec<Point>,
+}
+
+// A control point in homogeneous coordinates, i.e. (w * P, w).
+type HomogeneousPoint = (Point, EFloat64);
+
+// Finds the index i such that knots[i] <= t < knots[i + 1], restricted to the domain of the curve.
+fn find_span(degree: usize, knots: &[EFloat64], n: usize, t: f64) -> usize {
+    if t >= knots[n].mid() {
+        let mut i = n - 1;
+        while i > degree && knots[i].mid() >= knots[n].mid() {
+            i -= 1;
+        }
+        return i;
+    }
+    let mut i = degree;
+    while i < n - 1 && knots[i + 1].mid() <= t {
+        i += 1;
+    }
+    i
+}
+
+// Evaluates a (non rational) B-spline with the de Boor algorithm.
+fn de_boor(
+    degree: usize,
+    knots: &[EFloat64],
+    points: &[HomogeneousPoint],
+    t: EFloat64,
+) -> HomogeneousPoint {
+    let n = points.len();
+    let k = find_span(degree, knots, n, t.mid());
+    let mut d: Vec<HomogeneousPoint> = (0..=degree).map(|j| points[j + k - degree]).collect();
+    for r in 1..=degree {
+        for j in (r..=degree).rev() {
+            let i = j + k - degree;
+            let alpha = ((t - knots[i]) / (knots[i + degree + 1 - r] - knots[i])).unwrap();
+            let beta = EFloat64::one() - alpha;
+            d[j] = (
+                d[j - 1].0 * beta + d[j].0 * alpha,
+                d[j - 1].1 * beta + d[j].1 * alpha,
+            );
+        }
+    }
+    d[degree]
+}
+
+// Control points of the derivative of a (non rational) B-spline. The knot vector is knots[1..len - 1].
+fn derivative_points(
+    degree: usize,
+    knots: &[EFloat64],
+    points: &[HomogeneousPoint],
+) -> Vec<HomogeneousPoint> {
+    let factor = EFloat64::from(degree as f64);
+    (0..points.len() - 1)
+        .map(|i| {
+            let span = knots[i + degree + 1] - knots[i + 1];
+            match factor / span {
+                Ok(factor) => (
+                    (points[i + 1].0 - points[i].0) * factor,
+                    (points[i + 1].1 - points[i].1) * factor,
+                ),
+                Err(_) => (Point::zero(), EFloat64::zero()),
+            }
+        })
+        .collect()
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |b, i| b * (n - i) as f64 / (i + 1) as f64)
+}
+
+impl Nurbs {
+    pub fn new(
+        degree: usize,
+        knots: Vec<EFloat64>,
+        weights: Vec<EFloat64>,
+        control_points: Vec<Point>,
+    ) -> GeometryResult<Nurbs> {
+        let n = control_points.len();
+        if degree == 0 {
+            return Err(GeometryError::new("Degree must be at least 1".to_string()));
+        }
+        if n <= degree {
+            return Err(GeometryError::new(format!(
+                "A curve of degree {} needs at least {} control points, got {}",
+                degree,
+                degree + 1,
+                n
+            )));
+        }
+        if knots.len() != n + degree + 1 {
+            return Err(GeometryError::new(format!(
+                "Expected {} knots, got {}",
+                n + degree + 1,
+                knots.len()
+            )));
+        }
+        if weights.len() != n {
+            return Err(GeometryError::new(format!(
+                "Expected {} weights, got {}",
+                n,
+                weights.len()
+            )));
+        }
+        if knots.windows(2).any(|k| k[1].mid() < k[0].mid()) {
+            return Err(GeometryError::new(
+                "Knots must be non-decreasing".to_string(),
+            ));
+        }
+        if knots[degree] == knots[n] {
+            return Err(GeometryError::new(
+                "Domain of the curve is empty".to_string(),
+            ));
+        }
+        if weights.iter().any(|w| w.lower_bound <= 0.0) {
+            return Err(GeometryError::new("Weights must be positive".to_string()));
+        }
+        Ok(Nurbs {
+            degree,
+            knots,
+            weights,
+            control_points,
+        })
+    }
+
+    // Creates a non rational B-spline with a clamped, uniform knot vector.
+    pub fn new_uniform_clamped(degree: usize, control_points: Vec<Point>) -> GeometryResult<Nurbs> {
+        let n = control_points.len();
+        if n <= degree {
+            return Err(GeometryError::new(format!(
+                "A curve of degree {} needs at least {} control points, got {}",
+                degree,
+                degree + 1,
+                n
+            )));
+        }
+        let inner = n - degree;
+        let mut knots = Vec::<EFloat64>::new();
+        for i in 0..(n + degree + 1) {
+            let k = i.saturating_sub(degree).min(inner);
+            knots.push(EFloat64::from(k as f64 / inner as f64));
+        }
+        Nurbs::new(degree, knots, vec![EFloat64::one(); n], control_points)
+    }
+
+    // Creates a polyline through the given points, parameterized by chord length.
+    pub fn polyline(points: Vec<Point>) -> GeometryResult<Nurbs> {
+        if points.len() < 2 {
+            return Err(GeometryError::new(
+                "A polyline needs at least 2 points".to_string(),
+            ));
+        }
+        // The knots are the chord lengths. Only their order matters, so the error bounds of the points are dropped.
+        let mut knots = vec![EFloat64::zero(), EFloat64::zero()];
+        let mut length = EFloat64::zero();
+        for i in 1..points.len() {
+            length = length + EFloat64::from((points[i] - points[i - 1]).norm().mid());
+            knots.push(length);
+        }
+        knots.push(length);
+        let n = points.len();
+        Nurbs::new(1, knots, vec![EFloat64::one(); n], points)
+    }
+
+    // Elliptic arc c + x_axis * cos(t) + y_axis * sin(t) for t from start to end as rational quadratic segments of at most 90 degrees.
+    fn conic_arc(center: Point, x_axis: Point, y_axis: Point, start: f64, end: f64) -> Nurbs {
+        let n = ((end - start) / std::f64::consts::FRAC_PI_2)
+            .ceil()
+            .max(1.0) as usize;
+        let step = (end - start) / n as f64;
+        let point = |t: f64, scale: f64| {
+            center
+                + (x_axis * EFloat64::from(t.cos()) + y_axis * EFloat64::from(t.sin()))
+                    * EFloat64::from(scale)
+        };
+        let middle_weight = (step / 2.0).cos();
+        let mut control_points = vec![point(start, 1.0)];
+        let mut weights = vec![EFloat64::one()];
+        let mut knots = vec![EFloat64::zero(); 3];
+        for i in 0..n {
+            let t = start + step * i as f64;
+            control_points.push(point(t + step / 2.0, 1.0 / middle_weight));
+            control_points.push(point(t + step, 1.0));
+            weights.push(EFloat64::from(middle_weight));
+            weights.push(EFloat64::one());
+            let knot = EFloat64::from((i + 1) as f64 / n as f64);
+            knots.push(knot);
+            knots.push(knot);
+        }
+        knots.push(EFloat64::one());
+        Nurbs {
+            degree: 2,
+            knots,
+            weights,
+            control_points,
+        }
+    }
+
+    // Exact representation of the part of the curve between start and end. Curves that are not closed need both ends.
+    pub fn from_curve(
+        curve: &Curve,
+        start: Option<Point>,
+        end: Option<Point>,
+    ) -> GeometryResult<Nurbs> {
+        // Angles of the ends of an arc, where end is larger than start.
+        let arc = |center: Point, x_axis: Point, y_axis: Point| {
+            let angle = |p: Point| {
+                let p = p - center;
+                let x = (p.dot(x_axis) / x_axis.norm_sq()).unwrap().mid();
+                let y = (p.dot(y_axis) / y_axis.norm_sq()).unwrap().mid();
+                y.atan2(x)
+            };
+            match (start, end) {
+                (None, None) => {
+                    let mut arc =
+                        Nurbs::conic_arc(center, x_axis, y_axis, 0.0, 2.0 * std::f64::consts::PI);
+                    // The arc is closed exactly, although sin(2 pi) is not zero in floating point.
+                    let n = arc.control_points.len();
+                    arc.control_points[n - 1] = arc.control_points[0];
+                    Ok(arc)
+                }
+                (Some(start), Some(end)) => {
+                    let (a, mut b) = (angle(start), angle(end));
+                    if b <= a {
+                        b += 2.0 * std::f64::consts::PI;
+                    }
+                    let mut arc = Nurbs::conic_arc(center, x_axis, y_axis, a, b);
+                    // The ends are kept exactly.
+                    let n = arc.control_points.len();
+                    arc.control_points[0] = start;
+                    arc.control_points[n - 1] = end;
+                    Ok(arc)
+                }
+                _ => Err(GeometryError::new(
+                    "An arc needs both ends or none".to_string(),
+                )),
+            }
+        };
+        match curve {
+            Curve::Line(_) => match (start, end) {
+                (Some(start), Some(end)) => Nurbs::new(
+                    1,
+                    vec![
+                        EFloat64::zero(),
+                        EFloat64::zero(),
+                        EFloat64::one(),
+                        EFloat64::one(),
+                    ],
+                    vec![EFloat64::one(); 2],
+                    vec![start, end],
+                ),
+                _ => Err(GeometryError::new("A line needs both ends".to_string())),
+            },
+            Curve::Circle(circle) => arc(
+                circle.basis,
+                circle.radius,
+                circle.normal.cross(circle.radius),
+            ),
+            // The ellipse runs counter clockwise around its normal.
+            Curve::Ellipse(ellipse) => {
+                let minor = match ellipse
+                    .major_radius
+                    .cross(ellipse.minor_radius)
+                    .dot(ellipse.normal)
+                    > 0.0
+                {
+                    true => ellipse.minor_radius,
+                    false => -ellipse.minor_radius,
+                };
+                arc(ellipse.basis, ellipse.major_radius, minor)
+            }
+            Curve::Helix(_) => Err(GeometryError::new("A helix is not a nurbs".to_string())),
+            Curve::Nurbs(nurbs) => match (start, end) {
+                (None, None) => Ok(nurbs.clone()),
+                (Some(start), Some(end)) => {
+                    let mut sub = nurbs.subcurve(nurbs.parameter(start), nurbs.parameter(end));
+                    let n = sub.control_points.len();
+                    sub.control_points[0] = start;
+                    sub.control_points[n - 1] = end;
+                    Ok(sub)
+                }
+                _ => Err(GeometryError::new(
+                    "A nurbs needs both ends or none".to_string(),
+                )),
+            },
+        }
+    }
+
+    // Start and end of the parameter domain.
+    pub fn domain(&self) -> (EFloat64, EFloat64) {
+        (
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        )
+    }
+
+    // Start and end of the knot span that contains t, on which the curve is a single rational polynomial.
+    pub fn span(&self, t: EFloat64) -> (EFloat64, EFloat64) {
+        let n = self.control_points.len();
+        let k = find_span(self.degree, &self.knots, n, t.mid());
+        (self.knots[k], self.knots[k + 1])
+    }
+
+    fn domain_f64(&self) -> (f64, f64) {
+        let (start, end) = self.domain();
+        (start.mid(), end.mid())
+    }
+
+    fn homogeneous_points(&self) -> Vec<HomogeneousPoint> {
+        self.control_points
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(p, w)| (*p * *w, *w))
+            .collect()
+    }
+
+    // Control points of the derivative of the homogeneous curve. The knot vector is knots[1..len - 1].
+    fn derivative_points(&self) -> Vec<HomogeneousPoint> {
+        derivative_points(self.degree, &self.knots, &self.homogeneous_points())
+    }
+
+    pub fn point_at(&self, t: EFloat64) -> Point {
+        let (p, w) = de_boor(self.degree, &self.knots, &self.homogeneous_points(), t);
+        (p / w).unwrap()
+    }
+
+    // Returns the point at t together with the weight of the curve at t.
+    pub fn weighted_point_at(&self, t: EFloat64) -> (Point, EFloat64) {
+        let (p, w) = de_boor(self.degree, &self.knots, &self.homogeneous_points(), t);
+        ((p / w).unwrap(), w)
+    }
+
+    // Derivative of the curve with respect to the parameter t.
+    pub fn derivative_at(&self, t: EFloat64) -> Point {
+        let (p, w) = de_boor(self.degree, &self.knots, &self.homogeneous_points(), t);
+        let (dp, dw) = de_boor(
+            self.degree - 1,
+            &self.knots[1..self.knots.len() - 1],
+            &self.derivative_points(),
+            t,
+        );
+        let c = (p / w).unwrap();
+        ((dp - c * dw) / w).unwrap()
+    }
+
+    // Derivatives of the curve up to the given order with respect to the parameter t, starting with the point itself.
+    pub fn derivatives_at(&self, t: EFloat64, order: usize) -> Vec<Point> {
+        // Derivatives of the homogeneous curve, which vanish above its degree.
+        let mut homogeneous = Vec::with_capacity(order + 1);
+        let mut points = self.homogeneous_points();
+        for k in 0..=order {
+            match k <= self.degree {
+                true => {
+                    let knots = &self.knots[k..self.knots.len() - k];
+                    homogeneous.push(de_boor(self.degree - k, knots, &points, t));
+                    if k < self.degree {
+                        points = derivative_points(self.degree - k, knots, &points);
+                    }
+                }
+                false => homogeneous.push((Point::zero(), EFloat64::zero())),
+            }
+        }
+        // Leibniz rule for w * C = A gives C^(k) = (A^(k) - sum_i binomial(k, i) * w^(i) * C^(k - i)) / w.
+        let w = homogeneous[0].1;
+        let mut derivatives: Vec<Point> = Vec::with_capacity(order + 1);
+        for k in 0..=order {
+            let mut a = homogeneous[k].0;
+            for i in 1..=k {
+                a = a - derivatives[k - i] * (homogeneous[i].1 * EFloat64::from(binomial(k, i)));
+            }
+            derivatives.push((a / w).unwrap());
+        }
+        derivatives
+    }
+
+    // Finds the parameter of the point on the curve that is closest to p.
+    pub fn parameter(&self, p: Point) -> EFloat64 {
+        let (t_min, t_max) = self.domain_f64();
+        let t = self.closest_parameter(p);
+        let eps = Tolerance::current().parametric * (t_max - t_min);
+        EFloat64::new((t + eps).min(t_max), (t - eps).max(t_min))
+    }
+
+    // The refined parameter without error bounds. The bounds of parameter are cut at the ends of the domain, so their middle is not this parameter there.
+    fn closest_parameter(&self, p: Point) -> f64 {
+        let (t_min, t_max) = self.domain_f64();
+        let dist_sq = |t: f64| -> f64 {
+            let d = self.point_at(EFloat64::from(t)) - p;
+            d.norm_sq().mid()
+        };
+
+        // Find a good starting value by sampling every knot span.
+        let mut best_t = t_min;
+        let mut best_dist = dist_sq(t_min);
+        for i in self.degree..self.control_points.len() {
+            let a = self.knots[i].mid().max(t_min);
+            let b = self.knots[i + 1].mid().min(t_max);
+            if b <= a {
+                continue;
+            }
+            for j in 0..=PROJECTION_SAMPLES_PER_SPAN {
+                let t = a + (b - a) * j as f64 / PROJECTION_SAMPLES_PER_SPAN as f64;
+                let d = dist_sq(t);
+                if d < best_dist {
+                    best_dist = d;
+                    best_t = t;
+                }
+            }
+        }
+
+        // Refine with newton iterations on the derivative of the squared distance.
+        let g = |t: f64| -> f64 {
+            let t = EFloat64::from(t);
+            let v = self.derivative_at(t).dot(self.point_at(t) - p);
+            v.mid()
+        };
+        let h = (t_max - t_min) * 1e-7;
+        let mut t = best_t;
+        for _ in 0..NEWTON_ITERATIONS {
+            let a = (t - h).max(t_min);
+            let b = (t + h).min(t_max);
+            let dg = (g(b) - g(a)) / (b - a);
+            if dg == 0.0 || !dg.is_finite() {
+                break;
+            }
+            let t_new = (t - g(t) / dg).clamp(t_min, t_max);
+            if (t_new - t).abs() <= f64::EPSILON * (t_max - t_min) {
+                t = t_new;
+                break;
+            }
+            t = t_new;
+        }
+        if dist_sq(t) > best_dist {
+            t = best_t;
+        }
+        t
+    }
+
+    // Arc length from the parameter a to b, negative if b < a. Uses the composite simpson rule on the speed of the curve.
+    fn length_between_parameters(&self, a: f64, b: f64) -> EFloat64 {
+        if a == b {
+            return EFloat64::zero();
+        }
+        let steps = 64 * self.control_points.len();
+        let h = (b - a) / steps as f64;
+        let speed = |t: f64| self.derivative_at(EFloat64::from(t)).norm();
+        let mut sum = speed(a) + speed(b);
+        for i in 1..steps {
+            let factor = if i % 2 == 1 { 4.0 } else { 2.0 };
+            sum = sum + EFloat64::from(factor) * speed(a + h * i as f64);
+        }
+        sum * EFloat64::from(h / 3.0)
+    }
+
+    // Parameter of an optional point. None is mapped to the start or the end of the domain.
+    fn parameter_or(&self, p: Option<Point>, default: EFloat64) -> EFloat64 {
+        match p {
+            Some(p) => self.parameter(p),
+            None => default,
+        }
+    }
+
+    // A closed curve ends where it starts, its parameter wraps around at this seam.
+    pub fn is_closed(&self) -> bool {
+        self.control_points[0] == self.control_points[self.control_points.len() - 1]
+    }
+
+    // Parameters of the start and the end of an edge. On a closed curve an end before the start lies behind the seam and is moved by one period, past the end of the domain.
+    fn edge_parameters(&self, start: Option<Point>, end: Option<Point>) -> (EFloat64, EFloat64) {
+        let (t_min, t_max) = self.domain();
+        let mut t_start = self.parameter_or(start, t_min);
+        let mut t_end = self.parameter_or(end, t_max);
+        if self.is_closed() {
+            if t_start == t_max {
+                t_start = t_min;
+            }
+            if t_end.upper_bound < t_start.lower_bound {
+                t_end = t_end + (t_max - t_min);
+            }
+        }
+        (t_start, t_end)
+    }
+
+    // Moves a parameter past the end of the domain of a closed curve back by one period.
+    fn wrap_parameter(&self, t: EFloat64) -> EFloat64 {
+        let (t_min, t_max) = self.domain();
+        if t.lower_bound > t_max.upper_bound {
+            t - (t_max - t_min)
+        } else {
+            t
+        }
+    }
+
+    // Inserts the knot t once, without changing the shape of the curve.
+    pub fn insert_knot(&self, t: EFloat64) -> Nurbs {
+        let n = self.control_points.len();
+        let p = self.degree;
+        let k = find_span(p, &self.knots, n, t.mid());
+        let points = self.homogeneous_points();
+        let mut new_points = Vec::<HomogeneousPoint>::new();
+        for i in 0..=n {
+            if i <= k - p {
+                new_points.push(points[i]);
+            } else if i > k {
+                new_points.push(points[i - 1]);
+            } else {
+                let alpha = ((t - self.knots[i]) / (self.knots[i + p] - self.knots[i])).unwrap();
+                let beta = EFloat64::one() - alpha;
+                new_points.push((
+                    points[i - 1].0 * beta + points[i].0 * alpha,
+                    points[i - 1].1 * beta + points[i].1 * alpha,
+                ));
+            }
+        }
+        let mut knots = self.knots.clone();
+        knots.insert(k + 1, t);
+        Nurbs {
+            degree: p,
+            knots,
+            weights: new_points.iter().map(|(_, w)| *w).collect(),
+            control_points: new_points.iter().map(|(p, w)| (*p / *w).unwrap()).collect(),
+        }
+    }
+
+    // The same curve with a degree that is higher by one.
+    // The curve is split into bezier pieces at its knots, whose homogeneous control points are elevated one by one. The pieces stay joined at knots of full multiplicity.
+    pub fn elevate_degree(&self) -> Nurbs {
+        let p = self.degree;
+        let (t_min, t_max) = self.domain_f64();
+        let mut breaks: Vec<f64> = self
+            .knots
+            .iter()
+            .map(|k| k.mid())
+            .filter(|k| *k >= t_min && *k <= t_max)
+            .collect();
+        breaks.dedup();
+        let mut curve = self.clone();
+        for t in breaks.iter() {
+            let existing = curve.knots.iter().filter(|k| k.mid() == *t).count();
+            for _ in existing..p {
+                curve = curve.insert_knot(EFloat64::from(*t));
+            }
+        }
+        let points = curve.homogeneous_points();
+        let mut elevated = Vec::<HomogeneousPoint>::new();
+        let mut knots = vec![EFloat64::from(t_min); p + 2];
+        for (s, t) in breaks.iter().enumerate().skip(1) {
+            // The control points of the piece that ends at t.
+            let k = curve
+                .knots
+                .iter()
+                .rposition(|k| k.mid() == breaks[s - 1])
+                .unwrap();
+            let piece = &points[k - p..=k];
+            let start = if s == 1 { 0 } else { 1 };
+            for i in start..=p + 1 {
+                let alpha = EFloat64::from(i as f64 / (p + 1) as f64);
+                let beta = EFloat64::one() - alpha;
+                let point = match i {
+                    0 => piece[0],
+                    i if i == p + 1 => piece[p],
+                    i => (
+                        piece[i - 1].0 * alpha + piece[i].0 * beta,
+                        piece[i - 1].1 * alpha + piece[i].1 * beta,
+                    ),
+                };
+                elevated.push(point);
+            }
+            let multiplicity = if s + 1 == breaks.len() { p + 2 } else { p + 1 };
+            knots.extend(vec![EFloat64::from(*t); multiplicity]);
+        }
+        Nurbs {
+            degree: p + 1,
+            knots,
+            weights: elevated.iter().map(|(_, w)| *w).collect(),
+            control_points: elevated.iter().map(|(p, w)| (*p / *w).unwrap()).collect(),
+        }
+    }
+
+    // Returns the part of the curve between the parameters start and end as a new curve.
+    pub fn subcurve(&self, start: EFloat64, end: EFloat64) -> Nurbs {
+        let (t_min, t_max) = self.domain_f64();
+        let mut curve = self.clone();
+        for t in [start, end] {
+            if t.mid() <= t_min || t.mid() >= t_max {
+                continue;
+            }
+            let existing = curve
+                .knots
+                .iter()
+                .filter(|k| (**k).mid() == t.mid())
+                .count();
+            for _ in existing..self.degree {
+                curve = curve.insert_knot(t);
+            }
+        }
+        let start = start.mid().max(t_min);
+        let end = end.mid().min(t_max);
+        let n = curve.control_points.len();
+        let first = find_span(curve.degree, &curve.knots, n, start) - curve.degree;
+        // The last span is the one that ends at end, not the one that starts there.
+        let last = (curve.degree..n)
+            .rev()
+            .find(|i| curve.knots[*i].mid() < end)
+            .unwrap_or(curve.degree);
+        Nurbs {
+            degree: curve.degree,
+            knots: curve.knots[first..=last + curve.degree + 1].to_vec(),
+            weights: curve.weights[first..=last].to_vec(),
+            control_points: curve.control_points[first..=last].to_vec(),
+        }
+    }
+
+    pub fn transform(&self, transform: Transform) -> Nurbs {
+        Nurbs {
+            degree: self.degree,
+            knots: self.knots.clone(),
+            weights: self.weights.clone(),
+            control_points: self.control_points.iter().map(|p| transform * *p).collect(),
+        }
+    }
+
+    pub fn neg(&self) -> Nurbs {
+        // The knots are mirrored without error bounds, so that equal knots stay equal.
+        let (start, end) = self.domain_f64();
+        Nurbs {
+            degree: self.degree,
+            knots: self
+                .knots
+                .iter()
+                .rev()
+                .map(|k| EFloat64::from(start + end - (*k).mid()))
+                .collect(),
+            weights: self.weights.iter().rev().cloned().collect(),
+            control_points: self.control_points.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+impl CurveLike for Nurbs {
+    fn transform(&self, transform: Transform) -> Curve {
+        Curve::Nurbs(self.transform(transform))
+    }
+
+    fn neg(&self) -> Curve {
+        Curve::Nurbs(self.neg())
+    }
+
+    fn tangent(&self, p: Point) -> GeometryResult<Point> {
+        assert!(self.on_curve(p));
+        let t = self.parameter(p);
+        self.derivative_at(t).normalize()
+    }
+
+    fn derivative(&self, p: Point, order: usize) -> GeometryResult<Point> {
+        assert!(self.on_curve(p));
+        let t = self.parameter(p);
+        Ok(self.derivatives_at(t, order)[order])
+    }
+
+    fn on_curve(&self, p: Point) -> bool {
+        self.point_at(self.parameter(p)) == p
+    }
+
+    fn distance(&self, x: Point, y: Point) -> GeometryResult<EFloat64> {
+        assert!(self.on_curve(x));
+        assert!(self.on_curve(y));
+        let t_x = self.parameter(x).mid();
+        let t_y = self.parameter(y).mid();
+        Ok(self.length_between_parameters(t_x, t_y).abs())
+    }
+
+    fn length_between(&self, start: Point, end: Point) -> GeometryResult<EFloat64> {
+        assert!(self.on_curve(start));
+        assert!(self.on_curve(end));
+        let (t_start, t_end) = self.edge_parameters(Some(start), Some(end));
+        let (t_start, t_end) = (t_start.mid(), t_end.mid());
+        let (t_min, t_max) = self.domain_f64();
+        if t_end > t_max {
+            return Ok(self.length_between_parameters(t_start, t_max)
+                + self.length_between_parameters(t_min, t_end - (t_max - t_min)));
+        }
+        Ok(self.length_between_parameters(t_start, t_end))
+    }
+
+    // The parameter is found by newton iterations on the arc length. Fails if the length runs past the ends of the domain.
+    fn point_at_length(&self, start: Point, length: EFloat64) -> GeometryResult<Point> {
+        assert!(self.on_curve(start));
+        let (t_min, t_max) = self.domain_f64();
+        let t_start = self.parameter(start).mid();
+        let length = length.mid();
+        let speed = |t: f64| self.derivative_at(EFloat64::from(t)).norm().mid();
+
+        let mut t = t_start;
+        for _ in 0..NEWTON_ITERATIONS {
+            let error = self.length_between_parameters(t_start, t).mid() - length;
+            if error.abs() <= f64::EPSILON * length.abs().max(1.0) {
+                break;
+            }
+            let s = speed(t);
+            if s == 0.0 {
+                break;
+            }
+            t = (t - error / s).clamp(t_min, t_max);
+        }
+        let error = self.length_between_parameters(t_start, t).mid() - length;
+        if error.abs() > Tolerance::current().linear.max(1e-9) * length.abs().max(1.0) {
+            return Err(GeometryError::new(format!(
+                "The length {} runs past the end of the curve",
+                length
+            )));
+        }
+        Ok(self.point_at(EFloat64::from(t)))
+    }
+
+    fn interpolate(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+        t: f64,
+    ) -> GeometryResult<Point> {
+        let (t_start, t_end) = self.edge_parameters(start, end);
+        let t = t_start + EFloat64::from(t) * (t_end - t_start);
+        Ok(self.point_at(self.wrap_parameter(t)))
+    }
+
+    // Checks if m is between x and y. m==x and m==y are true.
+    fn between(&self, m: Point, start: Option<Point>, end: Option<Point>) -> GeometryResult<bool> {
+        assert!(self.on_curve(m));
+        let (t_start, t_end) = self.edge_parameters(start, end);
+        let t_m = self.parameter(m);
+        let inside = |t: EFloat64| t_start <= t.upper_bound && t <= t_end.upper_bound;
+        if !self.is_closed() {
+            return Ok(inside(t_m));
+        }
+        // The seam has two parameters, and m may lie behind it.
+        let (t_min, t_max) = self.domain();
+        let period = t_max - t_min;
+        Ok(inside(t_m) || inside(t_m + period) || inside(t_m - period))
+    }
+
+    fn get_midpoint(&self, start: Option<Point>, end: Option<Point>) -> GeometryResult<Point> {
+        let (t_start, t_end) = self.edge_parameters(start, end);
+        if t_start == t_end {
+            return Err(GeometryError::new("Start and end are the same".to_string()));
+        }
+        let t = EFloat64::from((t_start.mid() + t_end.mid()) / 2.0);
+        Ok(self.point_at(self.wrap_parameter(t)))
+    }
+
+    fn project(&self, p: Point) -> Point {
+        self.point_at(EFloat64::from(self.closest_parameter(p)))
+    }
+
+    // Uses the convex hull property, the curve lies inside of the bounding box of its control points.
+    fn get_bounding_box(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+    ) -> GeometryResult<BoundingBox> {
+        let (t_min, t_max) = self.domain();
+        let (t_start, t_end) = self.edge_parameters(start, end);
+        if t_start == t_end {
+            let p = self.point_at(t_start);
+            return Ok(BoundingBox::new(p, p));
+        }
+        // An edge across the seam is bounded by the pieces before and after it.
+        let curves = if t_end.lower_bound > t_max.upper_bound {
+            vec![
+                self.subcurve(t_start, t_max),
+                self.subcurve(t_min, self.wrap_parameter(t_end)),
+            ]
+        } else {
+            vec![self.subcurve(t_start, t_end)]
+        };
+        let mut bounding_box =
+            BoundingBox::new(curves[0].control_points[0], curves[0].control_points[0]);
+        for p in curves.iter().flat_map(|curve| curve.control_points.iter()) {
+            bounding_box.add_point(*p);
+        }
+        Ok(bounding_box)
+    }
+
+    fn shrink_bounding_box(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+        bounding_box: BoundingBox,
+    ) -> GeometryResult<BoundingBox> {
+        let curve_box = self.get_bounding_box(start, end)?;
+        let min = Point::new(
+            bounding_box.min.x.max(curve_box.min.x),
+            bounding_box.min.y.max(curve_box.min.y),
+            bounding_box.min.z.max(curve_box.min.z),
+        );
+        let max = Point::new(
+            bounding_box.max.x.min(curve_box.max.x),
+            bounding_box.max.y.min(curve_box.max.y),
+            bounding_box.max.z.min(curve_box.max.z),
+        );
+        Ok(BoundingBox::new(min, max))
+    }
+
+    // Points are sorted by their parameter. None is sorted to the end.
+    fn sort(&self, points: Vec<Option<Point>>) -> Vec<Option<Point>> {
+        let mut points = points
+            .into_iter()
+            .map(|p| (p.map(|p| self.parameter(p).mid()), p))
+            .collect::<Vec<(Option<f64>, Option<Point>)>>();
+        points.sort_by(|a, b| match (a.0, b.0) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap(),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        points.into_iter().map(|(_, p)| p).collect()
+    }
+}
+
+impl PartialEq for Nurbs {
+    fn eq(&self, other: &Nurbs) -> bool {
+        self.degree == other.degree
+            && self.knots.len() == other.knots.len()
+            && self.control_points.len() == other.control_points.len()
+            && self
+                .knots
+                .iter()
+                .zip(other.knots.iter())
+                .all(|(a, b)| a == b)
+            && self
+                .weights
+                .iter()
+                .zip(other.weights.iter())
+                .all(|(a, b)| a == b)
+            && self
+                .control_points
+                .iter()
+                .zip(other.control_points.iter())
+                .all(|(a, b)| a == b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Quarter circle of radius 1 in the xy plane as a rational quadratic curve.
+    fn quarter_circle() -> Nurbs {
+        let w = EFloat64::two().sqrt().unwrap();
+        let w = (w / EFloat64::two()).unwrap();
+        Nurbs::new(
+            2,
+            vec![
+                EFloat64::zero(),
+                EFloat64::zero(),
+                EFloat64::zero(),
+                EFloat64::one(),
+                EFloat64::one(),
+                EFloat64::one(),
+            ],
+            vec![EFloat64::one(), w, EFloat64::one()],
+            vec![
+                Point::unit_x(),
+                Point::from_f64(1.0, 1.0, 0.0),
+                Point::unit_y(),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nurbs_elevate_degree() {
+        // A full circle has pieces between its knots, which stay joined.
+        let circle = Nurbs::from_curve(
+            &Curve::Circle(crate::curves::circle::Circle::new(
+                Point::zero(),
+                Point::unit_z(),
+                EFloat64::one(),
+            )),
+            None,
+            None,
+        )
+        .unwrap();
+        for curve in [quarter_circle(), circle] {
+            let elevated = curve.elevate_degree().elevate_degree();
+            assert_eq!(elevated.degree, curve.degree + 2);
+            assert_eq!(elevated.domain().0, curve.domain().0);
+            assert_eq!(elevated.domain().1, curve.domain().1);
+            let (start, end) = curve.domain();
+            for i in 0..=20 {
+                let t = start + (end - start) * EFloat64::from(i as f64 / 20.0);
+                assert!((elevated.point_at(t) - curve.point_at(t)).norm().mid() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nurbs_exact_circle() {
+        let curve = quarter_circle();
+        assert_eq!(curve.point_at(EFloat64::zero()), Point::unit_x());
+        assert_eq!(curve.point_at(EFloat64::one()), Point::unit_y());
+        for i in 0..=10 {
+            let p = curve.point_at(EFloat64::from(i as f64 / 10.0));
+            assert_eq!(p.norm(), 1.0);
+        }
+        let p = curve.point_at(EFloat64::from(0.5));
+        assert!(curve.on_curve(p));
+        assert!(!curve.on_curve(Point::zero()));
+
+        // The arc length of a quarter circle is pi / 2
+        let length = curve.distance(Point::unit_x(), Point::unit_y()).unwrap();
+        assert!((length.lower_bound - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+        let length = curve
+            .length_between(Point::unit_y(), Point::unit_x())
+            .unwrap();
+        assert!((length.lower_bound + std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+
+        // The point at a third of the arc is at 30 degrees.
+        let p = curve
+            .point_at_length(Point::unit_x(), EFloat64::from(std::f64::consts::FRAC_PI_6))
+            .unwrap();
+        assert!((p - Point::from_f64(0.75f64.sqrt(), 0.5, 0.0)).norm() < 1e-6);
+        assert!(curve
+            .point_at_length(Point::unit_x(), EFloat64::from(2.0))
+            .is_err());
+
+        // Tangent at the start points in y direction
+        assert_eq!(curve.tangent(Point::unit_x()).unwrap(), Point::unit_y());
+    }
+
+    #[test]
+    fn test_nurbs_projection_and_sort() {
+        let curve = Nurbs::new_uniform_clamped(
+            3,
+            vec![
+                Point::from_f64(0.0, 0.0, 0.0),
+                Point::from_f64(1.0, 2.0, 0.0),
+                Point::from_f64(2.0, -1.0, 0.0),
+                Point::from_f64(3.0, 1.0, 1.0),
+                Point::from_f64(4.0, 0.0, 0.0),
+            ],
+        )
+        .unwrap();
+
+        let p1 = curve.point_at(EFloat64::from(0.2));
+        let p2 = curve.point_at(EFloat64::from(0.5));
+        let p3 = curve.point_at(EFloat64::from(0.8));
+        let normal = curve
+            .derivative_at(EFloat64::from(0.5))
+            .cross(Point::unit_z())
+            .normalize()
+            .unwrap();
+        assert_eq!(curve.project(p2 + normal * EFloat64::from(1e-3)), p2);
+        assert!(curve.between(p2, Some(p1), Some(p3)).unwrap());
+        assert!(!curve.between(p1, Some(p2), Some(p3)).unwrap());
+
+        let sorted = curve.sort(vec![Some(p3), None, Some(p1), Some(p2)]);
+        assert_eq!(sorted, vec![Some(p1), Some(p2), Some(p3), None]);
+
+        let m = curve.get_midpoint(Some(p1), Some(p3)).unwrap();
+        assert!(curve.between(m, Some(p1), Some(p3)).unwrap());
+    }
+
+    #[test]
+    fn test_nurbs_seam() {
+        use crate::curves::circle::Circle;
+
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::two());
+        let full = Nurbs::from_curve(&Curve::Circle(circle), None, None).unwrap();
+        assert!(full.is_closed());
+        assert!(!quarter_circle().is_closed());
+
+        // An edge from -45 to 45 degrees crosses the seam on the x axis.
+        let r = 2.0f64.sqrt();
+        let start = Point::from_f64(r, -r, 0.0);
+        let end = Point::from_f64(r, r, 0.0);
+        let seam = Point::from_f64(2.0, 0.0, 0.0);
+        let quarter = EFloat64::from(std::f64::consts::PI);
+        assert_eq!(full.length_between(start, end).unwrap(), quarter);
+        let rest = full.length_between(end, start).unwrap() - EFloat64::from(3.0) * quarter;
+        assert!(rest.abs() < 1e-6);
+
+        assert!(full.between(seam, Some(start), Some(end)).unwrap());
+        assert!(full.between(start, Some(start), Some(end)).unwrap());
+        assert!(full.between(end, Some(start), Some(end)).unwrap());
+        assert!(!full.between(-seam, Some(start), Some(end)).unwrap());
+        assert!(full.between(-seam, Some(end), Some(start)).unwrap());
+        assert!(!full.between(seam, Some(end), Some(start)).unwrap());
+        assert!(full.between(end, Some(seam), Some(end)).unwrap());
+        assert!(full.between(start, Some(start), Some(seam)).unwrap());
+
+        assert_eq!(full.get_midpoint(Some(start), Some(end)).unwrap(), seam);
+        assert_eq!(full.interpolate(Some(start), Some(end), 0.5).unwrap(), seam);
+        let bounding_box = full.get_bounding_box(Some(start), Some(end)).unwrap();
+        assert!(bounding_box.max.x >= 2.0 && bounding_box.min.x <= r);
+        assert!(bounding_box.min.x > 0.0);
+    }
+
+    #[test]
+    fn test_nurbs_subcurve_and_neg() {
+        let curve = quarter_circle();
+        let sub = curve.subcurve(EFloat64::from(0.25), EFloat64::from(0.75));
+        for i in 0..=10 {
+            let t = EFloat64::from(0.25 + 0.05 * i as f64);
+            assert_eq!(sub.point_at(t), curve.point_at(t));
+        }
+
+        let bounding_box = curve.get_bounding_box(None, None).unwrap();
+        assert!(bounding_box.max.x >= 1.0 && bounding_box.max.y >= 1.0);
+
+        let reversed = curve.neg();
+        assert_eq!(reversed.point_at(EFloat64::zero()), Point::unit_y());
+        assert_eq!(
+            reversed.point_at(EFloat64::from(0.3)),
+            curve.point_at(EFloat64::from(0.7))
+        );
+    }
+
+    #[test]
+    fn test_nurbs_from_curve() {
+        use crate::curves::{circle::Circle, ellipse::Ellipse, line::Line};
+
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::two());
+        let full = Nurbs::from_curve(&Curve::Circle(circle.clone()), None, None).unwrap();
+        assert_eq!(full.control_points.len(), 9);
+        for i in 0..=20 {
+            let p = full.point_at(EFloat64::from(i as f64 / 20.0));
+            assert!(circle.on_curve(p));
+        }
+
+        // Three quarters from the y axis to the x axis, running counter clockwise.
+        let (start, end) = (
+            Point::from_f64(0.0, 2.0, 0.0),
+            Point::from_f64(2.0, 0.0, 0.0),
+        );
+        let arc =
+            Nurbs::from_curve(&Curve::Circle(circle.clone()), Some(start), Some(end)).unwrap();
+        let (a, b) = arc.domain();
+        assert_eq!(arc.point_at(a), start);
+        assert_eq!(arc.point_at(b), end);
+        assert_eq!(
+            arc.point_at(EFloat64::from(0.5)),
+            Point::from_f64(-2.0f64.sqrt(), -2.0f64.sqrt(), 0.0)
+        );
+
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        let full = Nurbs::from_curve(&Curve::Ellipse(ellipse.clone()), None, None).unwrap();
+        for i in 0..=20 {
+            let p = full.point_at(EFloat64::from(i as f64 / 20.0));
+            assert!(ellipse.on_curve(p));
+            assert!(full.tangent(p).unwrap().dot(ellipse.tangent(p).unwrap()) > 0.0);
+        }
+
+        let line = Curve::Line(Line::new(Point::zero(), Point::unit_x()).unwrap());
+        let segment = Nurbs::from_curve(&line, Some(Point::zero()), Some(Point::unit_x())).unwrap();
+        assert_eq!(
+            segment.point_at(EFloat64::from(0.25)),
+            Point::from_f64(0.25, 0.0, 0.0)
+        );
+        assert!(Nurbs::from_curve(&line, None, None).is_err());
+    }
+
+    #[test]
+    fn test_nurbs_derivatives() {
+        use crate::curves::ellipse::Ellipse;
+
+        let arc = quarter_circle();
+        for i in 0..=4 {
+            let t = EFloat64::from(i as f64 / 4.0);
+            let derivatives = arc.derivatives_at(t, 3);
+            assert!((derivatives[1] - arc.derivative_at(t)).norm() < 1e-12);
+            let p = derivatives[0];
+            assert!((arc.curvature(p).unwrap() - EFloat64::one()).abs() < 1e-6);
+            assert!(arc.torsion(p).unwrap().abs() < 1e-6);
+            let frame = arc.frenet_frame(p).unwrap();
+            assert!((frame.normal + p).norm() < 1e-6);
+            assert!((frame.binormal - Point::unit_z()).norm() < 1e-6);
+        }
+
+        // The curvature of the exact ellipse agrees with the analytic one at the vertices.
+        let ellipse = Ellipse::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        let full = Nurbs::from_curve(&Curve::Ellipse(ellipse.clone()), None, None).unwrap();
+        for p in [
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        ] {
+            let expected = ellipse.curvature(p).unwrap();
+            assert!((full.curvature(p).unwrap() - expected).abs() < 1e-6);
+        }
+    }
+}
@@ -1 +0,0 @@
-pub mod circle_like;
@@ -0,0 +1,157 @@
+use crate::{curves::CurveLike, geometry_error::GeometryResult, point::Point};
+
+use super::{distance_f64, minimize_2d, sample_count, ClosestPoints};
+
+// Number of the best local minima of the samples that are refined.
+const CANDIDATES: usize = 8;
+
+// Finds the closest points between the curve within [start, end] and the other curve within [other_start, other_end].
+// Both curves are walked with interpolate, so the result always lies between the bounds. Unbounded curves are cut off at the horizon.
+// This is a heuristic: the distances between samples of both curves are refined by a pattern search from their best local minima. A minimum that falls between the samples and is not close to one of these is missed, and the result is only a local minimum.
+pub fn curve_curve_distance(
+    curve: &dyn CurveLike,
+    start: Option<Point>,
+    end: Option<Point>,
+    other: &dyn CurveLike,
+    other_start: Option<Point>,
+    other_end: Option<Point>,
+) -> GeometryResult<ClosestPoints> {
+    let point_at = |t: f64| curve.interpolate(start, end, t);
+    let other_point_at = |t: f64| other.interpolate(other_start, other_end, t);
+    let f = |s: f64, t: f64| match (point_at(s), other_point_at(t)) {
+        (Ok(a), Ok(b)) => distance_f64(a, b),
+        _ => f64::INFINITY,
+    };
+
+    let (n, m) = (sample_count(point_at)?, sample_count(other_point_at)?);
+    let params: Vec<f64> = (0..=n).map(|i| i as f64 / n as f64).collect();
+    let other_params: Vec<f64> = (0..=m).map(|j| j as f64 / m as f64).collect();
+    let points = params
+        .iter()
+        .map(|s| point_at(*s))
+        .collect::<GeometryResult<Vec<Point>>>()?;
+    let other_points = other_params
+        .iter()
+        .map(|t| other_point_at(*t))
+        .collect::<GeometryResult<Vec<Point>>>()?;
+    let grid: Vec<Vec<f64>> = points
+        .iter()
+        .map(|a| other_points.iter().map(|b| distance_f64(*a, *b)).collect())
+        .collect();
+
+    // Local minima of the sampled distances are the starting points of the refinement.
+    let mut minima = Vec::new();
+    for i in 0..=n {
+        for j in 0..=m {
+            // Ties are broken by the position, so that a plateau, e.g. of parallel lines, is refined only once.
+            let is_minimum = (i.saturating_sub(1)..=(i + 1).min(n)).all(|k| {
+                (j.saturating_sub(1)..=(j + 1).min(m)).all(|l| {
+                    grid[i][j] < grid[k][l] || (grid[i][j] == grid[k][l] && (i, j) <= (k, l))
+                })
+            });
+            if is_minimum {
+                minima.push((grid[i][j], i, j));
+            }
+        }
+    }
+    minima.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let mut result: Option<ClosestPoints> = None;
+    for (_, i, j) in minima.into_iter().take(CANDIDATES) {
+        let (s, t) = minimize_2d(f, params[i], other_params[j], 1.0 / n.min(m) as f64);
+        let closest = ClosestPoints::new(point_at(s)?, other_point_at(t)?);
+        result = Some(match result {
+            Some(result) => result.min(closest),
+            None => closest,
+        });
+    }
+    Ok(result.expect("The sampled distances always have a minimum"))
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use crate::curves::{circle::Circle, helix::Helix, line::Line};
+
+    use super::*;
+
+    fn assert_close(closest: &ClosestPoints, from: Point, to: Point) {
+        assert!(distance_f64(closest.from, from) < 1e-9);
+        assert!(distance_f64(closest.to, to) < 1e-9);
+        assert!((closest.distance.mid() - distance_f64(from, to)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_line_line_distance() {
+        let line = Line::new(Point::zero(), Point::unit_x()).unwrap();
+        let other = Line::new(Point::from_f64(0.0, 0.0, 1.0), Point::unit_y()).unwrap();
+
+        // The bounded lines are skew, and closest at the origin and above it.
+        let closest = curve_curve_distance(
+            &line,
+            Some(Point::from_f64(-1.0, 0.0, 0.0)),
+            Some(Point::from_f64(1.0, 0.0, 0.0)),
+            &other,
+            Some(Point::from_f64(0.0, -1.0, 1.0)),
+            Some(Point::from_f64(0.0, 1.0, 1.0)),
+        )
+        .unwrap();
+        assert_close(&closest, Point::zero(), Point::from_f64(0.0, 0.0, 1.0));
+
+        // Moving the bounds of the first line away, its start becomes the closest point.
+        let closest = curve_curve_distance(
+            &line,
+            Some(Point::from_f64(3.0, 0.0, 0.0)),
+            Some(Point::from_f64(4.0, 0.0, 0.0)),
+            &other,
+            Some(Point::from_f64(0.0, -1.0, 1.0)),
+            Some(Point::from_f64(0.0, 1.0, 1.0)),
+        )
+        .unwrap();
+        assert_close(
+            &closest,
+            Point::from_f64(3.0, 0.0, 0.0),
+            Point::from_f64(0.0, 0.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn test_circle_circle_distance() {
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::from(1.0));
+        let other = Circle::new(
+            Point::from_f64(3.0, 0.0, 0.0),
+            Point::unit_z(),
+            EFloat64::from(1.0),
+        );
+        let closest = curve_curve_distance(&circle, None, None, &other, None, None).unwrap();
+        assert_close(&closest, Point::unit_x(), Point::from_f64(2.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_helix_line_distance() {
+        // Eight turns get enough samples to find the one turn that passes below the short line.
+        let helix = Helix::new(Point::zero(), Point::unit_z(), Point::unit_x(), true);
+        let (start, end) = (
+            helix.point_at_pitch(EFloat64::zero()),
+            helix.point_at_pitch(EFloat64::from(8.0)),
+        );
+        // The direction of the helix turns a bit less than a full turn per turn around its axis.
+        let n = sample_count(|t| helix.interpolate(Some(start), Some(end), t)).unwrap();
+        assert!(n >= 7 * 32);
+        let line = Line::new(Point::from_f64(2.0, 0.0, 5.0), Point::unit_y()).unwrap();
+        let closest = curve_curve_distance(
+            &helix,
+            Some(start),
+            Some(end),
+            &line,
+            Some(Point::from_f64(2.0, -0.1, 5.0)),
+            Some(Point::from_f64(2.0, 0.1, 5.0)),
+        )
+        .unwrap();
+        // The distance is flat at its minimum, so the points are less accurate than the distance.
+        assert!(distance_f64(closest.from, Point::from_f64(1.0, 0.0, 5.0)) < 1e-6);
+        assert!(distance_f64(closest.to, Point::from_f64(2.0, 0.0, 5.0)) < 1e-6);
+        assert!((closest.distance.mid() - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    curves::CurveLike, geometry_error::GeometryResult, point::Point, surfaces::SurfaceLike,
+};
+
+use super::{distance_f64, minimize_1d, sample_count, ClosestPoints};
+
+// Finds the closest points between the curve within [start, end] and the part of the surface for which contains is true.
+// Points of the curve are projected onto the surface, so only pairs where the closest point on the surface is contained are found.
+// Returns None if no projection is contained, e.g. if the closest points lie on the boundary of a face. These have to be found by the caller.
+// This is a heuristic: the distances of samples of the curve are refined by a golden section search between the neighbours of their local minima. A minimum between two samples that does not show in the samples is missed, and the result is only a local minimum.
+pub fn curve_surface_distance(
+    curve: &dyn CurveLike,
+    start: Option<Point>,
+    end: Option<Point>,
+    surface: &dyn SurfaceLike,
+    contains: &dyn Fn(Point) -> bool,
+) -> GeometryResult<Option<ClosestPoints>> {
+    let pair_at = |t: f64| -> GeometryResult<Option<(Point, Point)>> {
+        let p = curve.interpolate(start, end, t)?;
+        let q = surface.project(p);
+        Ok(contains(q).then_some((p, q)))
+    };
+    let f = |t: f64| match pair_at(t) {
+        Ok(Some((p, q))) => distance_f64(p, q),
+        _ => f64::INFINITY,
+    };
+
+    // The surface may bend as well, so the curve gets twice the samples of its turns.
+    let n = 2 * sample_count(|t| curve.interpolate(start, end, t))?;
+    let params: Vec<f64> = (0..=n).map(|i| i as f64 / n as f64).collect();
+    let values: Vec<f64> = params.iter().map(|t| f(*t)).collect();
+
+    let mut result: Option<ClosestPoints> = None;
+    for i in 0..=n {
+        let lo = i.saturating_sub(1);
+        let hi = (i + 1).min(n);
+        // Only the first sample of a plateau, e.g. of a line parallel to a plane, is refined.
+        let is_first = i == lo || values[i] < values[lo];
+        if values[i].is_infinite() || !is_first || values[i] > values[hi] {
+            continue;
+        }
+        let t = minimize_1d(f, params[lo], params[hi]);
+        // The refinement may leave the contained part, in which case the sample itself is used.
+        let (p, q) = match pair_at(t)? {
+            Some(pair) => pair,
+            None => pair_at(params[i])?.expect("The sample is contained"),
+        };
+        let closest = ClosestPoints::new(p, q);
+        result = Some(match result {
+            Some(result) => result.min(closest),
+            None => closest,
+        });
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use crate::{
+        curves::{circle::Circle, line::Line},
+        surfaces::{plane::Plane, sphere::Sphere},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_curve_surface_distance() {
+        let sphere = Sphere::new(Point::zero(), EFloat64::from(1.0), true);
+        let line = Line::new(Point::from_f64(0.0, 0.0, 3.0), Point::unit_x()).unwrap();
+        let closest = curve_surface_distance(
+            &line,
+            Some(Point::from_f64(-1.0, 0.0, 3.0)),
+            Some(Point::from_f64(2.0, 0.0, 3.0)),
+            &sphere,
+            &|_| true,
+        )
+        .unwrap()
+        .unwrap();
+        assert!((closest.distance.mid() - 2.0).abs() < 1e-9);
+        assert!(distance_f64(closest.to, Point::unit_z()) < 1e-6);
+
+        // Restricting the sphere to its lower half moves the closest point away.
+        let closest =
+            curve_surface_distance(&line, None, None, &sphere, &|p: Point| p.z <= 0.0).unwrap();
+        assert!(closest.is_none());
+
+        // A circle crossing the plane touches it.
+        let plane = Plane::new(Point::zero(), Point::unit_x(), Point::unit_y());
+        let circle = Circle::new(
+            Point::from_f64(0.0, 0.0, 0.5),
+            Point::unit_x(),
+            EFloat64::from(1.0),
+        );
+        let closest = curve_surface_distance(&circle, None, None, &plane, &|_| true)
+            .unwrap()
+            .unwrap();
+        assert!(closest.distance.mid() < 1e-9);
+    }
+}
@@ -0,0 +1,121 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{geometry_error::GeometryResult, point::Point, tolerance::Tolerance};
+
+pub mod curve_curve;
+pub mod curve_surface;
+pub mod surface_surface;
+
+// Number of samples along a curve, which are the starting points of the minimization. Curves get SAMPLES_PER_TURN samples for every full turn of their direction, but at least MIN_SAMPLES and at most MAX_SAMPLES.
+const MIN_SAMPLES: usize = 32;
+const SAMPLES_PER_TURN: f64 = 32.0;
+const MAX_SAMPLES: usize = 512;
+// Points of the polyline that measures how much a curve turns.
+const TURN_SAMPLES: usize = 64;
+
+// The closest pair of points between two objects, where from lies on the first and to on the second object.
+// The searches sample the objects and refine the closest samples, so the pair is a local minimum of the distance that is the global one unless the samples miss it. distance encloses the distance between from and to, not the global minimum.
+#[derive(Clone, Debug)]
+pub struct ClosestPoints {
+    pub from: Point,
+    pub to: Point,
+    pub distance: EFloat64,
+}
+
+impl ClosestPoints {
+    pub fn new(from: Point, to: Point) -> ClosestPoints {
+        ClosestPoints {
+            from,
+            to,
+            distance: (to - from).norm(),
+        }
+    }
+
+    pub fn flip(&self) -> ClosestPoints {
+        ClosestPoints {
+            from: self.to,
+            to: self.from,
+            distance: self.distance,
+        }
+    }
+
+    // Picks the closer of two pairs. The midpoints of the distances are compared, as the intervals of nearby pairs overlap.
+    pub fn min(self, other: ClosestPoints) -> ClosestPoints {
+        match other.distance.mid() < self.distance.mid() {
+            true => other,
+            false => self,
+        }
+    }
+}
+
+// Number of samples of the curve given by point_at on [0, 1]. A curve that turns more, like a helix with many turns, has more local minima of its distance to other objects.
+pub(crate) fn sample_count(
+    point_at: impl Fn(f64) -> GeometryResult<Point>,
+) -> GeometryResult<usize> {
+    let points = (0..=TURN_SAMPLES)
+        .map(|i| point_at(i as f64 / TURN_SAMPLES as f64))
+        .collect::<GeometryResult<Vec<Point>>>()?;
+    let mut turn = 0.0;
+    for i in 2..points.len() {
+        let (a, b) = (points[i - 1] - points[i - 2], points[i] - points[i - 1]);
+        turn += a.angle(b).map(|angle| angle.mid()).unwrap_or(0.0);
+    }
+    let samples = (turn / (2.0 * std::f64::consts::PI) * SAMPLES_PER_TURN).ceil() as usize;
+    Ok(samples.clamp(MIN_SAMPLES, MAX_SAMPLES))
+}
+
+// Floating point distance between two points, which the minimization works with.
+pub(crate) fn distance_f64(a: Point, b: Point) -> f64 {
+    (b - a).norm().mid()
+}
+
+// Minimizes f on [lo, hi] by golden section search, up to the parametric tolerance. f has to be unimodal in the interval.
+pub(crate) fn minimize_1d(f: impl Fn(f64) -> f64, lo: f64, hi: f64) -> f64 {
+    let ratio = (5.0f64.sqrt() - 1.0) / 2.0;
+    // The interval cannot get smaller than the rounding of its ends.
+    let precision = (Tolerance::current().parametric * (hi - lo))
+        .max(4.0 * f64::EPSILON * lo.abs().max(hi.abs()));
+    let (mut lo, mut hi) = (lo, hi);
+    while hi - lo > precision {
+        let x1 = hi - ratio * (hi - lo);
+        let x2 = lo + ratio * (hi - lo);
+        match f(x1) < f(x2) {
+            true => hi = x2,
+            false => lo = x1,
+        }
+    }
+    (lo + hi) / 2.0
+}
+
+// Minimizes f on the unit square by a pattern search, starting at (s, t) with the given step size, up to the parametric tolerance.
+pub(crate) fn minimize_2d(f: impl Fn(f64, f64) -> f64, s: f64, t: f64, step: f64) -> (f64, f64) {
+    const DIRECTIONS: [(f64, f64); 8] = [
+        (1.0, 0.0),
+        (-1.0, 0.0),
+        (0.0, 1.0),
+        (0.0, -1.0),
+        (1.0, 1.0),
+        (-1.0, -1.0),
+        (1.0, -1.0),
+        (-1.0, 1.0),
+    ];
+    let (mut s, mut t, mut h) = (s, t, step);
+    let mut best = f(s, t);
+    let precision = Tolerance::current().parametric;
+    while h > precision {
+        let mut improved = false;
+        for (ds, dt) in DIRECTIONS {
+            let (ns, nt) = ((s + ds * h).clamp(0.0, 1.0), (t + dt * h).clamp(0.0, 1.0));
+            let value = f(ns, nt);
+            if value < best {
+                (s, t, best) = (ns, nt, value);
+                improved = true;
+                break;
+            }
+        }
+        if !improved {
+            h /= 2.0;
+        }
+    }
+    (s, t)
+}
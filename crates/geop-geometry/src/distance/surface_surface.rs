@@ -0,0 +1,78 @@
+use crate::{point::Point, surfaces::SurfaceLike, tolerance::Tolerance};
+
+use super::{distance_f64, ClosestPoints};
+
+// Maximum number of alternating projections per starting point.
+const ITERATIONS: usize = 256;
+
+// Finds the closest points between the parts of the surfaces for which contains and other_contains are true.
+// Starting from the seeds and a coarse point grid of the first surface, points are alternately projected onto the other surface and back until they settle.
+// Returns None if no settled pair is contained in both, e.g. if the closest points lie on the boundary of a face. These have to be found by the caller.
+pub fn surface_surface_distance(
+    surface: &dyn SurfaceLike,
+    contains: &dyn Fn(Point) -> bool,
+    other: &dyn SurfaceLike,
+    other_contains: &dyn Fn(Point) -> bool,
+    seeds: &[Point],
+) -> Option<ClosestPoints> {
+    // The alternating projections stop once the points move less than the numerical tolerance.
+    let precision = Tolerance::current().numerical;
+    let mut result: Option<ClosestPoints> = None;
+    for seed in seeds.iter().cloned().chain(surface.point_grid(1.0)) {
+        let mut p = surface.project(seed);
+        let mut q = other.project(p);
+        for _ in 0..ITERATIONS {
+            let next_p = surface.project(q);
+            let next_q = other.project(next_p);
+            let moved = distance_f64(p, next_p) + distance_f64(q, next_q);
+            (p, q) = (next_p, next_q);
+            if moved < precision {
+                break;
+            }
+        }
+        if !contains(p) || !other_contains(q) {
+            continue;
+        }
+        let closest = ClosestPoints::new(p, q);
+        result = Some(match result {
+            Some(result) => result.min(closest),
+            None => closest,
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use crate::surfaces::{plane::Plane, sphere::Sphere};
+
+    use super::*;
+
+    #[test]
+    fn test_surface_surface_distance() {
+        let sphere = Sphere::new(Point::zero(), EFloat64::from(1.0), true);
+        let other = Sphere::new(Point::from_f64(4.0, 0.0, 0.0), EFloat64::from(2.0), true);
+        let closest = surface_surface_distance(&sphere, &|_| true, &other, &|_| true, &[]).unwrap();
+        assert!((closest.distance.mid() - 1.0).abs() < 1e-9);
+        assert!(distance_f64(closest.from, Point::unit_x()) < 1e-6);
+        assert!(distance_f64(closest.to, Point::from_f64(2.0, 0.0, 0.0)) < 1e-6);
+
+        let plane = Plane::new(
+            Point::from_f64(0.0, 0.0, -3.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        let closest = surface_surface_distance(
+            &sphere,
+            &|_| true,
+            &plane,
+            &|p: Point| p.x >= 1.0,
+            &[Point::from_f64(2.0, 0.0, -1.0)],
+        );
+        assert!(closest.is_none());
+        let closest = surface_surface_distance(&sphere, &|_| true, &plane, &|_| true, &[]).unwrap();
+        assert!((closest.distance.mid() - 2.0).abs() < 1e-9);
+    }
+}
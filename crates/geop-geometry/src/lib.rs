@@ -6,6 +6,7 @@ pub mod color;
 pub mod curve_curve_intersection;
 pub mod curve_surface_intersection;
 pub mod curves;
+pub mod distance;
 pub mod efloat;
 pub mod geometry_error;
 pub mod geometry_scene;
@@ -13,4 +14,5 @@ pub mod point;
 pub mod roated_bounding_box;
 pub mod surface_surface_intersection;
 pub mod surfaces;
+pub mod tolerance;
 pub mod transforms;
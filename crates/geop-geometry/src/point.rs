@@ -1,11 +1,15 @@
 use std::{
+    cmp::Ordering,
     fmt::Display,
     ops::{Add, Div, Mul, Neg, Sub},
 };
 
-use geop_algebra::efloat::EFloat64;
+use geop_algebra::{efloat::EFloat64, predicates};
 
-use crate::geometry_error::GeometryResult;
+use crate::{
+    geometry_error::{GeometryError, GeometryResult},
+    tolerance::Tolerance,
+};
 
 #[derive(Debug, Copy, Clone)]
 pub struct Point {
@@ -27,6 +31,11 @@ impl Point {
         }
     }
 
+    // Drops the error bounds of the coordinates.
+    pub fn mid(self) -> Point {
+        Point::from_f64(self.x.mid(), self.y.mid(), self.z.mid())
+    }
+
     pub fn norm_sq(self) -> EFloat64 {
         self.x * self.x + self.y * self.y + self.z * self.z
     }
@@ -64,14 +73,29 @@ impl Point {
         ))
     }
 
+    // Sine of the angle between the vectors, or none if one of them might be zero.
+    fn sine(self, other: Point) -> Option<EFloat64> {
+        (self.cross(other).norm() / (self.norm() * other.norm())).ok()
+    }
+
     pub fn is_parallel(self, other: Point) -> bool {
         let cross = self.cross(other);
+        let tolerance = Tolerance::current();
         cross.is_zero()
+            || (tolerance.angular > 0.0
+                && self
+                    .sine(other)
+                    .is_none_or(|sine| tolerance.is_zero_angle(sine)))
     }
 
     pub fn is_perpendicular(self, other: Point) -> bool {
         let dot = self.dot(other);
+        let tolerance = Tolerance::current();
+        // The cosine of the angle is the sine of the deviation from a right angle.
         dot == 0.0
+            || (tolerance.angular > 0.0
+                && (dot / (self.norm() * other.norm()))
+                    .map_or(true, |cosine| tolerance.is_zero_angle(cosine)))
     }
 
     pub fn angle(&self, other: Point) -> GeometryResult<EFloat64> {
@@ -84,7 +108,9 @@ impl Point {
         if dot_norm <= -1.0 {
             return Ok(EFloat64::pi());
         }
-        Ok(dot_norm.acos())
+        dot_norm.acos().ok_or(GeometryError::new(
+            "Cosine is outside of [-1, 1]".to_string(),
+        ))
     }
 
     // Oriented angle between two vectors around a normal vector. Measured from self to other.
@@ -112,6 +138,21 @@ impl Point {
     pub fn unit_z() -> Point {
         Point::new(EFloat64::zero(), EFloat64::zero(), EFloat64::one())
     }
+
+    // Midpoints of the coordinates.
+    fn center(self) -> [f64; 3] {
+        [self.x.mid(), self.y.mid(), self.z.mid()]
+    }
+
+    // Sign of det(a - d, b - d, c - d), which is positive if d is below the plane through a, b, c, when they are counter clockwise seen from above.
+    // If the intervals cannot decide the sign, it is computed exactly for the midpoints, so the same points are always classified the same way.
+    // This is what decides inside and outside in face_point_contains, via ContourTangent::is_inside.
+    pub fn orient3d(a: Point, b: Point, c: Point, d: Point) -> Ordering {
+        let det = (a - d).cross(b - d).dot(c - d);
+        predicates::sign(det, || {
+            predicates::orient3d(a.center(), b.center(), c.center(), d.center())
+        })
+    }
 }
 
 impl Add for Point {
@@ -182,9 +223,12 @@ impl Neg for Point {
     }
 }
 
+// Points are equal if they might be equal within their error bounds. This is deliberately not decided by the exact predicates, see geop_algebra::predicates.
 impl PartialEq for Point {
     fn eq(&self, other: &Point) -> bool {
-        (self.x - other.x) == 0.0 && (self.y - other.y) == 0.0 && (self.z - other.z) == 0.0
+        let tolerance = Tolerance::current();
+        ((self.x - other.x) == 0.0 && (self.y - other.y) == 0.0 && (self.z - other.z) == 0.0)
+            || (tolerance.linear > 0.0 && tolerance.is_zero_length((*self - *other).norm()))
     }
 }
 
@@ -0,0 +1,222 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curve_surface_intersection::line_plane::{line_plane_intersection, LinePlaneIntersection},
+    curves::{curve::Curve, ellipse::Ellipse, line::Line},
+    point::Point,
+    surfaces::{cylinder::Cylinder, plane::Plane, surface::Surface},
+};
+
+use super::{
+    marching::marching_intersection,
+    plane_cylinder::{plane_cylinder_intersection, PlaneCylinderIntersection},
+    surface_surface::FaceSurfaceIntersection,
+};
+
+pub enum CylinderCylinderIntersection {
+    Cylinder(Cylinder),
+    TwoLines(Line, Line),
+    Line(Line),
+    TwoEllipses(Ellipse, Ellipse),
+    // Polylines along a curve of degree 4 and the points where the cylinders only touch.
+    Quartic(Vec<Curve>, Vec<Point>),
+    None,
+}
+
+pub fn cylinder_cylinder_intersection(a: &Cylinder, b: &Cylinder) -> CylinderCylinderIntersection {
+    let r_a = a.radius.norm();
+    let r_b = b.radius.norm();
+    let dir = a.extend_dir;
+
+    if dir.is_parallel(b.extend_dir) {
+        // Parallel axes reduce to a circle-circle intersection in the cross section.
+        let v = b.basis - a.basis;
+        let offset = v - dir * v.dot(dir);
+        let d = offset.norm();
+        if d == 0.0 {
+            if (r_a - r_b) == 0.0 {
+                return CylinderCylinderIntersection::Cylinder(a.clone());
+            }
+            return CylinderCylinderIntersection::None;
+        }
+
+        let z = (offset / d).unwrap();
+        if d == r_a + r_b {
+            return CylinderCylinderIntersection::Line(Line::new(a.basis + z * r_a, dir).unwrap());
+        }
+        if d == (r_a - r_b).abs() {
+            let side = match r_a.upper_bound > r_b.upper_bound {
+                true => z * r_a,
+                false => -z * r_a,
+            };
+            return CylinderCylinderIntersection::Line(Line::new(a.basis + side, dir).unwrap());
+        }
+        if d > (r_a + r_b).upper_bound || d < (r_a - r_b).abs().lower_bound {
+            return CylinderCylinderIntersection::None;
+        }
+
+        let x = ((r_a * r_a - r_b * r_b + d * d) / (EFloat64::two() * d)).unwrap();
+        let y = (r_a * r_a - x * x).sqrt().unwrap();
+        let p = a.basis + z * x;
+        let side = dir.cross(z).normalize().unwrap();
+        return CylinderCylinderIntersection::TwoLines(
+            Line::new(p + side * y, dir).unwrap(),
+            Line::new(p - side * y, dir).unwrap(),
+        );
+    }
+
+    // Distance between the two axes
+    let normal = dir.cross(b.extend_dir).normalize().unwrap();
+    let axis_dist = (b.basis - a.basis).dot(normal).abs();
+    if axis_dist > (r_a + r_b).upper_bound {
+        return CylinderCylinderIntersection::None;
+    }
+
+    if axis_dist == 0.0 && (r_a - r_b) == 0.0 {
+        // Two cylinders with the same radius and intersecting axes intersect in two ellipses. They lie in the planes through the crossing point with normals dir_a + dir_b and dir_a - dir_b.
+        let axis_plane = Plane::new(a.basis, dir, normal);
+        let axis_b = Line::new(b.basis, b.extend_dir).unwrap();
+        let crossing = match line_plane_intersection(&axis_b, &axis_plane) {
+            LinePlaneIntersection::Point(p) => p,
+            _ => panic!("Intersecting axes should have a crossing point"),
+        };
+
+        let ellipse_in_plane = |plane_normal: Point| -> Ellipse {
+            let plane_normal = plane_normal.normalize().unwrap();
+            let u = plane_normal.cross(normal).normalize().unwrap();
+            let plane = Plane::new(crossing, u, normal);
+            match plane_cylinder_intersection(&plane, a) {
+                PlaneCylinderIntersection::Ellipse(e) => e,
+                _ => panic!("Intersection should be an ellipse"),
+            }
+        };
+        return CylinderCylinderIntersection::TwoEllipses(
+            ellipse_in_plane(dir + b.extend_dir),
+            ellipse_in_plane(dir - b.extend_dir),
+        );
+    }
+
+    // In all other cases, the intersection is a curve of degree 4, which is traced numerically.
+    match marching_intersection(&Surface::Cylinder(a.clone()), &Surface::Cylinder(b.clone())) {
+        FaceSurfaceIntersection::CurvesAndPoints(curves, points) => {
+            CylinderCylinderIntersection::Quartic(curves, points)
+        }
+        _ => CylinderCylinderIntersection::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{curves::CurveLike, surfaces::SurfaceLike};
+
+    use super::*;
+
+    #[test]
+    fn test_cylinder_cylinder_intersection_parallel() {
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+
+        // Same cylinder, shifted along the axis
+        let other = Cylinder::new(Point::unit_z(), Point::unit_z(), EFloat64::one(), true);
+        match cylinder_cylinder_intersection(&cylinder, &other) {
+            CylinderCylinderIntersection::Cylinder(_) => {}
+            _ => panic!("Intersection should be a cylinder"),
+        }
+
+        // Coaxial with a different radius
+        let other = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::two(), true);
+        match cylinder_cylinder_intersection(&cylinder, &other) {
+            CylinderCylinderIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+
+        // Parallel axes with distance 1
+        let other = Cylinder::new(Point::unit_x(), Point::unit_z(), EFloat64::one(), true);
+        match cylinder_cylinder_intersection(&cylinder, &other) {
+            CylinderCylinderIntersection::TwoLines(l1, l2) => {
+                assert!(cylinder.on_surface(l1.basis));
+                assert!(other.on_surface(l1.basis));
+                assert!(cylinder.on_surface(l2.basis));
+                assert!(other.on_surface(l2.basis));
+            }
+            _ => panic!("Intersection should be two lines"),
+        }
+
+        // Touching cylinders
+        let other = Cylinder::new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_z(),
+            EFloat64::one(),
+            true,
+        );
+        match cylinder_cylinder_intersection(&cylinder, &other) {
+            CylinderCylinderIntersection::Line(l) => assert!(l.on_curve(Point::unit_x())),
+            _ => panic!("Intersection should be a line"),
+        }
+    }
+
+    // Checks that the cylinders meet in the given number of closed curves.
+    fn assert_quartic(a: &Cylinder, b: &Cylinder, count: usize) {
+        match cylinder_cylinder_intersection(a, b) {
+            CylinderCylinderIntersection::Quartic(curves, points) => {
+                assert!(points.is_empty());
+                assert_eq!(curves.len(), count);
+                for curve in curves {
+                    let Curve::Nurbs(curve) = curve else {
+                        panic!("Expected a nurbs curve");
+                    };
+                    assert_eq!(curve.control_points.first(), curve.control_points.last());
+                    for p in curve.control_points.iter() {
+                        assert!((a.project(*p) - *p).norm().upper_bound < 1e-6);
+                        assert!((b.project(*p) - *p).norm().upper_bound < 1e-6);
+                    }
+                }
+            }
+            _ => panic!("Intersection should be a curve of degree 4"),
+        }
+    }
+
+    #[test]
+    fn test_cylinder_cylinder_intersection_perpendicular() {
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+        let other = Cylinder::new(Point::zero(), Point::unit_x(), EFloat64::one(), true);
+        match cylinder_cylinder_intersection(&cylinder, &other) {
+            CylinderCylinderIntersection::TwoEllipses(e1, e2) => {
+                for e in [e1, e2] {
+                    assert_eq!(e.basis, Point::zero());
+                    assert!(e.on_curve(Point::unit_y()));
+                    assert!(e.on_curve(Point::from_f64(0.0, -1.0, 0.0)));
+                }
+            }
+            _ => panic!("Intersection should be two ellipses"),
+        }
+
+        // A thinner pipe pierces through both sides of the cylinder.
+        let branch = Cylinder::new(Point::zero(), Point::unit_x(), EFloat64::from(0.5), true);
+        assert_quartic(&cylinder, &branch, 2);
+
+        // Skew axes, still through both sides.
+        let branch = Cylinder::new(
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::unit_y(),
+            EFloat64::from(0.4),
+            true,
+        );
+        assert_quartic(&cylinder, &branch, 2);
+
+        // Skew axes with the pipe only biting into the side of the cylinder gives a single loop.
+        let branch = Cylinder::new(Point::unit_x(), Point::unit_y(), EFloat64::from(0.5), true);
+        assert_quartic(&cylinder, &branch, 1);
+
+        // Skew axes far apart
+        let other = Cylinder::new(
+            Point::from_f64(5.0, 0.0, 5.0),
+            Point::unit_y(),
+            EFloat64::one(),
+            true,
+        );
+        match cylinder_cylinder_intersection(&cylinder, &other) {
+            CylinderCylinderIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+    }
+}
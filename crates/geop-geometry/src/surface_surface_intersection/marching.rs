@@ -0,0 +1,269 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{curve::Curve, nurbs::Nurbs},
+    point::Point,
+    surfaces::{surface::Surface, SurfaceLike},
+    tolerance::Tolerance,
+    HORIZON_DIST,
+};
+
+use super::surface_surface::FaceSurfaceIntersection;
+
+// The grid points of both surfaces at this density are used as starting points for tracing.
+const SEED_DENSITY: f64 = 1.0;
+// Intersection curves are traced with steps of at most their size divided by this.
+const STEP_RESOLUTION: f64 = 64.0;
+// Maximal angle in radians by which the direction of a curve may turn within one step.
+const MAX_TURN: f64 = 0.1;
+const MAX_STEPS: usize = 4096;
+const REFINEMENT_ITERATIONS: usize = 32;
+// Near a point where the surfaces touch, the distance to them grows quadratically, so the refined points and their normals are only accurate to about the square root of the precision.
+// Normals closer than this are considered parallel, and touching points closer than this are merged.
+const TOUCHING: f64 = 3e-4;
+
+// The closest point of p on the surface and the normal there. None where the closest point is not unique, e.g. at the center of a sphere.
+fn foot(surface: &Surface, p: Point) -> Option<(Point, Point)> {
+    let q = surface.project(p);
+    match surface.on_surface(q) {
+        true => Some((q, surface.normal(q))),
+        false => None,
+    }
+}
+
+// Moves p onto both surfaces. Each iteration goes to the closest point of p on the intersection line of the tangent planes at the projections of p, which converges quadratically where the surfaces cross.
+// Fails if no common point is found near p.
+fn refine(surface: &Surface, other: &Surface, p: Point) -> Option<Point> {
+    let precision = Tolerance::current().numerical;
+    let mut p = p.mid();
+    for _ in 0..REFINEMENT_ITERATIONS {
+        let ((q1, n1), (q2, n2)) = (foot(surface, p)?, foot(other, p)?);
+        let (d1, d2) = (n1.dot(q1 - p), n2.dot(q2 - p));
+        let c = n1.dot(n2);
+        let det = EFloat64::one() - c * c;
+        // Where the surfaces touch, the tangent planes do not meet in a line and the projections are alternated instead.
+        let next = match det.mid() > precision {
+            true => {
+                let a = EFloat64::from((d1 - c * d2).mid() / det.mid());
+                let b = EFloat64::from((d2 - c * d1).mid() / det.mid());
+                p + n1 * a + n2 * b
+            }
+            false => surface.project(q2),
+        };
+        let next = next.mid();
+        let moved = (next - p).norm().mid();
+        p = next;
+        if !moved.is_finite() {
+            return None;
+        }
+        if moved <= precision / 100.0 {
+            break;
+        }
+    }
+    match (surface.project(p) - p).norm().mid() <= precision
+        && (other.project(p) - p).norm().mid() <= precision
+    {
+        true => Some(p),
+        false => None,
+    }
+}
+
+// Direction of the intersection curve, which is perpendicular to both normals. None where the surfaces touch.
+fn direction(surface: &Surface, other: &Surface, p: Point) -> Option<Point> {
+    let ((_, n1), (_, n2)) = (foot(surface, p)?, foot(other, p)?);
+    let d = n1.cross(n2);
+    match d.norm().mid() > TOUCHING {
+        true => d.normalize().ok(),
+        false => None,
+    }
+}
+
+// Follows the intersection curve from start. Returns the points and whether the curve is closed.
+// The step is halved where the curve turns quickly or the refinement fails, and grows back to max_step afterwards.
+// Curves that do not close are cut where they leave the horizon.
+fn march(
+    surface: &Surface,
+    other: &Surface,
+    start: Point,
+    forward: bool,
+    max_step: f64,
+) -> (Vec<Point>, bool) {
+    let precision = Tolerance::current().numerical;
+    let mut points = vec![start];
+    let mut dir = match direction(surface, other, start) {
+        Some(dir) if forward => dir,
+        Some(dir) => -dir,
+        None => return (points, false),
+    };
+    let mut current = start;
+    let mut h = max_step;
+    for i in 0..MAX_STEPS {
+        let mut next = None;
+        while h > precision {
+            let guess = current + dir * EFloat64::from(h);
+            let candidate = refine(surface, other, guess).and_then(|p| {
+                let d = direction(surface, other, p)?;
+                let d = match d.dot(dir) < 0.0 {
+                    true => -d,
+                    false => d,
+                };
+                match (p - current).dot(dir).mid() > precision && d.dot(dir).mid() >= MAX_TURN.cos()
+                {
+                    true => Some((p, d)),
+                    false => None,
+                }
+            });
+            if candidate.is_some() {
+                next = candidate;
+                break;
+            }
+            h /= 2.0;
+        }
+        let Some((next, next_dir)) = next else {
+            return (points, false);
+        };
+        if i > 1 && (next - start).norm().mid() < h {
+            points.push(start);
+            return (points, true);
+        }
+        points.push(next);
+        if next.norm().mid() > HORIZON_DIST {
+            return (points, false);
+        }
+        dir = next_dir;
+        current = next;
+        h = (2.0 * h).min(max_step);
+    }
+    (points, false)
+}
+
+// Traces the intersection of two surfaces numerically. This is the fallback for pairs without an analytic solution.
+// Curves are returned as polylines and points where the surfaces only touch as points.
+pub fn marching_intersection(surface: &Surface, other: &Surface) -> FaceSurfaceIntersection {
+    let precision = Tolerance::current().numerical;
+    let grid = surface.point_grid(SEED_DENSITY);
+    if grid.iter().all(|p| other.on_surface(*p)) {
+        return FaceSurfaceIntersection::Surface(surface.clone());
+    }
+
+    let mut seeds: Vec<Point> = Vec::new();
+    for p in grid.into_iter().chain(other.point_grid(SEED_DENSITY)) {
+        if let Some(seed) = refine(surface, other, p) {
+            if !seeds.iter().any(|s| (*s - seed).norm().mid() <= precision) {
+                seeds.push(seed);
+            }
+        }
+    }
+
+    // The seeds spread over the whole intersection, so their distances give its size.
+    let size = seeds
+        .iter()
+        .flat_map(|p| seeds.iter().map(move |q| (*p - *q).norm().mid()))
+        .fold(0.0, f64::max)
+        .min(HORIZON_DIST);
+    let max_step = size.max(precision) / STEP_RESOLUTION;
+    let mut traced: Vec<Point> = Vec::new();
+    let mut curves = Vec::new();
+    let mut points = Vec::new();
+    for seed in seeds {
+        if traced.iter().any(|p| (*p - seed).norm().mid() < max_step) {
+            continue;
+        }
+        let (mut polyline, closed) = march(surface, other, seed, true, max_step);
+        if !closed {
+            let (backward, _) = march(surface, other, seed, false, max_step);
+            polyline = backward.into_iter().skip(1).rev().chain(polyline).collect();
+        }
+        traced.extend(polyline.iter().cloned());
+        match polyline.len() {
+            1 if points
+                .iter()
+                .any(|p: &Point| (*p - seed).norm().mid() <= TOUCHING) => {}
+            1 => points.push(seed),
+            _ => curves.push(Curve::Nurbs(Nurbs::polyline(polyline).unwrap())),
+        }
+    }
+    match curves.is_empty() && points.is_empty() {
+        true => FaceSurfaceIntersection::None,
+        false => FaceSurfaceIntersection::CurvesAndPoints(curves, points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::{cylinder::Cylinder, sphere::Sphere, torus::Torus};
+
+    #[test]
+    fn test_marching_sphere_torus() {
+        // The sphere through the spine of the torus meets the tube in two circles at the heights +-sqrt(15) / 4.
+        let torus = Torus::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(2.0),
+            EFloat64::from(1.0),
+            true,
+        );
+        let sphere = Sphere::new(Point::zero(), EFloat64::from(2.0), true);
+        let FaceSurfaceIntersection::CurvesAndPoints(curves, points) = marching_intersection(
+            &Surface::Sphere(sphere.clone()),
+            &Surface::Torus(torus.clone()),
+        ) else {
+            panic!("Expected curves");
+        };
+        assert!(points.is_empty());
+        assert_eq!(curves.len(), 2);
+        let height = 15.0f64.sqrt() / 4.0;
+        for curve in curves {
+            let Curve::Nurbs(curve) = curve else {
+                panic!("Expected a nurbs curve");
+            };
+            assert_eq!(curve.control_points.first(), curve.control_points.last());
+            for p in curve.control_points.iter() {
+                assert!((sphere.project(*p) - *p).norm().upper_bound < 1e-6);
+                assert!((torus.project(*p) - *p).norm().upper_bound < 1e-6);
+                assert!((p.z.mid().abs() - height).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_marching_special_cases() {
+        let cylinder = Surface::Cylinder(Cylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::one(),
+            true,
+        ));
+        assert!(matches!(
+            marching_intersection(&cylinder, &cylinder),
+            FaceSurfaceIntersection::Surface(_)
+        ));
+
+        let sphere = Surface::Sphere(Sphere::new(Point::zero(), EFloat64::one(), true));
+        let far = Surface::Sphere(Sphere::new(
+            Point::from_f64(3.0, 0.0, 0.0),
+            EFloat64::one(),
+            true,
+        ));
+        assert!(matches!(
+            marching_intersection(&sphere, &far),
+            FaceSurfaceIntersection::None
+        ));
+
+        // Spheres that touch from outside meet in a single point.
+        let touching = Surface::Sphere(Sphere::new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            EFloat64::one(),
+            true,
+        ));
+        let FaceSurfaceIntersection::CurvesAndPoints(curves, points) =
+            marching_intersection(&sphere, &touching)
+        else {
+            panic!("Expected a point");
+        };
+        assert!(curves.is_empty());
+        assert_eq!(points.len(), 1);
+        assert!((points[0] - Point::unit_x()).norm().upper_bound < 1e-4);
+    }
+}
@@ -1,3 +1,11 @@
+pub mod cylinder_cylinder;
+pub mod marching;
+pub mod nurbs_surface;
+pub mod plane_cone;
+pub mod plane_cylinder;
 pub mod plane_plane;
 pub mod plane_sphere;
+pub mod plane_torus;
+pub mod sphere_cylinder;
+pub mod sphere_sphere;
 pub mod surface_surface;
@@ -0,0 +1,245 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{curve::Curve, nurbs::Nurbs},
+    point::Point,
+    surfaces::{nurbs_surface::NurbsSurface, SurfaceLike},
+    tolerance::Tolerance,
+};
+
+// Patches smaller than the size of the surface divided by this are used as starting points for tracing.
+const SEED_RESOLUTION: f64 = 16.0;
+// Intersection curves are traced with steps of the size of the surface divided by this.
+const STEP_RESOLUTION: f64 = 64.0;
+const MAX_STEPS: usize = 4096;
+const REFINEMENT_ITERATIONS: usize = 32;
+
+// A point on the nurbs surface together with its parameters.
+type SurfacePoint = (Point, (f64, f64));
+
+// Moves p onto both surfaces by projecting back and forth. Fails if no common point is found near p.
+fn refine(
+    nurbs: &NurbsSurface,
+    other: &dyn SurfaceLike,
+    p: Point,
+    uv: (f64, f64),
+) -> Option<SurfacePoint> {
+    let precision = Tolerance::current().numerical;
+    let (mut u, mut v) = uv;
+    let mut p = p;
+    for _ in 0..REFINEMENT_ITERATIONS {
+        (u, v) = nurbs.parameter_near(other.project(p), u, v);
+        let next = nurbs.point_at(EFloat64::from(u), EFloat64::from(v));
+        let moved = (next - p).norm().mid();
+        p = next;
+        if moved <= precision / 100.0 {
+            break;
+        }
+    }
+    match (other.project(p) - p).norm().mid() <= precision {
+        true => Some((p, (u, v))),
+        false => None,
+    }
+}
+
+// Direction of the intersection curve, which is perpendicular to both normals.
+fn direction(nurbs: &NurbsSurface, other: &dyn SurfaceLike, p: SurfacePoint) -> Option<Point> {
+    let (u, v) = p.1;
+    let normal = nurbs.normal_at(EFloat64::from(u), EFloat64::from(v));
+    normal.cross(other.normal(p.0)).normalize().ok()
+}
+
+// Collects points on the intersection by subdividing the nurbs surface until the patches are small.
+fn find_seeds(
+    nurbs: &NurbsSurface,
+    other: &dyn SurfaceLike,
+    u: (f64, f64),
+    v: (f64, f64),
+    min_radius: f64,
+    seeds: &mut Vec<SurfacePoint>,
+) {
+    let precision = Tolerance::current().numerical;
+    let bounding_box = nurbs
+        .subsurface(
+            EFloat64::from(u.0),
+            EFloat64::from(u.1),
+            EFloat64::from(v.0),
+            EFloat64::from(v.1),
+        )
+        .get_bounding_box();
+    let center = (bounding_box.min + bounding_box.max) * EFloat64::from(0.5);
+    let radius = (bounding_box.max - bounding_box.min).norm().mid() / 2.0;
+    if (other.project(center) - center).norm().mid() > radius + precision {
+        return;
+    }
+
+    if radius > min_radius {
+        let u_mid = (u.0 + u.1) / 2.0;
+        let v_mid = (v.0 + v.1) / 2.0;
+        for u in [(u.0, u_mid), (u_mid, u.1)] {
+            for v in [(v.0, v_mid), (v_mid, v.1)] {
+                find_seeds(nurbs, other, u, v, min_radius, seeds);
+            }
+        }
+        return;
+    }
+
+    let uv = ((u.0 + u.1) / 2.0, (v.0 + v.1) / 2.0);
+    let p = nurbs.point_at(EFloat64::from(uv.0), EFloat64::from(uv.1));
+    if let Some(seed) = refine(nurbs, other, p, uv) {
+        seeds.push(seed);
+    }
+}
+
+// Follows the intersection curve from start. Returns the points and whether the curve is closed.
+// At the boundary of the nurbs surface the step size is halved until the boundary is reached.
+fn march(
+    nurbs: &NurbsSurface,
+    other: &dyn SurfaceLike,
+    start: SurfacePoint,
+    forward: bool,
+    step: f64,
+) -> (Vec<Point>, bool) {
+    let precision = Tolerance::current().numerical;
+    let mut points = vec![start.0];
+    let mut dir = match direction(nurbs, other, start) {
+        Some(dir) if forward => dir,
+        Some(dir) => -dir,
+        None => return (points, false),
+    };
+    let mut current = start;
+    for i in 0..MAX_STEPS {
+        let mut h = step;
+        let mut next = None;
+        while h > precision {
+            let guess = current.0 + dir * EFloat64::from(h);
+            if let Some(p) = refine(nurbs, other, guess, current.1) {
+                if (p.0 - current.0).dot(dir) > precision {
+                    next = Some(p);
+                    break;
+                }
+            }
+            h /= 2.0;
+        }
+        let Some(next) = next else {
+            return (points, false);
+        };
+        if i > 1 && (next.0 - start.0).norm().mid() < step {
+            points.push(start.0);
+            return (points, true);
+        }
+        points.push(next.0);
+        dir = match direction(nurbs, other, next) {
+            Some(d) if d.dot(dir) < 0.0 => -d,
+            Some(d) => d,
+            None => return (points, false),
+        };
+        current = next;
+    }
+    (points, false)
+}
+
+// Traces the intersection curves of a nurbs surface with any other surface. The curves are returned as polylines.
+pub fn nurbs_surface_intersection(nurbs: &NurbsSurface, other: &dyn SurfaceLike) -> Vec<Curve> {
+    let size = nurbs.get_bounding_box().max_size().mid();
+    let (u_start, u_end) = nurbs.domain_u();
+    let (v_start, v_end) = nurbs.domain_v();
+    let mut seeds = Vec::new();
+    find_seeds(
+        nurbs,
+        other,
+        (u_start.mid(), u_end.mid()),
+        (v_start.mid(), v_end.mid()),
+        size / SEED_RESOLUTION,
+        &mut seeds,
+    );
+
+    let step = size / STEP_RESOLUTION;
+    let mut traced: Vec<Point> = Vec::new();
+    let mut curves = Vec::new();
+    for seed in seeds {
+        if traced.iter().any(|p| (*p - seed.0).norm().mid() < step) {
+            continue;
+        }
+        let (mut points, closed) = march(nurbs, other, seed, true, step);
+        if !closed {
+            let (backward, _) = march(nurbs, other, seed, false, step);
+            points = backward.into_iter().skip(1).rev().chain(points).collect();
+        }
+        traced.extend(points.iter().cloned());
+        if points.len() >= 2 {
+            curves.push(Curve::Nurbs(Nurbs::polyline(points).unwrap()));
+        }
+    }
+    curves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curves::CurveLike,
+        surfaces::{plane::Plane, sphere::Sphere},
+    };
+
+    fn flat_patch() -> NurbsSurface {
+        NurbsSurface::new_uniform_clamped(
+            2,
+            2,
+            (0..3)
+                .map(|i| {
+                    (0..3)
+                        .map(|j| Point::from_f64(i as f64, j as f64, 0.0))
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nurbs_plane_intersection() {
+        let surface = flat_patch();
+        let plane = Plane::new(
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        );
+        let curves = nurbs_surface_intersection(&surface, &plane);
+        assert_eq!(curves.len(), 1);
+        let Curve::Nurbs(curve) = &curves[0] else {
+            panic!("Expected a nurbs curve");
+        };
+        let distance = |p: Point, q: Point| (p - q).norm().upper_bound;
+        for p in curve.control_points.iter() {
+            assert!(distance(plane.project(*p), *p) < 1e-6);
+            assert!(distance(surface.project(*p), *p) < 1e-6);
+        }
+        let start = curve.interpolate(None, None, 0.0).unwrap();
+        let end = curve.interpolate(None, None, 1.0).unwrap();
+        let (a, b) = (
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::from_f64(1.0, 2.0, 0.0),
+        );
+        assert!(
+            (distance(start, a) < 1e-6 && distance(end, b) < 1e-6)
+                || (distance(start, b) < 1e-6 && distance(end, a) < 1e-6)
+        );
+    }
+
+    #[test]
+    fn test_nurbs_sphere_intersection() {
+        let surface = flat_patch();
+        let sphere = Sphere::new(Point::from_f64(1.0, 1.0, 0.0), EFloat64::from(0.5), true);
+        let curves = nurbs_surface_intersection(&surface, &sphere);
+        assert_eq!(curves.len(), 1);
+        let Curve::Nurbs(curve) = &curves[0] else {
+            panic!("Expected a nurbs curve");
+        };
+        // The intersection is a closed circle.
+        assert_eq!(curve.control_points.first(), curve.control_points.last());
+        for p in curve.control_points.iter() {
+            assert!((sphere.project(*p) - *p).norm().upper_bound < 1e-6);
+        }
+    }
+}
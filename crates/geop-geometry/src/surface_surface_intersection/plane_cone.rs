@@ -0,0 +1,210 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{circle::Circle, curve::Curve, ellipse::Ellipse, line::Line},
+    point::Point,
+    surfaces::{cone::Cone, plane::Plane, surface::Surface, SurfaceLike},
+};
+
+use super::{marching::marching_intersection, surface_surface::FaceSurfaceIntersection};
+
+pub enum PlaneConeIntersection {
+    TwoLines(Line, Line),
+    Line(Line),
+    Circle(Circle),
+    Ellipse(Ellipse),
+    Point(Point),
+    // Parabolas and hyperbolas are open, they are traced up to the horizon.
+    Unbounded(Vec<Curve>),
+    None,
+}
+
+// Planes through the apex contain the generators with direction g = d cos + u sin, where u is a unit vector orthogonal to the axis d and g . n = 0.
+fn generators(plane: &Plane, cone: &Cone, n: Point) -> PlaneConeIntersection {
+    let d = cone.extend_dir;
+    let n_d = n.dot(d);
+    let n_perp = n - d * n_d;
+    let nu = n_perp.norm();
+    if nu == 0.0 {
+        return PlaneConeIntersection::Point(cone.basis);
+    }
+    let n_perp = (n_perp / nu).unwrap();
+    let tan = (cone.half_angle.sin() / cone.half_angle.cos()).unwrap();
+    // The component of u along the normal is fixed by g . n = 0, the other one is free.
+    let lambda = (-n_d / (nu * tan)).unwrap();
+    let kappa_sq = EFloat64::one() - lambda * lambda;
+    if kappa_sq < 0.0 {
+        return PlaneConeIntersection::Point(cone.basis);
+    }
+    let side = d.cross(n_perp);
+    let generator = |kappa: EFloat64| {
+        let u = n_perp * lambda + side * kappa;
+        let g = d * cone.half_angle.cos() + u * cone.half_angle.sin();
+        Line::new(plane.project(cone.basis), g.normalize().unwrap()).unwrap()
+    };
+    if kappa_sq == 0.0 {
+        return PlaneConeIntersection::Line(generator(EFloat64::zero()));
+    }
+    let kappa = kappa_sq.sqrt().unwrap();
+    PlaneConeIntersection::TwoLines(generator(kappa), generator(-kappa))
+}
+
+// The sections of a cone are conics. Closed ones are found analytically, parabolas and hyperbolas are traced.
+pub fn plane_cone_intersection(plane: &Plane, cone: &Cone) -> PlaneConeIntersection {
+    let n = plane.normal(plane.basis).normalize().unwrap();
+    let d = cone.extend_dir;
+    if (cone.basis - plane.basis).dot(n) == 0.0 {
+        return generators(plane, cone, n);
+    }
+
+    // The plane is steeper than the generators exactly if the section is closed. Otherwise it is unbounded.
+    let c = n.dot(d).abs();
+    if c - cone.half_angle.sin() <= 0.0 {
+        let curves = match marching_intersection(
+            &Surface::Plane(plane.clone()),
+            &Surface::Cone(cone.clone()),
+        ) {
+            FaceSurfaceIntersection::CurvesAndPoints(curves, _) => curves,
+            _ => vec![],
+        };
+        return match curves.is_empty() {
+            true => PlaneConeIntersection::None,
+            false => PlaneConeIntersection::Unbounded(curves),
+        };
+    }
+
+    // A closed section encloses the point where the axis hits the plane, so it is on the nappe if this point is.
+    let t = ((plane.basis - cone.basis).dot(n) / n.dot(d)).unwrap();
+    if t <= 0.0 {
+        return PlaneConeIntersection::None;
+    }
+    let tan = (cone.half_angle.sin() / cone.half_angle.cos()).unwrap();
+    let hit = cone.basis + d * t;
+    if n.is_parallel(d) {
+        return PlaneConeIntersection::Circle(Circle::new(hit, n, t * tan));
+    }
+
+    // The major axis goes along the steepest direction m in the plane. With mu = m . d, the point hit + m s is on the cone if |s| c = tan (t + s mu).
+    let m = (d - n * n.dot(d)).normalize().unwrap();
+    let mu = m.dot(d);
+    let s1 = ((tan * t) / (c - tan * mu)).unwrap();
+    let s2 = -((tan * t) / (c + tan * mu)).unwrap();
+    let center = hit + m * ((s1 + s2) * EFloat64::from(0.5));
+    let major_radius = (s1 - s2) * EFloat64::from(0.5);
+    // The minor axis is orthogonal to the axis of the cone, so its half length follows from the radius of the cone at the height of the center.
+    let height = (center - cone.basis).dot(d);
+    let radial = (center - cone.basis - d * height).norm();
+    let minor_radius = ((height * tan).square() - radial.square()).sqrt().unwrap();
+    PlaneConeIntersection::Ellipse(Ellipse::new(
+        center,
+        n,
+        m * major_radius,
+        n.cross(m) * minor_radius,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::CurveLike;
+
+    // Cone with apex at the origin, opening in z direction with an angle of 45 degrees.
+    fn cone() -> Cone {
+        Cone::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(std::f64::consts::FRAC_PI_4),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_plane_cone_closed_sections() {
+        let plane = Plane::new(Point::unit_z(), Point::unit_x(), Point::unit_y());
+        match plane_cone_intersection(&plane, &cone()) {
+            PlaneConeIntersection::Circle(circle) => {
+                assert_eq!(circle.basis, Point::unit_z());
+                assert_eq!(circle.radius.norm(), 1.0);
+            }
+            _ => panic!("Intersection should be a circle"),
+        }
+
+        // Below the apex there is only the other nappe.
+        let plane = Plane::new(-Point::unit_z(), Point::unit_x(), Point::unit_y());
+        assert!(matches!(
+            plane_cone_intersection(&plane, &cone()),
+            PlaneConeIntersection::None
+        ));
+
+        // The plane z = 2 + x / 2 cuts the cone at x = 4 and x = -4 / 3.
+        let plane = Plane::new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::from_f64(2.0, 0.0, 1.0),
+            Point::unit_y(),
+        );
+        match plane_cone_intersection(&plane, &cone()) {
+            PlaneConeIntersection::Ellipse(ellipse) => {
+                assert!(ellipse.on_curve(Point::from_f64(4.0, 0.0, 4.0)));
+                assert!(ellipse.on_curve(Point::from_f64(-4.0 / 3.0, 0.0, 4.0 / 3.0)));
+                for i in 0..16 {
+                    let p = ellipse.interpolate(None, None, i as f64 / 16.0).unwrap();
+                    assert!((cone().project(p) - p).norm() < 1e-9);
+                    assert!(plane.on_surface(p));
+                }
+            }
+            _ => panic!("Intersection should be an ellipse"),
+        }
+    }
+
+    #[test]
+    fn test_plane_cone_through_apex() {
+        // The plane y = 0 contains the generators x = +-z.
+        let plane = Plane::new(Point::zero(), Point::unit_x(), Point::unit_z());
+        match plane_cone_intersection(&plane, &cone()) {
+            PlaneConeIntersection::TwoLines(l1, l2) => {
+                for p in [
+                    Point::from_f64(1.0, 0.0, 1.0),
+                    Point::from_f64(-1.0, 0.0, 1.0),
+                ] {
+                    assert!(l1.on_curve(p) || l2.on_curve(p));
+                }
+            }
+            _ => panic!("Intersection should be two lines"),
+        }
+
+        // The plane z = x touches the cone along a generator.
+        let plane = Plane::new(
+            Point::zero(),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::unit_y(),
+        );
+        match plane_cone_intersection(&plane, &cone()) {
+            PlaneConeIntersection::Line(l) => assert!(l.on_curve(Point::from_f64(2.0, 0.0, 2.0))),
+            _ => panic!("Intersection should be a line"),
+        }
+
+        let plane = Plane::new(Point::zero(), Point::unit_x(), Point::unit_y());
+        assert!(matches!(
+            plane_cone_intersection(&plane, &cone()),
+            PlaneConeIntersection::Point(_)
+        ));
+    }
+
+    #[test]
+    fn test_plane_cone_unbounded_sections() {
+        // The plane x = 1 is parallel to the axis and cuts out one branch of the hyperbola z^2 - y^2 = 1.
+        let plane = Plane::new(Point::unit_x(), Point::unit_y(), Point::unit_z());
+        let PlaneConeIntersection::Unbounded(curves) = plane_cone_intersection(&plane, &cone())
+        else {
+            panic!("Intersection should be unbounded");
+        };
+        assert_eq!(curves.len(), 1);
+        let Curve::Nurbs(curve) = &curves[0] else {
+            panic!("Expected a polyline");
+        };
+        for p in curve.control_points.iter() {
+            assert!((p.x - EFloat64::one()).abs() < 1e-6);
+            assert!((p.z.square() - p.y.square() - EFloat64::one()).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    curves::{circle::Circle, ellipse::Ellipse, line::Line},
+    surfaces::{cylinder::Cylinder, plane::Plane, SurfaceLike},
+};
+
+pub enum PlaneCylinderIntersection {
+    TwoLines(Line, Line),
+    Line(Line),
+    Circle(Circle),
+    Ellipse(Ellipse),
+    None,
+}
+
+pub fn plane_cylinder_intersection(a: &Plane, b: &Cylinder) -> PlaneCylinderIntersection {
+    let n = a.normal(a.basis).normalize().unwrap();
+    let dir = b.extend_dir;
+    let r = b.radius.norm();
+
+    if n.is_perpendicular(dir) {
+        // The plane is parallel to the axis of the cylinder
+        let h = (b.basis - a.basis).dot(n);
+        let foot = b.basis - n * h;
+        if h == r || h == -r {
+            return PlaneCylinderIntersection::Line(Line::new(foot, dir).unwrap());
+        }
+        if h > r.upper_bound || h < -r.upper_bound {
+            return PlaneCylinderIntersection::None;
+        }
+        let w = (r * r - h * h).sqrt().unwrap();
+        let side = dir.cross(n).normalize().unwrap();
+        return PlaneCylinderIntersection::TwoLines(
+            Line::new(foot + side * w, dir).unwrap(),
+            Line::new(foot - side * w, dir).unwrap(),
+        );
+    }
+
+    // The axis of the cylinder hits the plane in the center of the intersection
+    let cos = dir.dot(n);
+    let t = ((a.basis - b.basis).dot(n) / cos).unwrap();
+    let center = b.basis + dir * t;
+    if n.is_parallel(dir) {
+        return PlaneCylinderIntersection::Circle(Circle::new(center, n, r));
+    }
+
+    // The minor axis is perpendicular to the axis of the cylinder, the major axis is stretched by 1 / cos.
+    let minor_dir = dir.cross(n).normalize().unwrap();
+    let major_dir = n.cross(minor_dir).normalize().unwrap();
+    let major_radius = (r / cos.abs()).unwrap();
+    PlaneCylinderIntersection::Ellipse(Ellipse::new(
+        center,
+        n,
+        major_dir * major_radius,
+        minor_dir * r,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use crate::{curves::CurveLike, point::Point};
+
+    use super::*;
+
+    #[test]
+    fn test_plane_cylinder_intersection() {
+        // Cylinder of radius 1 around the z axis
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+
+        // Plane perpendicular to the axis
+        let plane = Plane::new(Point::unit_z(), Point::unit_x(), Point::unit_y());
+        match plane_cylinder_intersection(&plane, &cylinder) {
+            PlaneCylinderIntersection::Circle(circle) => {
+                assert_eq!(circle.basis, Point::unit_z());
+                assert_eq!(circle.radius.norm(), 1.0);
+            }
+            _ => panic!("Intersection should be a circle"),
+        }
+
+        // Plane containing the axis
+        let plane = Plane::new(Point::zero(), Point::unit_x(), Point::unit_z());
+        match plane_cylinder_intersection(&plane, &cylinder) {
+            PlaneCylinderIntersection::TwoLines(l1, l2) => {
+                assert!(l1.on_curve(Point::unit_x()) || l2.on_curve(Point::unit_x()));
+                assert!(
+                    l1.on_curve(Point::from_f64(-1.0, 0.0, 0.0))
+                        || l2.on_curve(Point::from_f64(-1.0, 0.0, 0.0))
+                );
+            }
+            _ => panic!("Intersection should be two lines"),
+        }
+
+        // Plane touching the cylinder
+        let plane = Plane::new(Point::unit_y(), Point::unit_x(), Point::unit_z());
+        match plane_cylinder_intersection(&plane, &cylinder) {
+            PlaneCylinderIntersection::Line(l) => assert!(l.on_curve(Point::unit_y())),
+            _ => panic!("Intersection should be a line"),
+        }
+
+        // Plane away from the cylinder
+        let plane = Plane::new(
+            Point::from_f64(0.0, 2.0, 0.0),
+            Point::unit_x(),
+            Point::unit_z(),
+        );
+        match plane_cylinder_intersection(&plane, &cylinder) {
+            PlaneCylinderIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+
+        // Oblique plane at 45 degrees
+        let plane = Plane::new(
+            Point::zero(),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::unit_y(),
+        );
+        match plane_cylinder_intersection(&plane, &cylinder) {
+            PlaneCylinderIntersection::Ellipse(ellipse) => {
+                assert_eq!(ellipse.basis, Point::zero());
+                assert_eq!(ellipse.major_radius.norm(), EFloat64::two().sqrt().unwrap());
+                assert_eq!(ellipse.minor_radius.norm(), 1.0);
+                assert!(ellipse.on_curve(Point::from_f64(1.0, 0.0, 1.0)));
+                assert!(ellipse.on_curve(Point::unit_y()));
+            }
+            _ => panic!("Intersection should be an ellipse"),
+        }
+    }
+}
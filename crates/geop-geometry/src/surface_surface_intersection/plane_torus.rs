@@ -0,0 +1,159 @@
+use crate::{
+    curves::{circle::Circle, curve::Curve},
+    point::Point,
+    surfaces::{plane::Plane, surface::Surface, torus::Torus, SurfaceLike},
+};
+
+use super::{marching::marching_intersection, surface_surface::FaceSurfaceIntersection};
+
+pub enum PlaneTorusIntersection {
+    Circles(Vec<Circle>),
+    // Planes that are neither perpendicular to the axis nor contain it cut out quartic curves, which are traced.
+    Quartic(Vec<Curve>, Vec<Point>),
+    None,
+}
+
+pub fn plane_torus_intersection(plane: &Plane, torus: &Torus) -> PlaneTorusIntersection {
+    let n = plane.normal(plane.basis).normalize().unwrap();
+    let height = (plane.basis - torus.basis).dot(torus.normal);
+
+    // Perpendicular to the axis, the plane cuts the tube in its outer and inner circle.
+    if n.is_parallel(torus.normal) {
+        let center = torus.basis + torus.normal * height;
+        let r = torus.minor_radius;
+        if height == r || height == -r {
+            return PlaneTorusIntersection::Circles(vec![Circle::new(
+                center,
+                n,
+                torus.major_radius,
+            )]);
+        }
+        if height.abs() - r > 0.0 {
+            return PlaneTorusIntersection::None;
+        }
+        let w = (r.square() - height.square()).sqrt().unwrap();
+        let mut circles = vec![Circle::new(center, n, torus.major_radius + w)];
+        // The inner circle vanishes if the tube reaches the axis there.
+        if torus.major_radius - w > 0.0 {
+            circles.push(Circle::new(center, n, torus.major_radius - w));
+        }
+        return PlaneTorusIntersection::Circles(circles);
+    }
+
+    // A plane containing the axis cuts the tube in two circles of the minor radius.
+    if n.is_perpendicular(torus.normal) && (plane.basis - torus.basis).dot(n) == 0.0 {
+        let radial = torus.normal.cross(n);
+        return PlaneTorusIntersection::Circles(vec![
+            Circle::new(
+                torus.basis + radial * torus.major_radius,
+                n,
+                torus.minor_radius,
+            ),
+            Circle::new(
+                torus.basis - radial * torus.major_radius,
+                n,
+                torus.minor_radius,
+            ),
+        ]);
+    }
+
+    match marching_intersection(
+        &Surface::Plane(plane.clone()),
+        &Surface::Torus(torus.clone()),
+    ) {
+        FaceSurfaceIntersection::CurvesAndPoints(curves, points) => {
+            PlaneTorusIntersection::Quartic(curves, points)
+        }
+        _ => PlaneTorusIntersection::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use super::*;
+    use crate::curves::CurveLike;
+
+    fn torus() -> Torus {
+        Torus::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::two(),
+            EFloat64::one(),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_plane_torus_perpendicular() {
+        let plane = Plane::new(Point::zero(), Point::unit_x(), Point::unit_y());
+        let PlaneTorusIntersection::Circles(circles) = plane_torus_intersection(&plane, &torus())
+        else {
+            panic!("Intersection should be circles");
+        };
+        assert_eq!(circles.len(), 2);
+        assert!(circles.iter().any(|c| c.radius.norm() == 3.0));
+        assert!(circles.iter().any(|c| c.radius.norm() == 1.0));
+
+        // The plane touches the top of the tube.
+        let plane = Plane::new(Point::unit_z(), Point::unit_x(), Point::unit_y());
+        let PlaneTorusIntersection::Circles(circles) = plane_torus_intersection(&plane, &torus())
+        else {
+            panic!("Intersection should be a circle");
+        };
+        assert_eq!(circles.len(), 1);
+        assert!(circles[0].on_curve(Point::from_f64(0.0, 2.0, 1.0)));
+
+        let plane = Plane::new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        assert!(matches!(
+            plane_torus_intersection(&plane, &torus()),
+            PlaneTorusIntersection::None
+        ));
+    }
+
+    #[test]
+    fn test_plane_torus_through_axis() {
+        let plane = Plane::new(Point::zero(), Point::unit_x(), Point::unit_z());
+        let PlaneTorusIntersection::Circles(circles) = plane_torus_intersection(&plane, &torus())
+        else {
+            panic!("Intersection should be circles");
+        };
+        assert_eq!(circles.len(), 2);
+        for p in [
+            Point::from_f64(3.0, 0.0, 0.0),
+            Point::from_f64(-1.0, 0.0, 0.0),
+            Point::from_f64(2.0, 0.0, 1.0),
+        ] {
+            assert!(circles.iter().any(|c| c.on_curve(p)));
+        }
+    }
+
+    #[test]
+    fn test_plane_torus_quartic() {
+        // The plane x = 2.5 only cuts the outer half of the tube, in a single closed curve.
+        let plane = Plane::new(
+            Point::from_f64(2.5, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        );
+        let PlaneTorusIntersection::Quartic(curves, _) = plane_torus_intersection(&plane, &torus())
+        else {
+            panic!("Intersection should be quartic");
+        };
+        assert_eq!(curves.len(), 1);
+        for curve in curves {
+            let Curve::Nurbs(curve) = curve else {
+                panic!("Expected a polyline");
+            };
+            for p in curve.control_points.iter() {
+                assert!((p.x - EFloat64::from(2.5)).abs() < 1e-6);
+                assert!((torus().project(*p) - *p).norm() < 1e-6);
+            }
+        }
+    }
+}
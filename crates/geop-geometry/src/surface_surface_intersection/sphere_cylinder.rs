@@ -0,0 +1,136 @@
+use crate::{
+    curves::{circle::Circle, curve::Curve},
+    point::Point,
+    surfaces::{cylinder::Cylinder, sphere::Sphere, surface::Surface},
+};
+
+use super::{marching::marching_intersection, surface_surface::FaceSurfaceIntersection};
+
+pub enum SphereCylinderIntersection {
+    TwoCircles(Circle, Circle),
+    Circle(Circle),
+    // Polylines along a curve of degree 4 and the points where the surfaces only touch.
+    Quartic(Vec<Curve>, Vec<Point>),
+    None,
+}
+
+pub fn sphere_cylinder_intersection(a: &Sphere, b: &Cylinder) -> SphereCylinderIntersection {
+    let dir = b.extend_dir;
+    let r_sphere = a.radius;
+    let r_cylinder = b.radius.norm();
+
+    let v = a.basis - b.basis;
+    let height = v.dot(dir);
+    let offset = v - dir * height;
+    let dist = offset.norm();
+
+    if dist > (r_sphere + r_cylinder).upper_bound
+        || (dist + r_sphere).upper_bound < r_cylinder.lower_bound
+    {
+        return SphereCylinderIntersection::None;
+    }
+
+    if dist != 0.0 {
+        // If the sphere is not centered on the axis, the intersection is a curve of degree 4, which is traced numerically.
+        return match marching_intersection(
+            &Surface::Sphere(a.clone()),
+            &Surface::Cylinder(b.clone()),
+        ) {
+            FaceSurfaceIntersection::CurvesAndPoints(curves, points) => {
+                SphereCylinderIntersection::Quartic(curves, points)
+            }
+            _ => SphereCylinderIntersection::None,
+        };
+    }
+
+    let center = b.basis + dir * height;
+    if r_sphere == r_cylinder {
+        return SphereCylinderIntersection::Circle(Circle::new(center, dir, r_cylinder));
+    }
+    if r_sphere < r_cylinder.lower_bound {
+        return SphereCylinderIntersection::None;
+    }
+    let h = (r_sphere * r_sphere - r_cylinder * r_cylinder)
+        .sqrt()
+        .unwrap();
+    SphereCylinderIntersection::TwoCircles(
+        Circle::new(center + dir * h, dir, r_cylinder),
+        Circle::new(center - dir * h, dir, r_cylinder),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use crate::surfaces::SurfaceLike;
+
+    use super::*;
+
+    #[test]
+    fn test_sphere_cylinder_intersection() {
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+
+        // Sphere with radius 2 on the axis cuts the cylinder in two circles at height +-sqrt(3)
+        let sphere = Sphere::new(Point::unit_z(), EFloat64::two(), true);
+        match sphere_cylinder_intersection(&sphere, &cylinder) {
+            SphereCylinderIntersection::TwoCircles(c1, c2) => {
+                let h = EFloat64::from(3.0).sqrt().unwrap();
+                assert_eq!(c1.basis, Point::unit_z() + Point::unit_z() * h);
+                assert_eq!(c2.basis, Point::unit_z() - Point::unit_z() * h);
+                assert_eq!(c1.radius.norm(), 1.0);
+            }
+            _ => panic!("Intersection should be two circles"),
+        }
+
+        // Sphere touching the cylinder from the inside
+        let sphere = Sphere::new(Point::zero(), EFloat64::one(), true);
+        match sphere_cylinder_intersection(&sphere, &cylinder) {
+            SphereCylinderIntersection::Circle(c) => assert_eq!(c.basis, Point::zero()),
+            _ => panic!("Intersection should be a circle"),
+        }
+
+        // Small sphere inside of the cylinder
+        let sphere = Sphere::new(Point::zero(), EFloat64::from(0.5), true);
+        match sphere_cylinder_intersection(&sphere, &cylinder) {
+            SphereCylinderIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+
+        // Sphere centered on the wall of the cylinder, which cuts it in a single loop through (1, 0, +-1).
+        let sphere = Sphere::new(Point::unit_x(), EFloat64::one(), true);
+        match sphere_cylinder_intersection(&sphere, &cylinder) {
+            SphereCylinderIntersection::Quartic(curves, points) => {
+                assert!(points.is_empty());
+                assert_eq!(curves.len(), 1);
+                let Curve::Nurbs(curve) = &curves[0] else {
+                    panic!("Expected a nurbs curve");
+                };
+                assert_eq!(curve.control_points.first(), curve.control_points.last());
+                for p in curve.control_points.iter() {
+                    assert!((sphere.project(*p) - *p).norm().upper_bound < 1e-6);
+                    assert!((cylinder.project(*p) - *p).norm().upper_bound < 1e-6);
+                }
+            }
+            _ => panic!("Intersection should be a curve of degree 4"),
+        }
+
+        // Sphere touching the cylinder from the outside
+        let sphere = Sphere::new(Point::from_f64(2.0, 0.0, 0.0), EFloat64::one(), true);
+        match sphere_cylinder_intersection(&sphere, &cylinder) {
+            SphereCylinderIntersection::Quartic(curves, points) => {
+                assert!(curves.is_empty());
+                assert_eq!(points.len(), 1);
+                assert!((points[0] - Point::unit_x()).norm().upper_bound < 1e-4);
+            }
+            _ => panic!("Intersection should be a point"),
+        }
+
+        // Sphere far away from the cylinder
+        let sphere = Sphere::new(Point::from_f64(5.0, 0.0, 0.0), EFloat64::one(), true);
+        match sphere_cylinder_intersection(&sphere, &cylinder) {
+            SphereCylinderIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+    }
+}
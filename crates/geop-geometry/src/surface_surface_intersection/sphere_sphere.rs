@@ -1,4 +1,6 @@
-use crate::geometry::{curves::circle::Circle, point::Point, surfaces::sphere::Sphere};
+use geop_algebra::efloat::EFloat64;
+
+use crate::{curves::circle::Circle, point::Point, surfaces::sphere::Sphere};
 
 pub enum SphereSphereIntersection {
     Sphere(Sphere),
@@ -8,27 +10,82 @@ pub enum SphereSphereIntersection {
 }
 
 pub fn sphere_sphere_intersection(a: &Sphere, b: &Sphere) -> SphereSphereIntersection {
-    let r_a: f64 = a.radius;
-    let r_b: f64 = b.radius;
-    let a: Point = a.basis;
-    let b: Point = b.basis;
+    let r_a = a.radius;
+    let r_b = b.radius;
+    let diff = b.basis - a.basis;
+    let d = diff.norm();
 
-    let d = (a - b).norm();
-    if d > r_a + r_b {
+    if d == 0.0 {
+        if (r_a - r_b) == 0.0 {
+            return SphereSphereIntersection::Sphere(a.clone());
+        }
         return SphereSphereIntersection::None;
-    } else if d < (r_a - r_b).abs() {
+    }
+
+    let z = (diff / d).unwrap();
+    if d == r_a + r_b {
+        return SphereSphereIntersection::Point(a.basis + z * r_a);
+    }
+    if d == (r_a - r_b).abs() {
+        // Internally tangent, the point lies on the side of the bigger sphere.
+        if r_a.upper_bound > r_b.upper_bound {
+            return SphereSphereIntersection::Point(a.basis + z * r_a);
+        }
+        return SphereSphereIntersection::Point(a.basis - z * r_a);
+    }
+    if d > (r_a + r_b).upper_bound || d < (r_a - r_b).abs().lower_bound {
         return SphereSphereIntersection::None;
-    } else if d <= 0.0 && (r_a - r_b).abs() <= 0.0 {
-        return SphereSphereIntersection::Sphere(Sphere::new(a, r_a.min(r_b)));
-    } else {
-        let x = (r_a.powi(2) - r_b.powi(2) + d.powi(2)) / (2.0 * d);
-        let y = (r_a.powi(2) - x.powi(2)).sqrt();
-        let z = (b - a) / d;
-        let p = a + z * x;
-        let n = z.cross(Point::from_f64(0.0, 0.0, 1.0)).cross(z);
-        if y <= 0.0 {
-            return SphereSphereIntersection::Point(p);
+    }
+
+    // see https://mathworld.wolfram.com/Sphere-SphereIntersection.html
+    let x = ((r_a * r_a - r_b * r_b + d * d) / (EFloat64::two() * d)).unwrap();
+    let y = (r_a * r_a - x * x).sqrt().unwrap();
+    SphereSphereIntersection::Circle(Circle::new(a.basis + z * x, z, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sphere_sphere_intersection() {
+        let sphere = Sphere::new(Point::zero(), EFloat64::one(), true);
+
+        // Same sphere
+        match sphere_sphere_intersection(&sphere, &sphere) {
+            SphereSphereIntersection::Sphere(s) => assert_eq!(s, sphere),
+            _ => panic!("Intersection should be a sphere"),
+        }
+
+        // Overlapping spheres intersect in a circle in the plane x = 0.5
+        let other = Sphere::new(Point::unit_x(), EFloat64::one(), true);
+        match sphere_sphere_intersection(&sphere, &other) {
+            SphereSphereIntersection::Circle(circle) => {
+                assert_eq!(circle.basis, Point::from_f64(0.5, 0.0, 0.0));
+                assert_eq!(circle.radius.norm(), EFloat64::from(0.75).sqrt().unwrap());
+            }
+            _ => panic!("Intersection should be a circle"),
+        }
+
+        // Touching spheres
+        let other = Sphere::new(Point::from_f64(2.0, 0.0, 0.0), EFloat64::one(), true);
+        match sphere_sphere_intersection(&sphere, &other) {
+            SphereSphereIntersection::Point(p) => assert_eq!(p, Point::unit_x()),
+            _ => panic!("Intersection should be a point"),
+        }
+
+        // Separate spheres
+        let other = Sphere::new(Point::from_f64(3.0, 0.0, 0.0), EFloat64::one(), true);
+        match sphere_sphere_intersection(&sphere, &other) {
+            SphereSphereIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+
+        // Sphere inside of the other sphere
+        let other = Sphere::new(Point::from_f64(0.2, 0.0, 0.0), EFloat64::from(0.5), true);
+        match sphere_sphere_intersection(&sphere, &other) {
+            SphereSphereIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
         }
-        return SphereSphereIntersection::Circle(Circle::new(p, n, y));
     }
 }
@@ -1,6 +1,29 @@
-use crate::{curves::curve::Curve, point::Point, surfaces::surface::Surface};
+use geop_algebra::efloat::EFloat64;
 
-use super::plane_plane::{plane_plane_intersection, PlanePlaneIntersection};
+use crate::{
+    curves::{curve::Curve, CurveLike},
+    geometry_error::GeometryResult,
+    point::Point,
+    surfaces::{
+        cone::Cone, cylinder::Cylinder, ellipsoid::Ellipsoid, elliptic_cylinder::EllipticCylinder,
+        nurbs_surface::NurbsSurface, plane::Plane, sphere::Sphere, surface::Surface, torus::Torus,
+        SurfaceLike,
+    },
+    transforms::Transform,
+};
+
+use super::{
+    cylinder_cylinder::{cylinder_cylinder_intersection, CylinderCylinderIntersection},
+    marching::marching_intersection,
+    nurbs_surface::nurbs_surface_intersection,
+    plane_cone::{plane_cone_intersection, PlaneConeIntersection},
+    plane_cylinder::{plane_cylinder_intersection, PlaneCylinderIntersection},
+    plane_plane::{plane_plane_intersection, PlanePlaneIntersection},
+    plane_sphere::{plane_sphere_intersection, PlaneSphereIntersection},
+    plane_torus::{plane_torus_intersection, PlaneTorusIntersection},
+    sphere_cylinder::{sphere_cylinder_intersection, SphereCylinderIntersection},
+    sphere_sphere::{sphere_sphere_intersection, SphereSphereIntersection},
+};
 
 pub enum FaceSurfaceIntersection {
     None,
@@ -8,6 +31,163 @@ pub enum FaceSurfaceIntersection {
     Surface(Surface),
 }
 
+fn plane_sphere(plane: &Plane, sphere: &Sphere) -> FaceSurfaceIntersection {
+    match plane_sphere_intersection(sphere, plane) {
+        PlaneSphereIntersection::None => FaceSurfaceIntersection::None,
+        PlaneSphereIntersection::Point(p) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![], vec![p])
+        }
+        PlaneSphereIntersection::Circle(c) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(c)], vec![])
+        }
+    }
+}
+
+fn plane_cylinder(plane: &Plane, cylinder: &Cylinder) -> FaceSurfaceIntersection {
+    match plane_cylinder_intersection(plane, cylinder) {
+        PlaneCylinderIntersection::None => FaceSurfaceIntersection::None,
+        PlaneCylinderIntersection::Line(l) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
+        }
+        PlaneCylinderIntersection::TwoLines(l1, l2) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l1), Curve::Line(l2)], vec![])
+        }
+        PlaneCylinderIntersection::Circle(c) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(c)], vec![])
+        }
+        PlaneCylinderIntersection::Ellipse(e) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Ellipse(e)], vec![])
+        }
+    }
+}
+
+fn plane_cone(plane: &Plane, cone: &Cone) -> FaceSurfaceIntersection {
+    match plane_cone_intersection(plane, cone) {
+        PlaneConeIntersection::None => FaceSurfaceIntersection::None,
+        PlaneConeIntersection::Point(p) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![], vec![p])
+        }
+        PlaneConeIntersection::Line(l) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
+        }
+        PlaneConeIntersection::TwoLines(l1, l2) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l1), Curve::Line(l2)], vec![])
+        }
+        PlaneConeIntersection::Circle(c) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(c)], vec![])
+        }
+        PlaneConeIntersection::Ellipse(e) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Ellipse(e)], vec![])
+        }
+        PlaneConeIntersection::Unbounded(curves) => {
+            FaceSurfaceIntersection::CurvesAndPoints(curves, vec![])
+        }
+    }
+}
+
+fn plane_torus(plane: &Plane, torus: &Torus) -> FaceSurfaceIntersection {
+    match plane_torus_intersection(plane, torus) {
+        PlaneTorusIntersection::None => FaceSurfaceIntersection::None,
+        PlaneTorusIntersection::Circles(circles) => FaceSurfaceIntersection::CurvesAndPoints(
+            circles.into_iter().map(Curve::Circle).collect(),
+            vec![],
+        ),
+        PlaneTorusIntersection::Quartic(curves, points) => {
+            FaceSurfaceIntersection::CurvesAndPoints(curves, points)
+        }
+    }
+}
+
+fn sphere_cylinder(sphere: &Sphere, cylinder: &Cylinder) -> FaceSurfaceIntersection {
+    match sphere_cylinder_intersection(sphere, cylinder) {
+        SphereCylinderIntersection::None => FaceSurfaceIntersection::None,
+        SphereCylinderIntersection::Circle(c) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(c)], vec![])
+        }
+        SphereCylinderIntersection::TwoCircles(c1, c2) => FaceSurfaceIntersection::CurvesAndPoints(
+            vec![Curve::Circle(c1), Curve::Circle(c2)],
+            vec![],
+        ),
+        SphereCylinderIntersection::Quartic(curves, points) => {
+            FaceSurfaceIntersection::CurvesAndPoints(curves, points)
+        }
+    }
+}
+
+fn nurbs_surface(nurbs: &NurbsSurface, other: &Surface) -> FaceSurfaceIntersection {
+    let curves = nurbs_surface_intersection(nurbs, other);
+    if curves.is_empty() {
+        return FaceSurfaceIntersection::None;
+    }
+    FaceSurfaceIntersection::CurvesAndPoints(curves, vec![])
+}
+
+// Intersects the other surface with the one that is mapped onto the original by frame, by mapping it back and the intersection forward again.
+// Gives None if the other surface is still an ellipsoid or an elliptic cylinder after mapping it back, as there is no closed form for these either.
+fn through_frame(
+    frame: Transform,
+    surface: &Surface,
+    other: &Surface,
+) -> GeometryResult<Option<FaceSurfaceIntersection>> {
+    let inverse = frame.inverse()?;
+    let local = other.transform(inverse);
+    if matches!(local, Surface::Ellipsoid(_) | Surface::EllipticCylinder(_)) {
+        return Ok(None);
+    }
+    Ok(Some(match surface_surface_intersection(surface, &local) {
+        FaceSurfaceIntersection::None => FaceSurfaceIntersection::None,
+        FaceSurfaceIntersection::CurvesAndPoints(curves, points) => {
+            FaceSurfaceIntersection::CurvesAndPoints(
+                curves.iter().map(|c| c.transform(frame)).collect(),
+                points.into_iter().map(|p| frame * p).collect(),
+            )
+        }
+        FaceSurfaceIntersection::Surface(surface) => {
+            FaceSurfaceIntersection::Surface(surface.transform(frame))
+        }
+    }))
+}
+
+// The ellipsoid is the image of the unit sphere.
+fn ellipsoid_surface(
+    ellipsoid: &Ellipsoid,
+    other: &Surface,
+) -> GeometryResult<Option<FaceSurfaceIntersection>> {
+    let sphere = Sphere::new(Point::zero(), EFloat64::one(), ellipsoid.normal_outwards);
+    through_frame(ellipsoid.frame(), &Surface::Sphere(sphere), other)
+}
+
+// The elliptic cylinder is the image of the cylinder of radius one around the z axis.
+fn elliptic_cylinder_surface(
+    cylinder: &EllipticCylinder,
+    other: &Surface,
+) -> GeometryResult<Option<FaceSurfaceIntersection>> {
+    let unit = Cylinder::new(
+        Point::zero(),
+        Point::unit_z(),
+        EFloat64::one(),
+        cylinder.normal_outwards,
+    );
+    through_frame(cylinder.frame(), &Surface::Cylinder(unit), other)
+}
+
+// Tries the frame of an ellipsoid or elliptic cylinder among the two surfaces, and marches if neither frame leads to a closed form.
+fn quadric_surface(surface: &Surface, other: &Surface) -> FaceSurfaceIntersection {
+    let through = |quadric: &Surface, other: &Surface| match quadric {
+        Surface::Ellipsoid(ellipsoid) => ellipsoid_surface(ellipsoid, other),
+        Surface::EllipticCylinder(cylinder) => elliptic_cylinder_surface(cylinder, other),
+        _ => Ok(None),
+    };
+    // A frame that cannot be inverted leaves the marcher, which works on the surfaces as they are.
+    if let Ok(Some(intersection)) = through(surface, other) {
+        return intersection;
+    }
+    if let Ok(Some(intersection)) = through(other, surface) {
+        return intersection;
+    }
+    marching_intersection(surface, other)
+}
+
 pub fn surface_surface_intersection(
     face_self: &Surface,
     face_other: &Surface,
@@ -25,34 +205,120 @@ pub fn surface_surface_intersection(
                     }
                 }
             }
-            Surface::Sphere(_sphere_other) => {
-                todo!("Plane-Sphere intersection")
-            }
-            Surface::Cylinder(_cylinder_other) => {
-                todo!("Plane-Cylinder intersection")
+            Surface::Sphere(sphere_other) => plane_sphere(plane_self, sphere_other),
+            Surface::Cylinder(cylinder_other) => plane_cylinder(plane_self, cylinder_other),
+            Surface::Ellipsoid(_) | Surface::EllipticCylinder(_) => {
+                quadric_surface(face_self, face_other)
             }
+            Surface::Cone(cone_other) => plane_cone(plane_self, cone_other),
+            Surface::Torus(torus_other) => plane_torus(plane_self, torus_other),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
         },
-        Surface::Sphere(_sphere_self) => match face_other {
-            Surface::Plane(_plane_other) => {
-                todo!("Sphere-Plane intersection")
-            }
-            Surface::Sphere(_sphere_other) => {
-                todo!("Sphere-Sphere intersection")
+        Surface::Sphere(sphere_self) => match face_other {
+            Surface::Plane(plane_other) => plane_sphere(plane_other, sphere_self),
+            Surface::Sphere(sphere_other) => {
+                match sphere_sphere_intersection(sphere_self, sphere_other) {
+                    SphereSphereIntersection::None => FaceSurfaceIntersection::None,
+                    SphereSphereIntersection::Point(p) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(vec![], vec![p])
+                    }
+                    SphereSphereIntersection::Circle(c) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(c)], vec![])
+                    }
+                    SphereSphereIntersection::Sphere(s) => {
+                        FaceSurfaceIntersection::Surface(Surface::Sphere(s))
+                    }
+                }
             }
-            Surface::Cylinder(_cylinder_other) => {
-                todo!("Sphere-Cylinder intersection")
+            Surface::Cylinder(cylinder_other) => sphere_cylinder(sphere_self, cylinder_other),
+            Surface::Ellipsoid(_) | Surface::EllipticCylinder(_) => {
+                quadric_surface(face_self, face_other)
             }
+            Surface::Cone(_) | Surface::Torus(_) => marching_intersection(face_self, face_other),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
         },
-        Surface::Cylinder(_cylinder_self) => match face_other {
-            Surface::Plane(_plane_other) => {
-                todo!("Cylinder-Plane intersection")
-            }
-            Surface::Sphere(_sphere_other) => {
-                todo!("Cylinder-Sphere intersection")
+        Surface::Cylinder(cylinder_self) => match face_other {
+            Surface::Plane(plane_other) => plane_cylinder(plane_other, cylinder_self),
+            Surface::Sphere(sphere_other) => sphere_cylinder(sphere_other, cylinder_self),
+            Surface::Cylinder(cylinder_other) => {
+                match cylinder_cylinder_intersection(cylinder_self, cylinder_other) {
+                    CylinderCylinderIntersection::None => FaceSurfaceIntersection::None,
+                    CylinderCylinderIntersection::Line(l) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
+                    }
+                    CylinderCylinderIntersection::TwoLines(l1, l2) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(
+                            vec![Curve::Line(l1), Curve::Line(l2)],
+                            vec![],
+                        )
+                    }
+                    CylinderCylinderIntersection::TwoEllipses(e1, e2) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(
+                            vec![Curve::Ellipse(e1), Curve::Ellipse(e2)],
+                            vec![],
+                        )
+                    }
+                    CylinderCylinderIntersection::Quartic(curves, points) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(curves, points)
+                    }
+                    CylinderCylinderIntersection::Cylinder(c) => {
+                        FaceSurfaceIntersection::Surface(Surface::Cylinder(c))
+                    }
+                }
             }
-            Surface::Cylinder(_cylinder_other) => {
-                todo!("Cylinder-Cylinder intersection")
+            Surface::Ellipsoid(_) | Surface::EllipticCylinder(_) => {
+                quadric_surface(face_self, face_other)
             }
+            Surface::Cone(_) | Surface::Torus(_) => marching_intersection(face_self, face_other),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
+        },
+        Surface::Ellipsoid(_) | Surface::EllipticCylinder(_) => match face_other {
+            Surface::Plane(_)
+            | Surface::Sphere(_)
+            | Surface::Cylinder(_)
+            | Surface::Ellipsoid(_)
+            | Surface::EllipticCylinder(_) => quadric_surface(face_self, face_other),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
+            _ => marching_intersection(face_self, face_other),
+        },
+        Surface::Cone(cone_self) => match face_other {
+            Surface::Plane(plane_other) => plane_cone(plane_other, cone_self),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
+            _ => marching_intersection(face_self, face_other),
         },
+        Surface::Torus(torus_self) => match face_other {
+            Surface::Plane(plane_other) => plane_torus(plane_other, torus_self),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
+            _ => marching_intersection(face_self, face_other),
+        },
+        Surface::Nurbs(nurbs_self) => nurbs_surface(nurbs_self, face_other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ellipsoid_ellipsoid_through_frame() {
+        // Both ellipsoids are unit spheres in the frame of the first, which meet in a circle at half the distance.
+        let radii = [
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        ];
+        let first = Surface::Ellipsoid(Ellipsoid::new(Point::zero(), radii, true));
+        let second =
+            Surface::Ellipsoid(Ellipsoid::new(Point::from_f64(2.0, 0.0, 0.0), radii, true));
+        match surface_surface_intersection(&first, &second) {
+            FaceSurfaceIntersection::CurvesAndPoints(curves, points) => {
+                assert!(points.is_empty());
+                assert_eq!(curves.len(), 1);
+                let p = curves[0].get_midpoint(None, None).unwrap();
+                assert!(p.x == 1.0);
+                assert!(first.on_surface(p) && second.on_surface(p));
+            }
+            _ => panic!("Expected a curve"),
+        }
     }
 }
@@ -0,0 +1,465 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{circle::Circle, curve::Curve, line::Line, nurbs::Nurbs},
+    point::Point,
+    tolerance::Tolerance,
+    transforms::Transform,
+    HORIZON_DIST,
+};
+
+use super::{
+    nurbs_surface::NurbsSurface,
+    parameter_space::SurfaceParameterSpace,
+    shape_operator::ShapeOperator,
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// Geodesics that are not lines are approximated by polylines with this many segments.
+const GEODESIC_SEGMENTS: usize = 32;
+
+// One nappe of a circular cone. It starts at the apex (basis) and opens in extend_dir.
+// The angle between the axis and the surface is half_angle.
+#[derive(Clone, Debug)]
+pub struct Cone {
+    pub basis: Point,
+    pub extend_dir: Point,
+    pub half_angle: EFloat64,
+    pub normal_outwards: bool,
+    ref_dir: Point,
+}
+
+impl Cone {
+    pub fn new(
+        basis: Point,
+        extend_dir: Point,
+        half_angle: EFloat64,
+        normal_outwards: bool,
+    ) -> Cone {
+        assert!(half_angle > 0.0);
+        assert!(half_angle < std::f64::consts::FRAC_PI_2);
+        let extend_dir = extend_dir.normalize().unwrap();
+        let ref_dir = match Point::unit_x().cross(extend_dir).norm_sq()
+            > Point::unit_y().cross(extend_dir).norm_sq().lower_bound
+        {
+            true => Point::unit_x().cross(extend_dir).normalize().unwrap(),
+            false => Point::unit_y().cross(extend_dir).normalize().unwrap(),
+        };
+        Cone {
+            basis,
+            extend_dir,
+            half_angle,
+            normal_outwards,
+            ref_dir,
+        }
+    }
+
+    // The section at height one along the axis is a circle of radius tan(half_angle). The image is a circular cone again if this circle stays a circle orthogonal to the axis.
+    // Otherwise the image is an elliptic or oblique cone, which is represented by the image of the nurbs up to the horizon.
+    fn transform(&self, transform: Transform) -> Surface {
+        let basis = transform * self.basis;
+        let image = |v: Point| transform * (v + self.basis) - basis;
+        let extend_dir = image(self.extend_dir);
+        let u = image(self.ref_dir);
+        let v = image(self.extend_dir.cross(self.ref_dir));
+        if !(u.dot(v) == 0.0
+            && u.norm() == v.norm()
+            && extend_dir.dot(u) == 0.0
+            && extend_dir.dot(v) == 0.0)
+        {
+            let original = self.to_nurbs();
+            let nurbs = original.transform(transform);
+            let (u, v) = (nurbs.domain_u().0, nurbs.domain_v().1);
+            // The image of the axis is inside the image of the cone.
+            let axis = transform
+                * (self.basis
+                    + self.extend_dir
+                        * (original.point_at(u, v) - self.basis).dot(self.extend_dir));
+            let outwards = nurbs.normal_at(u, v).dot(nurbs.point_at(u, v) - axis) > 0.0;
+            let surface = Surface::Nurbs(nurbs);
+            return match outwards == self.normal_outwards {
+                true => surface,
+                false => surface.neg(),
+            };
+        }
+        let tan = (self.half_angle.sin() / self.half_angle.cos()).unwrap();
+        let half_angle = ((tan * u.norm()) / extend_dir.norm()).unwrap().atan();
+        Surface::Cone(Cone::new(
+            basis,
+            extend_dir,
+            half_angle,
+            self.normal_outwards,
+        ))
+    }
+
+    // Exact representation of the cone up to the horizon as the generators between the apex and the circle there. Its normal points outwards.
+    pub fn to_nurbs(&self) -> NurbsSurface {
+        let around = Nurbs::from_curve(
+            &Curve::Circle(Circle::new(Point::zero(), Point::unit_z(), EFloat64::one())),
+            None,
+            None,
+        )
+        .unwrap();
+        let dir_cross = self.extend_dir.cross(self.ref_dir);
+        let height = self.half_angle.cos() * EFloat64::from(HORIZON_DIST);
+        let radius = self.half_angle.sin() * EFloat64::from(HORIZON_DIST);
+        let control_points = around
+            .control_points
+            .iter()
+            .map(|q| {
+                let radial = self.ref_dir * q.x + dir_cross * q.y;
+                vec![
+                    self.basis,
+                    self.basis + self.extend_dir * height + radial * radius,
+                ]
+            })
+            .collect();
+        let weights = around.weights.iter().map(|w| vec![*w, *w]).collect();
+        let knots = vec![
+            EFloat64::zero(),
+            EFloat64::zero(),
+            EFloat64::one(),
+            EFloat64::one(),
+        ];
+        NurbsSurface::new(2, 1, around.knots, knots, weights, control_points).unwrap()
+    }
+
+    fn neg(&self) -> Self {
+        Cone::new(
+            self.basis,
+            self.extend_dir,
+            self.half_angle,
+            !self.normal_outwards,
+        )
+    }
+
+    // Returns the height along the axis, the distance to the axis and the direction from the axis to p.
+    fn cylindrical(&self, p: Point) -> (EFloat64, EFloat64, Point) {
+        let p = p - self.basis;
+        let height = p.dot(self.extend_dir);
+        let radial = p - height * self.extend_dir;
+        let radius = radial.norm();
+        if radius <= 0.0 {
+            return (height, radius, self.ref_dir);
+        }
+        (height, radius, (radial / radius).unwrap())
+    }
+
+    // Unit vector along the surface, pointing away from the apex.
+    fn generator(&self, radial_dir: Point) -> Point {
+        self.extend_dir * self.half_angle.cos() + radial_dir * self.half_angle.sin()
+    }
+
+    // Point at distance s from the apex on the generator in radial_dir.
+    fn point_on_generator(&self, radial_dir: Point, s: EFloat64) -> Point {
+        self.basis + self.generator(radial_dir) * s
+    }
+
+    // The cone is developable. Unrolled, a point at distance s from the apex and angle theta around the axis lands at polar coordinates (s, theta * sin(half_angle)).
+    // This returns the distances of x and y from the apex and the unrolled angle between them.
+    fn unroll(&self, x: Point, y: Point) -> (EFloat64, EFloat64, EFloat64) {
+        let (_, _, x_dir) = self.cylindrical(x);
+        let (_, _, y_dir) = self.cylindrical(y);
+        let theta = x_dir.angle2(y_dir, self.extend_dir).unwrap();
+        (
+            (x - self.basis).norm(),
+            (y - self.basis).norm(),
+            theta * self.half_angle.sin(),
+        )
+    }
+}
+
+impl SurfaceLike for Cone {
+    fn transform(&self, transform: Transform) -> Surface {
+        self.transform(transform)
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::Cone(self.neg())
+    }
+
+    fn normal(&self, p: Point) -> Point {
+        let (_, _, radial_dir) = self.cylindrical(p);
+        let normal = radial_dir * self.half_angle.cos() - self.extend_dir * self.half_angle.sin();
+        if self.normal_outwards {
+            normal
+        } else {
+            -normal
+        }
+    }
+
+    // The surface is straight along the generators and bends around the axis by cos(half_angle) / radius. It is not defined at the apex.
+    fn shape_operator(&self, p: Point) -> ShapeOperator {
+        let (_, radius, radial_dir) = self.cylindrical(p);
+        let normal = self.normal(p);
+        let curvature = (self.half_angle.cos() / radius).unwrap();
+        let curvature = match self.normal_outwards {
+            true => curvature,
+            false => -curvature,
+        };
+        let around = self.extend_dir.cross(radial_dir);
+        ShapeOperator::from_principal(around, curvature, normal.cross(around), EFloat64::zero())
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        let (height, radius, _) = self.cylindrical(p);
+        let tan = (self.half_angle.sin() / self.half_angle.cos()).unwrap();
+        // The distance to the cone is the radial deviation times the cosine of the half angle.
+        height.upper_bound >= 0.0
+            && Tolerance::current().is_zero_length((radius - height * tan) * self.half_angle.cos())
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let (s_x, s_y, phi) = self.unroll(x, y);
+        let dx = s_y * phi.cos() - s_x;
+        let dy = s_y * phi.sin();
+        (dx * dx + dy * dy).sqrt().unwrap()
+    }
+
+    // Walks along the straight line in the unrolled cone.
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        let (_, _, x_dir) = self.cylindrical(x);
+        let generator = self.generator(x_dir);
+        let around = self.extend_dir.cross(x_dir);
+        let s = (x - self.basis).norm() + u.dot(generator);
+        let t = u.dot(around);
+        let phi = t.atan2(s);
+        let theta = (phi / self.half_angle.sin()).unwrap();
+        let y_dir = x_dir * theta.cos() + around * theta.sin();
+        self.point_on_generator(y_dir, (s * s + t * t).sqrt().unwrap())
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        if x == y {
+            return Some(Point::zero());
+        }
+        let (s_x, s_y, phi) = self.unroll(x, y);
+        // For the case that y is on the opposite side of the cone
+        if (phi.abs() - EFloat64::pi() * self.half_angle.sin()) == 0.0 {
+            return None;
+        }
+        let (_, _, x_dir) = self.cylindrical(x);
+        let generator = self.generator(x_dir);
+        let around = self.extend_dir.cross(x_dir);
+        Some(generator * (s_y * phi.cos() - s_x) + around * (s_y * phi.sin()))
+    }
+
+    // Unrolled, parallel transport keeps the vector constant. The frame of y is rotated by the unrolled angle.
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        let (_, _, phi) = self.unroll(x, y);
+        let (_, _, x_dir) = self.cylindrical(x);
+        let (_, _, y_dir) = self.cylindrical(y);
+        let v_s = v.dot(self.generator(x_dir));
+        let v_t = v.dot(self.extend_dir.cross(x_dir));
+        Some(
+            self.generator(y_dir) * (v_s * phi.cos() + v_t * phi.sin())
+                + self.extend_dir.cross(y_dir) * (v_t * phi.cos() - v_s * phi.sin()),
+        )
+    }
+
+    fn geodesic(&self, p: Point, q: Point) -> Curve {
+        assert!(self.on_surface(p));
+        assert!(self.on_surface(q));
+        assert!(p != q);
+        let (s_p, s_q, phi) = self.unroll(p, q);
+        if phi == 0.0 {
+            return Curve::Line(Line::new(p, (q - p).normalize().unwrap()).unwrap());
+        }
+
+        // Sample the straight line in the unrolled cone.
+        let (_, _, p_dir) = self.cylindrical(p);
+        let around = self.extend_dir.cross(p_dir);
+        let (qx, qy) = (s_q * phi.cos(), s_q * phi.sin());
+        let mut points = vec![p];
+        for i in 1..GEODESIC_SEGMENTS {
+            let t = EFloat64::from(i as f64 / GEODESIC_SEGMENTS as f64);
+            let x = s_p + (qx - s_p) * t;
+            let y = qy * t;
+            let theta = (y.atan2(x) / self.half_angle.sin()).unwrap();
+            let dir = p_dir * theta.cos() + around * theta.sin();
+            points.push(self.point_on_generator(dir, (x * x + y * y).sqrt().unwrap()));
+        }
+        points.push(q);
+        Curve::Nurbs(Nurbs::polyline(points).unwrap())
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = (16.0 * density) as usize;
+        let m = (16.0 * density) as usize;
+        let around = self.extend_dir.cross(self.ref_dir);
+        let mut points = Vec::with_capacity(n * m);
+        for i in 0..n {
+            for j in 0..m {
+                let theta = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                let theta = EFloat64::from(theta);
+                let s = EFloat64::from(j as f64 / (m as f64 - 1.0) * HORIZON_DIST);
+                let dir = self.ref_dir * theta.cos() + around * theta.sin();
+                points.push(self.point_on_generator(dir, s));
+            }
+        }
+        points
+    }
+
+    fn project(&self, point: Point) -> Point {
+        let (height, radius, radial_dir) = self.cylindrical(point);
+        let s = height * self.half_angle.cos() + radius * self.half_angle.sin();
+        if s <= 0.0 {
+            return self.basis;
+        }
+        self.point_on_generator(radial_dir, s)
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        Some(self.project(point) - point)
+    }
+}
+
+impl PartialEq for Cone {
+    fn eq(&self, other: &Cone) -> bool {
+        self.basis == other.basis
+            && self.extend_dir == other.extend_dir
+            && self.half_angle == other.half_angle
+            && self.normal_outwards == other.normal_outwards
+    }
+}
+
+// u is the angle around the axis and v the distance from the apex.
+impl SurfaceParameterSpace for Cone {
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64) {
+        let (_, _, radial_dir) = self.cylindrical(p);
+        let around = self.extend_dir.cross(self.ref_dir);
+        (
+            radial_dir.dot(around).atan2(radial_dir.dot(self.ref_dir)),
+            (p - self.basis).norm(),
+        )
+    }
+
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point {
+        let around = self.extend_dir.cross(self.ref_dir);
+        self.point_on_generator(self.ref_dir * u.cos() + around * u.sin(), v)
+    }
+
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>) {
+        (Some(EFloat64::two_pi()), None)
+    }
+
+    fn is_uv_singular(&self, p: Point) -> bool {
+        Tolerance::current().is_zero_length((p - self.basis).norm())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Cone with apex at the origin, opening in z direction with an angle of 45 degrees.
+    fn cone() -> Cone {
+        Cone::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(std::f64::consts::FRAC_PI_4),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_cone_on_surface_and_project() {
+        let cone = cone();
+        assert!(cone.on_surface(Point::zero()));
+        assert!(cone.on_surface(Point::from_f64(1.0, 0.0, 1.0)));
+        assert!(cone.on_surface(Point::from_f64(0.0, -2.0, 2.0)));
+        assert!(!cone.on_surface(Point::from_f64(1.0, 0.0, -1.0)));
+        assert!(!cone.on_surface(Point::from_f64(1.0, 0.0, 2.0)));
+
+        assert_eq!(
+            cone.project(Point::from_f64(2.0, 0.0, 0.0)),
+            Point::from_f64(1.0, 0.0, 1.0)
+        );
+        assert_eq!(cone.project(Point::from_f64(0.0, 0.0, -1.0)), Point::zero());
+
+        let normal = cone.normal(Point::from_f64(1.0, 0.0, 1.0));
+        let expected = Point::from_f64(1.0, 0.0, -1.0).normalize().unwrap();
+        assert_eq!(normal, expected);
+    }
+
+    #[test]
+    fn test_cone_transform() {
+        let cone = cone();
+        // A uniform scale keeps the angle.
+        let Surface::Cone(scaled) =
+            cone.transform(Transform::from_scale(Point::from_f64(2.0, 2.0, 2.0)))
+        else {
+            panic!("Expected a cone");
+        };
+        assert_eq!(scaled.half_angle, cone.half_angle);
+
+        // Stretching along the axis makes the cone steeper.
+        let Surface::Cone(stretched) =
+            cone.transform(Transform::from_scale(Point::from_f64(1.0, 1.0, 2.0)))
+        else {
+            panic!("Expected a cone");
+        };
+        assert_eq!(stretched.half_angle, EFloat64::from(0.5).atan());
+
+        // Stretching across the axis gives an elliptic cone, which is a nurbs.
+        let transform = Transform::from_scale(Point::from_f64(2.0, 1.0, 1.0));
+        let Surface::Nurbs(elliptic) = cone.transform(transform) else {
+            panic!("Expected a nurbs");
+        };
+        let p = Point::from_f64(2.0, 0.0, 1.0);
+        assert!(elliptic.on_surface(p));
+        assert!(elliptic.on_surface(Point::from_f64(0.0, -3.0, 3.0)));
+        assert!(!elliptic.on_surface(Point::from_f64(1.0, 0.0, 1.0)));
+        // The normal still points away from the axis.
+        assert!(elliptic.normal(p).dot(Point::unit_x()) > 0.0);
+    }
+
+    #[test]
+    fn test_cone_exp_log() {
+        let cone = cone();
+        let x = Point::from_f64(1.0, 0.0, 1.0);
+        let y = Point::from_f64(0.0, 2.0, 2.0);
+        let u = cone.log(x, y).unwrap();
+        assert!(u.dot(cone.normal(x)) == 0.0);
+        let y2 = cone.exp(x, u);
+        assert!((y2 - y).norm() < 1e-9);
+        assert_eq!(cone.distance(x, y), u.norm());
+
+        // Moving along a generator is a straight line.
+        let y = Point::from_f64(3.0, 0.0, 3.0);
+        assert_eq!(cone.log(x, y).unwrap(), Point::from_f64(2.0, 0.0, 2.0));
+        assert!(matches!(cone.geodesic(x, y), Curve::Line(_)));
+
+        // The geodesic stays on the cone.
+        let y = Point::from_f64(-1.0, 1.0, 2.0f64.sqrt());
+        let Curve::Nurbs(geodesic) = cone.geodesic(x, y) else {
+            panic!("Expected a polyline");
+        };
+        for p in geodesic.control_points.iter() {
+            assert!(cone.on_surface(*p));
+        }
+
+        // Parallel transport keeps the length of vectors.
+        let v = cone.parallel_transport(Some(u), x, y).unwrap();
+        assert_eq!(v.norm(), u.norm());
+        assert!(v.dot(cone.normal(y)) == 0.0);
+    }
+}
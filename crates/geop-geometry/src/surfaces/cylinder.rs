@@ -3,11 +3,15 @@ use geop_algebra::efloat::EFloat64;
 use crate::{
     curves::{circle::Circle, curve::Curve, helix::Helix, line::Line, CurveLike},
     point::Point,
+    tolerance::Tolerance,
     transforms::Transform,
     HORIZON_DIST,
 };
 
 use super::{
+    elliptic_cylinder::EllipticCylinder,
+    parameter_space::SurfaceParameterSpace,
+    shape_operator::ShapeOperator,
     surface::{Surface, TangentPoint},
     SurfaceLike,
 };
@@ -43,16 +47,18 @@ impl Cylinder {
             dir_cross: extend_dir.normalize().unwrap().cross(radius),
         }
     }
-    fn transform(&self, transform: Transform) -> Self {
+    // The images of radius and dir_cross are conjugate radii of an elliptic section, which may be circular again.
+    fn transform(&self, transform: Transform) -> Surface {
         let basis = transform * self.basis;
-        let normal = transform * (self.extend_dir + self.basis) - basis;
-        let radius = transform * (self.radius + self.basis) - basis;
-        Cylinder::new(
+        let image = |v: Point| transform * (v + self.basis) - basis;
+        EllipticCylinder::from_conjugate(
             basis,
-            normal.normalize().unwrap(),
-            radius.norm(),
+            image(self.extend_dir),
+            image(self.radius),
+            image(self.dir_cross),
             self.normal_outwards,
         )
+        .simplify()
     }
 
     fn neg(&self) -> Self {
@@ -67,7 +73,7 @@ impl Cylinder {
 
 impl SurfaceLike for Cylinder {
     fn transform(&self, transform: Transform) -> Surface {
-        Surface::Cylinder(self.transform(transform))
+        self.transform(transform)
     }
 
     fn normal(&self, p: Point) -> Point {
@@ -85,12 +91,24 @@ impl SurfaceLike for Cylinder {
         Surface::Cylinder(self.neg())
     }
 
+    // The surface bends around the axis and is straight along it.
+    fn shape_operator(&self, p: Point) -> ShapeOperator {
+        let normal = self.normal(p);
+        let curvature = (EFloat64::one() / self.radius.norm()).unwrap();
+        let curvature = match self.normal_outwards {
+            true => curvature,
+            false => -curvature,
+        };
+        let around = self.extend_dir.cross(normal);
+        ShapeOperator::from_principal(around, curvature, normal.cross(around), EFloat64::zero())
+    }
+
     fn on_surface(&self, p: Point) -> bool {
         let p_project = p - self.basis;
         let height_project = p_project.dot(self.extend_dir) * self.extend_dir;
         let radius_project = p_project - height_project;
         let dist = radius_project.norm();
-        (dist - self.radius.norm()) == 0.0
+        Tolerance::current().is_zero_length(dist - self.radius.norm())
     }
 
     fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
@@ -100,7 +118,6 @@ impl SurfaceLike for Cylinder {
     fn distance(&self, x: Point, y: Point) -> EFloat64 {
         assert!(self.on_surface(x), "{:?} has to be on {:?}", x, self);
         assert!(self.on_surface(y), "{:?} has to be on {:?}", y, self);
-        println!("x: {:?} y: {:?}", x, y);
         let x = x - self.basis;
         let height_diff = (y - x).dot(self.extend_dir);
         let x = x - x.dot(self.extend_dir) * self.extend_dir;
@@ -241,9 +258,28 @@ impl SurfaceLike for Cylinder {
 
 impl PartialEq for Cylinder {
     fn eq(&self, other: &Cylinder) -> bool {
-        self.basis == other.basis
+        (self.basis - other.basis).is_parallel(self.extend_dir)
             && (self.radius.norm() - other.radius.norm() == 0.0)
             && self.extend_dir.is_parallel(other.extend_dir)
             && self.normal_outwards == other.normal_outwards
     }
 }
+
+// u is the angle around the axis, starting at radius, and v the height along extend_dir.
+impl SurfaceParameterSpace for Cylinder {
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64) {
+        let d = p - self.basis;
+        (
+            d.dot(self.dir_cross).atan2(d.dot(self.radius)),
+            d.dot(self.extend_dir),
+        )
+    }
+
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point {
+        self.basis + self.radius * u.cos() + self.dir_cross * u.sin() + self.extend_dir * v
+    }
+
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>) {
+        (Some(EFloat64::two_pi()), None)
+    }
+}
@@ -0,0 +1,470 @@
+use geop_algebra::{efloat::EFloat64, matrix::Matrix};
+
+use crate::{
+    curves::{curve::Curve, ellipse::Ellipse, nurbs::Nurbs},
+    point::Point,
+    tolerance::Tolerance,
+    transforms::Transform,
+};
+
+use super::{
+    geodesic::{self, Polyline, GEODESIC_SEGMENTS},
+    parameter_space::SurfaceParameterSpace,
+    shape_operator::ShapeOperator,
+    sphere::Sphere,
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+const PROJECTION_ITERATIONS: usize = 128;
+
+// The image of a sphere under an affine transform. The radii are its orthogonal semi axes.
+#[derive(Clone, Debug)]
+pub struct Ellipsoid {
+    pub basis: Point,
+    pub radii: [Point; 3],
+    pub normal_outwards: bool,
+}
+
+impl Ellipsoid {
+    pub fn new(basis: Point, radii: [Point; 3], normal_outwards: bool) -> Ellipsoid {
+        for i in 0..3 {
+            assert!(radii[i].norm() > 0.0, "Radii must not be zero");
+            for j in 0..i {
+                assert!(radii[i].dot(radii[j]) == 0.0, "Radii must be orthogonal");
+            }
+        }
+        Ellipsoid {
+            basis,
+            radii,
+            normal_outwards,
+        }
+    }
+
+    // The ellipsoid with the points basis + x * radii[0] + y * radii[1] + z * radii[2] for unit vectors (x, y, z), e.g. the image of a sphere.
+    // Its semi axes are the eigenvectors of A A^T, where A has the conjugate radii as columns. The largest one comes first.
+    pub fn from_conjugate(basis: Point, radii: [Point; 3], normal_outwards: bool) -> Ellipsoid {
+        let columns = radii.map(|r| [r.x, r.y, r.z]);
+        let shape = Matrix::new(
+            (0..3)
+                .map(|i| {
+                    (0..3)
+                        .map(|j| {
+                            (0..3).fold(EFloat64::zero(), |sum, k| {
+                                sum + columns[k][i] * columns[k][j]
+                            })
+                        })
+                        .collect()
+                })
+                .collect(),
+        );
+        let (_, vectors) = shape.symmetric_eigen().unwrap();
+        let axis = |j: usize| {
+            let column = vectors.column(j);
+            Point::new(column[0], column[1], column[2])
+        };
+        // The eigenvectors are orthogonalized again, such that they are orthogonal within the error bounds.
+        let e0 = axis(2).normalize().unwrap();
+        let e1 = axis(1);
+        let e1 = (e1 - e0 * e0.dot(e1)).normalize().unwrap();
+        let e2 = e0.cross(e1);
+        // The semi axis in direction e has the length |A^T e|.
+        let semi_axis =
+            |e: Point| e * Point::new(radii[0].dot(e), radii[1].dot(e), radii[2].dot(e)).norm();
+        Ellipsoid::new(
+            basis,
+            [semi_axis(e0), semi_axis(e1), semi_axis(e2)],
+            normal_outwards,
+        )
+    }
+
+    // Maps the unit sphere around the origin onto the ellipsoid.
+    pub fn frame(&self) -> Transform {
+        Transform::from_basis(self.basis, self.radii[0], self.radii[1], self.radii[2])
+    }
+
+    // Coordinates of p along the semi axes, relative to their lengths. Points on the ellipsoid have norm one.
+    fn local(&self, p: Point) -> Point {
+        let p = p - self.basis;
+        let coordinate = |r: Point| (r.dot(p) / r.norm_sq()).unwrap();
+        Point::new(
+            coordinate(self.radii[0]),
+            coordinate(self.radii[1]),
+            coordinate(self.radii[2]),
+        )
+    }
+
+    fn global(&self, q: Point) -> Point {
+        self.basis + self.radii[0] * q.x + self.radii[1] * q.y + self.radii[2] * q.z
+    }
+
+    // Angle around radii[2] and angle from radii[2], like the spherical coordinates of a sphere.
+    pub fn point_at(&self, theta: EFloat64, phi: EFloat64) -> Point {
+        self.global(Point::new(
+            theta.cos() * phi.sin(),
+            theta.sin() * phi.sin(),
+            phi.cos(),
+        ))
+    }
+
+    // Approximates the shortest path between p and q by a polyline, starting with the central section through p and q.
+    fn geodesic_points(&self, p: Point, q: Point) -> Polyline {
+        let x = self.local(p).mid().normalize().unwrap();
+        let y = self.local(q).mid().normalize().unwrap();
+        let angle = x.angle(y).unwrap().mid();
+        let dir = (y - x * x.dot(y))
+            .normalize()
+            .or_else(|_| x.cross(Point::unit_x()).normalize())
+            .or_else(|_| x.cross(Point::unit_y()).normalize())
+            .unwrap();
+        let mut points: Vec<Point> = (0..=GEODESIC_SEGMENTS)
+            .map(|k| {
+                let t = EFloat64::from(angle * k as f64 / GEODESIC_SEGMENTS as f64);
+                self.global(x * t.cos() + dir * t.sin()).mid()
+            })
+            .collect();
+        points[0] = p;
+        points[GEODESIC_SEGMENTS] = q;
+        geodesic::shorten(self, points)
+    }
+
+    fn neg(&self) -> Ellipsoid {
+        Ellipsoid::new(self.basis, self.radii, !self.normal_outwards)
+    }
+}
+
+impl SurfaceLike for Ellipsoid {
+    // Ellipsoids with semi axes of the same length are spheres.
+    fn transform(&self, transform: Transform) -> Surface {
+        let basis = transform * self.basis;
+        let radii = self.radii.map(|r| transform * (r + self.basis) - basis);
+        let ellipsoid = Ellipsoid::from_conjugate(basis, radii, self.normal_outwards);
+        let [a, b, c] = ellipsoid.radii.map(|r| r.norm());
+        match a == b && a == c {
+            true => Surface::Sphere(Sphere::new(basis, a, self.normal_outwards)),
+            false => Surface::Ellipsoid(ellipsoid),
+        }
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::Ellipsoid(self.neg())
+    }
+
+    // The gradient of the sum of the squared local coordinates.
+    fn normal(&self, p: Point) -> Point {
+        let q = self.local(p);
+        let gradient = self.radii[0] * (q.x / self.radii[0].norm_sq()).unwrap()
+            + self.radii[1] * (q.y / self.radii[1].norm_sq()).unwrap()
+            + self.radii[2] * (q.z / self.radii[2].norm_sq()).unwrap();
+        let normal = gradient.normalize().unwrap();
+        if self.normal_outwards {
+            normal
+        } else {
+            -normal
+        }
+    }
+
+    // The derivative of the gradient of sum(q_i^2) / 2 along t is sum(r_i * (r_i . t) / |r_i|^4), which is divided by the length of the gradient.
+    fn shape_operator(&self, p: Point) -> ShapeOperator {
+        let q = self.local(p);
+        let gradient = self.radii[0] * (q.x / self.radii[0].norm_sq()).unwrap()
+            + self.radii[1] * (q.y / self.radii[1].norm_sq()).unwrap()
+            + self.radii[2] * (q.z / self.radii[2].norm_sq()).unwrap();
+        let scale = match self.normal_outwards {
+            true => gradient.norm(),
+            false => -gradient.norm(),
+        };
+        ShapeOperator::from_map(self.normal(p), |t| {
+            let derivative = self.radii.iter().fold(Point::zero(), |sum, r| {
+                sum + *r * (r.dot(t) / r.norm_sq().square()).unwrap()
+            });
+            (derivative / scale).unwrap()
+        })
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        let largest = self.radii.map(|r| r.norm());
+        let largest = largest[0].max(largest[1]).max(largest[2]);
+        // The relative radial deviation, scaled by the largest radius to bound the distance.
+        match self.local(p).norm_sq().sqrt() {
+            Some(r) => Tolerance::current().is_zero_length((r - EFloat64::one()) * largest),
+            None => false,
+        }
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        self.geodesic_points(x, y).length()
+    }
+
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        geodesic::exp(self, x, u)
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        if x == y {
+            return Some(Point::zero());
+        }
+        geodesic::log(&self.geodesic_points(x, y))
+    }
+
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        if x == y {
+            return Some(v);
+        }
+        geodesic::parallel_transport(v, &self.geodesic_points(x, y))
+    }
+
+    // The sections with the planes of two semi axes are geodesics. All other geodesics are approximated.
+    fn geodesic(&self, x: Point, y: Point) -> Curve {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        assert!(x != y);
+        let (x_local, y_local) = (self.local(x), self.local(y));
+        let coordinates = |q: Point| [q.x, q.y, q.z];
+        let (x_local, y_local) = (coordinates(x_local), coordinates(y_local));
+        for k in 0..3 {
+            if x_local[k] != 0.0 || y_local[k] != 0.0 {
+                continue;
+            }
+            let (major, minor) = (self.radii[(k + 1) % 3], self.radii[(k + 2) % 3]);
+            // The ellipse is oriented such that it runs the short way from x to y.
+            let normal = major.cross(minor).normalize().unwrap();
+            let normal = match (x - self.basis).cross(y - self.basis).dot(normal) < 0.0 {
+                true => -normal,
+                false => normal,
+            };
+            return Curve::Ellipse(Ellipse::new(self.basis, normal, major, minor));
+        }
+        Curve::Nurbs(Nurbs::polyline(self.geodesic_points(x, y).points).unwrap())
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = (16.0 * density) as usize;
+        let m = (16.0 * density) as usize;
+        let mut points = Vec::with_capacity(n * m);
+        for i in 0..n {
+            for j in 0..m {
+                let theta = EFloat64::from(2.0 * std::f64::consts::PI * i as f64 / n as f64);
+                let phi = EFloat64::from(std::f64::consts::PI * j as f64 / (m - 1) as f64);
+                points.push(self.point_at(theta, phi));
+            }
+        }
+        points
+    }
+
+    // Along the semi axes with lengths a, the closest point is a^2 q / (a^2 + t) for the root t > -min a^2 of sum (a q / (a^2 + t))^2 = 1.
+    // The sum decreases in t, so the root is found by bisection.
+    fn project(&self, point: Point) -> Point {
+        let lengths = self.radii.map(|r| r.norm().mid());
+        let q: Vec<f64> = (0..3)
+            .map(|i| self.radii[i].dot(point - self.basis).mid() / lengths[i])
+            .collect();
+        let f = |t: f64| {
+            (0..3)
+                .map(|i| (lengths[i] * q[i] / (lengths[i].powi(2) + t)).powi(2))
+                .sum::<f64>()
+                - 1.0
+        };
+        let smallest = lengths[0].min(lengths[1]).min(lengths[2]);
+        let largest = lengths[0].max(lengths[1]).max(lengths[2]);
+        let norm = q.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let (mut lo, mut hi) = (-smallest.powi(2), largest * norm);
+        for _ in 0..PROJECTION_ITERATIONS {
+            let mid = (lo + hi) / 2.0;
+            match f(mid) > 0.0 {
+                true => lo = mid,
+                false => hi = mid,
+            }
+        }
+        let t = (lo + hi) / 2.0;
+        // Inside the ellipsoid and in the plane orthogonal to the smallest axis, the root is at -min a^2 and the closest point leaves the plane.
+        let degenerate = |i: usize| lengths[i].powi(2) + t <= 1e-12 * lengths[i].powi(2);
+        let mut x: Vec<f64> = (0..3)
+            .map(|i| match degenerate(i) {
+                true => 0.0,
+                false => lengths[i].powi(2) * q[i] / (lengths[i].powi(2) + t),
+            })
+            .collect();
+        if let Some(i) = (0..3).find(|i| degenerate(*i)) {
+            let rest: f64 = (0..3).map(|j| (x[j] / lengths[j]).powi(2)).sum();
+            x[i] = (1.0 - rest).max(0.0).sqrt() * lengths[i] * q[i].signum();
+        }
+        (0..3).fold(self.basis, |p, i| {
+            p + self.radii[i] * EFloat64::from(x[i] / lengths[i])
+        })
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        Some(self.project(point) - point)
+    }
+}
+
+impl PartialEq for Ellipsoid {
+    fn eq(&self, other: &Ellipsoid) -> bool {
+        self.basis == other.basis
+            && self.radii.iter().all(|r| {
+                other
+                    .radii
+                    .iter()
+                    .any(|o| r.is_parallel(*o) && r.norm() == o.norm())
+            })
+            && self.normal_outwards == other.normal_outwards
+    }
+}
+
+// u is the angle around radii[2] and v the angle from radii[2].
+impl SurfaceParameterSpace for Ellipsoid {
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64) {
+        let q = self.local(p);
+        let radial = (q.x * q.x + q.y * q.y).sqrt().unwrap();
+        (q.y.atan2(q.x), radial.atan2(q.z))
+    }
+
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point {
+        self.point_at(u, v)
+    }
+
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>) {
+        (Some(EFloat64::two_pi()), None)
+    }
+
+    fn is_uv_singular(&self, p: Point) -> bool {
+        let q = self.local(p);
+        let radial = (q.x * q.x + q.y * q.y).sqrt().unwrap();
+        Tolerance::current().is_zero_length(radial * self.radii[0].norm().max(self.radii[1].norm()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::CurveLike;
+
+    // The unit sphere stretched to the semi axes 3, 2 and 1.
+    fn ellipsoid() -> Ellipsoid {
+        Ellipsoid::new(
+            Point::zero(),
+            [
+                Point::from_f64(3.0, 0.0, 0.0),
+                Point::from_f64(0.0, 2.0, 0.0),
+                Point::from_f64(0.0, 0.0, 1.0),
+            ],
+            true,
+        )
+    }
+
+    #[test]
+    fn test_ellipsoid_from_sphere() {
+        let sphere = Sphere::new(Point::unit_x(), EFloat64::one(), true);
+        let shear = Transform::from_basis(
+            Point::zero(),
+            Point::unit_x(),
+            Point::from_f64(1.0, 1.0, 0.0),
+            Point::unit_z(),
+        );
+        let Surface::Ellipsoid(ellipsoid) = sphere.transform(shear) else {
+            panic!("Expected an ellipsoid");
+        };
+        assert_eq!(ellipsoid.basis, Point::unit_x());
+        for p in [
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(2.0, 1.0, 0.0),
+            Point::from_f64(1.0, 0.0, 1.0),
+        ] {
+            assert!(ellipsoid.on_surface(p));
+        }
+        assert!(!ellipsoid.on_surface(Point::from_f64(1.0, 1.0, 0.0)));
+        // The semi axes are orthogonal, the largest comes first.
+        assert!(ellipsoid.radii[0].dot(ellipsoid.radii[1]) == 0.0);
+        assert!(ellipsoid.radii[0].norm() > ellipsoid.radii[1].norm().upper_bound);
+
+        // Scaling it back to a sphere gives a sphere.
+        let scale = Transform::from_scale(Point::from_f64(2.0, 2.0, 2.0));
+        assert!(matches!(
+            sphere.transform(scale),
+            Surface::Sphere(s) if s.radius == EFloat64::two()
+        ));
+        let stretch = Transform::from_scale(Point::from_f64(1.0, 2.0, 1.0));
+        let ellipsoid = sphere.transform(stretch);
+        assert!(matches!(
+            ellipsoid.transform(stretch.inverse().unwrap()),
+            Surface::Sphere(_)
+        ));
+    }
+
+    #[test]
+    fn test_ellipsoid_on_surface_and_project() {
+        let ellipsoid = ellipsoid();
+        assert!(ellipsoid.on_surface(Point::from_f64(-3.0, 0.0, 0.0)));
+        assert!(ellipsoid.on_surface(Point::from_f64(0.0, 0.0, 1.0)));
+        assert!(!ellipsoid.on_surface(Point::from_f64(0.0, 0.0, 2.0)));
+        assert!(!ellipsoid.on_surface(Point::zero()));
+
+        assert_eq!(
+            ellipsoid.project(Point::from_f64(0.0, 5.0, 0.0)),
+            Point::from_f64(0.0, 2.0, 0.0)
+        );
+        // The closest point to the center is on the smallest axis.
+        let p = ellipsoid.project(Point::zero());
+        assert!((p.z.abs() - EFloat64::one()).abs() < 1e-9);
+        // The closest point is where the normal points back to the point.
+        let point = Point::from_f64(2.0, 2.0, 2.0);
+        let p = ellipsoid.project(point);
+        assert!(ellipsoid.on_surface(p));
+        assert!(ellipsoid.normal(p).cross(point - p).norm() < 1e-9);
+
+        assert_eq!(
+            ellipsoid.normal(Point::from_f64(0.0, -2.0, 0.0)),
+            Point::from_f64(0.0, -1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_ellipsoid_geodesics() {
+        let ellipsoid = ellipsoid();
+
+        // The sections with the planes of the semi axes are ellipses.
+        let x = Point::from_f64(3.0, 0.0, 0.0);
+        let y = Point::from_f64(0.0, 0.0, 1.0);
+        let Curve::Ellipse(ellipse) = ellipsoid.geodesic(x, y) else {
+            panic!("Expected an ellipse");
+        };
+        assert!(ellipse.on_curve(x));
+        assert!(ellipse.on_curve(y));
+        assert!(ellipse.tangent(x).unwrap().dot(Point::unit_z()) > 0.0);
+        let u = ellipsoid.log(x, y).unwrap();
+        assert!(u.dot(ellipsoid.normal(x)).abs() < 1e-9);
+        assert!((ellipsoid.exp(x, u) - y).norm() < 0.1);
+
+        // Any other geodesic stays on the ellipsoid.
+        let y = Point::from_f64(0.0, 2.0f64.sqrt(), 0.5f64.sqrt());
+        let Curve::Nurbs(geodesic) = ellipsoid.geodesic(x, y) else {
+            panic!("Expected a polyline");
+        };
+        for p in geodesic.control_points.iter() {
+            assert!((ellipsoid.project(*p) - *p).norm() < 1e-9);
+        }
+        let v = ellipsoid
+            .parallel_transport(Some(Point::unit_z()), x, y)
+            .unwrap();
+        assert!((v.norm() - EFloat64::one()).abs() < 1e-9);
+        assert!(v.dot(ellipsoid.normal(y)).abs() < 1e-9);
+    }
+}
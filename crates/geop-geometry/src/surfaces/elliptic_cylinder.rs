@@ -0,0 +1,559 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{curve::Curve, ellipse::principal_radii, ellipse::Ellipse, line::Line, nurbs::Nurbs},
+    point::Point,
+    tolerance::Tolerance,
+    transforms::Transform,
+    HORIZON_DIST,
+};
+
+use super::{
+    cylinder::Cylinder,
+    parameter_space::SurfaceParameterSpace,
+    shape_operator::ShapeOperator,
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// Geodesics that are neither sections nor lines are approximated by polylines with this many segments.
+const GEODESIC_SEGMENTS: usize = 32;
+// Number of intervals of Simpson's rule for the arc length of a full turn of the section.
+const ARC_LENGTH_INTERVALS: usize = 256;
+const BISECTION_ITERATIONS: usize = 128;
+
+// Shortest signed difference between two angles.
+fn angle_diff(from: f64, to: f64) -> f64 {
+    let diff = (to - from).rem_euclid(2.0 * std::f64::consts::PI);
+    match diff > std::f64::consts::PI {
+        true => diff - 2.0 * std::f64::consts::PI,
+        false => diff,
+    }
+}
+
+// A cylinder with an elliptic section, e.g. the image of a cylinder under an affine transform.
+// The section orthogonal to extend_dir has the orthogonal semi axes major_radius and minor_radius.
+#[derive(Clone, Debug)]
+pub struct EllipticCylinder {
+    pub basis: Point,
+    pub extend_dir: Point,
+    pub major_radius: Point,
+    pub minor_radius: Point,
+    pub normal_outwards: bool,
+}
+
+impl EllipticCylinder {
+    pub fn new(
+        basis: Point,
+        extend_dir: Point,
+        major_radius: Point,
+        minor_radius: Point,
+        normal_outwards: bool,
+    ) -> EllipticCylinder {
+        let extend_dir = extend_dir.normalize().unwrap();
+        assert!(
+            extend_dir.dot(major_radius) == 0.0,
+            "Major radius and extend_dir must be orthogonal"
+        );
+        assert!(
+            extend_dir.dot(minor_radius) == 0.0,
+            "Minor radius and extend_dir must be orthogonal"
+        );
+        assert!(
+            major_radius.dot(minor_radius) == 0.0,
+            "Major and minor radii must be orthogonal"
+        );
+        EllipticCylinder {
+            basis,
+            extend_dir,
+            major_radius,
+            minor_radius,
+            normal_outwards,
+        }
+    }
+
+    // The cylinder with the points basis + cos * u + sin * v + t * extend_dir. The conjugate radii u and v do not have to be orthogonal to extend_dir.
+    pub fn from_conjugate(
+        basis: Point,
+        extend_dir: Point,
+        u: Point,
+        v: Point,
+        normal_outwards: bool,
+    ) -> EllipticCylinder {
+        let extend_dir = extend_dir.normalize().unwrap();
+        // Moving along extend_dir keeps the surface, so the projections are conjugate radii of the orthogonal section.
+        let project = |v: Point| v - extend_dir * extend_dir.dot(v);
+        let (major_radius, minor_radius) = principal_radii(project(u), project(v));
+        EllipticCylinder::new(
+            basis,
+            extend_dir,
+            major_radius,
+            minor_radius,
+            normal_outwards,
+        )
+    }
+
+    // Elliptic cylinders with radii of the same length are circular.
+    pub(crate) fn simplify(self) -> Surface {
+        match self.major_radius.norm() == self.minor_radius.norm() {
+            true => Surface::Cylinder(Cylinder::new(
+                self.basis,
+                self.extend_dir,
+                self.major_radius.norm(),
+                self.normal_outwards,
+            )),
+            false => Surface::EllipticCylinder(self),
+        }
+    }
+
+    // Maps the cylinder of radius one around the z axis onto this one.
+    pub fn frame(&self) -> Transform {
+        Transform::from_basis(
+            self.basis,
+            self.major_radius,
+            self.minor_radius,
+            self.extend_dir,
+        )
+    }
+
+    // Coordinates of p along the radii relative to their lengths, and the height along extend_dir.
+    fn local(&self, p: Point) -> (EFloat64, EFloat64, EFloat64) {
+        let p = p - self.basis;
+        let x = (self.major_radius.dot(p) / self.major_radius.norm_sq()).unwrap();
+        let y = (self.minor_radius.dot(p) / self.minor_radius.norm_sq()).unwrap();
+        (x, y, self.extend_dir.dot(p))
+    }
+
+    // Angle of p in the section and height along extend_dir.
+    pub fn parameter(&self, p: Point) -> (EFloat64, EFloat64) {
+        let (x, y, height) = self.local(p);
+        (y.atan2(x), height)
+    }
+
+    pub fn point_at(&self, angle: EFloat64, height: EFloat64) -> Point {
+        self.basis
+            + self.major_radius * angle.cos()
+            + self.minor_radius * angle.sin()
+            + self.extend_dir * height
+    }
+
+    // Unit tangent of the section at the angle, in the direction of increasing angles.
+    fn section_tangent(&self, angle: EFloat64) -> Point {
+        (self.minor_radius * angle.cos() - self.major_radius * angle.sin())
+            .normalize()
+            .unwrap()
+    }
+
+    // Signed arc length of the section between two angles by Simpson's rule.
+    fn arc_length(&self, from: f64, to: f64) -> f64 {
+        let (a, b) = (
+            self.major_radius.norm().mid(),
+            self.minor_radius.norm().mid(),
+        );
+        let speed = |t: f64| (a * a * t.sin().powi(2) + b * b * t.cos().powi(2)).sqrt();
+        let n = 2
+            * ((to - from).abs() / (2.0 * std::f64::consts::PI) * ARC_LENGTH_INTERVALS as f64 / 2.0)
+                .ceil()
+                .max(1.0) as usize;
+        let h = (to - from) / n as f64;
+        let sum: f64 = (1..n)
+            .map(|i| match i % 2 {
+                0 => 2.0 * speed(from + h * i as f64),
+                _ => 4.0 * speed(from + h * i as f64),
+            })
+            .sum();
+        h / 3.0 * (speed(from) + sum + speed(to))
+    }
+
+    // Angle that is the signed arc length away from the angle from. The arc length grows at least with the minor radius, which bounds the search.
+    fn angle_at_length(&self, from: f64, length: f64) -> f64 {
+        let smallest = self
+            .major_radius
+            .norm()
+            .mid()
+            .min(self.minor_radius.norm().mid());
+        let (mut lo, mut hi) = (from, from + length / smallest);
+        for _ in 0..BISECTION_ITERATIONS {
+            let mid = (lo + hi) / 2.0;
+            match (self.arc_length(from, mid) < length) == (length > 0.0) {
+                true => lo = mid,
+                false => hi = mid,
+            }
+        }
+        (lo + hi) / 2.0
+    }
+
+    // The surface is developable. Unrolled, the geodesic from x to y is the straight line to the arc length and height differences.
+    // The arc length is the one of the shorter way around the section.
+    fn unrolled(&self, x: Point, y: Point) -> (f64, f64, f64) {
+        let (x_angle, x_height) = self.parameter(x);
+        let (y_angle, y_height) = self.parameter(y);
+        let from = x_angle.mid();
+        let diff = angle_diff(from, y_angle.mid());
+        let length = self.arc_length(from, from + diff);
+        let perimeter = self.arc_length(0.0, 2.0 * std::f64::consts::PI);
+        let length = match length.abs() > perimeter / 2.0 {
+            true => length - perimeter * length.signum(),
+            false => length,
+        };
+        (from, length, (y_height - x_height).mid())
+    }
+
+    fn neg(&self) -> EllipticCylinder {
+        EllipticCylinder::new(
+            self.basis,
+            self.extend_dir,
+            self.major_radius,
+            self.minor_radius,
+            !self.normal_outwards,
+        )
+    }
+}
+
+impl SurfaceLike for EllipticCylinder {
+    fn transform(&self, transform: Transform) -> Surface {
+        let basis = transform * self.basis;
+        let image = |v: Point| transform * (v + self.basis) - basis;
+        EllipticCylinder::from_conjugate(
+            basis,
+            image(self.extend_dir),
+            image(self.major_radius),
+            image(self.minor_radius),
+            self.normal_outwards,
+        )
+        .simplify()
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::EllipticCylinder(self.neg())
+    }
+
+    // The gradient of x^2 + y^2 in the local coordinates.
+    fn normal(&self, p: Point) -> Point {
+        let (x, y, _) = self.local(p);
+        let gradient = self.major_radius * (x / self.major_radius.norm_sq()).unwrap()
+            + self.minor_radius * (y / self.minor_radius.norm_sq()).unwrap();
+        let normal = gradient.normalize().unwrap();
+        if self.normal_outwards {
+            normal
+        } else {
+            -normal
+        }
+    }
+
+    // Like the ellipsoid, the derivative of the gradient divided by its length. It vanishes along the axis.
+    fn shape_operator(&self, p: Point) -> ShapeOperator {
+        let (x, y, _) = self.local(p);
+        let gradient = self.major_radius * (x / self.major_radius.norm_sq()).unwrap()
+            + self.minor_radius * (y / self.minor_radius.norm_sq()).unwrap();
+        let scale = match self.normal_outwards {
+            true => gradient.norm(),
+            false => -gradient.norm(),
+        };
+        ShapeOperator::from_map(self.normal(p), |t| {
+            let derivative = [self.major_radius, self.minor_radius]
+                .iter()
+                .fold(Point::zero(), |sum, r| {
+                    sum + *r * (r.dot(t) / r.norm_sq().square()).unwrap()
+                });
+            (derivative / scale).unwrap()
+        })
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        let (x, y, _) = self.local(p);
+        let largest = self.major_radius.norm().max(self.minor_radius.norm());
+        // The relative radial deviation, scaled by the largest radius to bound the distance.
+        match (x * x + y * y).sqrt() {
+            Some(r) => Tolerance::current().is_zero_length((r - EFloat64::one()) * largest),
+            None => false,
+        }
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let (_, length, height) = self.unrolled(x, y);
+        EFloat64::from((length * length + height * height).sqrt())
+    }
+
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        let (angle, height) = self.parameter(x);
+        let length = u.dot(self.section_tangent(angle)).mid();
+        let angle = self.angle_at_length(angle.mid(), length);
+        self.point_at(EFloat64::from(angle), height + u.dot(self.extend_dir))
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let (from, length, height) = self.unrolled(x, y);
+        Some(
+            self.section_tangent(EFloat64::from(from)) * EFloat64::from(length)
+                + self.extend_dir * EFloat64::from(height),
+        )
+    }
+
+    // Unrolled, parallel transport keeps the components along the section and along extend_dir.
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        let (x_angle, _) = self.parameter(x);
+        let (y_angle, _) = self.parameter(y);
+        Some(
+            self.section_tangent(y_angle) * v.dot(self.section_tangent(x_angle))
+                + self.extend_dir * v.dot(self.extend_dir),
+        )
+    }
+
+    // Sections and lines along extend_dir are geodesics. All other geodesics wind around the cylinder and are approximated.
+    fn geodesic(&self, x: Point, y: Point) -> Curve {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        assert!(x != y);
+        let (from, length, height) = self.unrolled(x, y);
+        let (x_angle, x_height) = self.parameter(x);
+        let (y_angle, y_height) = self.parameter(y);
+        if x_height == y_height {
+            // The ellipse is oriented such that it runs the short way from x to y.
+            let normal = self
+                .major_radius
+                .cross(self.minor_radius)
+                .normalize()
+                .unwrap();
+            let normal = match length < 0.0 {
+                true => -normal,
+                false => normal,
+            };
+            return Curve::Ellipse(Ellipse::new(
+                self.basis + self.extend_dir * x_height,
+                normal,
+                self.major_radius,
+                self.minor_radius,
+            ));
+        }
+        if x_angle == y_angle {
+            return Curve::Line(Line::new(x, (y - x).normalize().unwrap()).unwrap());
+        }
+        let mut points: Vec<Point> = (0..=GEODESIC_SEGMENTS)
+            .map(|k| {
+                let t = k as f64 / GEODESIC_SEGMENTS as f64;
+                self.point_at(
+                    EFloat64::from(self.angle_at_length(from, length * t)),
+                    x_height + EFloat64::from(height * t),
+                )
+            })
+            .collect();
+        points[0] = x;
+        points[GEODESIC_SEGMENTS] = y;
+        Curve::Nurbs(Nurbs::polyline(points).unwrap())
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = (16.0 * density) as usize;
+        let m = (16.0 * density) as usize;
+        let mut points = Vec::with_capacity(n * m);
+        for i in 0..n {
+            for j in 0..m {
+                let angle = EFloat64::from(2.0 * std::f64::consts::PI * i as f64 / n as f64);
+                let height = (j as f64 / (m as f64 - 1.0) - 0.5) * HORIZON_DIST;
+                points.push(self.point_at(angle, EFloat64::from(height)));
+            }
+        }
+        points
+    }
+
+    // In the section, the closest point is a^2 q / (a^2 + t) for the root t > -min a^2 of sum (a q / (a^2 + t))^2 = 1 with the semi axes of lengths a.
+    // The sum decreases in t, so the root is found by bisection.
+    fn project(&self, point: Point) -> Point {
+        let radii = [self.major_radius, self.minor_radius];
+        let lengths = radii.map(|r| r.norm().mid());
+        let q = [0, 1].map(|i| radii[i].dot(point - self.basis).mid() / lengths[i]);
+        let f = |t: f64| {
+            (0..2)
+                .map(|i| (lengths[i] * q[i] / (lengths[i].powi(2) + t)).powi(2))
+                .sum::<f64>()
+                - 1.0
+        };
+        let smallest = lengths[0].min(lengths[1]);
+        let largest = lengths[0].max(lengths[1]);
+        let norm = (q[0] * q[0] + q[1] * q[1]).sqrt();
+        let (mut lo, mut hi) = (-smallest.powi(2), largest * norm);
+        for _ in 0..BISECTION_ITERATIONS {
+            let mid = (lo + hi) / 2.0;
+            match f(mid) > 0.0 {
+                true => lo = mid,
+                false => hi = mid,
+            }
+        }
+        let t = (lo + hi) / 2.0;
+        // Inside the section and on its major axis, the root is at -min a^2 and the closest point leaves the axis.
+        let degenerate = |i: usize| lengths[i].powi(2) + t <= 1e-12 * lengths[i].powi(2);
+        let mut x = [0, 1].map(|i| match degenerate(i) {
+            true => 0.0,
+            false => lengths[i].powi(2) * q[i] / (lengths[i].powi(2) + t),
+        });
+        if let Some(i) = (0..2).find(|i| degenerate(*i)) {
+            let rest: f64 = (0..2).map(|j| (x[j] / lengths[j]).powi(2)).sum();
+            x[i] = (1.0 - rest).max(0.0).sqrt() * lengths[i] * q[i].signum();
+        }
+        let height = self.extend_dir.dot(point - self.basis);
+        self.basis
+            + self.extend_dir * height
+            + radii[0] * EFloat64::from(x[0] / lengths[0])
+            + radii[1] * EFloat64::from(x[1] / lengths[1])
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        Some(self.project(point) - point)
+    }
+}
+
+impl PartialEq for EllipticCylinder {
+    fn eq(&self, other: &EllipticCylinder) -> bool {
+        (self.basis - other.basis).is_parallel(self.extend_dir)
+            && self.extend_dir.is_parallel(other.extend_dir)
+            && self.major_radius.is_parallel(other.major_radius)
+            && self.major_radius.norm() == other.major_radius.norm()
+            && self.minor_radius.norm() == other.minor_radius.norm()
+            && self.normal_outwards == other.normal_outwards
+    }
+}
+
+// u is the angle in the section and v the height along extend_dir.
+impl SurfaceParameterSpace for EllipticCylinder {
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64) {
+        self.parameter(p)
+    }
+
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point {
+        self.point_at(u, v)
+    }
+
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>) {
+        (Some(EFloat64::two_pi()), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::CurveLike;
+
+    // The cylinder around the z axis, stretched to the radii 2 and 1.
+    fn cylinder() -> EllipticCylinder {
+        EllipticCylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_elliptic_cylinder_from_cylinder() {
+        let circular = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+        let Surface::EllipticCylinder(elliptic) =
+            circular.transform(Transform::from_scale(Point::from_f64(2.0, 1.0, 1.0)))
+        else {
+            panic!("Expected an elliptic cylinder");
+        };
+        assert_eq!(elliptic, cylinder());
+        assert!(elliptic.on_surface(Point::from_f64(-2.0, 0.0, 5.0)));
+        assert!(elliptic.on_surface(Point::from_f64(0.0, 1.0, -3.0)));
+
+        // Scaling along the axis keeps the cylinder circular.
+        assert!(matches!(
+            circular.transform(Transform::from_scale(Point::from_f64(2.0, 2.0, 1.0))),
+            Surface::Cylinder(_)
+        ));
+
+        // A shear along the axis keeps the section.
+        let shear = Transform::from_basis(
+            Point::zero(),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        );
+        assert!(matches!(circular.transform(shear), Surface::Cylinder(_)));
+        // One orthogonal to it stretches the section.
+        let shear = Transform::from_basis(
+            Point::zero(),
+            Point::unit_x(),
+            Point::from_f64(1.0, 1.0, 0.0),
+            Point::unit_z(),
+        );
+        let Surface::EllipticCylinder(elliptic) = circular.transform(shear) else {
+            panic!("Expected an elliptic cylinder");
+        };
+        assert!(elliptic.on_surface(Point::from_f64(1.0, 1.0, 2.0)));
+        assert!(elliptic.on_surface(Point::from_f64(-1.0, 0.0, 0.0)));
+        assert!(!elliptic.on_surface(Point::from_f64(0.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_elliptic_cylinder_on_surface_and_project() {
+        let cylinder = cylinder();
+        assert!(!cylinder.on_surface(Point::from_f64(1.0, 0.0, 0.0)));
+        assert_eq!(
+            cylinder.project(Point::from_f64(5.0, 0.0, 3.0)),
+            Point::from_f64(2.0, 0.0, 3.0)
+        );
+        // The closest point to the axis is on the minor radius.
+        let p = cylinder.project(Point::from_f64(0.0, 0.0, 1.0));
+        assert!((p - Point::from_f64(0.0, 1.0, 1.0)).norm() < 1e-9);
+        let point = Point::from_f64(2.0, 2.0, 0.0);
+        let p = cylinder.project(point);
+        assert!(cylinder.on_surface(p));
+        assert!(cylinder.normal(p).cross(point - p).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_elliptic_cylinder_geodesics() {
+        let cylinder = cylinder();
+        let x = Point::from_f64(2.0, 0.0, 0.0);
+
+        // The sections are geodesics.
+        let y = Point::from_f64(0.0, 1.0, 0.0);
+        let Curve::Ellipse(ellipse) = cylinder.geodesic(x, y) else {
+            panic!("Expected an ellipse");
+        };
+        assert!(ellipse.on_curve(y));
+        assert!(ellipse.tangent(x).unwrap().dot(Point::unit_y()) > 0.0);
+        // A quarter of the ellipse with the radii 2 and 1 has the length 2.4221...
+        assert!((cylinder.distance(x, y) - EFloat64::from(2.422112055)).abs() < 1e-6);
+
+        // So are the lines along the axis.
+        let y = Point::from_f64(2.0, 0.0, 3.0);
+        assert!(matches!(cylinder.geodesic(x, y), Curve::Line(_)));
+        assert!((cylinder.distance(x, y) - EFloat64::from(3.0)).abs() < 1e-9);
+
+        // Everything else winds around the cylinder.
+        let y = Point::from_f64(0.0, -1.0, 1.0);
+        let Curve::Nurbs(geodesic) = cylinder.geodesic(x, y) else {
+            panic!("Expected a polyline");
+        };
+        for p in geodesic.control_points.iter() {
+            assert!(cylinder.on_surface(*p));
+        }
+        let u = cylinder.log(x, y).unwrap();
+        assert!(u.dot(cylinder.normal(x)).abs() < 1e-9);
+        assert!((u.norm() - cylinder.distance(x, y)).abs() < 1e-9);
+        assert!((cylinder.exp(x, u) - y).norm() < 1e-9);
+        let v = cylinder.parallel_transport(Some(u), x, y).unwrap();
+        assert!((v.norm() - u.norm()).abs() < 1e-9);
+        assert!(v.dot(cylinder.normal(y)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,114 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{point::Point, tolerance::Tolerance};
+
+use super::{surface::TangentPoint, SurfaceLike};
+
+// Numerical geodesics for surfaces without closed forms. The paths are polylines with this many segments, which are kept on the surface by projecting onto it.
+pub(crate) const GEODESIC_SEGMENTS: usize = 32;
+const GEODESIC_ITERATIONS: usize = 64;
+
+// Points on the surface with the normals there.
+pub(crate) struct Polyline {
+    pub points: Vec<Point>,
+    pub normals: Vec<Point>,
+}
+
+impl Polyline {
+    pub fn length(&self) -> EFloat64 {
+        let mut length = EFloat64::zero();
+        for k in 1..self.points.len() {
+            length = length + (self.points[k] - self.points[k - 1]).norm();
+        }
+        length
+    }
+}
+
+// Projection onto the surface that also gives the normal there.
+fn onto(surface: &dyn SurfaceLike, p: Point) -> (Point, Point) {
+    let p = surface.project(p).mid();
+    (p, surface.normal(p))
+}
+
+// Shortens the polyline between its first and last point by moving every other point towards the midpoint of its neighbours.
+// The surfaces start this with a guess of their own, like the straight line in their parameters.
+pub(crate) fn shorten(surface: &dyn SurfaceLike, points: Vec<Point>) -> Polyline {
+    shorten_with(points, |_, p| onto(surface, p))
+}
+
+// Like shorten, with a projection that is told which point of the polyline it moves. Surfaces that search for the closest point start where that point was before.
+pub(crate) fn shorten_with(
+    mut points: Vec<Point>,
+    mut project: impl FnMut(usize, Point) -> (Point, Point),
+) -> Polyline {
+    let n = points.len() - 1;
+    let mut normals: Vec<Point> = points
+        .iter()
+        .enumerate()
+        .map(|(k, p)| project(k, *p).1)
+        .collect();
+    let precision = Tolerance::current().numerical;
+    for _ in 0..GEODESIC_ITERATIONS {
+        let mut moved = 0.0f64;
+        for k in 1..n {
+            let m = (points[k - 1] + points[k + 1]) * EFloat64::from(0.5);
+            let previous = points[k];
+            (points[k], normals[k]) = project(k, m);
+            moved = moved.max((points[k] - previous).norm().mid());
+        }
+        if moved <= precision {
+            break;
+        }
+    }
+    Polyline { points, normals }
+}
+
+// Walks along u in small steps and keeps the direction tangential to the surface.
+pub(crate) fn exp(surface: &dyn SurfaceLike, x: Point, u: TangentPoint) -> Point {
+    exp_with(x, u, |p| onto(surface, p))
+}
+
+// Like exp, with a projection that is called for the steps in order.
+pub(crate) fn exp_with(
+    x: Point,
+    u: TangentPoint,
+    mut project: impl FnMut(Point) -> (Point, Point),
+) -> Point {
+    let length = u.norm();
+    if length <= 0.0 {
+        return x;
+    }
+    let step = EFloat64::from(1.0 / GEODESIC_SEGMENTS as f64);
+    let mut p = x;
+    let mut dir = u.mid();
+    for _ in 0..GEODESIC_SEGMENTS {
+        let normal;
+        (p, normal) = project(p + dir * step);
+        match (dir - normal * normal.dot(dir)).normalize() {
+            Ok(tangential) => dir = (tangential * length).mid(),
+            Err(_) => break,
+        }
+    }
+    p
+}
+
+// The direction in which the polyline leaves its first point, as long as the polyline.
+pub(crate) fn log(path: &Polyline) -> Option<TangentPoint> {
+    let normal = path.normals[0];
+    let dir = path.points[1] - path.points[0];
+    let dir = dir - normal * normal.dot(dir);
+    Some(dir.normalize().ok()? * path.length())
+}
+
+// Projects v onto the tangent plane at every point of the polyline and keeps its length.
+pub(crate) fn parallel_transport(v: TangentPoint, path: &Polyline) -> Option<TangentPoint> {
+    let length = v.norm();
+    if length <= 0.0 {
+        return Some(v);
+    }
+    let mut v = v;
+    for normal in path.normals.iter().skip(1) {
+        v = ((v - *normal * normal.dot(v)).normalize().ok()? * length).mid();
+    }
+    Some(v)
+}
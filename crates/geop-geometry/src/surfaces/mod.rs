@@ -1,12 +1,21 @@
 use geop_algebra::efloat::EFloat64;
+use shape_operator::{PrincipalCurvatures, ShapeOperator};
 use surface::{Surface, TangentPoint};
 
 use crate::{curves::curve::Curve, point::Point, transforms::Transform};
 
+pub mod cone;
 pub mod cylinder;
+pub mod ellipsoid;
+pub mod elliptic_cylinder;
+mod geodesic;
+pub mod nurbs_surface;
+pub mod parameter_space;
 pub mod plane;
+pub mod shape_operator;
 pub mod sphere;
 pub mod surface;
+pub mod torus;
 
 pub trait SurfaceLike {
     // Transforms the surface by the given transform.
@@ -18,6 +27,24 @@ pub trait SurfaceLike {
     // Returns the normal of the surface at point p.
     fn normal(&self, p: Point) -> Point;
 
+    // Returns the derivative of the normal along the tangent plane at point p.
+    fn shape_operator(&self, p: Point) -> ShapeOperator;
+
+    // Returns the principal curvatures and their directions at point p.
+    fn principal_curvatures(&self, p: Point) -> PrincipalCurvatures {
+        self.shape_operator(p).principal_curvatures()
+    }
+
+    // Returns the product of the principal curvatures at point p.
+    fn gaussian_curvature(&self, p: Point) -> EFloat64 {
+        self.shape_operator(p).gaussian_curvature()
+    }
+
+    // Returns the mean of the principal curvatures at point p.
+    fn mean_curvature(&self, p: Point) -> EFloat64 {
+        self.shape_operator(p).mean_curvature()
+    }
+
     // Checks if the point p is on the surface.
     fn on_surface(&self, p: Point) -> bool;
 
@@ -0,0 +1,702 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    bounding_box::BoundingBox,
+    curves::{curve::Curve, nurbs::Nurbs},
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    tolerance::Tolerance,
+    transforms::Transform,
+};
+
+use super::{
+    geodesic::{self, Polyline, GEODESIC_SEGMENTS},
+    parameter_space::SurfaceParameterSpace,
+    shape_operator::ShapeOperator,
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// Number of samples per knot span and direction used to find a good starting value for the projection.
+const PROJECTION_SAMPLES_PER_SPAN: usize = 4;
+const NEWTON_ITERATIONS: usize = 32;
+// Step of the central difference for the mixed derivative, relative to the v domain.
+const MIXED_DERIVATIVE_STEP: f64 = 1e-5;
+
+// Sample parameters in every knot span of the domain, including the end of the domain.
+fn sample_parameters(knots: &[EFloat64], degree: usize, n: usize) -> Vec<f64> {
+    let mut samples = Vec::new();
+    for i in degree..n {
+        let a = knots[i].mid();
+        let b = knots[i + 1].mid();
+        if b <= a {
+            continue;
+        }
+        for j in 0..PROJECTION_SAMPLES_PER_SPAN {
+            samples.push(a + (b - a) * j as f64 / PROJECTION_SAMPLES_PER_SPAN as f64);
+        }
+    }
+    samples.push(knots[n].mid());
+    samples
+}
+
+// Tensor product non-uniform rational B-spline surface.
+// The surface is defined on [knots_u[degree_u], knots_u[n_u]] x [knots_v[degree_v], knots_v[n_v]].
+#[derive(Debug, Clone)]
+pub struct NurbsSurface {
+    pub degree_u: usize,
+    pub degree_v: usize,
+    pub knots_u: Vec<EFloat64>,
+    pub knots_v: Vec<EFloat64>,
+    // weights[i][j] belongs to control_points[i][j]. i runs in u direction and j in v direction.
+    pub weights: Vec<Vec<EFloat64>>,
+    pub control_points: Vec<Vec<Point>>,
+}
+
+impl NurbsSurface {
+    pub fn new(
+        degree_u: usize,
+        degree_v: usize,
+        knots_u: Vec<EFloat64>,
+        knots_v: Vec<EFloat64>,
+        weights: Vec<Vec<EFloat64>>,
+        control_points: Vec<Vec<Point>>,
+    ) -> GeometryResult<NurbsSurface> {
+        if control_points.is_empty() || control_points.len() != weights.len() {
+            return Err(GeometryError::new(
+                "Control points and weights have to form a non empty grid".to_string(),
+            ));
+        }
+        let m = control_points[0].len();
+        if control_points
+            .iter()
+            .zip(weights.iter())
+            .any(|(c, w)| c.len() != m || w.len() != m)
+        {
+            return Err(GeometryError::new(
+                "Control points and weights have to form a rectangular grid".to_string(),
+            ));
+        }
+        // The curve constructor checks the knots, weights and degrees of every row and column.
+        for i in 0..control_points.len() {
+            Nurbs::new(
+                degree_v,
+                knots_v.clone(),
+                weights[i].clone(),
+                control_points[i].clone(),
+            )?;
+        }
+        for j in 0..m {
+            Nurbs::new(
+                degree_u,
+                knots_u.clone(),
+                weights.iter().map(|w| w[j]).collect(),
+                control_points.iter().map(|c| c[j]).collect(),
+            )?;
+        }
+        Ok(NurbsSurface {
+            degree_u,
+            degree_v,
+            knots_u,
+            knots_v,
+            weights,
+            control_points,
+        })
+    }
+
+    // Creates a non rational B-spline surface with clamped, uniform knot vectors.
+    pub fn new_uniform_clamped(
+        degree_u: usize,
+        degree_v: usize,
+        control_points: Vec<Vec<Point>>,
+    ) -> GeometryResult<NurbsSurface> {
+        if control_points.is_empty() {
+            return Err(GeometryError::new(
+                "Control points have to form a non empty grid".to_string(),
+            ));
+        }
+        let knots_u =
+            Nurbs::new_uniform_clamped(degree_u, control_points.iter().map(|c| c[0]).collect())?
+                .knots;
+        let knots_v = Nurbs::new_uniform_clamped(degree_v, control_points[0].clone())?.knots;
+        let weights = control_points
+            .iter()
+            .map(|c| vec![EFloat64::one(); c.len()])
+            .collect();
+        NurbsSurface::new(
+            degree_u,
+            degree_v,
+            knots_u,
+            knots_v,
+            weights,
+            control_points,
+        )
+    }
+
+    pub fn domain_u(&self) -> (EFloat64, EFloat64) {
+        (
+            self.knots_u[self.degree_u],
+            self.knots_u[self.control_points.len()],
+        )
+    }
+
+    pub fn domain_v(&self) -> (EFloat64, EFloat64) {
+        (
+            self.knots_v[self.degree_v],
+            self.knots_v[self.control_points[0].len()],
+        )
+    }
+
+    fn domain_f64(&self) -> ((f64, f64), (f64, f64)) {
+        let (u_min, u_max) = self.domain_u();
+        let (v_min, v_max) = self.domain_v();
+        ((u_min.mid(), u_max.mid()), (v_min.mid(), v_max.mid()))
+    }
+
+    // Curve in v direction through the i-th row of control points.
+    fn row(&self, i: usize) -> Nurbs {
+        Nurbs {
+            degree: self.degree_v,
+            knots: self.knots_v.clone(),
+            weights: self.weights[i].clone(),
+            control_points: self.control_points[i].clone(),
+        }
+    }
+
+    // Curve in u direction through the j-th column of control points.
+    fn column(&self, j: usize) -> Nurbs {
+        Nurbs {
+            degree: self.degree_u,
+            knots: self.knots_u.clone(),
+            weights: self.weights.iter().map(|w| w[j]).collect(),
+            control_points: self.control_points.iter().map(|c| c[j]).collect(),
+        }
+    }
+
+    // Iso curve in u direction at the fixed parameter v.
+    pub fn iso_u(&self, v: EFloat64) -> Nurbs {
+        let (control_points, weights) = (0..self.control_points.len())
+            .map(|i| self.row(i).weighted_point_at(v))
+            .unzip();
+        Nurbs {
+            degree: self.degree_u,
+            knots: self.knots_u.clone(),
+            weights,
+            control_points,
+        }
+    }
+
+    // Iso curve in v direction at the fixed parameter u.
+    pub fn iso_v(&self, u: EFloat64) -> Nurbs {
+        let (control_points, weights) = (0..self.control_points[0].len())
+            .map(|j| self.column(j).weighted_point_at(u))
+            .unzip();
+        Nurbs {
+            degree: self.degree_v,
+            knots: self.knots_v.clone(),
+            weights,
+            control_points,
+        }
+    }
+
+    pub fn point_at(&self, u: EFloat64, v: EFloat64) -> Point {
+        self.iso_u(v).point_at(u)
+    }
+
+    // Partial derivatives with respect to u and v.
+    pub fn derivatives_at(&self, u: EFloat64, v: EFloat64) -> (Point, Point) {
+        (
+            self.iso_u(v).derivative_at(u),
+            self.iso_v(u).derivative_at(v),
+        )
+    }
+
+    pub fn normal_at(&self, u: EFloat64, v: EFloat64) -> Point {
+        let (du, dv) = self.derivatives_at(u, v);
+        du.cross(dv).normalize().unwrap()
+    }
+
+    // Refines the parameters of the point closest to p with gauss newton iterations, starting at (u, v).
+    pub fn parameter_near(&self, p: Point, u: f64, v: f64) -> (f64, f64) {
+        let ((u_min, u_max), (v_min, v_max)) = self.domain_f64();
+        let (mut u, mut v) = (u, v);
+        for _ in 0..NEWTON_ITERATIONS {
+            let (eu, ev) = (EFloat64::from(u), EFloat64::from(v));
+            let d = self.point_at(eu, ev) - p;
+            let (su, sv) = self.derivatives_at(eu, ev);
+            let a = su.dot(su).mid();
+            let b = su.dot(sv).mid();
+            let c = sv.dot(sv).mid();
+            let gu = su.dot(d).mid();
+            let gv = sv.dot(d).mid();
+            let det = a * c - b * b;
+            if !det.is_finite() {
+                break;
+            }
+            // At a pole, e.g. the apex of a cone, one derivative vanishes and only the other direction is refined.
+            let (u_new, v_new) = match (det > f64::EPSILON * a * c, a > 0.0, c > 0.0) {
+                (true, _, _) => (u - (c * gu - b * gv) / det, v - (a * gv - b * gu) / det),
+                (false, _, true) if a <= c => (u, v - gv / c),
+                (false, true, _) => (u - gu / a, v),
+                (false, false, _) => break,
+            };
+            let u_new = u_new.clamp(u_min, u_max);
+            let v_new = v_new.clamp(v_min, v_max);
+            let converged = (u_new - u).abs() <= f64::EPSILON * (u_max - u_min)
+                && (v_new - v).abs() <= f64::EPSILON * (v_max - v_min);
+            u = u_new;
+            v = v_new;
+            if converged {
+                break;
+            }
+        }
+        (u, v)
+    }
+
+    // Finds the parameters of the point on the surface that is closest to p.
+    pub fn parameter(&self, p: Point) -> (EFloat64, EFloat64) {
+        let ((u_min, u_max), (v_min, v_max)) = self.domain_f64();
+        let (u, v) = self.closest_parameter(p);
+        let parametric = Tolerance::current().parametric;
+        let eps_u = parametric * (u_max - u_min);
+        let eps_v = parametric * (v_max - v_min);
+        (
+            EFloat64::new((u + eps_u).min(u_max), (u - eps_u).max(u_min)),
+            EFloat64::new((v + eps_v).min(v_max), (v - eps_v).max(v_min)),
+        )
+    }
+
+    // The refined parameters without error bounds. The bounds of parameter are cut at the boundary of the domain, so their middle is not these parameters there.
+    fn closest_parameter(&self, p: Point) -> (f64, f64) {
+        let ((u_min, _), (v_min, _)) = self.domain_f64();
+        let dist_sq = |u: f64, v: f64| -> f64 {
+            (self.point_at(EFloat64::from(u), EFloat64::from(v)) - p)
+                .norm_sq()
+                .mid()
+        };
+
+        // Find a good starting value by sampling every knot span.
+        let mut best = (u_min, v_min);
+        let mut best_dist = dist_sq(u_min, v_min);
+        let mut samples = Vec::new();
+        let samples_v =
+            sample_parameters(&self.knots_v, self.degree_v, self.control_points[0].len());
+        for u in sample_parameters(&self.knots_u, self.degree_u, self.control_points.len()) {
+            let curve = self.iso_v(EFloat64::from(u));
+            for v in samples_v.iter() {
+                let d = (curve.point_at(EFloat64::from(*v)) - p).norm_sq().mid();
+                samples.push((u, *v, d));
+                if d < best_dist {
+                    best_dist = d;
+                    best = (u, *v);
+                }
+            }
+        }
+        // At a pole, e.g. the apex of a cone, all samples of u give the same point. The direction towards p is taken from the closest sample off the pole.
+        let (su, sv) = self.derivatives_at(EFloat64::from(best.0), EFloat64::from(best.1));
+        if su.norm().mid() <= f64::EPSILON * sv.norm().mid() {
+            if let Some((u, _, _)) = samples
+                .iter()
+                .filter(|(_, v, _)| *v != best.1)
+                .min_by(|a, b| a.2.total_cmp(&b.2))
+            {
+                best.0 = *u;
+            }
+        }
+
+        let (mut u, mut v) = self.parameter_near(p, best.0, best.1);
+        if dist_sq(u, v) > best_dist {
+            (u, v) = best;
+        }
+        (u, v)
+    }
+
+    // Returns the part of the surface inside of the given parameter ranges as a new surface.
+    pub fn subsurface(
+        &self,
+        u_start: EFloat64,
+        u_end: EFloat64,
+        v_start: EFloat64,
+        v_end: EFloat64,
+    ) -> NurbsSurface {
+        let rows: Vec<Nurbs> = (0..self.control_points.len())
+            .map(|i| self.row(i).subcurve(v_start, v_end))
+            .collect();
+        let m = rows[0].control_points.len();
+        let columns: Vec<Nurbs> = (0..m)
+            .map(|j| {
+                Nurbs {
+                    degree: self.degree_u,
+                    knots: self.knots_u.clone(),
+                    weights: rows.iter().map(|r| r.weights[j]).collect(),
+                    control_points: rows.iter().map(|r| r.control_points[j]).collect(),
+                }
+                .subcurve(u_start, u_end)
+            })
+            .collect();
+        let n = columns[0].control_points.len();
+        NurbsSurface {
+            degree_u: self.degree_u,
+            degree_v: self.degree_v,
+            knots_u: columns[0].knots.clone(),
+            knots_v: rows[0].knots.clone(),
+            weights: (0..n)
+                .map(|i| columns.iter().map(|c| c.weights[i]).collect())
+                .collect(),
+            control_points: (0..n)
+                .map(|i| columns.iter().map(|c| c.control_points[i]).collect())
+                .collect(),
+        }
+    }
+
+    // Uses the convex hull property, the surface lies inside of the bounding box of its control points.
+    pub fn get_bounding_box(&self) -> BoundingBox {
+        let mut bounding_box =
+            BoundingBox::new(self.control_points[0][0], self.control_points[0][0]);
+        for p in self.control_points.iter().flatten() {
+            bounding_box.add_point(*p);
+        }
+        bounding_box
+    }
+
+    // Approximates the shortest path between p and q by a polyline, starting with the straight line in parameter space.
+    fn geodesic_points(&self, p: Point, q: Point) -> Polyline {
+        let (pu, pv) = self.parameter(p);
+        let (qu, qv) = self.parameter(q);
+        let (pu, pv, qu, qv) = (pu.mid(), pv.mid(), qu.mid(), qv.mid());
+        let mut parameters: Vec<(f64, f64)> = (0..=GEODESIC_SEGMENTS)
+            .map(|k| {
+                let t = k as f64 / GEODESIC_SEGMENTS as f64;
+                (pu + t * (qu - pu), pv + t * (qv - pv))
+            })
+            .collect();
+        let mut points: Vec<Point> = parameters
+            .iter()
+            .map(|(u, v)| self.point_at(EFloat64::from(*u), EFloat64::from(*v)))
+            .collect();
+        points[0] = p;
+        points[GEODESIC_SEGMENTS] = q;
+        geodesic::shorten_with(points, |k, m| {
+            let (u, v) = self.parameter_near(m, parameters[k].0, parameters[k].1);
+            parameters[k] = (u, v);
+            let (u, v) = (EFloat64::from(u), EFloat64::from(v));
+            (self.point_at(u, v), self.normal_at(u, v))
+        })
+    }
+
+    pub fn transform(&self, transform: Transform) -> NurbsSurface {
+        NurbsSurface {
+            degree_u: self.degree_u,
+            degree_v: self.degree_v,
+            knots_u: self.knots_u.clone(),
+            knots_v: self.knots_v.clone(),
+            weights: self.weights.clone(),
+            control_points: self
+                .control_points
+                .iter()
+                .map(|c| c.iter().map(|p| transform * *p).collect())
+                .collect(),
+        }
+    }
+
+    // Reverses the u direction, which flips the normal.
+    fn neg(&self) -> NurbsSurface {
+        let (start, end) = self.domain_u();
+        let (start, end) = (start.mid(), end.mid());
+        NurbsSurface {
+            degree_u: self.degree_u,
+            degree_v: self.degree_v,
+            knots_u: self
+                .knots_u
+                .iter()
+                .rev()
+                .map(|k| EFloat64::from(start + end - (*k).mid()))
+                .collect(),
+            knots_v: self.knots_v.clone(),
+            weights: self.weights.iter().rev().cloned().collect(),
+            control_points: self.control_points.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+impl SurfaceLike for NurbsSurface {
+    fn transform(&self, transform: Transform) -> Surface {
+        Surface::Nurbs(self.transform(transform))
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::Nurbs(self.neg())
+    }
+
+    fn normal(&self, p: Point) -> Point {
+        let (u, v) = self.parameter(p);
+        self.normal_at(EFloat64::from(u.mid()), EFloat64::from(v.mid()))
+    }
+
+    // The mixed derivative is approximated by a central difference of the derivative in u.
+    fn shape_operator(&self, p: Point) -> ShapeOperator {
+        let (u, v) = self.parameter(p);
+        let (u, v) = (EFloat64::from(u.mid()), EFloat64::from(v.mid()));
+        let (du, dv) = self.derivatives_at(u, v);
+        let duu = self.iso_u(v).derivatives_at(u, 2)[2];
+        let dvv = self.iso_v(u).derivatives_at(v, 2)[2];
+        let (_, (v_min, v_max)) = self.domain_f64();
+        let h = MIXED_DERIVATIVE_STEP * (v_max - v_min);
+        let (v0, v1) = ((v.mid() - h).max(v_min), (v.mid() + h).min(v_max));
+        let duv = (self.iso_u(EFloat64::from(v1)).derivative_at(u)
+            - self.iso_u(EFloat64::from(v0)).derivative_at(u))
+            / EFloat64::from(v1 - v0);
+        ShapeOperator::from_fundamental_forms(
+            self.normal_at(u, v),
+            du,
+            dv,
+            duu,
+            duv.unwrap().mid(),
+            dvv,
+        )
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        let (u, v) = self.parameter(p);
+        self.point_at(u, v) == p
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        self.geodesic_points(x, y).length()
+    }
+
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        let (pu, pv) = self.parameter(x);
+        let mut near = (pu.mid(), pv.mid());
+        geodesic::exp_with(x, u, |p| {
+            near = self.parameter_near(p, near.0, near.1);
+            let (u, v) = (EFloat64::from(near.0), EFloat64::from(near.1));
+            (self.point_at(u, v), self.normal_at(u, v))
+        })
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        if x == y {
+            return Some(Point::zero());
+        }
+        geodesic::log(&self.geodesic_points(x, y))
+    }
+
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        if x == y {
+            return Some(v);
+        }
+        geodesic::parallel_transport(v, &self.geodesic_points(x, y))
+    }
+
+    fn geodesic(&self, x: Point, y: Point) -> Curve {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        assert!(x != y);
+        Curve::Nurbs(Nurbs::polyline(self.geodesic_points(x, y).points).unwrap())
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = (16.0 * density) as usize;
+        let ((u_min, u_max), (v_min, v_max)) = self.domain_f64();
+        let mut points = Vec::with_capacity(n * n);
+        for i in 0..n {
+            let u = u_min + (u_max - u_min) * i as f64 / (n as f64 - 1.0);
+            let curve = self.iso_v(EFloat64::from(u));
+            for j in 0..n {
+                let v = v_min + (v_max - v_min) * j as f64 / (n as f64 - 1.0);
+                points.push(curve.point_at(EFloat64::from(v)));
+            }
+        }
+        points
+    }
+
+    fn project(&self, point: Point) -> Point {
+        let (u, v) = self.closest_parameter(point);
+        self.point_at(EFloat64::from(u), EFloat64::from(v))
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        Some(self.project(point) - point)
+    }
+}
+
+impl PartialEq for NurbsSurface {
+    fn eq(&self, other: &NurbsSurface) -> bool {
+        self.degree_u == other.degree_u
+            && self.degree_v == other.degree_v
+            && self.knots_u.len() == other.knots_u.len()
+            && self.knots_v.len() == other.knots_v.len()
+            && self.control_points.len() == other.control_points.len()
+            && self.control_points[0].len() == other.control_points[0].len()
+            && self
+                .knots_u
+                .iter()
+                .zip(other.knots_u.iter())
+                .all(|(a, b)| a == b)
+            && self
+                .knots_v
+                .iter()
+                .zip(other.knots_v.iter())
+                .all(|(a, b)| a == b)
+            && self
+                .weights
+                .iter()
+                .flatten()
+                .zip(other.weights.iter().flatten())
+                .all(|(a, b)| a == b)
+            && self
+                .control_points
+                .iter()
+                .flatten()
+                .zip(other.control_points.iter().flatten())
+                .all(|(a, b)| a == b)
+    }
+}
+
+// The parameters of the nurbs surface itself.
+impl SurfaceParameterSpace for NurbsSurface {
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64) {
+        self.parameter(p)
+    }
+
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point {
+        self.point_at(u, v)
+    }
+
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>) {
+        (None, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Flat patch covering [0, 2] x [0, 2] in the xy plane.
+    fn flat_patch() -> NurbsSurface {
+        NurbsSurface::new_uniform_clamped(
+            2,
+            2,
+            (0..3)
+                .map(|i| {
+                    (0..3)
+                        .map(|j| Point::from_f64(i as f64, j as f64, 0.0))
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    // Quarter of a cylinder with radius 1 around the z axis as a rational quadratic surface.
+    fn quarter_cylinder() -> NurbsSurface {
+        let w = (EFloat64::two().sqrt().unwrap() / EFloat64::two()).unwrap();
+        let zero = EFloat64::zero();
+        let one = EFloat64::one();
+        NurbsSurface::new(
+            2,
+            1,
+            vec![zero, zero, zero, one, one, one],
+            vec![zero, zero, one, one],
+            vec![vec![one, one], vec![w, w], vec![one, one]],
+            vec![
+                vec![Point::unit_x(), Point::from_f64(1.0, 0.0, 1.0)],
+                vec![
+                    Point::from_f64(1.0, 1.0, 0.0),
+                    Point::from_f64(1.0, 1.0, 1.0),
+                ],
+                vec![Point::unit_y(), Point::from_f64(0.0, 1.0, 1.0)],
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nurbs_surface_flat() {
+        let surface = flat_patch();
+        let p = Point::from_f64(0.5, 1.5, 0.0);
+        assert!(surface.on_surface(p));
+        assert!(!surface.on_surface(Point::from_f64(0.5, 1.5, 0.1)));
+        assert_eq!(surface.normal(p), Point::unit_z());
+        assert_eq!(surface.project(Point::from_f64(0.5, 1.5, 3.0)), p);
+        assert_eq!(
+            surface
+                .unsigned_l2_squared_distance_gradient(Point::from_f64(0.5, 1.5, 3.0))
+                .unwrap(),
+            Point::from_f64(0.0, 0.0, -3.0)
+        );
+        assert_eq!(surface.point_grid(1.0).len(), 256);
+
+        // Geodesics on a flat patch are straight lines.
+        let q = Point::from_f64(1.5, 0.5, 0.0);
+        let distance = surface.distance(p, q);
+        assert!((distance.lower_bound - 2.0f64.sqrt()).abs() < 1e-6);
+        let u = surface.log(p, q).unwrap();
+        assert!((u - (q - p)).norm() < 1e-6);
+        assert!((surface.exp(p, u) - q).norm() < 1e-6);
+
+        let reversed = surface.neg();
+        assert_eq!(reversed.normal(p), -Point::unit_z());
+    }
+
+    #[test]
+    fn test_nurbs_surface_cylinder() {
+        let surface = quarter_cylinder();
+        for i in 0..=4 {
+            for j in 0..=4 {
+                let p = surface.point_at(
+                    EFloat64::from(i as f64 / 4.0),
+                    EFloat64::from(j as f64 / 4.0),
+                );
+                let radial = Point::new(p.x, p.y, EFloat64::zero());
+                assert_eq!(radial.norm(), 1.0);
+                assert!(surface.on_surface(p));
+                let radial = radial.normalize().unwrap();
+                assert!((surface.normal(p) - radial).norm() < 1e-6);
+            }
+        }
+
+        let p = surface.project(Point::from_f64(2.0, 2.0, 0.5));
+        let s = EFloat64::two().sqrt().unwrap();
+        let s = (EFloat64::one() / s).unwrap();
+        assert_eq!(p, Point::new(s, s, EFloat64::from(0.5)));
+
+        let sub = surface.subsurface(
+            EFloat64::from(0.25),
+            EFloat64::from(0.75),
+            EFloat64::from(0.5),
+            EFloat64::one(),
+        );
+        let (u, v) = (EFloat64::from(0.4), EFloat64::from(0.7));
+        assert_eq!(sub.point_at(u, v), surface.point_at(u, v));
+    }
+
+    #[test]
+    fn test_nurbs_surface_curvature() {
+        let surface = quarter_cylinder();
+        let p = surface.point_at(EFloat64::from(0.3), EFloat64::from(0.6));
+        let principal = surface.principal_curvatures(p);
+        assert!((principal.max - EFloat64::one()).abs() < 1e-6);
+        assert!(principal.min.abs() < 1e-6);
+        assert!((principal.min_direction.cross(Point::unit_z())).norm() < 1e-6);
+        assert!((surface.mean_curvature(p) - EFloat64::from(0.5)).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,77 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::point::Point;
+
+// Opt-in (u, v) parameterisation of a surface, e.g. for triangulation, texture mapping and STEP files.
+// The kernel itself works without parameters. Angles are in (-pi, pi], so periodic surfaces have a seam at pi.
+pub trait SurfaceParameterSpace {
+    // Returns the (u, v) parameters of the point p on the surface.
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64);
+
+    // Returns the point of the surface at the parameters (u, v).
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point;
+
+    // Returns the periods of u and v, or None if the parameter does not wrap around.
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>);
+
+    // Checks if u is arbitrary at p, e.g. at the poles of a sphere or the apex of a cone.
+    fn is_uv_singular(&self, _p: Point) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::surfaces::{
+        cone::Cone, cylinder::Cylinder, plane::Plane, sphere::Sphere, surface::Surface,
+        torus::Torus, SurfaceLike,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_uv_round_trip() {
+        let surfaces = [
+            Surface::Plane(Plane::new(
+                Point::unit_z(),
+                Point::unit_x(),
+                Point::from_f64(1.0, 1.0, 0.0),
+            )),
+            Surface::Sphere(Sphere::new(Point::unit_x(), EFloat64::two(), true)),
+            Surface::Cylinder(Cylinder::new(
+                Point::zero(),
+                Point::unit_y(),
+                EFloat64::one(),
+                true,
+            )),
+            Surface::Cone(Cone::new(
+                Point::zero(),
+                Point::unit_z(),
+                EFloat64::from(0.5),
+                true,
+            )),
+            Surface::Torus(Torus::new(
+                Point::zero(),
+                Point::unit_z(),
+                EFloat64::two(),
+                EFloat64::one(),
+                true,
+            )),
+        ];
+        for surface in surfaces.iter() {
+            for (u, v) in [(0.3, 0.7), (-2.5, 1.2), (3.0, 2.5)] {
+                let p = surface.point_at_uv(EFloat64::from(u), EFloat64::from(v));
+                assert!(surface.on_surface(p));
+                assert!(!surface.is_uv_singular(p));
+                let (u2, v2) = surface.uv(p);
+                assert!((u2 - EFloat64::from(u)).abs() < 1e-9);
+                assert!((v2 - EFloat64::from(v)).abs() < 1e-9);
+            }
+        }
+
+        // The poles of the sphere have no defined u.
+        let sphere = Sphere::new(Point::zero(), EFloat64::one(), true);
+        assert!(sphere.is_uv_singular(Point::unit_z()));
+        assert_eq!(sphere.uv_periods(), (Some(EFloat64::two_pi()), None));
+    }
+}
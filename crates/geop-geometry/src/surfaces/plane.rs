@@ -3,11 +3,14 @@ use geop_algebra::efloat::EFloat64;
 use crate::{
     curves::{curve::Curve, line::Line},
     point::Point,
+    tolerance::Tolerance,
     transforms::Transform,
     HORIZON_DIST,
 };
 
 use super::{
+    parameter_space::SurfaceParameterSpace,
+    shape_operator::ShapeOperator,
     surface::{Surface, TangentPoint},
     SurfaceLike,
 };
@@ -83,11 +86,20 @@ impl SurfaceLike for Plane {
         Surface::Plane(self.neg())
     }
 
+    fn shape_operator(&self, p: Point) -> ShapeOperator {
+        assert!(self.on_surface(p));
+        ShapeOperator::flat(self.normal().normalize().unwrap())
+    }
+
     fn on_surface(&self, p: Point) -> bool {
         let normal = self.normal();
         let p_project = p.dot(normal);
         let b_project = self.basis.dot(normal);
-        (p_project - b_project) == 0.0
+        // The normal is not normalized, so the projection is scaled to a distance.
+        match (p_project - b_project) / normal.norm() {
+            Ok(distance) => Tolerance::current().is_zero_length(distance),
+            Err(_) => p_project == b_project,
+        }
     }
 
     fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
@@ -147,3 +159,25 @@ impl PartialEq for Plane {
         self.normal() == other.normal() && (self.basis - other.basis).dot(self.normal()) == 0.0
     }
 }
+
+// u and v are the coordinates along the slopes, which need not be orthogonal.
+impl SurfaceParameterSpace for Plane {
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64) {
+        let d = p - self.basis;
+        let (du, dv) = (d.dot(self.u_slope), d.dot(self.v_slope));
+        let cos = self.u_slope.dot(self.v_slope);
+        let det = EFloat64::one() - cos * cos;
+        (
+            ((du - cos * dv) / det).unwrap(),
+            ((dv - cos * du) / det).unwrap(),
+        )
+    }
+
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point {
+        self.basis + self.u_slope * u + self.v_slope * v
+    }
+
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>) {
+        (None, None)
+    }
+}
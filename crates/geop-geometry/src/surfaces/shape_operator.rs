@@ -0,0 +1,313 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::point::Point;
+
+use super::surface::TangentPoint;
+
+// Some unit vector that is orthogonal to the normal.
+fn perpendicular(normal: Point) -> Point {
+    match normal.cross(Point::unit_x()).norm_sq().lower_bound
+        > normal.cross(Point::unit_y()).norm_sq().lower_bound
+    {
+        true => normal.cross(Point::unit_x()).normalize().unwrap(),
+        false => normal.cross(Point::unit_y()).normalize().unwrap(),
+    }
+}
+
+// The shape operator is the derivative of the unit normal along a tangent vector. It is a symmetric map of the tangent plane,
+// which is stored by its entries in an orthonormal basis (u, v) of the tangent plane with u x v = normal.
+// Curvatures are positive where the surface bends away from its normal, e.g. 1 / r on a sphere with outward normals.
+#[derive(Debug, Clone)]
+pub struct ShapeOperator {
+    pub u: TangentPoint,
+    pub v: TangentPoint,
+    pub uu: EFloat64,
+    pub uv: EFloat64,
+    pub vv: EFloat64,
+}
+
+// Principal curvatures with their unit directions. The maximal curvature is at least the minimal one.
+#[derive(Debug, Clone)]
+pub struct PrincipalCurvatures {
+    pub max: EFloat64,
+    pub min: EFloat64,
+    pub max_direction: TangentPoint,
+    pub min_direction: TangentPoint,
+}
+
+impl ShapeOperator {
+    // The shape operator of a surface which bends by the given curvatures along the orthonormal tangent directions u and v.
+    pub fn from_principal(
+        u: TangentPoint,
+        u_curvature: EFloat64,
+        v: TangentPoint,
+        v_curvature: EFloat64,
+    ) -> ShapeOperator {
+        ShapeOperator {
+            u,
+            v,
+            uu: u_curvature,
+            uv: EFloat64::zero(),
+            vv: v_curvature,
+        }
+    }
+
+    // The shape operator of a surface that does not bend at all.
+    pub fn flat(normal: Point) -> ShapeOperator {
+        let u = perpendicular(normal);
+        ShapeOperator::from_principal(u, EFloat64::zero(), normal.cross(u), EFloat64::zero())
+    }
+
+    // The shape operator of a surface that bends by the same curvature in all directions.
+    pub fn umbilic(normal: Point, curvature: EFloat64) -> ShapeOperator {
+        let u = perpendicular(normal);
+        ShapeOperator::from_principal(u, curvature, normal.cross(u), curvature)
+    }
+
+    // The shape operator from a map that gives the derivative of the normal along a tangent vector.
+    // Only the tangential part of the map is used, so it may also be the derivative of a normal that is not normalized, divided by its length.
+    pub fn from_map(normal: Point, map: impl Fn(TangentPoint) -> Point) -> ShapeOperator {
+        let u = perpendicular(normal);
+        let v = normal.cross(u);
+        let (mu, mv) = (map(u), map(v));
+        ShapeOperator {
+            u,
+            v,
+            uu: u.dot(mu),
+            uv: (u.dot(mv) + v.dot(mu)) * EFloat64::from(0.5),
+            vv: v.dot(mv),
+        }
+    }
+
+    // The shape operator of a parameterised surface from its first and second partial derivatives and its unit normal.
+    // Tangent vectors are expressed in the basis of the partial derivatives with the first fundamental form I, and then paired by the second one II.
+    pub fn from_fundamental_forms(
+        normal: Point,
+        du: Point,
+        dv: Point,
+        duu: Point,
+        duv: Point,
+        dvv: Point,
+    ) -> ShapeOperator {
+        let (e, f, g) = (du.dot(du), du.dot(dv), dv.dot(dv));
+        let (l, m, n) = (duu.dot(normal), duv.dot(normal), dvv.dot(normal));
+        let det = e * g - f * f;
+        // Coefficients of t in the basis du, dv.
+        let coefficients = move |t: Point| {
+            let (tu, tv) = (t.dot(du), t.dot(dv));
+            (
+                ((g * tu - f * tv) / det).unwrap(),
+                ((e * tv - f * tu) / det).unwrap(),
+            )
+        };
+        // Second fundamental form of the tangent vectors t and s. Since the normal is orthogonal to the surface, <S t, s> = -II(t, s).
+        let second = move |t: Point, s: Point| {
+            let (ta, tb) = coefficients(t);
+            let (sa, sb) = coefficients(s);
+            l * ta * sa + m * (ta * sb + tb * sa) + n * tb * sb
+        };
+        let u = du.normalize().unwrap();
+        let v = normal.cross(u);
+        ShapeOperator {
+            u,
+            v,
+            uu: -second(u, u),
+            uv: -second(u, v),
+            vv: -second(v, v),
+        }
+    }
+
+    // Applies the shape operator to a tangent vector.
+    pub fn apply(&self, t: TangentPoint) -> TangentPoint {
+        let (a, b) = (t.dot(self.u), t.dot(self.v));
+        self.u * (self.uu * a + self.uv * b) + self.v * (self.uv * a + self.vv * b)
+    }
+
+    // Normal curvature in direction of the unit tangent vector t.
+    pub fn normal_curvature(&self, t: TangentPoint) -> EFloat64 {
+        self.apply(t).dot(t)
+    }
+
+    // Product of the principal curvatures, i.e. the determinant.
+    pub fn gaussian_curvature(&self) -> EFloat64 {
+        self.uu * self.vv - self.uv * self.uv
+    }
+
+    // Mean of the principal curvatures, i.e. half the trace.
+    pub fn mean_curvature(&self) -> EFloat64 {
+        (self.uu + self.vv) * EFloat64::from(0.5)
+    }
+
+    // Eigenvalues and eigenvectors of the symmetric 2x2 matrix.
+    pub fn principal_curvatures(&self) -> PrincipalCurvatures {
+        let mean = self.mean_curvature();
+        let half_diff = (self.uu - self.vv) * EFloat64::from(0.5);
+        let radius = (half_diff * half_diff + self.uv * self.uv).sqrt().unwrap();
+        // If the basis is already principal, it is kept. Otherwise the direction is only defined up to the error bounds, so it is computed from the centers.
+        let (max_direction, min_direction) = match self.uv == 0.0 {
+            true if self.uu.mid() >= self.vv.mid() => (self.u, self.v),
+            true => (self.v, -self.u),
+            false => {
+                let angle = EFloat64::from(
+                    (EFloat64::two() * self.uv)
+                        .mid()
+                        .atan2((self.uu - self.vv).mid())
+                        / 2.0,
+                );
+                (
+                    self.u * angle.cos() + self.v * angle.sin(),
+                    self.v * angle.cos() - self.u * angle.sin(),
+                )
+            }
+        };
+        PrincipalCurvatures {
+            max: mean + radius,
+            min: mean - radius,
+            max_direction,
+            min_direction,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::surfaces::{
+        cone::Cone, cylinder::Cylinder, ellipsoid::Ellipsoid, elliptic_cylinder::EllipticCylinder,
+        plane::Plane, sphere::Sphere, torus::Torus, SurfaceLike,
+    };
+
+    use super::*;
+
+    fn close(a: EFloat64, b: f64) -> bool {
+        (a - EFloat64::from(b)).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_primitive_curvatures() {
+        let plane = Plane::new(Point::zero(), Point::unit_x(), Point::unit_y());
+        let p = Point::from_f64(1.0, 2.0, 0.0);
+        assert!(close(plane.gaussian_curvature(p), 0.0));
+        assert!(close(plane.mean_curvature(p), 0.0));
+
+        // A sphere with outward normals bends away from them, with inward normals towards them.
+        let sphere = Sphere::new(Point::zero(), EFloat64::two(), true);
+        let p = Point::from_f64(0.0, 2.0, 0.0);
+        assert!(close(sphere.gaussian_curvature(p), 0.25));
+        assert!(close(sphere.mean_curvature(p), 0.5));
+        assert!(close(sphere.neg().mean_curvature(p), -0.5));
+
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::two(), true);
+        let p = Point::from_f64(0.0, 2.0, 1.0);
+        let principal = cylinder.principal_curvatures(p);
+        assert!(close(principal.max, 0.5));
+        assert!(close(principal.min, 0.0));
+        assert!(principal.max_direction.is_parallel(Point::unit_x()));
+        assert!(principal.min_direction.is_parallel(Point::unit_z()));
+        assert!(close(cylinder.gaussian_curvature(p), 0.0));
+
+        // At distance r from the axis, the cone bends by cos(half_angle) / r around it.
+        let cone = Cone::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(std::f64::consts::FRAC_PI_4),
+            true,
+        );
+        let p = Point::from_f64(2.0, 0.0, 2.0);
+        let principal = cone.principal_curvatures(p);
+        assert!(close(principal.max, 0.5f64.sqrt() / 2.0));
+        assert!(close(principal.min, 0.0));
+        assert!(principal.max_direction.is_parallel(Point::unit_y()));
+    }
+
+    #[test]
+    fn test_torus_curvatures() {
+        let torus = Torus::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(3.0),
+            EFloat64::one(),
+            true,
+        );
+        // Convex on the outer equator, a saddle on the inner one and parabolic on top.
+        let outer = Point::from_f64(4.0, 0.0, 0.0);
+        assert!(close(torus.gaussian_curvature(outer), 0.25));
+        let inner = Point::from_f64(0.0, 2.0, 0.0);
+        assert!(close(torus.gaussian_curvature(inner), -0.5));
+        let top = Point::from_f64(0.0, -3.0, 1.0);
+        assert!(close(torus.gaussian_curvature(top), 0.0));
+        assert!(close(torus.mean_curvature(top), 0.5));
+
+        // The shape operator of the exact nurbs representation agrees.
+        let nurbs = torus.to_nurbs();
+        let p = Point::from_f64(2.0, 2.0, 0.5);
+        let p = torus.project(p);
+        let (a, b) = (torus.shape_operator(p), nurbs.shape_operator(p));
+        assert!((a.gaussian_curvature() - b.gaussian_curvature()).abs() < 1e-5);
+        assert!((a.mean_curvature() - b.mean_curvature()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_quadric_curvatures() {
+        // At the end of the semi axis a of an ellipsoid, the curvature towards the semi axis b is a / b^2.
+        let ellipsoid = Ellipsoid::new(
+            Point::zero(),
+            [
+                Point::from_f64(3.0, 0.0, 0.0),
+                Point::from_f64(0.0, 2.0, 0.0),
+                Point::from_f64(0.0, 0.0, 1.0),
+            ],
+            true,
+        );
+        let principal = ellipsoid.principal_curvatures(Point::from_f64(3.0, 0.0, 0.0));
+        assert!(close(principal.max, 3.0));
+        assert!(close(principal.min, 0.75));
+        assert!(principal.max_direction.is_parallel(Point::unit_z()));
+        assert!(principal.min_direction.is_parallel(Point::unit_y()));
+
+        // A sphere and the same sphere as an ellipsoid have the same shape operator.
+        let round = Ellipsoid::new(
+            Point::zero(),
+            [Point::unit_x(), Point::unit_y(), Point::unit_z()],
+            false,
+        );
+        let p = Point::from_f64(0.6, 0.0, 0.8);
+        let sphere = Sphere::new(Point::zero(), EFloat64::one(), false);
+        assert!(close(round.mean_curvature(p), -1.0));
+        assert!(close(
+            round.gaussian_curvature(p),
+            sphere.gaussian_curvature(p).lower_bound
+        ));
+
+        let cylinder = EllipticCylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            true,
+        );
+        let principal = cylinder.principal_curvatures(Point::from_f64(0.0, 1.0, 5.0));
+        assert!(close(principal.max, 0.25));
+        assert!(close(principal.min, 0.0));
+        assert!(principal.min_direction.is_parallel(Point::unit_z()));
+    }
+
+    #[test]
+    fn test_shape_operator_from_map() {
+        // The map is symmetrized, and the principal directions are its eigenvectors.
+        let shape = ShapeOperator::from_map(Point::unit_z(), |t| {
+            Point::new(
+                t.x * EFloat64::two() + t.y,
+                t.x + t.y * EFloat64::two(),
+                EFloat64::zero(),
+            )
+        });
+        let principal = shape.principal_curvatures();
+        assert!(close(principal.max, 3.0));
+        assert!(close(principal.min, 1.0));
+        assert!(principal
+            .max_direction
+            .is_parallel(Point::from_f64(1.0, 1.0, 0.0)));
+        assert!(close(shape.normal_curvature(Point::unit_x()), 2.0));
+        assert!((shape.apply(principal.min_direction) - principal.min_direction).norm() < 1e-9);
+    }
+}
@@ -3,10 +3,14 @@ use geop_algebra::efloat::EFloat64;
 use crate::{
     curves::{circle::Circle, curve::Curve},
     point::Point,
+    tolerance::Tolerance,
     transforms::Transform,
 };
 
 use super::{
+    ellipsoid::Ellipsoid,
+    parameter_space::SurfaceParameterSpace,
+    shape_operator::ShapeOperator,
     surface::{Surface, TangentPoint},
     SurfaceLike,
 };
@@ -20,7 +24,7 @@ pub struct Sphere {
 
 pub enum SphereTransform {
     Sphere(Sphere),
-    Ellipsoid(), // TODO: Implement this
+    Ellipsoid(Ellipsoid),
 }
 
 impl Sphere {
@@ -34,8 +38,18 @@ impl Sphere {
 
     fn transform(&self, transform: Transform) -> SphereTransform {
         let basis = transform * self.basis;
-        let radius = self.radius * transform.uniform_scale_factor();
-        SphereTransform::Sphere(Sphere::new(basis, radius, self.normal_outwards))
+        if transform.is_similarity() {
+            let radius = self.radius * transform.uniform_scale_factor();
+            return SphereTransform::Sphere(Sphere::new(basis, radius, self.normal_outwards));
+        }
+        // The images of the radii along the axes are conjugate radii of the ellipsoid.
+        let radii = [Point::unit_x(), Point::unit_y(), Point::unit_z()]
+            .map(|axis| transform * (axis * self.radius + self.basis) - basis);
+        SphereTransform::Ellipsoid(Ellipsoid::from_conjugate(
+            basis,
+            radii,
+            self.normal_outwards,
+        ))
     }
 
     fn normal(&self, p: Point) -> Point {
@@ -56,7 +70,7 @@ impl SurfaceLike for Sphere {
     fn transform(&self, transform: Transform) -> Surface {
         match self.transform(transform) {
             SphereTransform::Sphere(sphere) => Surface::Sphere(sphere),
-            SphereTransform::Ellipsoid() => todo!("Implement this"),
+            SphereTransform::Ellipsoid(ellipsoid) => Surface::Ellipsoid(ellipsoid),
         }
     }
 
@@ -68,10 +82,18 @@ impl SurfaceLike for Sphere {
         Surface::Sphere(self.neg())
     }
 
+    fn shape_operator(&self, p: Point) -> ShapeOperator {
+        let curvature = (EFloat64::one() / self.radius).unwrap();
+        match self.normal_outwards {
+            true => ShapeOperator::umbilic(self.normal(p), curvature),
+            false => ShapeOperator::umbilic(self.normal(p), -curvature),
+        }
+    }
+
     fn on_surface(&self, p: Point) -> bool {
         let diff = p - self.basis;
-        let dist = diff.norm_sq();
-        (dist - self.radius * self.radius) == 0.0
+        let dist = diff.norm();
+        Tolerance::current().is_zero_length(dist - self.radius)
     }
 
     fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
@@ -205,3 +227,25 @@ impl PartialEq for Sphere {
             && self.normal_outwards == other.normal_outwards
     }
 }
+
+// u is the angle around the z axis and v the angle from the z axis.
+impl SurfaceParameterSpace for Sphere {
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64) {
+        let d = p - self.basis;
+        let radial = (d.x * d.x + d.y * d.y).sqrt().unwrap();
+        (d.y.atan2(d.x), radial.atan2(d.z))
+    }
+
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point {
+        self.basis + Point::new(u.cos() * v.sin(), u.sin() * v.sin(), v.cos()) * self.radius
+    }
+
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>) {
+        (Some(EFloat64::two_pi()), None)
+    }
+
+    fn is_uv_singular(&self, p: Point) -> bool {
+        let d = p - self.basis;
+        Tolerance::current().is_zero_length((d.x * d.x + d.y * d.y).sqrt().unwrap())
+    }
+}
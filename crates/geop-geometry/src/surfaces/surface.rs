@@ -2,7 +2,11 @@ use geop_algebra::efloat::EFloat64;
 
 use crate::{curves::curve::Curve, point::Point, transforms::Transform};
 
-use super::{cylinder::Cylinder, plane::Plane, sphere::Sphere, SurfaceLike};
+use super::{
+    cone::Cone, cylinder::Cylinder, ellipsoid::Ellipsoid, elliptic_cylinder::EllipticCylinder,
+    nurbs_surface::NurbsSurface, parameter_space::SurfaceParameterSpace, plane::Plane,
+    shape_operator::ShapeOperator, sphere::Sphere, torus::Torus, SurfaceLike,
+};
 
 pub type TangentPoint = Point;
 
@@ -11,6 +15,11 @@ pub enum Surface {
     Plane(Plane),
     Sphere(Sphere),
     Cylinder(Cylinder),
+    Cone(Cone),
+    Torus(Torus),
+    Ellipsoid(Ellipsoid),
+    EllipticCylinder(EllipticCylinder),
+    Nurbs(NurbsSurface),
 }
 
 impl SurfaceLike for Surface {
@@ -20,6 +29,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.transform(transform),
             Surface::Sphere(sphere) => sphere.transform(transform),
             Surface::Cylinder(cylinder) => cylinder.transform(transform),
+            Surface::Cone(cone) => cone.transform(transform),
+            Surface::Torus(torus) => torus.transform(transform),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.transform(transform),
+            Surface::EllipticCylinder(cylinder) => cylinder.transform(transform),
+            Surface::Nurbs(nurbs) => Surface::Nurbs(nurbs.transform(transform)),
         }
     }
 
@@ -29,6 +43,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.neg(),
             Surface::Sphere(sphere) => sphere.neg(),
             Surface::Cylinder(cylinder) => cylinder.neg(),
+            Surface::Cone(cone) => cone.neg(),
+            Surface::Torus(torus) => torus.neg(),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.neg(),
+            Surface::EllipticCylinder(cylinder) => cylinder.neg(),
+            Surface::Nurbs(nurbs) => nurbs.neg(),
         }
     }
 
@@ -38,14 +57,38 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.normal(p),
             Surface::Sphere(sphere) => sphere.normal(p),
             Surface::Cylinder(cylinder) => cylinder.normal(p),
+            Surface::Cone(cone) => cone.normal(p),
+            Surface::Torus(torus) => torus.normal(p),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.normal(p),
+            Surface::EllipticCylinder(cylinder) => cylinder.normal(p),
+            Surface::Nurbs(nurbs) => nurbs.normal(p),
         }
     }
+    // Returns the derivative of the normal along the tangent plane at point p.
+    fn shape_operator(&self, p: Point) -> ShapeOperator {
+        match self {
+            Surface::Plane(plane) => plane.shape_operator(p),
+            Surface::Sphere(sphere) => sphere.shape_operator(p),
+            Surface::Cylinder(cylinder) => cylinder.shape_operator(p),
+            Surface::Cone(cone) => cone.shape_operator(p),
+            Surface::Torus(torus) => torus.shape_operator(p),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.shape_operator(p),
+            Surface::EllipticCylinder(cylinder) => cylinder.shape_operator(p),
+            Surface::Nurbs(nurbs) => nurbs.shape_operator(p),
+        }
+    }
+
     // Checks if the point p is on the surface.
     fn on_surface(&self, p: Point) -> bool {
         match self {
             Surface::Plane(plane) => plane.on_surface(p),
             Surface::Sphere(sphere) => sphere.on_surface(p),
             Surface::Cylinder(cylinder) => cylinder.on_surface(p),
+            Surface::Cone(cone) => cone.on_surface(p),
+            Surface::Torus(torus) => torus.on_surface(p),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.on_surface(p),
+            Surface::EllipticCylinder(cylinder) => cylinder.on_surface(p),
+            Surface::Nurbs(nurbs) => nurbs.on_surface(p),
         }
     }
 
@@ -55,6 +98,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.metric(x, u, v),
             Surface::Sphere(sphere) => sphere.metric(x, u, v),
             Surface::Cylinder(cylinder) => cylinder.metric(x, u, v),
+            Surface::Cone(cone) => cone.metric(x, u, v),
+            Surface::Torus(torus) => torus.metric(x, u, v),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.metric(x, u, v),
+            Surface::EllipticCylinder(cylinder) => cylinder.metric(x, u, v),
+            Surface::Nurbs(nurbs) => nurbs.metric(x, u, v),
         }
     }
     // Returns the Riemannian distance between x and y.
@@ -63,6 +111,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.distance(x, y),
             Surface::Sphere(sphere) => sphere.distance(x, y),
             Surface::Cylinder(cylinder) => cylinder.distance(x, y),
+            Surface::Cone(cone) => cone.distance(x, y),
+            Surface::Torus(torus) => torus.distance(x, y),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.distance(x, y),
+            Surface::EllipticCylinder(cylinder) => cylinder.distance(x, y),
+            Surface::Nurbs(nurbs) => nurbs.distance(x, y),
         }
     }
     // Exponential of u at base x. u_z is ignored.
@@ -71,6 +124,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.exp(x, u),
             Surface::Sphere(sphere) => sphere.exp(x, u),
             Surface::Cylinder(cylinder) => cylinder.exp(x, u),
+            Surface::Cone(cone) => cone.exp(x, u),
+            Surface::Torus(torus) => torus.exp(x, u),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.exp(x, u),
+            Surface::EllipticCylinder(cylinder) => cylinder.exp(x, u),
+            Surface::Nurbs(nurbs) => nurbs.exp(x, u),
         }
     }
     // Log of y at base x. Z coordinate is set to 0.
@@ -79,6 +137,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.log(x, y),
             Surface::Sphere(sphere) => sphere.log(x, y),
             Surface::Cylinder(cylinder) => cylinder.log(x, y),
+            Surface::Cone(cone) => cone.log(x, y),
+            Surface::Torus(torus) => torus.log(x, y),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.log(x, y),
+            Surface::EllipticCylinder(cylinder) => cylinder.log(x, y),
+            Surface::Nurbs(nurbs) => nurbs.log(x, y),
         }
     }
     // Parallel transport of v from x to y.
@@ -92,6 +155,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.parallel_transport(v, x, y),
             Surface::Sphere(sphere) => sphere.parallel_transport(v, x, y),
             Surface::Cylinder(cylinder) => cylinder.parallel_transport(v, x, y),
+            Surface::Cone(cone) => cone.parallel_transport(v, x, y),
+            Surface::Torus(torus) => torus.parallel_transport(v, x, y),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.parallel_transport(v, x, y),
+            Surface::EllipticCylinder(cylinder) => cylinder.parallel_transport(v, x, y),
+            Surface::Nurbs(nurbs) => nurbs.parallel_transport(v, x, y),
         }
     }
     // Returns the geodesic between p and q.
@@ -100,6 +168,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.geodesic(x, y),
             Surface::Sphere(sphere) => sphere.geodesic(x, y),
             Surface::Cylinder(cylinder) => cylinder.geodesic(x, y),
+            Surface::Cone(cone) => cone.geodesic(x, y),
+            Surface::Torus(torus) => torus.geodesic(x, y),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.geodesic(x, y),
+            Surface::EllipticCylinder(cylinder) => cylinder.geodesic(x, y),
+            Surface::Nurbs(nurbs) => nurbs.geodesic(x, y),
         }
     }
     // Returns a point grid on the surface, which can be used for visualization.
@@ -108,6 +181,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.point_grid(density),
             Surface::Sphere(sphere) => sphere.point_grid(density),
             Surface::Cylinder(cylinder) => cylinder.point_grid(density),
+            Surface::Cone(cone) => cone.point_grid(density),
+            Surface::Torus(torus) => torus.point_grid(density),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.point_grid(density),
+            Surface::EllipticCylinder(cylinder) => cylinder.point_grid(density),
+            Surface::Nurbs(nurbs) => nurbs.point_grid(density),
         }
     }
     // Finds the closest point on the surface to the given point.
@@ -116,6 +194,11 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.project(point),
             Surface::Sphere(sphere) => sphere.project(point),
             Surface::Cylinder(cylinder) => cylinder.project(point),
+            Surface::Cone(cone) => cone.project(point),
+            Surface::Torus(torus) => torus.project(point),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.project(point),
+            Surface::EllipticCylinder(cylinder) => cylinder.project(point),
+            Surface::Nurbs(nurbs) => nurbs.project(point),
         }
     }
 
@@ -125,6 +208,67 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.unsigned_l2_squared_distance_gradient(point),
             Surface::Sphere(sphere) => sphere.unsigned_l2_squared_distance_gradient(point),
             Surface::Cylinder(cylinder) => cylinder.unsigned_l2_squared_distance_gradient(point),
+            Surface::Cone(cone) => cone.unsigned_l2_squared_distance_gradient(point),
+            Surface::Torus(torus) => torus.unsigned_l2_squared_distance_gradient(point),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.unsigned_l2_squared_distance_gradient(point),
+            Surface::EllipticCylinder(cylinder) => {
+                cylinder.unsigned_l2_squared_distance_gradient(point)
+            }
+            Surface::Nurbs(nurbs) => nurbs.unsigned_l2_squared_distance_gradient(point),
+        }
+    }
+}
+
+impl SurfaceParameterSpace for Surface {
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64) {
+        match self {
+            Surface::Plane(plane) => plane.uv(p),
+            Surface::Sphere(sphere) => sphere.uv(p),
+            Surface::Cylinder(cylinder) => cylinder.uv(p),
+            Surface::Cone(cone) => cone.uv(p),
+            Surface::Torus(torus) => torus.uv(p),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.uv(p),
+            Surface::EllipticCylinder(cylinder) => cylinder.uv(p),
+            Surface::Nurbs(nurbs) => nurbs.uv(p),
+        }
+    }
+
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point {
+        match self {
+            Surface::Plane(plane) => plane.point_at_uv(u, v),
+            Surface::Sphere(sphere) => sphere.point_at_uv(u, v),
+            Surface::Cylinder(cylinder) => cylinder.point_at_uv(u, v),
+            Surface::Cone(cone) => cone.point_at_uv(u, v),
+            Surface::Torus(torus) => torus.point_at_uv(u, v),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.point_at_uv(u, v),
+            Surface::EllipticCylinder(cylinder) => cylinder.point_at_uv(u, v),
+            Surface::Nurbs(nurbs) => nurbs.point_at_uv(u, v),
+        }
+    }
+
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>) {
+        match self {
+            Surface::Plane(plane) => plane.uv_periods(),
+            Surface::Sphere(sphere) => sphere.uv_periods(),
+            Surface::Cylinder(cylinder) => cylinder.uv_periods(),
+            Surface::Cone(cone) => cone.uv_periods(),
+            Surface::Torus(torus) => torus.uv_periods(),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.uv_periods(),
+            Surface::EllipticCylinder(cylinder) => cylinder.uv_periods(),
+            Surface::Nurbs(nurbs) => nurbs.uv_periods(),
+        }
+    }
+
+    fn is_uv_singular(&self, p: Point) -> bool {
+        match self {
+            Surface::Plane(plane) => plane.is_uv_singular(p),
+            Surface::Sphere(sphere) => sphere.is_uv_singular(p),
+            Surface::Cylinder(cylinder) => cylinder.is_uv_singular(p),
+            Surface::Cone(cone) => cone.is_uv_singular(p),
+            Surface::Torus(torus) => torus.is_uv_singular(p),
+            Surface::Ellipsoid(ellipsoid) => ellipsoid.is_uv_singular(p),
+            Surface::EllipticCylinder(cylinder) => cylinder.is_uv_singular(p),
+            Surface::Nurbs(nurbs) => nurbs.is_uv_singular(p),
         }
     }
 }
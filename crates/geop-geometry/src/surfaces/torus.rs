@@ -0,0 +1,469 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{circle::Circle, curve::Curve, nurbs::Nurbs},
+    point::Point,
+    tolerance::Tolerance,
+    transforms::Transform,
+};
+
+use super::{
+    geodesic::{self, Polyline, GEODESIC_SEGMENTS},
+    nurbs_surface::NurbsSurface,
+    parameter_space::SurfaceParameterSpace,
+    shape_operator::ShapeOperator,
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// Shortest signed difference between two angles.
+fn angle_diff(from: f64, to: f64) -> f64 {
+    let diff = (to - from).rem_euclid(2.0 * std::f64::consts::PI);
+    match diff > std::f64::consts::PI {
+        true => diff - 2.0 * std::f64::consts::PI,
+        false => diff,
+    }
+}
+
+// A torus around the axis through basis in direction normal.
+// The circle in the middle of the tube has major_radius, the tube itself has minor_radius.
+#[derive(Clone, Debug)]
+pub struct Torus {
+    pub basis: Point,
+    pub normal: Point,
+    pub major_radius: EFloat64,
+    pub minor_radius: EFloat64,
+    pub normal_outwards: bool,
+    ref_dir: Point,
+    dir_cross: Point,
+}
+
+impl Torus {
+    pub fn new(
+        basis: Point,
+        normal: Point,
+        major_radius: EFloat64,
+        minor_radius: EFloat64,
+        normal_outwards: bool,
+    ) -> Torus {
+        assert!(minor_radius > 0.0);
+        assert!(major_radius > minor_radius.upper_bound);
+        let normal = normal.normalize().unwrap();
+        let ref_dir = match Point::unit_x().cross(normal).norm_sq()
+            > Point::unit_y().cross(normal).norm_sq().lower_bound
+        {
+            true => Point::unit_x().cross(normal).normalize().unwrap(),
+            false => Point::unit_y().cross(normal).normalize().unwrap(),
+        };
+        Torus {
+            basis,
+            normal,
+            major_radius,
+            minor_radius,
+            normal_outwards,
+            ref_dir,
+            dir_cross: normal.cross(ref_dir),
+        }
+    }
+
+    // Only similarities keep a torus. Otherwise the image is given by its exact nurbs representation.
+    fn transform(&self, transform: Transform) -> Surface {
+        if !transform.is_similarity() {
+            let original = self.to_nurbs();
+            let nurbs = original.transform(transform);
+            let (u, v) = (nurbs.domain_u().0, nurbs.domain_v().0);
+            // The image of the tube around the spine point is on the inner side of the surface.
+            let spine = transform * self.spine_point(original.point_at(u, v));
+            let outwards = nurbs.normal_at(u, v).dot(nurbs.point_at(u, v) - spine) > 0.0;
+            let surface = Surface::Nurbs(nurbs);
+            return match outwards == self.normal_outwards {
+                true => surface,
+                false => surface.neg(),
+            };
+        }
+        let basis = transform * self.basis;
+        let normal = transform * (self.normal + self.basis) - basis;
+        let scale = transform.uniform_scale_factor();
+        Surface::Torus(Torus::new(
+            basis,
+            normal,
+            self.major_radius * scale,
+            self.minor_radius * scale,
+            self.normal_outwards,
+        ))
+    }
+
+    // Exact representation as the circle through the tube, revolved around the axis. Its normal points outwards.
+    // The control points of the revolved circle are scaled by the distances of the control points of the tube circle to the axis, and the weights are multiplied.
+    pub fn to_nurbs(&self) -> NurbsSurface {
+        let unit_circle = |basis: Point, normal: Point, radius: EFloat64| {
+            Nurbs::from_curve(
+                &Curve::Circle(Circle::new(basis, normal, radius)),
+                None,
+                None,
+            )
+            .unwrap()
+        };
+        let around = unit_circle(Point::zero(), Point::unit_z(), EFloat64::one());
+        // The tube circle in the plane of the distance to the axis (x) and the height (z).
+        let tube = unit_circle(
+            Point::unit_x() * self.major_radius,
+            -Point::unit_y(),
+            self.minor_radius,
+        );
+        let control_points = around
+            .control_points
+            .iter()
+            .map(|q| {
+                let radial = self.ref_dir * q.x + self.dir_cross * q.y;
+                tube.control_points
+                    .iter()
+                    .map(|p| self.basis + radial * p.x + self.normal * p.z)
+                    .collect()
+            })
+            .collect();
+        let weights = around
+            .weights
+            .iter()
+            .map(|v| tube.weights.iter().map(|w| *v * *w).collect())
+            .collect();
+        NurbsSurface::new(2, 2, around.knots, tube.knots, weights, control_points).unwrap()
+    }
+
+    fn neg(&self) -> Self {
+        Torus::new(
+            self.basis,
+            self.normal,
+            self.major_radius,
+            self.minor_radius,
+            !self.normal_outwards,
+        )
+    }
+
+    // Direction from the axis to p, perpendicular to the axis.
+    fn radial_dir(&self, p: Point) -> Point {
+        let p = p - self.basis;
+        let radial = p - p.dot(self.normal) * self.normal;
+        radial.normalize().unwrap_or(self.ref_dir)
+    }
+
+    // Closest point to p on the circle in the middle of the tube.
+    fn spine_point(&self, p: Point) -> Point {
+        self.basis + self.radial_dir(p) * self.major_radius
+    }
+
+    // Angle around the axis and angle around the tube. The second one is 0 on the outer equator.
+    pub fn parameter(&self, p: Point) -> (EFloat64, EFloat64) {
+        let d = p - self.basis;
+        let theta = d.dot(self.dir_cross).atan2(d.dot(self.ref_dir));
+        let height = d.dot(self.normal);
+        let radius = (d - height * self.normal).norm();
+        (theta, height.atan2(radius - self.major_radius))
+    }
+
+    pub fn point_at(&self, theta: EFloat64, psi: EFloat64) -> Point {
+        let radial_dir = self.ref_dir * theta.cos() + self.dir_cross * theta.sin();
+        self.basis
+            + radial_dir * (self.major_radius + self.minor_radius * psi.cos())
+            + self.normal * (self.minor_radius * psi.sin())
+    }
+
+    // Approximates the shortest path between p and q by a polyline, starting with the straight line in parameter space.
+    fn geodesic_points(&self, p: Point, q: Point) -> Polyline {
+        let (p_theta, p_psi) = self.parameter(p);
+        let (q_theta, q_psi) = self.parameter(q);
+        let (p_theta, p_psi) = (p_theta.mid(), p_psi.mid());
+        let d_theta = angle_diff(p_theta, q_theta.mid());
+        let d_psi = angle_diff(p_psi, q_psi.mid());
+        let mut points: Vec<Point> = (0..=GEODESIC_SEGMENTS)
+            .map(|k| {
+                let t = k as f64 / GEODESIC_SEGMENTS as f64;
+                self.point_at(
+                    EFloat64::from(p_theta + t * d_theta),
+                    EFloat64::from(p_psi + t * d_psi),
+                )
+                .mid()
+            })
+            .collect();
+        points[0] = p;
+        points[GEODESIC_SEGMENTS] = q;
+        geodesic::shorten(self, points)
+    }
+}
+
+impl SurfaceLike for Torus {
+    fn transform(&self, transform: Transform) -> Surface {
+        self.transform(transform)
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::Torus(self.neg())
+    }
+
+    fn normal(&self, p: Point) -> Point {
+        let normal = (p - self.spine_point(p)).normalize().unwrap();
+        if self.normal_outwards {
+            normal
+        } else {
+            -normal
+        }
+    }
+
+    // Around the tube the curvature is 1 / minor_radius. Around the axis it is cos(angle around the tube) / distance to the axis, which is negative on the inner half.
+    fn shape_operator(&self, p: Point) -> ShapeOperator {
+        let normal = self.normal(p);
+        let radial_dir = self.radial_dir(p);
+        let d = p - self.basis;
+        let distance = (d - self.normal * d.dot(self.normal)).norm();
+        let tube = ((p - self.spine_point(p)).dot(radial_dir) / self.minor_radius).unwrap();
+        let (around_axis, around_tube) = (
+            (tube / distance).unwrap(),
+            (EFloat64::one() / self.minor_radius).unwrap(),
+        );
+        let (around_axis, around_tube) = match self.normal_outwards {
+            true => (around_axis, around_tube),
+            false => (-around_axis, -around_tube),
+        };
+        let along_axis = self.normal.cross(radial_dir);
+        ShapeOperator::from_principal(
+            along_axis,
+            around_axis,
+            normal.cross(along_axis),
+            around_tube,
+        )
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        Tolerance::current().is_zero_length((p - self.spine_point(p)).norm() - self.minor_radius)
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        self.geodesic_points(x, y).length()
+    }
+
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        geodesic::exp(self, x, u)
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        if x == y {
+            return Some(Point::zero());
+        }
+        geodesic::log(&self.geodesic_points(x, y))
+    }
+
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        if x == y {
+            return Some(v);
+        }
+        geodesic::parallel_transport(v, &self.geodesic_points(x, y))
+    }
+
+    // Meridians and the equators are circles. All other geodesics are approximated.
+    fn geodesic(&self, x: Point, y: Point) -> Curve {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        assert!(x != y);
+        let (x_theta, x_psi) = self.parameter(x);
+        let (y_theta, y_psi) = self.parameter(y);
+        // The circles are oriented such that they run the short way from x to y.
+        let short_way =
+            |basis: Point, normal: Point| match (x - basis).cross(y - basis).dot(normal) < 0.0 {
+                true => -normal,
+                false => normal,
+            };
+        if x_theta == y_theta {
+            let basis = self.spine_point(x);
+            let normal = short_way(basis, self.normal.cross(self.radial_dir(x)));
+            return Curve::Circle(Circle::new(basis, normal, self.minor_radius));
+        }
+        if x_psi.sin() == 0.0 && y_psi.sin() == 0.0 && x_psi.cos() == y_psi.cos() {
+            let radius = self.major_radius + self.minor_radius * x_psi.cos();
+            let normal = short_way(self.basis, self.normal);
+            return Curve::Circle(Circle::new(self.basis, normal, radius));
+        }
+        Curve::Nurbs(Nurbs::polyline(self.geodesic_points(x, y).points).unwrap())
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = (16.0 * density) as usize;
+        let mut points = Vec::with_capacity(n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let theta = EFloat64::from(2.0 * std::f64::consts::PI * i as f64 / n as f64);
+                let psi = EFloat64::from(2.0 * std::f64::consts::PI * j as f64 / n as f64);
+                points.push(self.point_at(theta, psi));
+            }
+        }
+        points
+    }
+
+    fn project(&self, point: Point) -> Point {
+        let spine = self.spine_point(point);
+        match (point - spine).normalize() {
+            Ok(dir) => spine + dir * self.minor_radius,
+            Err(_) => spine + self.normal * self.minor_radius,
+        }
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        Some(self.project(point) - point)
+    }
+}
+
+impl PartialEq for Torus {
+    fn eq(&self, other: &Torus) -> bool {
+        self.basis == other.basis
+            && self.normal == other.normal
+            && self.major_radius == other.major_radius
+            && self.minor_radius == other.minor_radius
+            && self.normal_outwards == other.normal_outwards
+    }
+}
+
+// u is the angle around the axis and v the angle around the tube.
+impl SurfaceParameterSpace for Torus {
+    fn uv(&self, p: Point) -> (EFloat64, EFloat64) {
+        self.parameter(p)
+    }
+
+    fn point_at_uv(&self, u: EFloat64, v: EFloat64) -> Point {
+        self.point_at(u, v)
+    }
+
+    fn uv_periods(&self) -> (Option<EFloat64>, Option<EFloat64>) {
+        (Some(EFloat64::two_pi()), Some(EFloat64::two_pi()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::CurveLike;
+
+    fn torus() -> Torus {
+        Torus::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(3.0),
+            EFloat64::one(),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_torus_on_surface_and_project() {
+        let torus = torus();
+        assert!(torus.on_surface(Point::from_f64(4.0, 0.0, 0.0)));
+        assert!(torus.on_surface(Point::from_f64(0.0, -2.0, 0.0)));
+        assert!(torus.on_surface(Point::from_f64(3.0, 0.0, 1.0)));
+        assert!(!torus.on_surface(Point::from_f64(3.0, 0.0, 0.0)));
+        assert!(!torus.on_surface(Point::from_f64(0.0, 0.0, 1.0)));
+
+        assert_eq!(
+            torus.project(Point::from_f64(0.0, 6.0, 0.0)),
+            Point::from_f64(0.0, 4.0, 0.0)
+        );
+        assert_eq!(
+            torus.normal(Point::from_f64(2.0, 0.0, 0.0)),
+            Point::from_f64(-1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            torus.normal(Point::from_f64(3.0, 0.0, 1.0)),
+            Point::from_f64(0.0, 0.0, 1.0)
+        );
+
+        let (theta, psi) = torus.parameter(Point::from_f64(0.0, 3.0, 1.0));
+        assert_eq!(theta, EFloat64::half_pi());
+        assert_eq!(psi, EFloat64::half_pi());
+    }
+
+    #[test]
+    fn test_torus_transform() {
+        let torus = torus();
+        let nurbs = torus.to_nurbs();
+        for (u, v) in [(0.1, 0.2), (0.5, 0.7), (0.9, 0.35)] {
+            let p = nurbs.point_at(EFloat64::from(u), EFloat64::from(v));
+            assert!(torus.on_surface(p));
+            assert!(
+                nurbs
+                    .normal_at(EFloat64::from(u), EFloat64::from(v))
+                    .dot(torus.normal(p))
+                    > 0.0
+            );
+        }
+
+        // Stretching the torus gives the stretched nurbs.
+        let stretch = Transform::from_scale(Point::from_f64(1.0, 2.0, 1.0));
+        let Surface::Nurbs(stretched) = torus.transform(stretch) else {
+            panic!("Expected a nurbs surface");
+        };
+        let p = stretched.point_at(EFloat64::from(0.3), EFloat64::from(0.6));
+        assert!(torus.on_surface(Transform::from_scale(Point::from_f64(1.0, 0.5, 1.0)) * p));
+        assert!(stretched.on_surface(Point::from_f64(0.0, 8.0, 0.0)));
+        // The normal still points outwards.
+        assert!(stretched.normal(Point::from_f64(0.0, 8.0, 0.0)).y > 0.0);
+        assert!(
+            matches!(torus.neg().transform(stretch), Surface::Nurbs(n) if n.normal(Point::from_f64(0.0, 8.0, 0.0)).y < 0.0)
+        );
+
+        // Rotations and uniform scales keep the torus.
+        let rotation =
+            Transform::from_axis_angle(Point::zero(), Point::unit_x(), EFloat64::from(0.5));
+        assert!(matches!(torus.transform(rotation), Surface::Torus(_)));
+    }
+
+    #[test]
+    fn test_torus_geodesics() {
+        let torus = torus();
+
+        // The outer equator is a geodesic.
+        let x = Point::from_f64(4.0, 0.0, 0.0);
+        let y = Point::from_f64(0.0, 4.0, 0.0);
+        assert!(matches!(torus.geodesic(x, y), Curve::Circle(_)));
+
+        // So are the meridians.
+        let y = Point::from_f64(3.0, 0.0, 1.0);
+        let Curve::Circle(circle) = torus.geodesic(x, y) else {
+            panic!("Expected a circle");
+        };
+        assert!(circle.on_curve(x));
+        assert!(circle.on_curve(y));
+        // The circle runs the short way from x to y.
+        assert!(circle.tangent(x).unwrap().dot(Point::unit_z()) > 0.0);
+        let u = torus.log(x, y).unwrap();
+        assert!((u.norm() - EFloat64::half_pi()).abs() < 1e-3);
+        assert!((torus.exp(x, u) - y).norm() < 1e-2);
+
+        // Any other geodesic stays on the torus.
+        let y = Point::from_f64(0.0, 3.0, 1.0);
+        let Curve::Nurbs(geodesic) = torus.geodesic(x, y) else {
+            panic!("Expected a polyline");
+        };
+        for p in geodesic.control_points.iter() {
+            assert!((torus.project(*p) - *p).norm() < 1e-9);
+        }
+        let v = torus
+            .parallel_transport(Some(Point::unit_z()), x, y)
+            .unwrap();
+        assert!((v.norm() - EFloat64::one()).abs() < 1e-9);
+        assert!(v.dot(torus.normal(y)).abs() < 1e-9);
+    }
+}
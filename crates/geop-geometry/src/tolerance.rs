@@ -0,0 +1,115 @@
+use std::cell::Cell;
+
+use geop_algebra::efloat::EFloat64;
+
+// Tolerances used to decide whether geometry coincides, e.g. whether a point is on a curve.
+// The default has no linear and angular tolerance, so values are only equal if their intervals overlap.
+// The tolerance is ambient instead of being passed along, because comparisons like Point::eq cannot take it as an argument. A model with its own tolerance runs its operations in Tolerance::apply.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    // Distances up to this are zero.
+    pub linear: f64,
+    // Angles in radians up to this are zero.
+    pub angular: f64,
+    // Width of the parameter intervals of projections, relative to the parameter domain.
+    pub parametric: f64,
+    // Distance to which numerical solvers, e.g. root finding and marching, refine their results.
+    pub numerical: f64,
+}
+
+const DEFAULT: Tolerance = Tolerance {
+    linear: 0.0,
+    angular: 0.0,
+    parametric: 1e-10,
+    numerical: 1e-9,
+};
+
+thread_local! {
+    static CURRENT: Cell<Tolerance> = const { Cell::new(DEFAULT) };
+}
+
+// Restores the previous tolerance, also if the operation panics.
+struct Restore(Tolerance);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|c| c.set(self.0));
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        DEFAULT
+    }
+}
+
+impl Tolerance {
+    // The numerical tolerance has to be positive, since iterative solvers only reach their result up to rounding.
+    pub fn new(linear: f64, angular: f64, parametric: f64, numerical: f64) -> Tolerance {
+        assert!(linear >= 0.0 && angular >= 0.0 && parametric >= 0.0 && numerical > 0.0);
+        Tolerance {
+            linear,
+            angular,
+            parametric,
+            numerical,
+        }
+    }
+
+    // The tolerance used by the operations on this thread.
+    pub fn current() -> Tolerance {
+        CURRENT.with(|c| c.get())
+    }
+
+    // Sets the tolerance of the model, which is used by all following operations on this thread.
+    pub fn set_current(tolerance: Tolerance) {
+        CURRENT.with(|c| c.set(tolerance));
+    }
+
+    // Runs a single operation with this tolerance.
+    pub fn apply<T>(self, operation: impl FnOnce() -> T) -> T {
+        let _restore = Restore(Tolerance::current());
+        Tolerance::set_current(self);
+        operation()
+    }
+
+    // Whether a distance might be zero.
+    pub fn is_zero_length(&self, distance: EFloat64) -> bool {
+        distance.lower_bound <= self.linear && distance.upper_bound >= -self.linear
+    }
+
+    // Whether an angle with the given sine might be zero.
+    pub fn is_zero_angle(&self, sine: EFloat64) -> bool {
+        let limit = self.angular.min(std::f64::consts::FRAC_PI_2).sin();
+        sine.lower_bound <= limit && sine.upper_bound >= -limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{circle::Circle, CurveLike},
+        point::Point,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_tolerance() {
+        let p = Point::from_f64(1.0, 0.0, 0.0);
+        let q = Point::from_f64(1.0, 1e-6, 0.0);
+        let circle = Circle::new(Point::zero(), Point::unit_z(), EFloat64::one());
+        assert!(p != q);
+        assert!(!circle.on_curve(Point::from_f64(1.0 + 1e-6, 0.0, 0.0)));
+        Tolerance::new(1e-5, 1e-5, 1e-10, 1e-9).apply(|| {
+            assert!(p == q);
+            assert!(circle.on_curve(Point::from_f64(1.0 + 1e-6, 0.0, 0.0)));
+            assert!(Point::unit_x().is_parallel(Point::from_f64(1.0, 1e-6, 0.0)));
+        });
+        assert!(p != q);
+        assert!(!Point::unit_x().is_parallel(Point::from_f64(1.0, 1e-6, 0.0)));
+        assert_eq!(Tolerance::current(), Tolerance::default());
+        Tolerance::new(0.0, 0.0, 1e-10, 1e-6).apply(|| {
+            assert_eq!(Tolerance::current().numerical, 1e-6);
+        });
+    }
+}
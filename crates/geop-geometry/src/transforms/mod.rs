@@ -1,8 +1,11 @@
 use std::{fmt::Display, ops::Mul};
 
-use geop_algebra::efloat::EFloat64;
+use geop_algebra::{efloat::EFloat64, matrix::Matrix};
 
-use crate::point::Point;
+use crate::{
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Transform {
@@ -98,6 +101,32 @@ impl Transform {
         Transform { matrix }
     }
 
+    // Rotation by angle around the axis through axis_point in direction axis_dir, counter clockwise when looking against axis_dir.
+    pub fn from_axis_angle(axis_point: Point, axis_dir: Point, angle: EFloat64) -> Transform {
+        let k = axis_dir.normalize().unwrap();
+        let k = [k.x, k.y, k.z];
+        let (sin, cos) = (angle.sin(), angle.cos());
+        // Rodrigues' formula: cos * I + sin * [k]_x + (1 - cos) * k k^T
+        let cross = [
+            [EFloat64::zero(), -k[2], k[1]],
+            [k[2], EFloat64::zero(), -k[0]],
+            [-k[1], k[0], EFloat64::zero()],
+        ];
+        let mut matrix = [[EFloat64::zero(); 4]; 4];
+        for i in 0..3 {
+            for j in 0..3 {
+                matrix[i][j] = sin * cross[i][j] + (EFloat64::one() - cos) * k[i] * k[j];
+            }
+            matrix[i][i] = matrix[i][i] + cos;
+        }
+        matrix[3][3] = EFloat64::one();
+        let rotation = Transform { matrix };
+        // Rotate around the origin and move the axis back into place.
+        Transform::from_translation(axis_point)
+            * rotation
+            * Transform::from_translation(-axis_point)
+    }
+
     pub fn from_scale(scale: Point) -> Transform {
         let mut matrix = [[EFloat64::zero(); 4]; 4];
         matrix[0][0] = scale.x;
@@ -107,19 +136,118 @@ impl Transform {
         Transform { matrix }
     }
 
+    // Maps the origin to origin and the unit vectors to x, y and z.
+    pub fn from_basis(origin: Point, x: Point, y: Point, z: Point) -> Transform {
+        let mut matrix = [[EFloat64::zero(); 4]; 4];
+        for (j, column) in [x, y, z, origin].iter().enumerate() {
+            matrix[0][j] = column.x;
+            matrix[1][j] = column.y;
+            matrix[2][j] = column.z;
+        }
+        matrix[3][3] = EFloat64::one();
+        Transform { matrix }
+    }
+
+    // Length that unit vectors have after the transform. The columns of the linear part all have this length if the scaling is uniform.
     pub fn uniform_scale_factor(&self) -> EFloat64 {
-        let scale_x = self.matrix[0][0] + self.matrix[0][1] + self.matrix[0][2];
-        let scale_y = self.matrix[1][0] + self.matrix[1][1] + self.matrix[1][2];
-        let scale_z = self.matrix[2][0] + self.matrix[2][1] + self.matrix[2][2];
-        assert!(
-            (scale_x.abs() - scale_y.abs()) == 0.0,
-            "Scale must be uniform"
-        );
-        assert!(
-            (scale_x.abs() - scale_z.abs()) == 0.0,
-            "Scale must be uniform"
-        );
-        return scale_x;
+        let column =
+            |j: usize| Point::new(self.matrix[0][j], self.matrix[1][j], self.matrix[2][j]).norm();
+        let (scale_x, scale_y, scale_z) = (column(0), column(1), column(2));
+        assert!((scale_x - scale_y) == 0.0, "Scale must be uniform");
+        assert!((scale_x - scale_z) == 0.0, "Scale must be uniform");
+        scale_x
+    }
+}
+
+// A transform split into translation * rotation * scale. The scale is symmetric and stretches along its eigenvectors, so it is diagonal for scales along the axes.
+#[derive(Debug, Clone, Copy)]
+pub struct TransformDecomposition {
+    pub translation: Point,
+    pub rotation: Transform,
+    pub scale: Transform,
+}
+
+impl Transform {
+    fn from_matrix(matrix: &Matrix) -> Transform {
+        let mut result = [[EFloat64::zero(); 4]; 4];
+        for (i, row) in matrix.entries.iter().enumerate() {
+            for (j, x) in row.iter().enumerate() {
+                result[i][j] = *x;
+            }
+        }
+        if matrix.rows() == 3 {
+            result[3][3] = EFloat64::one();
+        }
+        Transform { matrix: result }
+    }
+
+    fn to_matrix(self) -> Matrix {
+        Matrix::new(self.matrix.iter().map(|r| r.to_vec()).collect())
+    }
+
+    // The 3x3 part without the translation.
+    fn linear_part(&self) -> Matrix {
+        Matrix::new(self.matrix[..3].iter().map(|r| r[..3].to_vec()).collect())
+    }
+
+    fn is_affine(&self) -> bool {
+        self.matrix[3][0] == 0.0
+            && self.matrix[3][1] == 0.0
+            && self.matrix[3][2] == 0.0
+            && self.matrix[3][3] == 1.0
+    }
+
+    pub fn inverse(&self) -> GeometryResult<Transform> {
+        let inverse = self
+            .to_matrix()
+            .inverse()
+            .map_err(|e| GeometryError::from(e).with_context("Invert transform".to_string()))?;
+        Ok(Transform::from_matrix(&inverse))
+    }
+
+    // Whether the transform only rotates and moves, i.e. keeps lengths, angles and orientation.
+    pub fn is_rigid(&self) -> bool {
+        let linear = self.linear_part();
+        self.is_affine()
+            && &linear.transpose() * &linear == Matrix::identity(3)
+            && linear.determinant() == 1.0
+    }
+
+    // Whether the transform keeps angles, i.e. only rotates, mirrors, moves and scales uniformly. Circles and spheres stay circles and spheres then.
+    pub fn is_similarity(&self) -> bool {
+        let linear = self.linear_part();
+        let gram = &linear.transpose() * &linear;
+        let scale = gram.entries[0][0];
+        self.is_affine()
+            && (0..3).all(|i| {
+                (0..3).all(|j| match i == j {
+                    true => gram.entries[i][j] == scale,
+                    false => gram.entries[i][j] == 0.0,
+                })
+            })
+    }
+
+    // Splits an affine transform into translation, rotation and scale by the polar decomposition M = R S with S = sqrt(M^T M).
+    pub fn decompose(&self) -> GeometryResult<TransformDecomposition> {
+        if !self.is_affine() {
+            return Err("Projective transforms cannot be decomposed".into());
+        }
+        let linear = self.linear_part();
+        if !(linear.determinant() > 0.0) {
+            return Err("Transform must not be singular or mirror".into());
+        }
+        let (values, vectors) = (&linear.transpose() * &linear).symmetric_eigen()?;
+        let mut root = Matrix::zeros(3, 3);
+        for (i, value) in values.iter().enumerate() {
+            root.entries[i][i] = value.sqrt().unwrap();
+        }
+        let scale = &(&vectors * &root) * &vectors.transpose();
+        let rotation = &linear * &scale.inverse()?;
+        Ok(TransformDecomposition {
+            translation: Point::new(self.matrix[0][3], self.matrix[1][3], self.matrix[2][3]),
+            rotation: Transform::from_matrix(&rotation),
+            scale: Transform::from_matrix(&scale),
+        })
     }
 }
 
@@ -155,4 +283,73 @@ mod tests {
             Point::from_f64(0.0, 0.0, 1.0)
         );
     }
+
+    #[test]
+    fn test_axis_angle() {
+        let t = Transform::from_axis_angle(
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::unit_z(),
+            EFloat64::half_pi(),
+        );
+        assert_eq!(
+            t * Point::from_f64(2.0, 0.0, 5.0),
+            Point::from_f64(1.0, 1.0, 5.0)
+        );
+        assert_eq!(
+            t * Point::from_f64(1.0, 0.0, -1.0),
+            Point::from_f64(1.0, 0.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let t = Transform::from_translation(Point::from_f64(1.0, 2.0, 3.0))
+            * Transform::from_axis_angle(Point::zero(), Point::unit_z(), EFloat64::from(0.3))
+            * Transform::from_scale(Point::from_f64(2.0, 3.0, 4.0));
+        let p = Point::from_f64(0.5, -1.0, 2.0);
+        assert_eq!(t.inverse().unwrap() * (t * p), p);
+        assert!(Transform::from_scale(Point::from_f64(1.0, 0.0, 1.0))
+            .inverse()
+            .is_err());
+    }
+
+    #[test]
+    fn test_decompose() {
+        let rotation =
+            Transform::from_axis_angle(Point::zero(), Point::unit_z(), EFloat64::from(0.3));
+        let t = Transform::from_translation(Point::from_f64(1.0, 2.0, 3.0))
+            * rotation
+            * Transform::from_scale(Point::from_f64(2.0, 3.0, 4.0));
+        assert!(rotation.is_rigid());
+        assert!(!t.is_rigid());
+        let decomposition = t.decompose().unwrap();
+        assert!(decomposition.rotation.is_rigid());
+        assert_eq!(decomposition.translation, Point::from_f64(1.0, 2.0, 3.0));
+        assert_eq!(
+            decomposition.rotation * Point::unit_x(),
+            rotation * Point::unit_x()
+        );
+        assert_eq!(
+            decomposition.scale * Point::unit_y(),
+            Point::from_f64(0.0, 3.0, 0.0)
+        );
+        assert!(Transform::from_scale(Point::from_f64(-1.0, 1.0, 1.0))
+            .decompose()
+            .is_err());
+    }
+
+    #[test]
+    fn test_basis_and_similarity() {
+        let t = Transform::from_basis(
+            Point::from_f64(1.0, 2.0, 3.0),
+            Point::from_f64(0.0, 2.0, 0.0),
+            Point::from_f64(-2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 0.0, 2.0),
+        );
+        assert_eq!(t * Point::unit_x(), Point::from_f64(1.0, 4.0, 3.0));
+        assert_eq!(t * Point::zero(), Point::from_f64(1.0, 2.0, 3.0));
+        assert!(t.is_similarity());
+        assert!(Transform::from_scale(Point::from_f64(-1.0, 1.0, 1.0)).is_similarity());
+        assert!(!Transform::from_scale(Point::from_f64(1.0, 2.0, 1.0)).is_similarity());
+    }
 }
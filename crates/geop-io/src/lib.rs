@@ -0,0 +1,4 @@
+pub mod mesh;
+pub mod obj;
+pub mod step;
+pub mod stl;
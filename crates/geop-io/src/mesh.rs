@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use geop_geometry::tolerance::Tolerance;
+use geop_rasterize::{
+    face::rasterize_face_into_triangle_list, triangle_buffer::TriangleBuffer,
+    vertex_normal_buffer::RenderNormalVertex,
+};
+use geop_topology::topology::{face::Face, scene::Color, volume::Volume};
+
+// A corner of a triangle, given by indices into the positions and normals of the mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshVertex {
+    pub position: usize,
+    pub normal: usize,
+}
+
+// The triangles of one face.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshGroup {
+    pub name: String,
+    pub triangles: Vec<[MeshVertex; 3]>,
+}
+
+// Triangulated faces with shared vertices. Triangles are counter clockwise when seen from the side the normals point to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub groups: Vec<MeshGroup>,
+    weld_tolerance: f32,
+    position_cells: HashMap<[i64; 3], Vec<usize>>,
+    normal_ids: HashMap<[u32; 3], usize>,
+}
+
+// Vertices of the rasterizer are intervals. The mesh uses their centers.
+fn center(min: [f32; 3], max: [f32; 3]) -> [f32; 3] {
+    // Adding 0.0 turns -0.0 into 0.0, so that both are merged.
+    [
+        (min[0] + max[0]) / 2.0 + 0.0,
+        (min[1] + max[1]) / 2.0 + 0.0,
+        (min[2] + max[2]) / 2.0 + 0.0,
+    ]
+}
+
+// Positions closer than this times the size of the mesh are welded into one vertex. The same edge is rasterized for both of its faces, but from different ends, so the points are not bitwise equal.
+// The size is the diagonal of the bounding box of the mesh and the origin, as positions are rounded to f32 relative to their distance from the origin.
+const WELD_RELATIVE: f32 = 1e-6;
+
+fn insert(values: &mut Vec<[f32; 3]>, ids: &mut HashMap<[u32; 3], usize>, v: [f32; 3]) -> usize {
+    let key = [v[0].to_bits(), v[1].to_bits(), v[2].to_bits()];
+    *ids.entry(key).or_insert_with(|| {
+        values.push(v);
+        values.len() - 1
+    })
+}
+
+fn rasterize(face: &Face) -> TriangleBuffer {
+    rasterize_face_into_triangle_list(face, Color::new(1.0, 1.0, 1.0, 1.0))
+}
+
+impl Mesh {
+    // An empty mesh that welds positions up to the given distance.
+    pub fn empty(weld_tolerance: f32) -> Mesh {
+        Mesh {
+            positions: Vec::new(),
+            normals: Vec::new(),
+            groups: Vec::new(),
+            weld_tolerance,
+            position_cells: HashMap::new(),
+            normal_ids: HashMap::new(),
+        }
+    }
+
+    // Triangulates each face into its own group. The faces are named face_0, face_1, ...
+    // Positions are welded up to the linear tolerance, but at least up to the rounding for the size of the mesh.
+    pub fn from_faces(faces: &[Face]) -> Mesh {
+        let buffers: Vec<TriangleBuffer> = faces.iter().map(rasterize).collect();
+        let (mut min, mut max) = ([0.0f32; 3], [0.0f32; 3]);
+        for v in buffers
+            .iter()
+            .flat_map(|b| b.triangles.iter())
+            .flat_map(|t| [&t.a, &t.b, &t.c])
+        {
+            for i in 0..3 {
+                min[i] = min[i].min(v.min_position[i]);
+                max[i] = max[i].max(v.max_position[i]);
+            }
+        }
+        let size = (0..3)
+            .map(|i| (max[i] - min[i]).powi(2))
+            .sum::<f32>()
+            .sqrt();
+        let linear = Tolerance::current().linear as f32;
+        let mut mesh = Mesh::empty(linear.max(size * WELD_RELATIVE));
+        for buffer in buffers.iter() {
+            mesh.add_buffer(buffer, format!("face_{}", mesh.groups.len()));
+        }
+        mesh
+    }
+
+    // Triangulates the boundary and the cavities of the volume.
+    pub fn from_volume(volume: &Volume) -> Mesh {
+        Mesh::from_faces(&volume.all_faces())
+    }
+
+    fn vertex(&mut self, v: &RenderNormalVertex) -> MeshVertex {
+        let position = center(v.min_position, v.max_position);
+        let normal = center(v.min_normal, v.max_normal);
+        MeshVertex {
+            position: self.weld(position),
+            normal: insert(&mut self.normals, &mut self.normal_ids, normal),
+        }
+    }
+
+    // Index of a position within the weld tolerance, which is added if there is none.
+    fn weld(&mut self, position: [f32; 3]) -> usize {
+        let c = position.map(|x| (x / self.weld_tolerance).floor() as i64);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(ids) = self.position_cells.get(&[c[0] + dx, c[1] + dy, c[2] + dz])
+                    else {
+                        continue;
+                    };
+                    for &id in ids.iter() {
+                        let p = self.positions[id];
+                        if (0..3).all(|i| (p[i] - position[i]).abs() <= self.weld_tolerance) {
+                            return id;
+                        }
+                    }
+                }
+            }
+        }
+        self.positions.push(position);
+        self.position_cells
+            .entry(c)
+            .or_default()
+            .push(self.positions.len() - 1);
+        self.positions.len() - 1
+    }
+
+    pub fn add_face(&mut self, face: &Face, name: String) {
+        self.add_buffer(&rasterize(face), name);
+    }
+
+    fn add_buffer(&mut self, buffer: &TriangleBuffer, name: String) {
+        let triangles = buffer
+            .triangles
+            .iter()
+            .map(|t| [self.vertex(&t.a), self.vertex(&t.b), self.vertex(&t.c)])
+            .collect();
+        self.groups.push(MeshGroup { name, triangles });
+    }
+
+    pub fn triangles(&self) -> impl Iterator<Item = &[MeshVertex; 3]> {
+        self.groups.iter().flat_map(|g| g.triangles.iter())
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.groups.iter().map(|g| g.triangles.len()).sum()
+    }
+
+    // Normal of a triangle. This is the mean of the normals of the surface at the corners, which is more precise than the normal of the flat triangle.
+    pub fn facet_normal(&self, triangle: &[MeshVertex; 3]) -> [f32; 3] {
+        let mut n = [0.0f32; 3];
+        for v in triangle.iter() {
+            for (i, x) in self.normals[v.normal].iter().enumerate() {
+                n[i] += x;
+            }
+        }
+        let norm = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        match norm > 0.0 {
+            true => [n[0] / norm, n[1] / norm, n[2] / norm],
+            false => [0.0; 3],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use geop_geometry::efloat::EFloat64;
+    use geop_topology::primitive_objects::volumes::cube::primitive_cube;
+
+    use super::*;
+
+    // In a closed mesh every edge is used once in each direction.
+    fn assert_closed(mesh: &Mesh) {
+        let mut edges = HashMap::<(usize, usize), usize>::new();
+        for t in mesh.triangles() {
+            for i in 0..3 {
+                *edges
+                    .entry((t[i].position, t[(i + 1) % 3].position))
+                    .or_default() += 1;
+            }
+        }
+        for (&(a, b), &count) in edges.iter() {
+            assert_eq!(count, 1);
+            assert_eq!(edges.get(&(b, a)), Some(&1));
+        }
+    }
+
+    #[test]
+    fn test_mesh_cube_is_closed() {
+        let cube = primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(3.0),
+            EFloat64::from(4.0),
+        );
+        let mesh = Mesh::from_volume(&cube);
+        assert_eq!(mesh.groups.len(), 6);
+        // The faces of the cube are flat, so only the 6 face normals are needed.
+        assert_eq!(mesh.normals.len(), 6);
+        assert_closed(&mesh);
+    }
+
+    #[test]
+    fn test_mesh_weld_tolerance() {
+        let cube = |size: f64| {
+            primitive_cube(
+                EFloat64::from(2.0 * size),
+                EFloat64::from(3.0 * size),
+                EFloat64::from(4.0 * size),
+            )
+        };
+        let small = Mesh::from_volume(&cube(1.0));
+        let mut large = Mesh::from_volume(&cube(1e3));
+        assert!((large.weld_tolerance / small.weld_tolerance - 1e3).abs() < 1.0);
+        assert_closed(&large);
+
+        // Neighbouring f32 values at the size of the large cube are welded.
+        let x = 2e3f32;
+        let next = f32::from_bits(x.to_bits() + 1);
+        assert_eq!(large.weld([x, 0.0, 0.0]), large.weld([next, 0.0, 0.0]));
+
+        // A linear tolerance that is larger than the rounding is used instead.
+        let mesh = Tolerance::new(0.1, 0.0, 1e-10, 1e-9).apply(|| Mesh::from_volume(&cube(1.0)));
+        assert_eq!(mesh.weld_tolerance, 0.1);
+    }
+}
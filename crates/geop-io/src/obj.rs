@@ -0,0 +1,54 @@
+use std::fmt::Write;
+
+use crate::mesh::Mesh;
+
+// Wavefront OBJ with shared positions and normals. Each face of the mesh becomes a group.
+pub fn mesh_to_obj(mesh: &Mesh) -> String {
+    let mut text = String::new();
+    writeln!(text, "# geop").unwrap();
+    for [x, y, z] in mesh.positions.iter() {
+        writeln!(text, "v {} {} {}", x, y, z).unwrap();
+    }
+    for [x, y, z] in mesh.normals.iter() {
+        writeln!(text, "vn {} {} {}", x, y, z).unwrap();
+    }
+    for group in mesh.groups.iter() {
+        writeln!(text, "g {}", group.name).unwrap();
+        for triangle in group.triangles.iter() {
+            // Indices in OBJ files start at 1.
+            let [a, b, c] = triangle.map(|v| format!("{}//{}", v.position + 1, v.normal + 1));
+            writeln!(text, "f {} {} {}", a, b, c).unwrap();
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+    use geop_topology::primitive_objects::volumes::cube::primitive_cube;
+
+    use super::*;
+
+    #[test]
+    fn test_obj_cube() {
+        let cube = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one());
+        let mesh = Mesh::from_volume(&cube);
+        let text = mesh_to_obj(&mesh);
+        assert_eq!(text.matches("\nv ").count(), mesh.positions.len());
+        assert_eq!(text.matches("\nvn ").count(), 6);
+        assert_eq!(text.matches("\ng face_").count(), 6);
+        assert_eq!(text.matches("\nf ").count(), mesh.triangle_count());
+
+        // Every index refers to an existing vertex.
+        for line in text.lines().filter(|l| l.starts_with("f ")) {
+            for corner in line[2..].split(' ') {
+                let (v, n) = corner.split_once("//").unwrap();
+                let v: usize = v.parse().unwrap();
+                let n: usize = n.parse().unwrap();
+                assert!(1 <= v && v <= mesh.positions.len());
+                assert!(1 <= n && n <= mesh.normals.len());
+            }
+        }
+    }
+}
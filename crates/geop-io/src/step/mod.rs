@@ -0,0 +1,240 @@
+use std::fmt::Display;
+
+pub mod parser;
+pub mod reader;
+pub mod writer;
+
+// Reading and writing of ISO 10303-21 (STEP) files with AP203/AP214 boundary representations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepError {
+    pub message: String,
+}
+
+impl StepError {
+    pub fn new(message: String) -> StepError {
+        StepError { message }
+    }
+}
+
+impl Display for StepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "StepError: {}", self.message)
+    }
+}
+
+impl std::error::Error for StepError {}
+
+pub type StepResult<T> = Result<T, StepError>;
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        efloat::EFloat64,
+        point::Point,
+        surfaces::{plane::Plane, surface::Surface},
+        tolerance::Tolerance,
+        transforms::Transform,
+    };
+    use geop_topology::{
+        operations::extrude::extrude,
+        primitive_objects::{
+            edges::circle::primitive_circle, faces::sphere::primitive_sphere,
+            volumes::cube::primitive_cube,
+        },
+        topology::{contour::Contour, face::Face, shell::Shell, volume::Volume},
+    };
+
+    use super::{
+        reader::{shells_from_step, volumes_from_step},
+        writer::{shells_to_step, volumes_to_step},
+    };
+
+    fn assert_faces_eq(a: &[Face], b: &[Face]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert_eq!(a.surface, b.surface);
+            assert_eq!(a.boundaries.len(), b.boundaries.len());
+            for (a, b) in a.boundaries.iter().zip(b.boundaries.iter()) {
+                assert_eq!(a.edges, b.edges);
+            }
+        }
+    }
+
+    fn assert_volumes_eq(a: &Volume, b: &Volume) {
+        assert_faces_eq(&a.boundary.faces, &b.boundary.faces);
+        assert_eq!(a.cavities.len(), b.cavities.len());
+        for (a, b) in a.cavities.iter().zip(b.cavities.iter()) {
+            assert_faces_eq(&a.faces, &b.faces);
+        }
+    }
+
+    #[test]
+    fn test_step_cube_round_trip() {
+        let cube = primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(3.0),
+            EFloat64::from(4.0),
+        );
+        let text = volumes_to_step(std::slice::from_ref(&cube)).unwrap();
+        assert!(text.contains("MANIFOLD_SOLID_BREP"));
+        // Every edge of the cube is shared by two faces, but only written once.
+        assert_eq!(text.matches("EDGE_CURVE(").count(), 12);
+        assert_eq!(text.matches("VERTEX_POINT(").count(), 8);
+
+        let volumes = volumes_from_step(&text).unwrap();
+        assert_eq!(volumes.len(), 1);
+        assert_volumes_eq(&cube, &volumes[0]);
+    }
+
+    #[test]
+    fn test_step_cylinder_round_trip() {
+        let bottom = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(bottom, Point::from_f64(0.0, 0.0, 2.0));
+        let text = volumes_to_step(std::slice::from_ref(&cylinder)).unwrap();
+        assert!(text.contains("CYLINDRICAL_SURFACE"));
+        let volumes = volumes_from_step(&text).unwrap();
+        assert_eq!(volumes.len(), 1);
+        assert_volumes_eq(&cylinder, &volumes[0]);
+    }
+
+    #[test]
+    fn test_step_cavity_round_trip() {
+        let outer = primitive_cube(
+            EFloat64::from(4.0),
+            EFloat64::from(4.0),
+            EFloat64::from(4.0),
+        );
+        let inner = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one())
+            .transform(Transform::from_translation(Point::from_f64(0.5, 0.0, 0.0)));
+        let volume = Volume::new(outer.boundary, vec![inner.boundary.flip()]);
+        let text = volumes_to_step(std::slice::from_ref(&volume)).unwrap();
+        assert!(text.contains("BREP_WITH_VOIDS"));
+        let volumes = volumes_from_step(&text).unwrap();
+        assert_eq!(volumes.len(), 1);
+        assert_volumes_eq(&volume, &volumes[0]);
+    }
+
+    #[test]
+    fn test_step_shell_round_trip() {
+        let shells = vec![
+            primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one()).boundary,
+            Shell::new(vec![primitive_sphere(Point::zero(), EFloat64::two())]),
+        ];
+        let text = shells_to_step(&shells).unwrap();
+        assert!(text.contains("SHELL_BASED_SURFACE_MODEL"));
+        assert!(volumes_from_step(&text).unwrap().is_empty());
+        let result = shells_from_step(&text).unwrap();
+        assert_eq!(result.len(), 2);
+        for (a, b) in shells.iter().zip(result.iter()) {
+            assert_faces_eq(&a.faces, &b.faces);
+        }
+    }
+
+    // The same file in inches, which are defined by their length in millimetres.
+    fn in_inches(text: &str) -> String {
+        let millimetres = "(LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.))";
+        let end = text.rfind("ENDSEC;").unwrap();
+        format!(
+            "{}#1001=LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(25.4),#1003);
+#1002=DIMENSIONAL_EXPONENTS(1.,0.,0.,0.,0.,0.,0.);
+#1003={};
+{}",
+            text[..end].replace(
+                millimetres,
+                "(CONVERSION_BASED_UNIT('INCH',#1001) LENGTH_UNIT() NAMED_UNIT(#1002))"
+            ),
+            millimetres,
+            &text[end..]
+        )
+    }
+
+    #[test]
+    fn test_step_inch_round_trip() {
+        let cube = |scale: f64| {
+            primitive_cube(
+                EFloat64::from(2.0 * scale),
+                EFloat64::from(3.0 * scale),
+                EFloat64::from(4.0 * scale),
+            )
+        };
+        let text = in_inches(&volumes_to_step(&[cube(1.0)]).unwrap());
+        assert!(text.contains("CONVERSION_BASED_UNIT('INCH'"));
+        let volumes = volumes_from_step(&text).unwrap();
+        assert_volumes_eq(&cube(25.4), &volumes[0]);
+
+        // Written again, the lengths are in millimetres.
+        let text = volumes_to_step(&volumes).unwrap();
+        assert!(!text.contains("INCH"));
+        assert_volumes_eq(&cube(25.4), &volumes_from_step(&text).unwrap()[0]);
+    }
+
+    #[test]
+    fn test_step_uncertainty() {
+        let cube = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one());
+        let text = Tolerance::new(1e-4, 0.0, 1e-10, 1e-9)
+            .apply(|| volumes_to_step(std::slice::from_ref(&cube)))
+            .unwrap();
+        assert!(text.contains("LENGTH_MEASURE(0.0001)"));
+        // The uncertainty is in inches as well.
+        let volumes = volumes_from_step(&in_inches(&text)).unwrap();
+        let p = volumes[0].boundary.faces[0].boundaries[0].edges[0]
+            .start
+            .unwrap();
+        assert!((p.x.upper_bound - p.x.lower_bound - 2.0 * 25.4e-4).abs() < 1e-9);
+    }
+
+    // A cone given in degrees, which is converted to radians.
+    fn cone_step(half_angle: &str) -> String {
+        format!(
+            "ISO-10303-21;
+HEADER;
+FILE_NAME('cone.stp','',(''),(''),'','','');
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,1.));
+#2=DIRECTION('',(0.,0.,1.));
+#3=DIRECTION('',(1.,0.,0.));
+#4=AXIS2_PLACEMENT_3D('',#1,#2,#3);
+#5=CONICAL_SURFACE('',#4,1.,{});
+#6=ADVANCED_FACE('',(),#5,.T.);
+#7=OPEN_SHELL('',(#6));
+#8=SHELL_BASED_SURFACE_MODEL('',(#7));
+#9=(NAMED_UNIT(*) PLANE_ANGLE_UNIT() SI_UNIT($,.RADIAN.));
+#10=PLANE_ANGLE_MEASURE_WITH_UNIT(PLANE_ANGLE_MEASURE(0.0174532925199433),#9);
+#11=DIMENSIONAL_EXPONENTS(0.,0.,0.,0.,0.,0.,0.);
+#12=(CONVERSION_BASED_UNIT('DEGREE',#10) NAMED_UNIT(#11) PLANE_ANGLE_UNIT());
+ENDSEC;
+END-ISO-10303-21;
+",
+            half_angle
+        )
+    }
+
+    #[test]
+    fn test_step_cone_in_degrees() {
+        let shells = shells_from_step(&cone_step("45.")).unwrap();
+        let Surface::Cone(cone) = shells[0].faces[0].surface.as_ref() else {
+            panic!("Expected a cone");
+        };
+        assert!((cone.half_angle - EFloat64::from(std::f64::consts::FRAC_PI_4)).abs() < 1e-12);
+        // The radius of 1 at height 1 puts the apex at the origin.
+        assert!(cone.basis.norm() < 1e-6);
+
+        // Cones have to open by less than a right angle.
+        assert!(shells_from_step(&cone_step("90.")).is_err());
+        assert!(shells_from_step(&cone_step("0.")).is_err());
+    }
+}
@@ -0,0 +1,418 @@
+use std::collections::BTreeMap;
+
+use super::{StepError, StepResult};
+
+// A parameter of an entity instance in the data section of a STEP file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Parameter {
+    Integer(i64),
+    Real(f64),
+    String(String),
+    Enumeration(String),
+    Reference(usize),
+    List(Vec<Parameter>),
+    // A value with an explicit type, e.g. LENGTH_MEASURE(1.0)
+    Typed(String, Vec<Parameter>),
+    // $
+    Unset,
+    // *
+    Derived,
+}
+
+impl Parameter {
+    pub fn as_f64(&self) -> StepResult<f64> {
+        match self {
+            Parameter::Integer(i) => Ok(*i as f64),
+            Parameter::Real(r) => Ok(*r),
+            Parameter::Typed(_, params) if params.len() == 1 => params[0].as_f64(),
+            _ => Err(StepError::new(format!(
+                "Expected a number, found {:?}",
+                self
+            ))),
+        }
+    }
+
+    pub fn as_usize(&self) -> StepResult<usize> {
+        match self {
+            Parameter::Integer(i) if *i >= 0 => Ok(*i as usize),
+            _ => Err(StepError::new(format!(
+                "Expected a non-negative integer, found {:?}",
+                self
+            ))),
+        }
+    }
+
+    pub fn as_reference(&self) -> StepResult<usize> {
+        match self {
+            Parameter::Reference(id) => Ok(*id),
+            _ => Err(StepError::new(format!(
+                "Expected a reference, found {:?}",
+                self
+            ))),
+        }
+    }
+
+    pub fn as_list(&self) -> StepResult<&[Parameter]> {
+        match self {
+            Parameter::List(list) => Ok(list),
+            _ => Err(StepError::new(format!("Expected a list, found {:?}", self))),
+        }
+    }
+
+    pub fn as_bool(&self) -> StepResult<bool> {
+        match self {
+            Parameter::Enumeration(e) if e == "T" => Ok(true),
+            Parameter::Enumeration(e) if e == "F" => Ok(false),
+            _ => Err(StepError::new(format!(
+                "Expected a boolean, found {:?}",
+                self
+            ))),
+        }
+    }
+
+    pub fn is_unset(&self) -> bool {
+        matches!(self, Parameter::Unset | Parameter::Derived)
+    }
+}
+
+// An entity instance. Simple instances have one record, complex instances like (A() B() C()) have one record per partial type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub records: Vec<(String, Vec<Parameter>)>,
+}
+
+impl Entity {
+    pub fn name(&self) -> &str {
+        match self.records.len() {
+            1 => &self.records[0].0,
+            _ => "",
+        }
+    }
+
+    // Parameters of the record with the given type name.
+    pub fn record(&self, name: &str) -> Option<&[Parameter]> {
+        self.records
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, params)| params.as_slice())
+    }
+}
+
+// The data section of a STEP file, which maps instance ids to entities.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepFile {
+    pub entities: BTreeMap<usize, Entity>,
+}
+
+impl StepFile {
+    pub fn get(&self, id: usize) -> StepResult<&Entity> {
+        self.entities
+            .get(&id)
+            .ok_or_else(|| StepError::new(format!("Missing entity #{}", id)))
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> StepError {
+        StepError::new(format!("{} at byte {}", message, self.pos))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    // Skips whitespace and comments.
+    fn skip(&mut self) {
+        loop {
+            while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+                self.pos += 1;
+            }
+            if self.bytes[self.pos..].starts_with(b"/*") {
+                match self.bytes[self.pos..].windows(2).position(|w| w == b"*/") {
+                    Some(end) => self.pos += end + 2,
+                    None => self.pos = self.bytes.len(),
+                }
+                continue;
+            }
+            return;
+        }
+    }
+
+    fn try_consume(&mut self, token: &[u8]) -> bool {
+        self.skip();
+        if self.bytes[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, token: &[u8]) -> StepResult<()> {
+        match self.try_consume(token) {
+            true => Ok(()),
+            false => Err(self.error(&format!("Expected '{}'", String::from_utf8_lossy(token)))),
+        }
+    }
+
+    fn keyword(&mut self) -> StepResult<String> {
+        self.skip();
+        let start = self.pos;
+        if self.peek() == Some(b'!') {
+            self.pos += 1;
+        }
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'-')
+        {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error("Expected a keyword"));
+        }
+        Ok(String::from_utf8_lossy(&self.bytes[start..self.pos]).to_uppercase())
+    }
+
+    fn integer(&mut self) -> StepResult<usize> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .unwrap()
+            .parse()
+            .map_err(|_| self.error("Expected an integer"))
+    }
+
+    fn number(&mut self) -> StepResult<Parameter> {
+        let start = self.pos;
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut real = false;
+        while let Some(c) = self.peek() {
+            match c {
+                b'0'..=b'9' => {}
+                b'.' | b'E' | b'e' => real = true,
+                b'+' | b'-' if matches!(self.bytes[self.pos - 1], b'E' | b'e') => {}
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap();
+        let value = match real {
+            true => text.parse().ok().map(Parameter::Real),
+            false => text.parse().ok().map(Parameter::Integer),
+        };
+        value.ok_or_else(|| self.error(&format!("Invalid number '{}'", text)))
+    }
+
+    fn string(&mut self) -> StepResult<String> {
+        let mut result = Vec::new();
+        loop {
+            match self.peek() {
+                Some(b'\'') if self.bytes.get(self.pos + 1) == Some(&b'\'') => {
+                    result.push(b'\'');
+                    self.pos += 2;
+                }
+                Some(b'\'') => {
+                    self.pos += 1;
+                    return Ok(String::from_utf8_lossy(&result).to_string());
+                }
+                Some(c) => {
+                    result.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(self.error("Unterminated string")),
+            }
+        }
+    }
+
+    // Parses a comma separated list of parameters. The opening bracket has already been consumed.
+    fn parameters(&mut self) -> StepResult<Vec<Parameter>> {
+        let mut params = Vec::new();
+        if self.try_consume(b")") {
+            return Ok(params);
+        }
+        loop {
+            params.push(self.parameter()?);
+            if self.try_consume(b")") {
+                return Ok(params);
+            }
+            self.expect(b",")?;
+        }
+    }
+
+    fn parameter(&mut self) -> StepResult<Parameter> {
+        self.skip();
+        match self.peek() {
+            Some(b'#') => {
+                self.pos += 1;
+                Ok(Parameter::Reference(self.integer()?))
+            }
+            Some(b'\'') => {
+                self.pos += 1;
+                Ok(Parameter::String(self.string()?))
+            }
+            Some(b'.') => {
+                self.pos += 1;
+                let value = self.keyword()?;
+                self.expect(b".")?;
+                Ok(Parameter::Enumeration(value))
+            }
+            Some(b'$') => {
+                self.pos += 1;
+                Ok(Parameter::Unset)
+            }
+            Some(b'*') => {
+                self.pos += 1;
+                Ok(Parameter::Derived)
+            }
+            Some(b'(') => {
+                self.pos += 1;
+                Ok(Parameter::List(self.parameters()?))
+            }
+            Some(b'"') => {
+                // Binary values are not used by the geometry, so they are kept as strings.
+                self.pos += 1;
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c != b'"') {
+                    self.pos += 1;
+                }
+                let value = String::from_utf8_lossy(&self.bytes[start..self.pos]).to_string();
+                self.expect(b"\"")?;
+                Ok(Parameter::String(value))
+            }
+            Some(c) if c.is_ascii_digit() || c == b'+' || c == b'-' => self.number(),
+            Some(_) => {
+                let name = self.keyword()?;
+                self.expect(b"(")?;
+                Ok(Parameter::Typed(name, self.parameters()?))
+            }
+            None => Err(self.error("Unexpected end of file")),
+        }
+    }
+
+    fn entity(&mut self) -> StepResult<Entity> {
+        let mut records = Vec::new();
+        if self.try_consume(b"(") {
+            while !self.try_consume(b")") {
+                let name = self.keyword()?;
+                self.expect(b"(")?;
+                records.push((name, self.parameters()?));
+            }
+        } else {
+            let name = self.keyword()?;
+            self.expect(b"(")?;
+            records.push((name, self.parameters()?));
+        }
+        self.expect(b";")?;
+        Ok(Entity { records })
+    }
+
+    // Skips everything up to and including the next ';' outside of strings.
+    fn skip_statement(&mut self) -> StepResult<()> {
+        loop {
+            self.skip();
+            match self.peek() {
+                Some(b';') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(b'\'') => {
+                    self.pos += 1;
+                    self.string()?;
+                }
+                Some(_) => self.pos += 1,
+                None => return Err(self.error("Unexpected end of file")),
+            }
+        }
+    }
+}
+
+// Parses the data section of a STEP file. The header is checked for the magic but otherwise ignored.
+pub fn parse_step(text: &str) -> StepResult<StepFile> {
+    let mut parser = Parser {
+        bytes: text.as_bytes(),
+        pos: 0,
+    };
+    parser.expect(b"ISO-10303-21;")?;
+    let mut entities = BTreeMap::new();
+    loop {
+        parser.skip();
+        if parser.try_consume(b"END-ISO-10303-21;") {
+            return Ok(StepFile { entities });
+        }
+        if parser.try_consume(b"DATA") {
+            // Since edition 3 the data section can have parameters.
+            parser.skip_statement()?;
+            while !parser.try_consume(b"ENDSEC;") {
+                parser.expect(b"#")?;
+                let id = parser.integer()?;
+                parser.expect(b"=")?;
+                let entity = parser.entity()?;
+                if entities.insert(id, entity).is_some() {
+                    return Err(StepError::new(format!("Duplicate entity #{}", id)));
+                }
+            }
+            continue;
+        }
+        parser.skip_statement()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_step() {
+        let text = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('a; b'),'2;1');
+ENDSEC;
+DATA;
+/* comment */
+#1=CARTESIAN_POINT('it''s',(0.,1.5E-1,-2));
+#2 = ( NAMED_UNIT(*) SI_UNIT($,.RADIAN.) PLANE_ANGLE_UNIT() );
+#3=MEASURE(LENGTH_MEASURE(1.E-07),#1);
+ENDSEC;
+END-ISO-10303-21;
+";
+        let file = parse_step(text).unwrap();
+        assert_eq!(file.entities.len(), 3);
+
+        let point = file.get(1).unwrap();
+        assert_eq!(point.name(), "CARTESIAN_POINT");
+        let params = point.record("CARTESIAN_POINT").unwrap();
+        assert_eq!(params[0], Parameter::String("it's".to_string()));
+        let coordinates: Vec<f64> = params[1]
+            .as_list()
+            .unwrap()
+            .iter()
+            .map(|p| p.as_f64().unwrap())
+            .collect();
+        assert_eq!(coordinates, vec![0.0, 0.15, -2.0]);
+
+        let unit = file.get(2).unwrap();
+        assert_eq!(unit.records.len(), 3);
+        assert_eq!(
+            unit.record("SI_UNIT").unwrap(),
+            &[
+                Parameter::Unset,
+                Parameter::Enumeration("RADIAN".to_string())
+            ]
+        );
+
+        let measure = file.get(3).unwrap().record("MEASURE").unwrap();
+        assert_eq!(measure[0].as_f64().unwrap(), 1e-7);
+        assert_eq!(measure[1].as_reference().unwrap(), 1);
+
+        assert!(parse_step("#1=A();").is_err());
+        assert!(file.get(4).is_err());
+    }
+}
@@ -0,0 +1,641 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve, ellipse::Ellipse, line::Line, nurbs::Nurbs, CurveLike},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{
+        cone::Cone, cylinder::Cylinder, nurbs_surface::NurbsSurface, plane::Plane, sphere::Sphere,
+        surface::Surface, torus::Torus, SurfaceLike,
+    },
+};
+use geop_topology::topology::{
+    contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume,
+};
+
+use super::{
+    parser::{parse_step, Entity, Parameter, StepFile},
+    StepError, StepResult,
+};
+
+// Accuracy of distances in the length unit of the file, if the file does not specify it.
+const DEFAULT_UNCERTAINTY: f64 = 1e-7;
+// Components of directions are unitless and only rounded when they are written, so they get this error bound instead of the uncertainty of lengths.
+const DIRECTION_UNCERTAINTY: f64 = 1e-12;
+
+fn reals(param: &Parameter) -> StepResult<Vec<f64>> {
+    param.as_list()?.iter().map(|p| p.as_f64()).collect()
+}
+
+fn efloats(param: &Parameter) -> StepResult<Vec<EFloat64>> {
+    Ok(reals(param)?.into_iter().map(EFloat64::from).collect())
+}
+
+fn expand_knots(multiplicities: &Parameter, knots: &Parameter) -> StepResult<Vec<EFloat64>> {
+    let multiplicities = multiplicities
+        .as_list()?
+        .iter()
+        .map(|m| m.as_usize())
+        .collect::<StepResult<Vec<usize>>>()?;
+    let knots = reals(knots)?;
+    if multiplicities.len() != knots.len() {
+        return Err(StepError::new(
+            "Knots and multiplicities have different lengths".to_string(),
+        ));
+    }
+    Ok(knots
+        .iter()
+        .zip(multiplicities)
+        .flat_map(|(k, m)| std::iter::repeat_n(EFloat64::from(*k), m))
+        .collect())
+}
+
+fn geometry_error<T>(
+    result: geop_geometry::geometry_error::GeometryResult<T>,
+    id: usize,
+) -> StepResult<T> {
+    result.map_err(|e| StepError::new(format!("Invalid geometry at #{}: {}", id, e)))
+}
+
+// Radians per angle unit of the file. Angles are in radians unless the file declares a unit like degrees by its conversion factor.
+fn angle_unit(file: &StepFile) -> StepResult<f64> {
+    // The factor of a converted unit refers to the SI unit, which is an angle unit as well.
+    let Some(params) = file
+        .entities
+        .values()
+        .filter(|e| e.record("PLANE_ANGLE_UNIT").is_some())
+        .find_map(|e| e.record("CONVERSION_BASED_UNIT"))
+    else {
+        return Ok(1.0);
+    };
+    let measure = params
+        .get(1)
+        .ok_or_else(|| StepError::new("Missing factor of the angle unit".to_string()))?
+        .as_reference()?;
+    let factor = file
+        .get(measure)?
+        .records
+        .first()
+        .and_then(|(_, params)| params.first())
+        .ok_or_else(|| StepError::new(format!("Missing value of #{}", measure)))?
+        .as_f64()?;
+    match factor > 0.0 {
+        true => Ok(factor),
+        false => Err(StepError::new(format!(
+            "Invalid angle unit factor {} at #{}",
+            factor, measure
+        ))),
+    }
+}
+
+// Millimetres per length unit with the given id, which is either a metre with a prefix or converted from another length unit, like inches from millimetres.
+fn millimetres(file: &StepFile, id: usize) -> StepResult<f64> {
+    let entity = file.get(id)?;
+    if let Some(params) = entity.record("SI_UNIT") {
+        // The simple entity has the dimensions in front of the prefix and the name.
+        let [.., prefix, name] = params else {
+            return Err(StepError::new(format!("Incomplete SI unit at #{}", id)));
+        };
+        if *name != Parameter::Enumeration("METRE".to_string()) {
+            return Err(StepError::new(format!("Expected metres at #{}", id)));
+        }
+        return match prefix {
+            Parameter::Unset => Ok(1e3),
+            Parameter::Enumeration(prefix) => match prefix.as_str() {
+                "KILO" => Ok(1e6),
+                "DECI" => Ok(1e2),
+                "CENTI" => Ok(1e1),
+                "MILLI" => Ok(1.0),
+                "MICRO" => Ok(1e-3),
+                "NANO" => Ok(1e-6),
+                _ => Err(StepError::new(format!(
+                    "Unsupported prefix {} at #{}",
+                    prefix, id
+                ))),
+            },
+            _ => Err(StepError::new(format!("Invalid prefix at #{}", id))),
+        };
+    }
+    let params = entity
+        .record("CONVERSION_BASED_UNIT")
+        .ok_or_else(|| StepError::new(format!("Expected a length unit at #{}", id)))?;
+    let measure = params
+        .get(1)
+        .ok_or_else(|| StepError::new(format!("Missing factor of the length unit #{}", id)))?
+        .as_reference()?;
+    let (factor, unit) = match file.get(measure)?.records.first() {
+        Some((_, params)) if params.len() >= 2 => (params[0].as_f64()?, params[1].as_reference()?),
+        _ => return Err(StepError::new(format!("Missing value of #{}", measure))),
+    };
+    match factor > 0.0 {
+        true => Ok(factor * millimetres(file, unit)?),
+        false => Err(StepError::new(format!(
+            "Invalid length unit factor {} at #{}",
+            factor, measure
+        ))),
+    }
+}
+
+// Millimetres per length unit of the file, which is the unit the writer uses. The unit is the one assigned to the geometric context, files without one are in millimetres.
+fn length_unit(file: &StepFile) -> StepResult<f64> {
+    let unit = file
+        .entities
+        .values()
+        .filter_map(|e| e.record("GLOBAL_UNIT_ASSIGNED_CONTEXT"))
+        .filter_map(|params| params.first()?.as_list().ok())
+        .flatten()
+        .filter_map(|unit| unit.as_reference().ok())
+        .find(|id| {
+            file.get(*id)
+                .is_ok_and(|e| e.record("LENGTH_UNIT").is_some())
+        });
+    match unit {
+        Some(id) => millimetres(file, id),
+        None => Ok(1.0),
+    }
+}
+
+// Builds topology from the entities of a STEP file.
+// Lengths in the file are only accurate up to the uncertainty of the file, which is kept as the error bound of the values.
+struct StepReader<'a> {
+    file: &'a StepFile,
+    // In millimetres, like the lengths read.
+    uncertainty: f64,
+    length_unit: f64,
+    angle_unit: f64,
+}
+
+impl<'a> StepReader<'a> {
+    fn new(file: &'a StepFile) -> StepResult<StepReader<'a>> {
+        let length_unit = length_unit(file)?;
+        // The uncertainty has a unit of its own, which usually is the length unit of the file.
+        let uncertainty = match file
+            .entities
+            .values()
+            .filter_map(|e| e.record("UNCERTAINTY_MEASURE_WITH_UNIT"))
+            .find(|params| {
+                params.get(2) == Some(&Parameter::String("distance_accuracy_value".to_string()))
+            }) {
+            Some(params) => {
+                let unit = match params.get(1).map(|p| p.as_reference()) {
+                    Some(Ok(id)) => millimetres(file, id)?,
+                    _ => length_unit,
+                };
+                params[0].as_f64()? * unit
+            }
+            None => DEFAULT_UNCERTAINTY * length_unit,
+        };
+        Ok(StepReader {
+            file,
+            uncertainty,
+            length_unit,
+            angle_unit: angle_unit(file)?,
+        })
+    }
+
+    fn length(&self, x: f64) -> EFloat64 {
+        let x = x * self.length_unit;
+        EFloat64::new(x + self.uncertainty, x - self.uncertainty)
+    }
+
+    fn angle(&self, x: f64) -> EFloat64 {
+        EFloat64::from(x * self.angle_unit)
+    }
+
+    fn entity(&self, id: usize) -> StepResult<&'a Entity> {
+        self.file.get(id)
+    }
+
+    // Parameters of a simple entity with the given type.
+    fn params(&self, id: usize, name: &str) -> StepResult<&'a [Parameter]> {
+        let entity = self.entity(id)?;
+        match entity.name() == name {
+            true => Ok(&entity.records[0].1),
+            false => Err(StepError::new(format!(
+                "Expected {} at #{}, found {:?}",
+                name, id, entity.records
+            ))),
+        }
+    }
+
+    // Parameter i of params, which may be shorter than expected in broken files.
+    fn param<'b>(&self, params: &'b [Parameter], i: usize, id: usize) -> StepResult<&'b Parameter> {
+        params
+            .get(i)
+            .ok_or_else(|| StepError::new(format!("Missing parameter {} of #{}", i, id)))
+    }
+
+    fn point(&self, id: usize) -> StepResult<Point> {
+        let params = self.params(id, "CARTESIAN_POINT")?;
+        match reals(self.param(params, 1, id)?)?[..] {
+            [x, y, z] => Ok(Point::new(self.length(x), self.length(y), self.length(z))),
+            _ => Err(StepError::new(format!("Expected a 3D point at #{}", id))),
+        }
+    }
+
+    fn direction(&self, id: usize) -> StepResult<Point> {
+        let params = self.params(id, "DIRECTION")?;
+        let component =
+            |x: f64| EFloat64::new(x + DIRECTION_UNCERTAINTY, x - DIRECTION_UNCERTAINTY);
+        match reals(self.param(params, 1, id)?)?[..] {
+            [x, y, z] => geometry_error(
+                Point::new(component(x), component(y), component(z)).normalize(),
+                id,
+            ),
+            _ => Err(StepError::new(format!(
+                "Expected a 3D direction at #{}",
+                id
+            ))),
+        }
+    }
+
+    // Location, axis and reference direction of an AXIS2_PLACEMENT_3D. The reference direction is made perpendicular to the axis.
+    fn placement(&self, id: usize) -> StepResult<(Point, Point, Point)> {
+        let params = self.params(id, "AXIS2_PLACEMENT_3D")?;
+        let location = self.point(self.param(params, 1, id)?.as_reference()?)?;
+        let axis = match self.param(params, 2, id)? {
+            p if p.is_unset() => Point::unit_z(),
+            p => self.direction(p.as_reference()?)?,
+        };
+        let ref_dir = match self.param(params, 3, id)? {
+            p if p.is_unset() => Point::unit_x(),
+            p => self.direction(p.as_reference()?)?,
+        };
+        let ref_dir = match (ref_dir - axis * axis.dot(ref_dir)).normalize() {
+            Ok(ref_dir) => ref_dir,
+            Err(_) => geometry_error(
+                (Point::unit_y() - axis * axis.dot(Point::unit_y())).normalize(),
+                id,
+            )?,
+        };
+        Ok((location, axis, ref_dir))
+    }
+
+    fn curve(&self, id: usize) -> StepResult<Curve> {
+        let entity = self.entity(id)?;
+        if entity.record("RATIONAL_B_SPLINE_CURVE").is_some() {
+            return self.rational_b_spline_curve(id, entity);
+        }
+        let params = &entity.records[0].1;
+        match entity.name() {
+            "LINE" => {
+                let point = self.point(self.param(params, 1, id)?.as_reference()?)?;
+                let vector_id = self.param(params, 2, id)?.as_reference()?;
+                let vector = self.params(vector_id, "VECTOR")?;
+                let direction =
+                    self.direction(self.param(vector, 1, vector_id)?.as_reference()?)?;
+                Ok(Curve::Line(geometry_error(
+                    Line::new(point, direction),
+                    id,
+                )?))
+            }
+            "CIRCLE" => {
+                let (location, axis, _) =
+                    self.placement(self.param(params, 1, id)?.as_reference()?)?;
+                let radius = self.length(self.param(params, 2, id)?.as_f64()?);
+                Ok(Curve::Circle(Circle::new(location, axis, radius)))
+            }
+            "ELLIPSE" => {
+                let (location, axis, ref_dir) =
+                    self.placement(self.param(params, 1, id)?.as_reference()?)?;
+                let major = self.length(self.param(params, 2, id)?.as_f64()?);
+                let minor = self.length(self.param(params, 3, id)?.as_f64()?);
+                Ok(Curve::Ellipse(Ellipse::new(
+                    location,
+                    axis,
+                    ref_dir * major,
+                    axis.cross(ref_dir) * minor,
+                )))
+            }
+            "B_SPLINE_CURVE_WITH_KNOTS" => {
+                let degree = self.param(params, 1, id)?.as_usize()?;
+                let points = self.points(self.param(params, 2, id)?)?;
+                let knots = expand_knots(self.param(params, 6, id)?, self.param(params, 7, id)?)?;
+                let weights = vec![EFloat64::one(); points.len()];
+                Ok(Curve::Nurbs(geometry_error(
+                    Nurbs::new(degree, knots, weights, points),
+                    id,
+                )?))
+            }
+            // Curves on surfaces are read as their 3D curve.
+            "SURFACE_CURVE" | "SEAM_CURVE" => {
+                self.curve(self.param(params, 1, id)?.as_reference()?)
+            }
+            _ => Err(StepError::new(format!(
+                "Unsupported curve at #{}: {:?}",
+                id, entity.records
+            ))),
+        }
+    }
+
+    fn rational_b_spline_curve(&self, id: usize, entity: &Entity) -> StepResult<Curve> {
+        let missing = || StepError::new(format!("Incomplete rational B-spline curve at #{}", id));
+        let curve = entity.record("B_SPLINE_CURVE").ok_or_else(missing)?;
+        let knots = entity
+            .record("B_SPLINE_CURVE_WITH_KNOTS")
+            .ok_or_else(missing)?;
+        let weights = entity
+            .record("RATIONAL_B_SPLINE_CURVE")
+            .ok_or_else(missing)?;
+        let degree = self.param(curve, 0, id)?.as_usize()?;
+        let points = self.points(self.param(curve, 1, id)?)?;
+        let knots = expand_knots(self.param(knots, 0, id)?, self.param(knots, 1, id)?)?;
+        let weights = efloats(self.param(weights, 0, id)?)?;
+        Ok(Curve::Nurbs(geometry_error(
+            Nurbs::new(degree, knots, weights, points),
+            id,
+        )?))
+    }
+
+    fn points(&self, param: &Parameter) -> StepResult<Vec<Point>> {
+        param
+            .as_list()?
+            .iter()
+            .map(|p| self.point(p.as_reference()?))
+            .collect()
+    }
+
+    fn point_grid(&self, param: &Parameter) -> StepResult<Vec<Vec<Point>>> {
+        param
+            .as_list()?
+            .iter()
+            .map(|row| self.points(row))
+            .collect()
+    }
+
+    fn surface(&self, id: usize) -> StepResult<Surface> {
+        let entity = self.entity(id)?;
+        if entity.record("RATIONAL_B_SPLINE_SURFACE").is_some() {
+            return self.rational_b_spline_surface(id, entity);
+        }
+        let params = &entity.records[0].1;
+        let placement = || self.placement(self.param(params, 1, id)?.as_reference()?);
+        let real = |i: usize| -> StepResult<EFloat64> {
+            Ok(self.length(self.param(params, i, id)?.as_f64()?))
+        };
+        match entity.name() {
+            "PLANE" => {
+                let (location, axis, ref_dir) = placement()?;
+                Ok(Surface::Plane(Plane::new(
+                    location,
+                    ref_dir,
+                    axis.cross(ref_dir),
+                )))
+            }
+            "SPHERICAL_SURFACE" => {
+                let (location, _, _) = placement()?;
+                Ok(Surface::Sphere(Sphere::new(location, real(2)?, true)))
+            }
+            "CYLINDRICAL_SURFACE" => {
+                let (location, axis, _) = placement()?;
+                Ok(Surface::Cylinder(Cylinder::new(
+                    location,
+                    axis,
+                    real(2)?,
+                    true,
+                )))
+            }
+            // The radius is measured at the location, so the apex is below it.
+            "CONICAL_SURFACE" => {
+                let (location, axis, _) = placement()?;
+                let radius = real(2)?;
+                let half_angle = self.angle(self.param(params, 3, id)?.as_f64()?);
+                if !(half_angle > 0.0 && half_angle < std::f64::consts::FRAC_PI_2) {
+                    return Err(StepError::new(format!(
+                        "Invalid half angle of the cone at #{}",
+                        id
+                    )));
+                }
+                let height = (half_angle.sin() / half_angle.cos())
+                    .and_then(|tan| radius / tan)
+                    .map_err(|e| StepError::new(format!("Invalid cone at #{}: {}", id, e)))?;
+                Ok(Surface::Cone(Cone::new(
+                    location - axis * height,
+                    axis,
+                    half_angle,
+                    true,
+                )))
+            }
+            "TOROIDAL_SURFACE" => {
+                let (location, axis, _) = placement()?;
+                Ok(Surface::Torus(Torus::new(
+                    location,
+                    axis,
+                    real(2)?,
+                    real(3)?,
+                    true,
+                )))
+            }
+            "B_SPLINE_SURFACE_WITH_KNOTS" => {
+                let degree_u = self.param(params, 1, id)?.as_usize()?;
+                let degree_v = self.param(params, 2, id)?.as_usize()?;
+                let points = self.point_grid(self.param(params, 3, id)?)?;
+                let knots_u =
+                    expand_knots(self.param(params, 8, id)?, self.param(params, 10, id)?)?;
+                let knots_v =
+                    expand_knots(self.param(params, 9, id)?, self.param(params, 11, id)?)?;
+                let weights = points
+                    .iter()
+                    .map(|row| vec![EFloat64::one(); row.len()])
+                    .collect();
+                Ok(Surface::Nurbs(geometry_error(
+                    NurbsSurface::new(degree_u, degree_v, knots_u, knots_v, weights, points),
+                    id,
+                )?))
+            }
+            _ => Err(StepError::new(format!(
+                "Unsupported surface at #{}: {:?}",
+                id, entity.records
+            ))),
+        }
+    }
+
+    fn rational_b_spline_surface(&self, id: usize, entity: &Entity) -> StepResult<Surface> {
+        let missing = || StepError::new(format!("Incomplete rational B-spline surface at #{}", id));
+        let surface = entity.record("B_SPLINE_SURFACE").ok_or_else(missing)?;
+        let knots = entity
+            .record("B_SPLINE_SURFACE_WITH_KNOTS")
+            .ok_or_else(missing)?;
+        let weights = entity
+            .record("RATIONAL_B_SPLINE_SURFACE")
+            .ok_or_else(missing)?;
+        let degree_u = self.param(surface, 0, id)?.as_usize()?;
+        let degree_v = self.param(surface, 1, id)?.as_usize()?;
+        let points = self.point_grid(self.param(surface, 2, id)?)?;
+        let knots_u = expand_knots(self.param(knots, 0, id)?, self.param(knots, 2, id)?)?;
+        let knots_v = expand_knots(self.param(knots, 1, id)?, self.param(knots, 3, id)?)?;
+        let weights = self
+            .param(weights, 0, id)?
+            .as_list()?
+            .iter()
+            .map(efloats)
+            .collect::<StepResult<Vec<Vec<EFloat64>>>>()?;
+        Ok(Surface::Nurbs(geometry_error(
+            NurbsSurface::new(degree_u, degree_v, knots_u, knots_v, weights, points),
+            id,
+        )?))
+    }
+
+    fn vertex(&self, id: usize) -> StepResult<Point> {
+        let params = self.params(id, "VERTEX_POINT")?;
+        self.point(self.param(params, 1, id)?.as_reference()?)
+    }
+
+    fn edge_curve(&self, id: usize) -> StepResult<Edge> {
+        let params = self.params(id, "EDGE_CURVE")?;
+        let start = self.vertex(self.param(params, 1, id)?.as_reference()?)?;
+        let end = self.vertex(self.param(params, 2, id)?.as_reference()?)?;
+        let curve = self.curve(self.param(params, 3, id)?.as_reference()?)?;
+        let curve = match self.param(params, 4, id)?.as_bool()? {
+            true => curve,
+            false => curve.neg(),
+        };
+        if !curve.on_curve(start) || !curve.on_curve(end) {
+            return Err(StepError::new(format!(
+                "Vertices of #{} are not on its curve",
+                id
+            )));
+        }
+        // Closed edges start and end at the same vertex.
+        match start == end {
+            true => Ok(Edge::new(None, None, curve)),
+            false => Ok(Edge::new(Some(start), Some(end), curve)),
+        }
+    }
+
+    fn oriented_edge(&self, id: usize) -> StepResult<Edge> {
+        let params = self.params(id, "ORIENTED_EDGE")?;
+        let edge = self.edge_curve(self.param(params, 3, id)?.as_reference()?)?;
+        match self.param(params, 4, id)?.as_bool()? {
+            true => Ok(edge),
+            false => Ok(edge.flip()),
+        }
+    }
+
+    fn contour(&self, id: usize) -> StepResult<Contour> {
+        let params = self.params(id, "EDGE_LOOP")?;
+        let edges = self
+            .param(params, 1, id)?
+            .as_list()?
+            .iter()
+            .map(|e| self.oriented_edge(e.as_reference()?))
+            .collect::<StepResult<Vec<Edge>>>()?;
+        if edges.is_empty() {
+            return Err(StepError::new(format!("Empty edge loop at #{}", id)));
+        }
+        Ok(Contour::new(edges))
+    }
+
+    fn face(&self, id: usize) -> StepResult<Face> {
+        let entity = self.entity(id)?;
+        if !matches!(entity.name(), "ADVANCED_FACE" | "FACE_SURFACE") {
+            return Err(StepError::new(format!(
+                "Expected a face at #{}, found {:?}",
+                id, entity.records
+            )));
+        }
+        let params = &entity.records[0].1;
+        let mut outer = Vec::new();
+        let mut inner = Vec::new();
+        for bound in self.param(params, 1, id)?.as_list()? {
+            let bound_id = bound.as_reference()?;
+            let bound = self.entity(bound_id)?;
+            let bound_params = &bound.records[0].1;
+            let contour = self.contour(self.param(bound_params, 1, bound_id)?.as_reference()?)?;
+            let contour = match self.param(bound_params, 2, bound_id)?.as_bool()? {
+                true => contour,
+                false => contour.flip(),
+            };
+            match bound.name() {
+                "FACE_OUTER_BOUND" => outer.push(contour),
+                "FACE_BOUND" => inner.push(contour),
+                _ => {
+                    return Err(StepError::new(format!(
+                        "Expected a face bound at #{}",
+                        bound_id
+                    )))
+                }
+            }
+        }
+        let surface = self.surface(self.param(params, 2, id)?.as_reference()?)?;
+        let surface = match self.param(params, 3, id)?.as_bool()? {
+            true => surface,
+            false => surface.neg(),
+        };
+        outer.extend(inner);
+        Ok(Face::new(outer, Rc::new(surface)))
+    }
+
+    fn shell(&self, id: usize) -> StepResult<Shell> {
+        let entity = self.entity(id)?;
+        let params = &entity.records[0].1;
+        match entity.name() {
+            "CLOSED_SHELL" | "OPEN_SHELL" => {
+                let faces = self
+                    .param(params, 1, id)?
+                    .as_list()?
+                    .iter()
+                    .map(|f| self.face(f.as_reference()?))
+                    .collect::<StepResult<Vec<Face>>>()?;
+                if faces.is_empty() {
+                    return Err(StepError::new(format!("Empty shell at #{}", id)));
+                }
+                Ok(Shell::new(faces))
+            }
+            "ORIENTED_CLOSED_SHELL" | "ORIENTED_OPEN_SHELL" => {
+                let shell = self.shell(self.param(params, 2, id)?.as_reference()?)?;
+                match self.param(params, 3, id)?.as_bool()? {
+                    true => Ok(shell),
+                    false => Ok(shell.flip()),
+                }
+            }
+            _ => Err(StepError::new(format!(
+                "Expected a shell at #{}, found {:?}",
+                id, entity.records
+            ))),
+        }
+    }
+
+    fn volume(&self, id: usize) -> StepResult<Volume> {
+        let entity = self.entity(id)?;
+        let params = &entity.records[0].1;
+        let boundary = self.shell(self.param(params, 1, id)?.as_reference()?)?;
+        let cavities = match entity.name() {
+            "BREP_WITH_VOIDS" => self
+                .param(params, 2, id)?
+                .as_list()?
+                .iter()
+                .map(|s| self.shell(s.as_reference()?))
+                .collect::<StepResult<Vec<Shell>>>()?,
+            _ => Vec::new(),
+        };
+        Ok(Volume::new(boundary, cavities))
+    }
+}
+
+// Reads all solids of a STEP file. Lengths are converted to millimetres, angles to radians.
+pub fn volumes_from_step(text: &str) -> StepResult<Vec<Volume>> {
+    let file = parse_step(text)?;
+    let reader = StepReader::new(&file)?;
+    file.entities
+        .iter()
+        .filter(|(_, e)| matches!(e.name(), "MANIFOLD_SOLID_BREP" | "BREP_WITH_VOIDS"))
+        .map(|(id, _)| reader.volume(*id))
+        .collect()
+}
+
+// Reads the shells of all surface models of a STEP file.
+pub fn shells_from_step(text: &str) -> StepResult<Vec<Shell>> {
+    let file = parse_step(text)?;
+    let reader = StepReader::new(&file)?;
+    let mut shells = Vec::new();
+    for (id, entity) in file.entities.iter() {
+        if entity.name() != "SHELL_BASED_SURFACE_MODEL" {
+            continue;
+        }
+        for shell in reader.param(&entity.records[0].1, 1, *id)?.as_list()? {
+            shells.push(reader.shell(shell.as_reference()?)?);
+        }
+    }
+    Ok(shells)
+}
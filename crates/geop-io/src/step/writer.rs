@@ -0,0 +1,488 @@
+use geop_geometry::{
+    curves::{curve::Curve, CurveLike},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::surface::Surface,
+    tolerance::Tolerance,
+};
+use geop_topology::topology::{
+    contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume,
+};
+
+use super::{StepError, StepResult};
+
+// Accuracy of distances written for models without linear tolerance.
+const DEFAULT_UNCERTAINTY: f64 = 1e-7;
+
+// STEP reals need a decimal point, also in front of the exponent.
+fn real(x: EFloat64) -> String {
+    let text = format!("{:?}", x.mid());
+    match text.split_once('e') {
+        Some((mantissa, exponent)) if mantissa.contains('.') => {
+            format!("{}E{}", mantissa, exponent)
+        }
+        Some((mantissa, exponent)) => format!("{}.E{}", mantissa, exponent),
+        None => text,
+    }
+}
+
+fn list(items: impl IntoIterator<Item = String>) -> String {
+    format!("({})", items.into_iter().collect::<Vec<String>>().join(","))
+}
+
+fn references(ids: &[usize]) -> String {
+    list(ids.iter().map(|id| format!("#{}", id)))
+}
+
+fn boolean(b: bool) -> &'static str {
+    match b {
+        true => ".T.",
+        false => ".F.",
+    }
+}
+
+// Any unit vector perpendicular to axis.
+fn perpendicular(axis: Point) -> Point {
+    match Point::unit_x().cross(axis).norm_sq() > Point::unit_y().cross(axis).norm_sq().lower_bound
+    {
+        true => Point::unit_x().cross(axis).normalize().unwrap(),
+        false => Point::unit_y().cross(axis).normalize().unwrap(),
+    }
+}
+
+// STEP stores knots without repetition and their multiplicities separately.
+fn compress_knots(knots: &[EFloat64]) -> (Vec<usize>, Vec<EFloat64>) {
+    let mut multiplicities: Vec<usize> = Vec::new();
+    let mut distinct: Vec<EFloat64> = Vec::new();
+    for knot in knots {
+        match distinct.last() {
+            Some(last) if (*last).mid() == (*knot).mid() => {
+                *multiplicities.last_mut().unwrap() += 1
+            }
+            _ => {
+                distinct.push(*knot);
+                multiplicities.push(1);
+            }
+        }
+    }
+    (multiplicities, distinct)
+}
+
+fn knot_lists(knots: &[EFloat64]) -> (String, String) {
+    let (multiplicities, distinct) = compress_knots(knots);
+    (
+        list(multiplicities.iter().map(|m| m.to_string())),
+        list(distinct.iter().map(|k| real(*k))),
+    )
+}
+
+fn is_rational(weights: &[EFloat64]) -> bool {
+    weights.iter().any(|w| (*w).mid() != 1.0)
+}
+
+const HEADER: &str = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('geop model'),'2;1');
+FILE_NAME('','',(''),(''),'geop','geop','');
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));
+ENDSEC;
+DATA;
+";
+
+const FOOTER: &str = "ENDSEC;
+END-ISO-10303-21;
+";
+
+// Collects the entity instances of a STEP file. Vertices and edges that are shared by faces are only written once.
+struct StepWriter {
+    entities: Vec<String>,
+    vertices: Vec<(Point, usize)>,
+    edges: Vec<(Edge, usize)>,
+    product_shape: usize,
+    context: usize,
+}
+
+impl StepWriter {
+    fn new() -> StepWriter {
+        let mut writer = StepWriter {
+            entities: Vec::new(),
+            vertices: Vec::new(),
+            edges: Vec::new(),
+            product_shape: 0,
+            context: 0,
+        };
+        let application = writer.add("APPLICATION_CONTEXT('automotive design')".to_string());
+        writer.add(format!(
+            "APPLICATION_PROTOCOL_DEFINITION('international standard','automotive_design',2000,#{})",
+            application
+        ));
+        let product_context =
+            writer.add(format!("PRODUCT_CONTEXT('',#{},'mechanical')", application));
+        let product = writer.add(format!("PRODUCT('geop','geop','',(#{}))", product_context));
+        let definition_context = writer.add(format!(
+            "PRODUCT_DEFINITION_CONTEXT('part definition',#{},'design')",
+            application
+        ));
+        let formation = writer.add(format!("PRODUCT_DEFINITION_FORMATION('','',#{})", product));
+        let definition = writer.add(format!(
+            "PRODUCT_DEFINITION('design','',#{},#{})",
+            formation, definition_context
+        ));
+        writer.product_shape =
+            writer.add(format!("PRODUCT_DEFINITION_SHAPE('','',#{})", definition));
+
+        // Lengths are written as they are, which is interpreted as millimetres.
+        let length =
+            writer.add("(LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.))".to_string());
+        let angle =
+            writer.add("(NAMED_UNIT(*) PLANE_ANGLE_UNIT() SI_UNIT($,.RADIAN.))".to_string());
+        let solid_angle =
+            writer.add("(NAMED_UNIT(*) SI_UNIT($,.STERADIAN.) SOLID_ANGLE_UNIT())".to_string());
+        // The linear tolerance of the model is the accuracy of distances. Importers expect a positive accuracy, also for models without tolerance.
+        let linear = match Tolerance::current().linear {
+            linear if linear > 0.0 => linear,
+            _ => DEFAULT_UNCERTAINTY,
+        };
+        let uncertainty = writer.add(format!(
+            "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE({}),#{},'distance_accuracy_value','confusion accuracy')",
+            real(EFloat64::from(linear)),
+            length
+        ));
+        writer.context = writer.add(format!(
+            "(GEOMETRIC_REPRESENTATION_CONTEXT(3) GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{})) GLOBAL_UNIT_ASSIGNED_CONTEXT((#{},#{},#{})) REPRESENTATION_CONTEXT('',''))",
+            uncertainty, length, angle, solid_angle
+        ));
+        writer
+    }
+
+    fn add(&mut self, entity: String) -> usize {
+        self.entities.push(entity);
+        self.entities.len()
+    }
+
+    fn point(&mut self, p: Point) -> usize {
+        self.add(format!(
+            "CARTESIAN_POINT('',{})",
+            list([real(p.x), real(p.y), real(p.z)])
+        ))
+    }
+
+    fn direction(&mut self, d: Point) -> usize {
+        let d = d.normalize().unwrap();
+        self.add(format!(
+            "DIRECTION('',{})",
+            list([real(d.x), real(d.y), real(d.z)])
+        ))
+    }
+
+    fn placement(&mut self, location: Point, axis: Point, ref_dir: Point) -> usize {
+        let location = self.point(location);
+        let axis = self.direction(axis);
+        let ref_dir = self.direction(ref_dir);
+        self.add(format!(
+            "AXIS2_PLACEMENT_3D('',#{},#{},#{})",
+            location, axis, ref_dir
+        ))
+    }
+
+    fn control_points(&mut self, points: &[Point]) -> String {
+        let ids: Vec<usize> = points.iter().map(|p| self.point(*p)).collect();
+        references(&ids)
+    }
+
+    fn curve(&mut self, curve: &Curve) -> StepResult<usize> {
+        match curve {
+            Curve::Line(line) => {
+                let point = self.point(line.basis);
+                let direction = self.direction(line.direction);
+                let vector = self.add(format!("VECTOR('',#{},1.)", direction));
+                Ok(self.add(format!("LINE('',#{},#{})", point, vector)))
+            }
+            Curve::Circle(circle) => {
+                let position = self.placement(circle.basis, circle.normal, circle.radius);
+                Ok(self.add(format!(
+                    "CIRCLE('',#{},{})",
+                    position,
+                    real(circle.radius.norm())
+                )))
+            }
+            Curve::Ellipse(ellipse) => {
+                let position = self.placement(ellipse.basis, ellipse.normal, ellipse.major_radius);
+                Ok(self.add(format!(
+                    "ELLIPSE('',#{},{},{})",
+                    position,
+                    real(ellipse.major_radius.norm()),
+                    real(ellipse.minor_radius.norm())
+                )))
+            }
+            Curve::Helix(_) => Err(StepError::new(
+                "Helices can not be written to STEP files".to_string(),
+            )),
+            Curve::Nurbs(nurbs) => {
+                let points = self.control_points(&nurbs.control_points);
+                let (multiplicities, knots) = knot_lists(&nurbs.knots);
+                if !is_rational(&nurbs.weights) {
+                    return Ok(self.add(format!(
+                        "B_SPLINE_CURVE_WITH_KNOTS('',{},{},.UNSPECIFIED.,.F.,.F.,{},{},.UNSPECIFIED.)",
+                        nurbs.degree, points, multiplicities, knots
+                    )));
+                }
+                let weights = list(nurbs.weights.iter().map(|w| real(*w)));
+                Ok(self.add(format!(
+                    "(BOUNDED_CURVE() B_SPLINE_CURVE({},{},.UNSPECIFIED.,.F.,.F.) B_SPLINE_CURVE_WITH_KNOTS({},{},.UNSPECIFIED.) CURVE() GEOMETRIC_REPRESENTATION_ITEM() RATIONAL_B_SPLINE_CURVE({}) REPRESENTATION_ITEM(''))",
+                    nurbs.degree, points, multiplicities, knots, weights
+                )))
+            }
+        }
+    }
+
+    // Returns the surface and whether its normal agrees with the normal of the STEP surface.
+    fn surface(&mut self, surface: &Surface) -> StepResult<(usize, bool)> {
+        match surface {
+            Surface::Plane(plane) => {
+                let normal = plane.u_slope.cross(plane.v_slope);
+                let position = self.placement(plane.basis, normal, plane.u_slope);
+                Ok((self.add(format!("PLANE('',#{})", position)), true))
+            }
+            Surface::Sphere(sphere) => {
+                let position = self.placement(sphere.basis, Point::unit_z(), Point::unit_x());
+                let id = self.add(format!(
+                    "SPHERICAL_SURFACE('',#{},{})",
+                    position,
+                    real(sphere.radius)
+                ));
+                Ok((id, sphere.normal_outwards))
+            }
+            Surface::Cylinder(cylinder) => {
+                let position = self.placement(cylinder.basis, cylinder.extend_dir, cylinder.radius);
+                let id = self.add(format!(
+                    "CYLINDRICAL_SURFACE('',#{},{})",
+                    position,
+                    real(cylinder.radius.norm())
+                ));
+                Ok((id, cylinder.normal_outwards))
+            }
+            Surface::Cone(cone) => {
+                let position =
+                    self.placement(cone.basis, cone.extend_dir, perpendicular(cone.extend_dir));
+                let id = self.add(format!(
+                    "CONICAL_SURFACE('',#{},0.,{})",
+                    position,
+                    real(cone.half_angle)
+                ));
+                Ok((id, cone.normal_outwards))
+            }
+            Surface::Torus(torus) => {
+                let position =
+                    self.placement(torus.basis, torus.normal, perpendicular(torus.normal));
+                let id = self.add(format!(
+                    "TOROIDAL_SURFACE('',#{},{},{})",
+                    position,
+                    real(torus.major_radius),
+                    real(torus.minor_radius)
+                ));
+                Ok((id, torus.normal_outwards))
+            }
+            Surface::Ellipsoid(_) => Err(StepError::new(
+                "Ellipsoids can not be written to STEP files".to_string(),
+            )),
+            Surface::EllipticCylinder(_) => Err(StepError::new(
+                "Elliptic cylinders can not be written to STEP files".to_string(),
+            )),
+            Surface::Nurbs(nurbs) => {
+                let rows: Vec<String> = nurbs
+                    .control_points
+                    .iter()
+                    .map(|row| self.control_points(row))
+                    .collect();
+                let points = list(rows);
+                let (multiplicities_u, knots_u) = knot_lists(&nurbs.knots_u);
+                let (multiplicities_v, knots_v) = knot_lists(&nurbs.knots_v);
+                let weights: Vec<EFloat64> = nurbs.weights.iter().flatten().cloned().collect();
+                if !is_rational(&weights) {
+                    let id = self.add(format!(
+                        "B_SPLINE_SURFACE_WITH_KNOTS('',{},{},{},.UNSPECIFIED.,.F.,.F.,.F.,{},{},{},{},.UNSPECIFIED.)",
+                        nurbs.degree_u, nurbs.degree_v, points, multiplicities_u, multiplicities_v, knots_u, knots_v
+                    ));
+                    return Ok((id, true));
+                }
+                let weights = list(
+                    nurbs
+                        .weights
+                        .iter()
+                        .map(|row| list(row.iter().map(|w| real(*w)))),
+                );
+                let id = self.add(format!(
+                    "(BOUNDED_SURFACE() B_SPLINE_SURFACE({},{},{},.UNSPECIFIED.,.F.,.F.,.F.) B_SPLINE_SURFACE_WITH_KNOTS({},{},{},{},.UNSPECIFIED.) GEOMETRIC_REPRESENTATION_ITEM() RATIONAL_B_SPLINE_SURFACE({}) REPRESENTATION_ITEM('') SURFACE())",
+                    nurbs.degree_u, nurbs.degree_v, points, multiplicities_u, multiplicities_v, knots_u, knots_v, weights
+                ));
+                Ok((id, true))
+            }
+        }
+    }
+
+    fn vertex(&mut self, p: Point) -> usize {
+        if let Some((_, id)) = self.vertices.iter().find(|(v, _)| *v == p) {
+            return *id;
+        }
+        let point = self.point(p);
+        let id = self.add(format!("VERTEX_POINT('',#{})", point));
+        self.vertices.push((p, id));
+        id
+    }
+
+    // Writes the edge as an oriented edge. The underlying edge curve is shared with the neighbouring face, which uses it in the opposite direction.
+    fn edge(&mut self, edge: &Edge) -> StepResult<usize> {
+        let existing = self.edges.iter().find(|(e, _)| e == edge);
+        let (edge_curve, orientation) = match existing {
+            Some((e, id)) => (
+                *id,
+                e.start == edge.start && e.end == edge.end && e.curve == edge.curve,
+            ),
+            None => {
+                let (start, end) = match (edge.start, edge.end) {
+                    (Some(start), Some(end)) => (start, end),
+                    (None, None) => {
+                        // Closed curves start and end at the same vertex.
+                        let p = edge.curve.get_midpoint(None, None).map_err(|_| {
+                            StepError::new(format!("Failed to find a vertex on {:?}", edge.curve))
+                        })?;
+                        (p, p)
+                    }
+                    _ => {
+                        return Err(StepError::new(
+                            "Edges have to be bounded or closed".to_string(),
+                        ))
+                    }
+                };
+                let start = self.vertex(start);
+                let end = self.vertex(end);
+                let curve = self.curve(&edge.curve)?;
+                let id = self.add(format!("EDGE_CURVE('',#{},#{},#{},.T.)", start, end, curve));
+                self.edges.push((edge.clone(), id));
+                (id, true)
+            }
+        };
+        Ok(self.add(format!(
+            "ORIENTED_EDGE('',*,*,#{},{})",
+            edge_curve,
+            boolean(orientation)
+        )))
+    }
+
+    fn contour(&mut self, contour: &Contour) -> StepResult<usize> {
+        let edges = contour
+            .edges
+            .iter()
+            .map(|e| self.edge(e))
+            .collect::<StepResult<Vec<usize>>>()?;
+        Ok(self.add(format!("EDGE_LOOP('',{})", references(&edges))))
+    }
+
+    fn face(&mut self, face: &Face) -> StepResult<usize> {
+        let mut bounds = Vec::new();
+        for (i, contour) in face.boundaries.iter().enumerate() {
+            let edge_loop = self.contour(contour)?;
+            let bound = match i {
+                0 => "FACE_OUTER_BOUND",
+                _ => "FACE_BOUND",
+            };
+            bounds.push(self.add(format!("{}('',#{},.T.)", bound, edge_loop)));
+        }
+        let (surface, same_sense) = self.surface(&face.surface)?;
+        Ok(self.add(format!(
+            "ADVANCED_FACE('',{},#{},{})",
+            references(&bounds),
+            surface,
+            boolean(same_sense)
+        )))
+    }
+
+    fn shell(&mut self, shell: &Shell, kind: &str) -> StepResult<usize> {
+        let faces = shell
+            .faces
+            .iter()
+            .map(|f| self.face(f))
+            .collect::<StepResult<Vec<usize>>>()?;
+        Ok(self.add(format!("{}('',{})", kind, references(&faces))))
+    }
+
+    fn volume(&mut self, volume: &Volume) -> StepResult<usize> {
+        let boundary = self.shell(&volume.boundary, "CLOSED_SHELL")?;
+        if volume.cavities.is_empty() {
+            return Ok(self.add(format!("MANIFOLD_SOLID_BREP('',#{})", boundary)));
+        }
+        // Voids are closed shells with outwards normals, that are used in the opposite orientation.
+        let mut voids = Vec::new();
+        for cavity in volume.cavities.iter() {
+            let shell = self.shell(&cavity.flip(), "CLOSED_SHELL")?;
+            voids.push(self.add(format!("ORIENTED_CLOSED_SHELL('',*,#{},.F.)", shell)));
+        }
+        Ok(self.add(format!(
+            "BREP_WITH_VOIDS('',#{},{})",
+            boundary,
+            references(&voids)
+        )))
+    }
+
+    fn finish(mut self, representation: &str, items: &[usize]) -> String {
+        let origin = self.placement(Point::zero(), Point::unit_z(), Point::unit_x());
+        let mut items = items.to_vec();
+        items.push(origin);
+        let representation = self.add(format!(
+            "{}('',{},#{})",
+            representation,
+            references(&items),
+            self.context
+        ));
+        self.add(format!(
+            "SHAPE_DEFINITION_REPRESENTATION(#{},#{})",
+            self.product_shape, representation
+        ));
+
+        let mut text = HEADER.to_string();
+        for (i, entity) in self.entities.iter().enumerate() {
+            text.push_str(&format!("#{}={};\n", i + 1, entity));
+        }
+        text.push_str(FOOTER);
+        text
+    }
+}
+
+// Writes the volumes as solids into an AP214 STEP file.
+pub fn volumes_to_step(volumes: &[Volume]) -> StepResult<String> {
+    let mut writer = StepWriter::new();
+    let solids = volumes
+        .iter()
+        .map(|v| writer.volume(v))
+        .collect::<StepResult<Vec<usize>>>()?;
+    Ok(writer.finish("ADVANCED_BREP_SHAPE_REPRESENTATION", &solids))
+}
+
+// Writes the shells as a surface model into an AP214 STEP file. Shells with a boundary are written as open shells.
+pub fn shells_to_step(shells: &[Shell]) -> StepResult<String> {
+    let mut writer = StepWriter::new();
+    let shells = shells
+        .iter()
+        .map(|s| {
+            let kind = match is_closed(s) {
+                true => "CLOSED_SHELL",
+                false => "OPEN_SHELL",
+            };
+            writer.shell(s, kind)
+        })
+        .collect::<StepResult<Vec<usize>>>()?;
+    let model = writer.add(format!(
+        "SHELL_BASED_SURFACE_MODEL('',{})",
+        references(&shells)
+    ));
+    Ok(writer.finish("MANIFOLD_SURFACE_SHAPE_REPRESENTATION", &[model]))
+}
+
+// A shell is closed if every edge is used by exactly two faces in opposite directions.
+fn is_closed(shell: &Shell) -> bool {
+    let edges: Vec<Edge> = shell.faces.iter().flat_map(|f| f.all_edges()).collect();
+    edges
+        .iter()
+        .all(|e| edges.iter().filter(|other| *other == e).count() == 2)
+}
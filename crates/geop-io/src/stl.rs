@@ -0,0 +1,72 @@
+use std::fmt::Write;
+
+use crate::mesh::Mesh;
+
+// Binary STL: An 80 byte header, the number of triangles and 50 bytes per triangle, all little endian.
+pub fn mesh_to_stl_binary(mesh: &Mesh) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(84 + 50 * mesh.triangle_count());
+    let mut header = [b' '; 80];
+    header[..16].copy_from_slice(b"geop binary stl ");
+    bytes.extend_from_slice(&header);
+    bytes.extend_from_slice(&(mesh.triangle_count() as u32).to_le_bytes());
+    for triangle in mesh.triangles() {
+        let normal = mesh.facet_normal(triangle);
+        let corners = triangle.iter().map(|v| mesh.positions[v.position]);
+        for value in std::iter::once(normal).chain(corners).flatten() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        // Attribute byte count, which is unused.
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+    }
+    bytes
+}
+
+// ASCII STL. Groups are not supported by the format, so all faces end up in one solid.
+pub fn mesh_to_stl_ascii(mesh: &Mesh, name: &str) -> String {
+    let mut text = String::new();
+    writeln!(text, "solid {}", name).unwrap();
+    for triangle in mesh.triangles() {
+        let [nx, ny, nz] = mesh.facet_normal(triangle);
+        writeln!(text, "  facet normal {} {} {}", nx, ny, nz).unwrap();
+        writeln!(text, "    outer loop").unwrap();
+        for v in triangle.iter() {
+            let [x, y, z] = mesh.positions[v.position];
+            writeln!(text, "      vertex {} {} {}", x, y, z).unwrap();
+        }
+        writeln!(text, "    endloop").unwrap();
+        writeln!(text, "  endfacet").unwrap();
+    }
+    writeln!(text, "endsolid {}", name).unwrap();
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+    use geop_topology::primitive_objects::volumes::cube::primitive_cube;
+
+    use super::*;
+
+    #[test]
+    fn test_stl_cube() {
+        let cube = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one());
+        let mesh = Mesh::from_volume(&cube);
+        let n = mesh.triangle_count();
+
+        let bytes = mesh_to_stl_binary(&mesh);
+        assert_eq!(bytes.len(), 84 + 50 * n);
+        assert_eq!(
+            u32::from_le_bytes(bytes[80..84].try_into().unwrap()) as usize,
+            n
+        );
+
+        let text = mesh_to_stl_ascii(&mesh, "cube");
+        assert!(text.starts_with("solid cube\n"));
+        assert!(text.ends_with("endsolid cube\n"));
+        assert_eq!(text.matches("facet normal").count(), n);
+        assert_eq!(text.matches("vertex").count(), 3 * n);
+        // Normals of the cube are axis aligned.
+        assert!(text.contains("facet normal 0 1 0\n"));
+        assert!(text.contains("facet normal -1 0 0\n"));
+    }
+}
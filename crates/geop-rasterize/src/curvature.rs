@@ -0,0 +1,175 @@
+use geop_geometry::{
+    curves::CurveLike,
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{surface::Surface, SurfaceLike},
+};
+use geop_topology::{
+    primitive_objects::edges::line::primitive_line,
+    topology::{edge::Edge, face::Face, scene::Color},
+};
+
+use crate::{face::rasterize_face_into_triangle_list, triangle_buffer::TriangleBuffer};
+
+// The curvature of a surface that is used to colour it.
+#[derive(Copy, Clone, Debug)]
+pub enum CurvatureKind {
+    Gaussian,
+    Mean,
+    Max,
+    Min,
+}
+
+fn value(surface: &Surface, p: Point, kind: CurvatureKind) -> f64 {
+    let curvature = match kind {
+        CurvatureKind::Gaussian => surface.gaussian_curvature(p),
+        CurvatureKind::Mean => surface.mean_curvature(p),
+        CurvatureKind::Max => surface.principal_curvatures(p).max,
+        CurvatureKind::Min => surface.principal_curvatures(p).min,
+    };
+    curvature.mid()
+}
+
+// Diverging colour map from blue for negative over white for zero to red for positive curvatures. Curvatures outside of [-range, range] are clamped.
+pub fn curvature_color(curvature: f64, range: f64) -> Color {
+    let t = (curvature / range).clamp(-1.0, 1.0) as f32;
+    match t >= 0.0 {
+        true => Color::new(1.0, 1.0 - t, 1.0 - t, 1.0),
+        false => Color::new(1.0 + t, 1.0 + t, 1.0, 1.0),
+    }
+}
+
+// Rasterizes a face into a triangle list, where every vertex is coloured by the curvature of the surface at it.
+pub fn rasterize_face_into_curvature_triangle_list(
+    face: &Face,
+    kind: CurvatureKind,
+    range: f64,
+) -> TriangleBuffer {
+    let mut buffer = rasterize_face_into_triangle_list(face, Color::white());
+    for triangle in buffer.triangles.iter_mut() {
+        for vertex in [&mut triangle.a, &mut triangle.b, &mut triangle.c] {
+            // The vertex is stored in single precision, so it is moved back onto the surface.
+            let p = face.surface.project(vertex.point());
+            let color = curvature_color(value(&face.surface, p, kind), range);
+            vertex.color = [color.r, color.g, color.b, color.a];
+        }
+    }
+    buffer
+}
+
+// Rasterizes the curvature comb of an edge into lines. The teeth stand on the outer side of the curve with a length of scale times the curvature, and their tips are connected.
+pub fn rasterize_curvature_comb_into_edges(edge: &Edge, scale: f64, teeth: usize) -> Vec<Edge> {
+    let tips: Vec<(Point, Point)> = (0..=teeth)
+        .map(|i| {
+            let p = edge.interpolate(i as f64 / teeth as f64);
+            // Where the curve is straight, the frenet frame is not defined and the tooth vanishes.
+            let tip = match (edge.curve.curvature(p), edge.curve.frenet_frame(p)) {
+                (Ok(curvature), Ok(frame)) => {
+                    p - frame.normal * (curvature * EFloat64::from(scale))
+                }
+                _ => p,
+            };
+            (p, tip)
+        })
+        .collect();
+
+    let mut result = Vec::new();
+    for (p, tip) in tips.iter() {
+        if p != tip {
+            result.push(primitive_line(*p, *tip).unwrap());
+        }
+    }
+    for pair in tips.windows(2) {
+        if pair[0].1 != pair[1].1 {
+            result.push(primitive_line(pair[0].1, pair[1].1).unwrap());
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::surfaces::{plane::Plane, sphere::Sphere};
+    use geop_topology::{
+        operations::extrude::extrude,
+        primitive_objects::edges::circle::primitive_circle,
+        topology::{contour::Contour, face::Face},
+    };
+
+    use super::*;
+
+    fn colors(buffer: &TriangleBuffer) -> Vec<[f32; 4]> {
+        buffer
+            .triangles
+            .iter()
+            .flat_map(|triangle| [triangle.a.color, triangle.b.color, triangle.c.color])
+            .collect()
+    }
+
+    fn rgba(color: Color) -> [f32; 4] {
+        [color.r, color.g, color.b, color.a]
+    }
+
+    #[test]
+    fn test_curvature_sphere() {
+        // The upper half of a sphere of radius 2 bends by 1/2 in every direction.
+        let face = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                Point::unit_z(),
+                EFloat64::two(),
+            )])],
+            Rc::new(Surface::Sphere(Sphere::new(
+                Point::zero(),
+                EFloat64::two(),
+                true,
+            ))),
+        );
+        let buffer = rasterize_face_into_curvature_triangle_list(&face, CurvatureKind::Mean, 1.0);
+        let colors = colors(&buffer);
+        assert!(!colors.is_empty());
+        let expected = rgba(curvature_color(0.5, 1.0));
+        assert!(colors.iter().all(|color| *color == expected));
+    }
+
+    #[test]
+    fn test_curvature_cylinder() {
+        // The side of a cylinder of radius 1/2 bends by 2 around its axis and is straight along it.
+        let basis = Point::zero();
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                basis,
+                -Point::unit_z(),
+                EFloat64::from(0.5),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                basis,
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let volume = extrude(disk, Point::from_f64(0.0, 0.0, 1.0));
+        let side = volume
+            .all_faces()
+            .into_iter()
+            .find(|face| matches!(*face.surface, Surface::Cylinder(_)))
+            .unwrap();
+
+        for p in side.surface.point_grid(1.0) {
+            let principal = side.surface.principal_curvatures(p);
+            assert!((principal.max - EFloat64::two()).abs() < 1e-9);
+            assert!(principal.min.abs() < 1e-9);
+        }
+
+        let max = rasterize_face_into_curvature_triangle_list(&side, CurvatureKind::Max, 4.0);
+        let expected = rgba(curvature_color(2.0, 4.0));
+        assert!(!max.triangles.is_empty());
+        assert!(colors(&max).iter().all(|color| *color == expected));
+
+        let min = rasterize_face_into_curvature_triangle_list(&side, CurvatureKind::Min, 4.0);
+        let expected = rgba(Color::white());
+        assert!(colors(&min).iter().all(|color| *color == expected));
+    }
+}
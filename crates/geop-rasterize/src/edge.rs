@@ -1,29 +1,42 @@
+use std::f64::consts::TAU;
+
 use geop_geometry::{curves::curve::Curve, HORIZON_DIST};
 use geop_topology::topology::{edge::Edge, scene::Color};
 
 use crate::{
     edge_buffer::{EdgeBuffer, RenderEdge},
+    tessellation::Tessellation,
     vertex_buffer::{RenderVertex, VertexBuffer},
 };
 
 // Rasterizes an edge loop into triangle list.
 pub fn rasterize_edge_into_line_list(edge: &Edge, color: Color) -> EdgeBuffer {
+    let tessellation = Tessellation::current();
+    // Curves are refined by their largest radius of curvature.
     let n = match edge.curve {
         Curve::Line(_) => 10,
-        Curve::Circle(_) => 32,
-        Curve::Ellipse(_) => 32,
-        Curve::Helix(_) => 32 * HORIZON_DIST as usize,
+        Curve::Circle(ref circle) => tessellation.segments(32, TAU, circle.radius.norm().mid()),
+        Curve::Ellipse(ref ellipse) => {
+            let (a, b) = (ellipse.major_radius.norm(), ellipse.minor_radius.norm());
+            tessellation.segments(32, TAU, (a * a).mid() / b.mid())
+        }
+        Curve::Helix(ref helix) => {
+            let r = helix.radius.norm().mid();
+            let c = helix.pitch.norm().mid() / TAU;
+            tessellation.segments(
+                32 * HORIZON_DIST as usize,
+                TAU * HORIZON_DIST,
+                r + c * c / r,
+            )
+        }
+        Curve::Nurbs(ref nurbs) => 8 * nurbs.control_points.len(),
     };
-    let mut edges = Vec::<RenderEdge>::with_capacity(n);
-    for j in 0..n {
-        let v1 = (j as f64) / n as f64;
-        let v2 = ((j + 1) as f64) / n as f64;
-        edges.push(RenderEdge::new(
-            edge.interpolate(v1),
-            edge.interpolate(v2),
-            color,
-        ));
-    }
+    // The segments are evenly spaced by arc length.
+    let points = edge.sample_uniform(n + 1);
+    let edges = points
+        .windows(2)
+        .map(|pair| RenderEdge::new(pair[0], pair[1], color))
+        .collect();
     EdgeBuffer::new(edges)
 }
 
@@ -13,6 +13,7 @@ use geop_topology::{
 use crate::{
     contour::rasterize_contour_into_line_list,
     edge_buffer::{EdgeBuffer, RenderEdge},
+    tessellation::Tessellation,
     triangle_buffer::{RenderTriangle, TriangleBuffer},
     vertex_buffer::{RenderVertex, VertexBuffer},
 };
@@ -339,7 +340,7 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
     // Then generate additional points on the surface
     connection_points.extend(
         face.surface
-            .point_grid(1.0)
+            .point_grid(Tessellation::current().density(&face.surface))
             .drain(..)
             .filter(|p| face_point_contains(face, *p) == FacePointContains::Inside)
             .map(|point| RenderVertex::new(point.clone(), color)),
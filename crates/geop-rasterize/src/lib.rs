@@ -1,8 +1,10 @@
 pub mod boundingbox;
 pub mod contour;
+pub mod curvature;
 pub mod edge;
 pub mod edge_buffer;
 pub mod face;
+pub mod tessellation;
 pub mod triangle_buffer;
 pub mod vertex_buffer;
 pub mod vertex_normal_buffer;
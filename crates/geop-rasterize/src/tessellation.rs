@@ -0,0 +1,168 @@
+use std::{
+    cell::Cell,
+    f64::consts::{SQRT_2, TAU},
+};
+
+use geop_geometry::surfaces::surface::Surface;
+
+// How closely curves are approximated by line segments, and curved surfaces by triangles.
+// The default has no limits, so curves get a fixed number of segments and surfaces a fixed grid. It is set like geop_geometry::tolerance::Tolerance, which decides whether geometry coincides and not how fine it is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tessellation {
+    // Largest distance between a curve and its segments, or between a curved surface and its triangles.
+    pub chord: f64,
+    // Largest angle in radians that the direction of a curve turns by within a segment.
+    pub angle: f64,
+}
+
+const DEFAULT: Tessellation = Tessellation {
+    chord: 0.0,
+    angle: 0.0,
+};
+
+thread_local! {
+    static CURRENT: Cell<Tessellation> = const { Cell::new(DEFAULT) };
+}
+
+// Restores the previous tessellation, also if the rasterization panics.
+struct Restore(Tessellation);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|c| c.set(self.0));
+    }
+}
+
+impl Default for Tessellation {
+    fn default() -> Self {
+        DEFAULT
+    }
+}
+
+impl Tessellation {
+    pub fn new(chord: f64, angle: f64) -> Tessellation {
+        assert!(chord >= 0.0 && angle >= 0.0);
+        Tessellation { chord, angle }
+    }
+
+    // The tessellation used on this thread.
+    pub fn current() -> Tessellation {
+        CURRENT.with(|c| c.get())
+    }
+
+    pub fn set_current(tessellation: Tessellation) {
+        CURRENT.with(|c| c.set(tessellation));
+    }
+
+    // Runs a single rasterization with this tessellation.
+    pub fn apply<T>(self, operation: impl FnOnce() -> T) -> T {
+        let _restore = Restore(Tessellation::current());
+        Tessellation::set_current(self);
+        operation()
+    }
+
+    // Number of segments for a curve that turns by the given angle, with the given largest radius of curvature. At least default segments are used.
+    pub fn segments(&self, default: usize, turn: f64, radius: f64) -> usize {
+        let mut n = default;
+        if self.angle > 0.0 {
+            n = n.max((turn / self.angle).ceil() as usize);
+        }
+        // A segment that turns by a deviates from an arc of radius r by r * (1 - cos(a / 2)).
+        if self.chord > 0.0 && self.chord < radius {
+            let angle = 2.0 * (1.0 - self.chord / radius).acos();
+            n = n.max((turn / angle).ceil() as usize);
+        }
+        n
+    }
+
+    // Density of the point grid that faces on the surface are triangulated from. The grid has 16 points per turn at density 1, which is used for flat surfaces and as the least density.
+    pub fn density(&self, surface: &Surface) -> f64 {
+        let radius = match surface {
+            Surface::Sphere(sphere) => sphere.radius.mid(),
+            Surface::Cylinder(cylinder) => cylinder.radius.norm().mid(),
+            Surface::Torus(torus) => (torus.major_radius + torus.minor_radius).mid(),
+            _ => return 1.0,
+        };
+        // A triangle spans the diagonal of a grid cell, so it deviates from the surface like a segment that turns by sqrt(2) times the angle of the cell.
+        self.segments(16, TAU * SQRT_2, radius) as f64 / 16.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{efloat::EFloat64, point::Point, surfaces::sphere::Sphere};
+    use geop_topology::{
+        primitive_objects::edges::circle::primitive_circle,
+        topology::{contour::Contour, face::Face, scene::Color},
+    };
+
+    use crate::{edge::rasterize_edge_into_line_list, face::rasterize_face_into_triangle_list};
+
+    use super::*;
+
+    #[test]
+    fn test_tessellation_circle() {
+        let edge = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::from(10.0));
+        let segments = |tessellation: Tessellation| {
+            tessellation.apply(|| rasterize_edge_into_line_list(&edge, Color::white()).edges)
+        };
+        let coarse = segments(Tessellation::default());
+        let chord = segments(Tessellation::new(0.01, 0.0));
+        let angle = segments(Tessellation::new(0.0, 0.05));
+        assert!(chord.len() > coarse.len());
+        assert!(angle.len() > coarse.len());
+
+        // The middle of a segment is where it is farthest from the circle.
+        for edge in chord.iter() {
+            let deviation = 10.0 - edge.mid_point().norm().mid();
+            assert!(deviation <= 0.01);
+        }
+        for pair in angle.windows(2) {
+            let a = pair[0].end.point() - pair[0].start.point();
+            let b = pair[1].end.point() - pair[1].start.point();
+            let turn = a.cross(b).norm().mid().atan2(a.dot(b).mid());
+            assert!(turn <= 0.05);
+        }
+    }
+
+    #[test]
+    fn test_tessellation_sphere() {
+        // The upper half of the unit sphere.
+        let face = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Sphere(Sphere::new(
+                Point::zero(),
+                EFloat64::one(),
+                true,
+            ))),
+        );
+        let triangles = |tessellation: Tessellation| {
+            tessellation
+                .apply(|| rasterize_face_into_triangle_list(&face, Color::white()).triangles)
+        };
+        let coarse = triangles(Tessellation::default());
+        let fine = triangles(Tessellation::new(0.03, 0.0));
+        assert!(fine.len() > coarse.len());
+
+        // The triangles are checked at their centers and at the middles of their sides.
+        for triangle in fine.iter() {
+            let (a, b, c) = (triangle.a.point(), triangle.b.point(), triangle.c.point());
+            let points = [
+                (a + b + c) / EFloat64::from(3.0),
+                (a + b) / EFloat64::two(),
+                (b + c) / EFloat64::two(),
+                (c + a) / EFloat64::two(),
+            ];
+            for p in points {
+                let p = p.unwrap();
+                assert!(1.0 - p.norm().mid() <= 0.03);
+            }
+        }
+    }
+}
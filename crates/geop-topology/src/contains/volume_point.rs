@@ -53,7 +53,14 @@ pub fn volume_point_contains(volume: &Volume, other: Point) -> VolumePointContai
                                 }
                             }
                         }
-                        CurveCurveIntersection::InfiniteDiscretePoints(_) => todo!(),
+                        // Only a helix edge along the line, whose points up to q are crossings.
+                        CurveCurveIntersection::InfiniteDiscretePoints(array) => {
+                            for point in array.points_between(other, q) {
+                                if face_point_contains(face, point) != FacePointContains::Outside {
+                                    intersection_points.push(point)
+                                }
+                            }
+                        }
                         CurveCurveIntersection::Curve(_) => {
                             if let Some(start) = edge.start {
                                 if face_point_contains(&face, start) != FacePointContains::Outside {
@@ -83,6 +90,14 @@ pub fn volume_point_contains(volume: &Volume, other: Point) -> VolumePointContai
                     }
                 }
             }
+            // Only the crossings up to q count.
+            CurveSurfaceIntersection::InfiniteDiscretePoints(arrays) => {
+                for point in arrays.iter().flat_map(|a| a.points_between(other, q)) {
+                    if face_point_contains(face, point) != FacePointContains::Outside {
+                        intersection_points.push(point)
+                    }
+                }
+            }
             CurveSurfaceIntersection::None => {}
         }
     }
@@ -93,6 +108,10 @@ pub fn volume_point_contains(volume: &Volume, other: Point) -> VolumePointContai
     let normal = volume.boundary_normal(q);
     let mut closest_intersect_from_inside = normal.is_from_inside(curve_dir);
     for point in intersection_points.iter() {
+        // Only intersections between the point and q count, the ones behind the point are irrelevant.
+        if (*point - other).dot(curve_dir) <= 0.0 {
+            continue;
+        }
         let distance = (other - *point).norm();
         if distance < closest_distance.lower_bound {
             let curve_dir = geodesic.curve.tangent(*point).unwrap();
@@ -0,0 +1,42 @@
+use geop_geometry::distance::{curve_curve::curve_curve_distance, ClosestPoints};
+
+use crate::topology::edge::Edge;
+
+pub fn edge_edge_distance(edge: &Edge, other: &Edge) -> ClosestPoints {
+    curve_curve_distance(
+        &edge.curve,
+        edge.start,
+        edge.end,
+        &other.curve,
+        other.start,
+        other.end,
+    )
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{efloat::EFloat64, point::Point};
+
+    use crate::primitive_objects::edges::{circle::primitive_circle, line::primitive_line};
+
+    use super::*;
+
+    #[test]
+    fn test_edge_edge_distance() {
+        let edge = primitive_line(
+            Point::from_f64(0.0, 0.0, 0.0),
+            Point::from_f64(1.0, 0.0, 0.0),
+        )
+        .unwrap();
+        let other = primitive_circle(
+            Point::from_f64(3.0, 0.0, 0.0),
+            Point::unit_z(),
+            EFloat64::from(1.0),
+        );
+        let closest = edge_edge_distance(&edge, &other);
+        assert!((closest.distance - EFloat64::from(1.0)).abs() < 1e-9);
+        assert!((closest.from - Point::from_f64(1.0, 0.0, 0.0)).norm() < 1e-9);
+        assert!((closest.to - Point::from_f64(2.0, 0.0, 0.0)).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,107 @@
+use geop_geometry::{
+    distance::{
+        curve_surface::curve_surface_distance, surface_surface::surface_surface_distance,
+        ClosestPoints,
+    },
+    point::Point,
+};
+
+use crate::{
+    contains::face_point::{face_point_contains, FacePointContains},
+    topology::{edge::Edge, face::Face},
+};
+
+use super::edge_edge::edge_edge_distance;
+
+fn on_face(face: &Face, point: Point) -> bool {
+    !matches!(
+        face_point_contains(face, point),
+        FacePointContains::Outside | FacePointContains::NotOnSurface
+    )
+}
+
+pub(crate) fn closest(
+    result: Option<ClosestPoints>,
+    other: ClosestPoints,
+) -> Option<ClosestPoints> {
+    Some(match result {
+        Some(result) => result.min(other),
+        None => other,
+    })
+}
+
+// The closest points are either on the boundary of the face, or where the edge is closest to the inside of the face.
+pub fn edge_face_distance(edge: &Edge, face: &Face) -> ClosestPoints {
+    let mut result =
+        curve_surface_distance(&edge.curve, edge.start, edge.end, &*face.surface, &|p| {
+            on_face(face, p)
+        })
+        .unwrap();
+    for boundary in face.all_edges() {
+        result = closest(result, edge_edge_distance(edge, &boundary));
+    }
+    result.expect("A face without boundaries contains every projection")
+}
+
+// The closest points are either on the boundary of one of the faces, or inside of both.
+pub fn face_face_distance(face: &Face, other: &Face) -> ClosestPoints {
+    let mut result = None;
+    for edge in face.all_edges() {
+        result = closest(result, edge_face_distance(&edge, other));
+    }
+    for edge in other.all_edges() {
+        result = closest(result, edge_face_distance(&edge, face).flip());
+    }
+
+    if let Some(inside) = interior_distance(face, other) {
+        result = closest(result, inside);
+    }
+    result.expect("A face without boundaries contains every projection")
+}
+
+// The closest points between the insides of both faces, if they are not on a boundary.
+pub(crate) fn interior_distance(face: &Face, other: &Face) -> Option<ClosestPoints> {
+    let mut seeds = vec![face.inner_point(), other.inner_point()];
+    for edge in face.all_edges().iter().chain(other.all_edges().iter()) {
+        seeds.push(edge.get_midpoint());
+    }
+    surface_surface_distance(
+        &*face.surface,
+        &|p| on_face(face, p),
+        &*other.surface,
+        &|p| on_face(other, p),
+        &seeds,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+
+    use crate::primitive_objects::faces::{
+        rectangle::primitive_rectangle, sphere::primitive_sphere,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_face_face_distance() {
+        let face = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
+        let other = primitive_rectangle(
+            Point::from_f64(3.0, 0.0, 1.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        // The rectangles are closest along the edges at x = 1 and x = 2.
+        let closest = face_face_distance(&face, &other);
+        assert!((closest.distance - EFloat64::from(2.0f64.sqrt())).abs() < 1e-9);
+        assert!((closest.from.x - EFloat64::from(1.0)).abs() < 1e-9);
+        assert!((closest.to.x - EFloat64::from(2.0)).abs() < 1e-9);
+
+        // The sphere hovers above the inside of the rectangle.
+        let sphere = primitive_sphere(Point::from_f64(0.2, 0.3, 2.0), EFloat64::from(0.5));
+        let closest = face_face_distance(&face, &sphere);
+        assert!((closest.distance - EFloat64::from(1.5)).abs() < 1e-9);
+        assert!((closest.from - Point::from_f64(0.2, 0.3, 0.0)).norm() < 1e-6);
+    }
+}
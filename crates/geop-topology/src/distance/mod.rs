@@ -0,0 +1,3 @@
+pub mod edge_edge;
+pub mod face_face;
+pub mod volume_volume;
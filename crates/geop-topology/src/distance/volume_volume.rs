@@ -0,0 +1,80 @@
+use geop_geometry::distance::ClosestPoints;
+
+use crate::{
+    contains::volume_point::{volume_point_contains, VolumePointContains},
+    topology::{edge::Edge, volume::Volume},
+};
+
+use super::face_face::{closest, edge_face_distance, interior_distance};
+
+// The distance between two volumes as solids. If one of them lies inside of the other, the distance is zero.
+pub fn volume_volume_distance(volume: &Volume, other: &Volume) -> ClosestPoints {
+    for (a, b) in [(volume, other), (other, volume)] {
+        let p = b.all_faces()[0].inner_point();
+        if !matches!(volume_point_contains(a, p), VolumePointContains::Outside) {
+            return ClosestPoints::new(p, p);
+        }
+    }
+
+    // Every edge is shared by two faces, so the edges are compared with the faces of the other volume only once.
+    let mut result: Option<ClosestPoints> = None;
+    for edge in unique_edges(volume) {
+        for face in other.all_faces() {
+            result = closest(result, edge_face_distance(&edge, &face));
+        }
+    }
+    for edge in unique_edges(other) {
+        for face in volume.all_faces() {
+            result = closest(result, edge_face_distance(&edge, &face).flip());
+        }
+    }
+    for face in volume.all_faces() {
+        for other_face in other.all_faces() {
+            if let Some(inside) = interior_distance(&face, &other_face) {
+                result = closest(result, inside);
+            }
+        }
+    }
+    result.expect("A volume has at least one face")
+}
+
+fn unique_edges(volume: &Volume) -> Vec<Edge> {
+    let mut edges: Vec<Edge> = Vec::new();
+    for face in volume.all_faces() {
+        for edge in face.all_edges() {
+            if !edges.contains(&edge) {
+                edges.push(edge);
+            }
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{efloat::EFloat64, point::Point, transforms::Transform};
+
+    use crate::primitive_objects::volumes::cube::primitive_cube;
+
+    use super::*;
+
+    #[test]
+    fn test_volume_volume_distance() {
+        let cube = primitive_cube(
+            EFloat64::from(1.0),
+            EFloat64::from(1.0),
+            EFloat64::from(1.0),
+        );
+        let other = cube.transform(Transform::from_translation(Point::from_f64(3.0, 0.0, 0.0)));
+        let closest = volume_volume_distance(&cube, &other);
+        assert!((closest.distance - EFloat64::from(2.0)).abs() < 1e-9);
+
+        let inner = primitive_cube(
+            EFloat64::from(0.5),
+            EFloat64::from(0.5),
+            EFloat64::from(0.5),
+        );
+        let closest = volume_volume_distance(&cube, &inner);
+        assert!(closest.distance == 0.0);
+    }
+}
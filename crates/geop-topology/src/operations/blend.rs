@@ -0,0 +1,339 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve, CurveLike},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{cylinder::Cylinder, plane::Plane, surface::Surface, SurfaceLike},
+    transforms::Transform,
+};
+
+use crate::{
+    contains::edge_point::{edge_point_contains, EdgePointContains},
+    primitive_objects::edges::line::primitive_line,
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+use super::revolve::{swept_surface, Axis};
+
+// Cross section of the face that replaces an edge.
+#[derive(Clone, Copy)]
+pub(crate) enum BlendProfile {
+    // Circular arc of the given radius, tangent to both faces.
+    Round(EFloat64),
+    // Straight line between the points at the given distance from the edge on both faces.
+    Bevel(EFloat64),
+}
+
+// Where the blend face meets the two faces of the edge at a point of the edge.
+#[derive(Clone, Copy)]
+struct Section {
+    point: Point,
+    // Points on the first and second face.
+    a: Point,
+    b: Point,
+    // Center of the arc, for round profiles.
+    center: Point,
+}
+
+impl Section {
+    fn edge(&self, from: Point, to: Point, profile: BlendProfile) -> Edge {
+        match profile {
+            BlendProfile::Round(radius) => {
+                let normal = (from - self.center)
+                    .cross(to - self.center)
+                    .normalize()
+                    .unwrap();
+                Edge::new(
+                    Some(from),
+                    Some(to),
+                    Curve::Circle(Circle::new(self.center, normal, radius)),
+                )
+            }
+            BlendProfile::Bevel(_) => primitive_line(from, to).unwrap(),
+        }
+    }
+}
+
+// An edge that is replaced, with the edge as it appears in each of its two faces.
+struct Blend {
+    faces: (usize, usize),
+    edges: (Edge, Edge),
+    offsets: (Edge, Edge),
+    sections: Vec<Section>,
+    face: Face,
+}
+
+// Direction from the edge into the face, perpendicular to the edge.
+fn inwards(face: &Face, edge: &Edge, p: Point) -> Point {
+    face.surface.normal(p).cross(edge.tangent(p))
+}
+
+// The edge moved into the face, such that p moves to q. The new ends are given for edges that are not closed.
+fn offset_edge(
+    face: &Face,
+    edge: &Edge,
+    p: Point,
+    q: Point,
+    ends: Option<(Point, Point)>,
+) -> TopologyResult<Edge> {
+    let transform = match (&edge.curve, &*face.surface) {
+        (Curve::Line(_), Surface::Plane(_)) => Transform::from_translation(q - p),
+        (Curve::Circle(circle), Surface::Cylinder(_)) => {
+            if !(q - p).cross(circle.normal).is_zero() {
+                return Err(TopologyError::new(
+                    "Circles on cylinders have to be perpendicular to the axis".to_string(),
+                ));
+            }
+            Transform::from_translation(q - p)
+        }
+        // The circle shrinks or grows in its plane.
+        (Curve::Circle(circle), Surface::Plane(_)) => {
+            let factor = ((q - circle.basis).norm() / (p - circle.basis).norm()).unwrap();
+            Transform::from_translation(circle.basis)
+                * Transform::from_scale(Point::new(factor, factor, factor))
+                * Transform::from_translation(-circle.basis)
+        }
+        _ => {
+            return Err(TopologyError::new(
+                "Only lines and circles on planes and cylinders can be blended".to_string(),
+            ))
+        }
+    };
+    let curve = edge.curve.transform(transform);
+    Ok(match ends {
+        Some((start, end)) => Edge::new(Some(start), Some(end), curve),
+        None => Edge::new(None, None, curve),
+    })
+}
+
+// The faces that contain the edge, with the edge in the direction of their boundary.
+fn find_edge(faces: &[Face], edge: &Edge) -> Vec<(usize, Edge)> {
+    faces
+        .iter()
+        .enumerate()
+        .filter_map(|(i, f)| {
+            f.all_edges()
+                .into_iter()
+                .find(|e| e == edge || *e == edge.flip())
+                .map(|e| (i, e))
+        })
+        .collect()
+}
+
+fn blend_edge(faces: &[Face], edge: &Edge, profile: BlendProfile) -> TopologyResult<Blend> {
+    let found = find_edge(faces, edge);
+    if found.len() != 2 {
+        return Err(TopologyError::new(
+            "Edge must be shared by exactly two faces".to_string(),
+        ));
+    }
+    let ((ia, edge_a), (ib, edge_b)) = (found[0].clone(), found[1].clone());
+    let (face_a, face_b) = (&faces[ia], &faces[ib]);
+
+    let m = edge_a.get_midpoint();
+    let (da, db) = (inwards(face_a, &edge_a, m), inwards(face_b, &edge_b, m));
+    if face_a.surface.normal(m).dot(db) >= 0.0 {
+        return Err(TopologyError::new(
+            "Only convex edges can be blended".to_string(),
+        ));
+    }
+    // Distance of the blend from the edge on both faces.
+    let (distance, radius) = match profile {
+        BlendProfile::Round(radius) => {
+            let half = (da.angle(db).unwrap() / EFloat64::two()).unwrap();
+            ((radius * half.cos() / half.sin()).unwrap(), radius)
+        }
+        BlendProfile::Bevel(distance) => (distance, EFloat64::zero()),
+    };
+    let distance = EFloat64::from(distance.mid());
+    let section = |p: Point| {
+        let a = (p + inwards(face_a, &edge_a, p) * distance).mid();
+        let b = (p + inwards(face_b, &edge_b, p) * distance).mid();
+        Section {
+            point: p,
+            a,
+            b,
+            center: (a - face_a.surface.normal(a) * radius).mid(),
+        }
+    };
+
+    let sections: Vec<Section> = [edge_a.start, edge_a.end]
+        .iter()
+        .filter_map(|p| p.map(section))
+        .collect();
+    // The blend has to end in faces that are perpendicular to the edge, so that its cross section fits into them.
+    for s in sections.iter() {
+        let tangent = edge_a.tangent(s.point);
+        for (i, face) in faces.iter().enumerate() {
+            if i != ia
+                && i != ib
+                && face.all_points().contains(&s.point)
+                && !face.surface.normal(s.point).cross(tangent).is_zero()
+            {
+                return Err(TopologyError::new(
+                    "Faces at the ends of the edge must be perpendicular to it".to_string(),
+                ));
+            }
+        }
+    }
+
+    let at = |p: Option<Point>| sections.iter().find(|s| Some(s.point) == p);
+    let middle = section(m);
+    let ends_a = at(edge_a.start)
+        .zip(at(edge_a.end))
+        .map(|(s, e)| (s.a, e.a));
+    let ends_b = at(edge_b.start)
+        .zip(at(edge_b.end))
+        .map(|(s, e)| (s.b, e.b));
+    let offset_a = offset_edge(face_a, &edge_a, m, middle.a, ends_a)?;
+    let offset_b = offset_edge(face_b, &edge_b, m, middle.b, ends_b)?;
+
+    // The blend surface is the cross section, moved along the edge.
+    let cross = middle.edge(middle.a, middle.b, profile);
+    let p = cross.get_midpoint();
+    let surface = match (&edge_a.curve, profile) {
+        (Curve::Line(line), BlendProfile::Round(radius)) => {
+            Surface::Cylinder(Cylinder::new(middle.center, line.direction, radius, true))
+        }
+        (Curve::Line(line), BlendProfile::Bevel(_)) => {
+            Surface::Plane(Plane::new(middle.a, line.direction, middle.b - middle.a))
+        }
+        (Curve::Circle(circle), _) => swept_surface(
+            &cross.curve,
+            &Axis {
+                point: circle.basis,
+                dir: circle.normal,
+            },
+            p,
+        )
+        .ok_or_else(|| {
+            TopologyError::new("Blend must not touch the axis of the edge".to_string())
+        })?,
+        _ => {
+            return Err(TopologyError::new(
+                "Only lines and circles can be blended".to_string(),
+            ))
+        }
+    };
+    let outwards = face_a.surface.normal(m) + face_b.surface.normal(m);
+    let surface = match surface.normal(p).dot(outwards) > 0.0 {
+        true => surface,
+        false => surface.neg(),
+    };
+    let contours = match (at(edge_a.start), at(edge_a.end)) {
+        (Some(s), Some(e)) => vec![Contour::new(vec![
+            offset_a.flip(),
+            s.edge(s.a, s.b, profile),
+            offset_b.flip(),
+            e.edge(e.b, e.a, profile),
+        ])],
+        _ => vec![
+            Contour::new(vec![offset_a.flip()]),
+            Contour::new(vec![offset_b.flip()]),
+        ],
+    };
+
+    Ok(Blend {
+        faces: (ia, ib),
+        edges: (edge_a, edge_b),
+        offsets: (offset_a, offset_b),
+        sections,
+        face: Face::new(contours, Rc::new(surface)),
+    })
+}
+
+// The contour with the blended edges replaced and the edges next to them trimmed.
+fn blend_contour(
+    contour: &Contour,
+    face: usize,
+    blends: &[Blend],
+    profile: BlendProfile,
+) -> TopologyResult<Contour> {
+    let sections: Vec<&Section> = blends.iter().flat_map(|b| b.sections.iter()).collect();
+    let section = |p: Option<Point>| sections.iter().find(|s| p == Some(s.point));
+    // An end of an edge next to a blended edge moves to where the blend meets the edge.
+    let trim = |edge: &Edge, p: Option<Point>| match section(p) {
+        Some(s) => [s.a, s.b]
+            .into_iter()
+            .find(|q| edge_point_contains(edge, *q) == EdgePointContains::Inside)
+            .map(Some)
+            .ok_or_else(|| {
+                TopologyError::new("Blend is too large for the edges next to it".to_string())
+            }),
+        None => Ok(p),
+    };
+    let edges: Vec<Edge> = contour
+        .edges
+        .iter()
+        .map(|edge| -> TopologyResult<Edge> {
+            let replaced = blends.iter().find_map(|b| match face {
+                f if f == b.faces.0 && *edge == b.edges.0 => Some(b.offsets.0.clone()),
+                f if f == b.faces.1 && *edge == b.edges.1 => Some(b.offsets.1.clone()),
+                _ => None,
+            });
+            match replaced {
+                Some(offset) => Ok(offset),
+                None => Ok(Edge::new(
+                    trim(edge, edge.start)?,
+                    trim(edge, edge.end)?,
+                    edge.curve.clone(),
+                )),
+            }
+        })
+        .collect::<TopologyResult<Vec<Edge>>>()?;
+
+    // Faces at the ends of a blended edge get the cross section of the blend at their corner.
+    let mut result = Vec::<Edge>::new();
+    for (i, edge) in edges.iter().enumerate() {
+        result.push(edge.clone());
+        let j = (i + 1) % edges.len();
+        if edge.end != edges[j].start {
+            let s = section(contour.edges[j].start).unwrap();
+            result.push(s.edge(edge.end.unwrap(), edges[j].start.unwrap(), profile));
+        }
+    }
+    Ok(Contour::new(result))
+}
+
+// Replaces convex edges of a volume by faces with the given cross section. The edges have to be lines or circles between planes and cylinders, and must not share corners.
+pub(crate) fn blend(
+    volume: &Volume,
+    edges: &[Edge],
+    profile: BlendProfile,
+) -> TopologyResult<Volume> {
+    let faces = &volume.boundary.faces;
+    let blends = edges
+        .iter()
+        .map(|e| blend_edge(faces, e, profile))
+        .collect::<TopologyResult<Vec<Blend>>>()?;
+    for (i, a) in blends.iter().enumerate() {
+        for b in blends[i + 1..].iter() {
+            if a.sections
+                .iter()
+                .any(|s| b.sections.iter().any(|t| s.point == t.point))
+            {
+                return Err(TopologyError::new(
+                    "Blended edges must not share corners".to_string(),
+                ));
+            }
+        }
+    }
+
+    let mut result: Vec<Face> = faces
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            Ok(Face::new(
+                f.boundaries
+                    .iter()
+                    .map(|c| blend_contour(c, i, &blends, profile))
+                    .collect::<TopologyResult<Vec<Contour>>>()?,
+                f.surface.clone(),
+            ))
+        })
+        .collect::<TopologyResult<Vec<Face>>>()?;
+    result.extend(blends.into_iter().map(|b| b.face));
+    Ok(Volume::new(Shell::new(result), volume.cavities.clone()))
+}
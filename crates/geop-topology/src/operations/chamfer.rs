@@ -0,0 +1,52 @@
+use geop_geometry::efloat::EFloat64;
+
+use crate::{
+    topology::{edge::Edge, volume::Volume},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+use super::blend::{blend, BlendProfile};
+
+// Bevels the edges of the volume, by cutting them off at the given distance from the edge on both faces.
+// The same restrictions as for fillet apply.
+pub fn chamfer(volume: &Volume, edges: &[Edge], distance: EFloat64) -> TopologyResult<Volume> {
+    if distance <= 0.0 {
+        return Err(TopologyError::new("Distance must be positive".to_string()));
+    }
+    blend(volume, edges, BlendProfile::Bevel(distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{point::Point, surfaces::surface::Surface};
+
+    use crate::{
+        contains::volume_point::{volume_point_contains, VolumePointContains},
+        primitive_objects::volumes::cube::primitive_cube,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_chamfer_cube() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        // A vertical edge and an edge of the top face, which do not share a corner.
+        let vertical = cube.boundary.faces[0].boundaries[0].edges[0].clone();
+        let top = cube.boundary.faces[5]
+            .all_edges()
+            .into_iter()
+            .find(|e| e.start != vertical.end && e.end != vertical.end)
+            .unwrap();
+        let beveled = chamfer(&cube, &[vertical, top], EFloat64::from(0.25)).unwrap();
+        assert_eq!(beveled.boundary.faces.len(), 8);
+        assert!(beveled
+            .boundary
+            .faces
+            .iter()
+            .all(|f| matches!(&*f.surface, Surface::Plane(_))));
+        assert!(matches!(
+            volume_point_contains(&beveled, Point::zero()),
+            VolumePointContains::Inside
+        ));
+    }
+}
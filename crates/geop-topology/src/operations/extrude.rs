@@ -1,9 +1,9 @@
 use std::rc::Rc;
 
 use geop_geometry::{
-    curves::{curve::Curve, CurveLike},
+    curves::{curve::Curve, ellipse::Ellipse, nurbs::Nurbs, CurveLike},
     point::Point,
-    surfaces::{cylinder::Cylinder, plane::Plane, surface::Surface},
+    surfaces::{cylinder::Cylinder, plane::Plane, surface::Surface, SurfaceLike},
     transforms::Transform,
 };
 
@@ -12,6 +12,43 @@ use crate::{
     topology::{contour::Contour, face::Face, shell::Shell, volume::Volume},
 };
 
+use super::skin::skin;
+
+// Side surface of an extruded elliptic edge, with the normal along outwards at the midpoint.
+// If the ellipse is an oblique section of a circular cylinder along the direction, this is the cylinder. Otherwise it is the ruled nurbs surface between the whole ellipse and its translation.
+fn ellipse_side(
+    curve: &Curve,
+    ellipse: &Ellipse,
+    direction: Point,
+    midpoint: Point,
+    outwards: Point,
+) -> Surface {
+    let axis = direction.normalize().unwrap();
+    let project = |v: Point| v - axis * axis.dot(v);
+    let (major, minor) = (project(ellipse.major_radius), project(ellipse.minor_radius));
+    if major.is_perpendicular(minor) && major.norm() == minor.norm() {
+        let radial = project(midpoint - ellipse.basis);
+        return Surface::Cylinder(Cylinder::new(
+            ellipse.basis,
+            axis,
+            minor.norm(),
+            outwards.dot(radial) > 0.0,
+        ));
+    }
+    ruled_side(curve, direction, midpoint, outwards)
+}
+
+// Ruled nurbs surface between the whole curve and its translation, with the normal along outwards at the midpoint.
+fn ruled_side(curve: &Curve, direction: Point, midpoint: Point, outwards: Point) -> Surface {
+    let section = Nurbs::from_curve(curve, None, None).unwrap();
+    let translated = section.transform(Transform::from_translation(direction));
+    let surface = Surface::Nurbs(skin(&[&section, &translated]));
+    match surface.normal(midpoint).dot(outwards) > 0.0 {
+        true => surface,
+        false => surface.neg(),
+    }
+}
+
 pub fn extrude(start_face: Face, direction: Point) -> Volume {
     let end_face = start_face
         .transform(Transform::from_translation(direction))
@@ -87,24 +124,61 @@ pub fn extrude(start_face: Face, direction: Point) -> Volume {
                     normal_outwards,
                 ));
 
-                match (left, right) {
-                    (Some(left), Some(right)) => {
-                        let contour = Contour::new(vec![right, top, left, bottom]);
+                // A closed edge gives a ring between the two closed edges. Otherwise the side is bounded by the lines between the ends that exist, like for lines.
+                let contours = match (left, right) {
+                    (None, None) => vec![Contour::new(vec![top]), Contour::new(vec![bottom])],
+                    (left, right) => vec![Contour::new(
+                        vec![right, Some(top), left, Some(bottom)]
+                            .into_iter()
+                            .flatten()
+                            .collect(),
+                    )],
+                };
+                faces.push(Face::new(contours, Rc::new(cylinder)));
+            }
+            Curve::Ellipse(_) | Curve::Nurbs(_) => {
+                let top = all_edges[i].flip();
+                let bottom = end_edges
+                    .iter()
+                    .find(|e| {
+                        **e == all_edges[i]
+                            .transform(Transform::from_translation(direction))
+                            .flip()
+                    })
+                    .unwrap()
+                    .flip();
 
-                        let face = Face::new(vec![contour], Rc::new(cylinder));
-                        faces.push(face);
-                    }
-                    (None, None) => {
-                        let contour = Contour::new(vec![top]);
+                let right = match (bottom.end, top.start) {
+                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
+                    _ => None,
+                };
+                let left = match (top.end, bottom.start) {
+                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
+                    _ => None,
+                };
 
-                        let face =
-                            Face::new(vec![contour, Contour::new(vec![bottom])], Rc::new(cylinder));
-                        faces.push(face);
+                let curve = &all_edges[i].curve;
+                let midpoint = curve.get_midpoint(top.start, top.end).unwrap();
+                let outwards = direction.cross(curve.tangent(midpoint).unwrap());
+                let surface = match curve {
+                    Curve::Ellipse(ellipse) => {
+                        ellipse_side(curve, ellipse, direction, midpoint, outwards)
                     }
-                    _ => todo!("Not implemented"),
-                }
+                    _ => ruled_side(curve, direction, midpoint, outwards),
+                };
+
+                // A closed edge gives a ring between the two closed edges. Otherwise the side is bounded by the lines between the ends that exist, like for lines.
+                let contours = match (left, right) {
+                    (None, None) => vec![Contour::new(vec![top]), Contour::new(vec![bottom])],
+                    (left, right) => vec![Contour::new(
+                        vec![right, Some(top), left, Some(bottom)]
+                            .into_iter()
+                            .flatten()
+                            .collect(),
+                    )],
+                };
+                faces.push(Face::new(contours, Rc::new(surface)));
             }
-            Curve::Ellipse(_) => todo!("Implement this"),
             Curve::Helix(_) => panic!("Cannot extrude helix"),
         }
     }
@@ -113,3 +187,86 @@ pub fn extrude(start_face: Face, direction: Point) -> Volume {
 
     Volume::new(Shell::new(faces), vec![])
 }
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+
+    use crate::{primitive_objects::edges::ellipse::primitive_ellipse, topology::edge::Edge};
+
+    use super::*;
+
+    fn elliptic_disk() -> Face {
+        Face::new(
+            vec![Contour::new(vec![primitive_ellipse(
+                Point::zero(),
+                -Point::unit_z(),
+                Point::from_f64(2.0, 0.0, 0.0),
+                Point::from_f64(0.0, 1.0, 0.0),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        )
+    }
+
+    #[test]
+    fn test_extrude_nurbs() {
+        // A face bounded by a parabola and a line, extruded straight up.
+        let parabola = Nurbs::new_uniform_clamped(
+            2,
+            vec![
+                Point::from_f64(0.0, 0.0, 0.0),
+                Point::from_f64(1.0, 2.0, 0.0),
+                Point::from_f64(2.0, 0.0, 0.0),
+            ],
+        )
+        .unwrap();
+        let face = Face::new(
+            vec![Contour::new(vec![
+                Edge::new(
+                    Some(Point::zero()),
+                    Some(Point::from_f64(2.0, 0.0, 0.0)),
+                    Curve::Nurbs(parabola),
+                ),
+                primitive_line(Point::from_f64(2.0, 0.0, 0.0), Point::zero()).unwrap(),
+            ])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let volume = extrude(face, Point::unit_z());
+        assert_eq!(volume.boundary.faces.len(), 4);
+        let side = &volume.boundary.faces[0];
+        assert!(matches!(&*side.surface, Surface::Nurbs(_)));
+        // The parabola reaches y = 1 at x = 1, where the side faces outwards along y.
+        let p = Point::from_f64(1.0, 1.0, 0.5);
+        assert!(side.surface.on_surface(p));
+        assert!(side.surface.normal(p).dot(Point::unit_y()) > 0.0);
+    }
+
+    #[test]
+    fn test_extrude_ellipse() {
+        // Straight up, the side is an elliptic cylinder.
+        let volume = extrude(elliptic_disk(), Point::from_f64(0.0, 0.0, 1.0));
+        assert_eq!(volume.boundary.faces.len(), 3);
+        let side = &volume.boundary.faces[0];
+        assert!(matches!(&*side.surface, Surface::Nurbs(_)));
+        let p = Point::from_f64(0.0, 1.0, 0.5);
+        assert!(side.surface.on_surface(p));
+        assert!(!side.surface.on_surface(Point::from_f64(0.0, 1.5, 0.5)));
+        assert!(side.surface.normal(p).dot(Point::unit_y()) > 0.0);
+
+        // At 30 degrees to the major axis, the ellipse is a section of a circular cylinder of radius 1.
+        let direction = Point::from_f64(3.0f64.sqrt(), 0.0, 1.0);
+        let volume = extrude(elliptic_disk(), direction);
+        assert!(matches!(
+            &*volume.boundary.faces[0].surface,
+            Surface::Cylinder(cylinder) if cylinder.radius.norm() == EFloat64::one()
+        ));
+    }
+}
@@ -0,0 +1,118 @@
+use geop_geometry::efloat::EFloat64;
+
+use crate::{
+    topology::{edge::Edge, volume::Volume},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+use super::blend::{blend, BlendProfile};
+
+// Rounds the edges of the volume, by replacing them with faces that are tangent to both faces of the edge.
+// The edges have to be convex lines or circles between planes and cylinders. Edges must not share corners, and the faces at the ends of an edge must be perpendicular to it, otherwise an error is returned.
+pub fn fillet(volume: &Volume, edges: &[Edge], radius: EFloat64) -> TopologyResult<Volume> {
+    if radius <= 0.0 {
+        return Err(TopologyError::new("Radius must be positive".to_string()));
+    }
+    blend(volume, edges, BlendProfile::Round(radius))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        point::Point,
+        surfaces::{plane::Plane, surface::Surface},
+    };
+
+    use crate::{
+        contains::volume_point::{volume_point_contains, VolumePointContains},
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+        topology::{contour::Contour, face::Face},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_fillet_cube() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        // A vertical edge between two side faces.
+        let edge = cube.boundary.faces[0].boundaries[0].edges[0].clone();
+        let p = edge.get_midpoint();
+        let rounded = fillet(&cube, &[edge], EFloat64::from(0.5)).unwrap();
+        assert_eq!(rounded.boundary.faces.len(), 7);
+        let cylinders = rounded
+            .boundary
+            .faces
+            .iter()
+            .filter(|f| matches!(&*f.surface, Surface::Cylinder(_)))
+            .count();
+        assert_eq!(cylinders, 1);
+        // The old edge is cut away, the rest of the cube is not.
+        assert!(matches!(
+            volume_point_contains(&rounded, p),
+            VolumePointContains::Outside
+        ));
+        assert!(matches!(
+            volume_point_contains(&rounded, Point::zero()),
+            VolumePointContains::Inside
+        ));
+    }
+
+    #[test]
+    fn test_fillet_shared_corner() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        // Two edges of the same face that meet in a corner of the cube.
+        let edges = &cube.boundary.faces[0].boundaries[0].edges;
+        assert_eq!(edges[0].end, edges[1].start);
+        let result = fillet(
+            &cube,
+            &[edges[0].clone(), edges[1].clone()],
+            EFloat64::from(0.5),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_fillet_too_large() {
+        // The blend would reach past the edges of length two next to it.
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let edge = cube.boundary.faces[0].boundaries[0].edges[0].clone();
+        assert!(fillet(&cube, &[edge], EFloat64::from(3.0)).is_err());
+    }
+
+    #[test]
+    fn test_fillet_cylinder() {
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::two(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(disk, Point::from_f64(0.0, 0.0, 2.0));
+        // The circle around the top face.
+        let top = &cylinder.boundary.faces[cylinder.boundary.faces.len() - 1];
+        let edge = top.boundaries[0].edges[0].clone();
+        let rounded = fillet(&cylinder, &[edge], EFloat64::from(0.5)).unwrap();
+        assert_eq!(rounded.boundary.faces.len(), 4);
+        assert!(matches!(
+            &*rounded.boundary.faces[3].surface,
+            Surface::Torus(_)
+        ));
+        assert!(matches!(
+            volume_point_contains(&rounded, Point::from_f64(1.95, 0.0, 1.95)),
+            VolumePointContains::Outside
+        ));
+        assert!(matches!(
+            volume_point_contains(&rounded, Point::from_f64(1.0, 0.0, 1.0)),
+            VolumePointContains::Inside
+        ));
+    }
+}
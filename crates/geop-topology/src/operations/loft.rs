@@ -0,0 +1,244 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::{curve::Curve, nurbs::Nurbs},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{plane::Plane, surface::Surface},
+};
+
+use crate::topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume};
+
+use super::skin::{nurbs_edge, skin_face};
+
+// Samples per edge used to find the plane of a profile.
+const PLANE_SAMPLES: usize = 16;
+
+// Centroid and normal of a planar profile, computed with Newell's method from points on its edges.
+fn profile_plane(contour: &Contour) -> (Point, Point) {
+    let points: Vec<Point> = contour
+        .edges
+        .iter()
+        .flat_map(|e| {
+            (0..PLANE_SAMPLES).map(move |i| e.interpolate(i as f64 / PLANE_SAMPLES as f64).mid())
+        })
+        .collect();
+    let mut normal = Point::zero();
+    let mut sum = Point::zero();
+    for (i, p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+        normal = normal + p.cross(q);
+        sum = sum + *p;
+    }
+    let centroid = (sum / EFloat64::from(points.len() as f64)).unwrap();
+    (centroid.mid(), normal.normalize().unwrap().mid())
+}
+
+// The same curve with the given degree, which is at least its own.
+fn raise_degree(nurbs: &Nurbs, degree: usize) -> Nurbs {
+    let mut curve = nurbs.clone();
+    while curve.degree < degree {
+        curve = curve.elevate_degree();
+    }
+    curve
+}
+
+// The same curve with the parameter domain [0, 1].
+fn normalize_knots(nurbs: &Nurbs) -> Nurbs {
+    let (start, end) = nurbs.domain();
+    let (start, end) = (start.mid(), end.mid());
+    Nurbs {
+        knots: nurbs
+            .knots
+            .iter()
+            .map(|k| EFloat64::from(((*k).mid() - start) / (end - start)))
+            .collect(),
+        ..nurbs.clone()
+    }
+}
+
+// Brings the curves to the same degree and knots without changing their shape, so that a surface can be skinned through them.
+fn compatible(curves: Vec<Nurbs>) -> Vec<Nurbs> {
+    let degree = curves.iter().map(|c| c.degree).max().unwrap();
+    let curves: Vec<Nurbs> = curves
+        .iter()
+        .map(|c| normalize_knots(&raise_degree(c, degree)))
+        .collect();
+    // Every knot with the highest multiplicity it has in any of the curves.
+    let mut knots = Vec::<(f64, usize)>::new();
+    for curve in curves.iter() {
+        let mut values: Vec<f64> = curve.knots.iter().map(|k| (*k).mid()).collect();
+        values.dedup();
+        for v in values {
+            let count = curve.knots.iter().filter(|k| (**k).mid() == v).count();
+            match knots.iter_mut().find(|(k, _)| *k == v) {
+                Some((_, c)) => *c = (*c).max(count),
+                None => knots.push((v, count)),
+            }
+        }
+    }
+    curves
+        .into_iter()
+        .map(|mut curve| {
+            for (k, count) in knots.iter() {
+                let existing = curve.knots.iter().filter(|c| (**c).mid() == *k).count();
+                for _ in existing..*count {
+                    curve = curve.insert_knot(EFloat64::from(*k));
+                }
+            }
+            curve
+        })
+        .collect()
+}
+
+// Face in the plane of the profile, pointing in the direction of the normal.
+fn cap(edges: Vec<Edge>, centroid: Point, normal: Point) -> Face {
+    let u = match normal.cross(Point::unit_x()).norm() > 0.5 {
+        true => normal.cross(Point::unit_x()),
+        false => normal.cross(Point::unit_y()),
+    };
+    let u = u.normalize().unwrap().mid();
+    let plane = Surface::Plane(Plane::new(centroid, u, normal.cross(u).mid()));
+    Face::new(vec![Contour::new(edges)], Rc::new(plane))
+}
+
+// Creates a volume through the planar profiles, which are connected in the given order by ruled nurbs surfaces.
+// All profiles need the same number of edges, and the i-th edges of neighbouring profiles are connected.
+pub fn loft(profiles: Vec<Contour>) -> Volume {
+    assert!(profiles.len() >= 2, "Loft needs at least two profiles");
+    let n = profiles[0].edges.len();
+    assert!(
+        profiles.iter().all(|p| p.edges.len() == n),
+        "Profiles need the same number of edges"
+    );
+    let planes: Vec<(Point, Point)> = profiles.iter().map(profile_plane).collect();
+    // The profiles have to run counter clockwise around the direction pointing away from the next profile.
+    let flip = planes[0].1.dot(planes[1].0 - planes[0].0) > 0.0;
+    let (profiles, planes): (Vec<Contour>, Vec<(Point, Point)>) = match flip {
+        true => (
+            profiles.iter().map(|p| p.flip()).collect(),
+            planes.iter().map(|(c, n)| (*c, -*n)).collect(),
+        ),
+        false => (profiles, planes),
+    };
+    assert!(
+        planes.iter().all(|(_, n)| n.dot(planes[0].1) > 0.0),
+        "Profiles need to be oriented in the same way"
+    );
+
+    // sections[k][i] is the i-th edge of the k-th profile.
+    let mut sections: Vec<Vec<Edge>> = vec![Vec::new(); profiles.len()];
+    for i in 0..n {
+        let edges: Vec<Edge> = profiles.iter().map(|p| nurbs_edge(&p.edges[i])).collect();
+        let curves = compatible(
+            edges
+                .iter()
+                .map(|e| match &e.curve {
+                    Curve::Nurbs(nurbs) => nurbs.clone(),
+                    _ => unreachable!(),
+                })
+                .collect(),
+        );
+        for (k, curve) in curves.into_iter().enumerate() {
+            let edge = &edges[k];
+            sections[k].push(Edge::new(edge.start, edge.end, Curve::Nurbs(curve)));
+        }
+    }
+
+    let mut faces = Vec::<Face>::new();
+    for k in 1..profiles.len() {
+        let backwards = planes[k - 1].0 - planes[k].0;
+        for (edge, next) in sections[k - 1].iter().zip(sections[k].iter()) {
+            let outwards = edge.tangent(edge.get_midpoint()).cross(backwards);
+            faces.push(skin_face(&[edge.clone(), next.clone()], outwards));
+        }
+    }
+    let (first_centroid, first_normal) = planes[0];
+    faces.push(cap(sections[0].clone(), first_centroid, first_normal));
+    let (last_centroid, last_normal) = planes[planes.len() - 1];
+    faces.push(
+        cap(
+            sections[sections.len() - 1].clone(),
+            last_centroid,
+            last_normal,
+        )
+        .flip(),
+    );
+
+    Volume::new(Shell::new(faces), vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitive_objects::{
+        curves::rectangle::primitive_rectangle_curve, edges::circle::primitive_circle,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_loft_squares() {
+        let profiles = vec![
+            primitive_rectangle_curve(Point::zero(), Point::unit_x(), Point::unit_y()),
+            primitive_rectangle_curve(
+                Point::from_f64(0.0, 0.0, 2.0),
+                Point::from_f64(0.5, 0.0, 0.0),
+                Point::from_f64(0.0, 0.5, 0.0),
+            ),
+        ];
+        let volume = loft(profiles);
+        assert_eq!(volume.boundary.faces.len(), 6);
+        // The caps point away from the volume.
+        let caps = &volume.boundary.faces[4..];
+        let p = caps[0].boundaries[0].edges[0].get_midpoint();
+        assert!((caps[0].normal(p) + Point::unit_z()).norm().mid() < 1e-9);
+        let p = caps[1].boundaries[0].edges[0].get_midpoint();
+        assert!((caps[1].normal(p) - Point::unit_z()).norm().mid() < 1e-9);
+    }
+
+    #[test]
+    fn test_loft_circles() {
+        let circle = |z: f64, r: f64| {
+            Contour::new(vec![primitive_circle(
+                Point::from_f64(0.0, 0.0, z),
+                Point::unit_z(),
+                EFloat64::from(r),
+            )])
+        };
+        let volume = loft(vec![circle(0.0, 1.0), circle(1.0, 2.0), circle(3.0, 1.0)]);
+        assert_eq!(volume.boundary.faces.len(), 4);
+        for face in volume.boundary.faces[..2].iter() {
+            assert!(matches!(&*face.surface, Surface::Nurbs(_)));
+            assert_eq!(face.boundaries.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_loft_circle_to_cubic() {
+        // The circle is raised to the degree of the cubic profile, which is a circle of radius 2 one unit above it.
+        let circle = Contour::new(vec![primitive_circle(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::one(),
+        )]);
+        let cubic = Nurbs::from_curve(
+            &Curve::Circle(geop_geometry::curves::circle::Circle::new(
+                Point::unit_z(),
+                Point::unit_z(),
+                EFloat64::two(),
+            )),
+            None,
+            None,
+        )
+        .unwrap()
+        .elevate_degree();
+        assert_eq!(cubic.degree, 3);
+        let cubic = Contour::new(vec![Edge::new(None, None, Curve::Nurbs(cubic))]);
+        let volume = loft(vec![circle, cubic]);
+        assert_eq!(volume.boundary.faces.len(), 3);
+        match &*volume.boundary.faces[0].surface {
+            Surface::Nurbs(nurbs) => assert_eq!(nurbs.degree_v.max(nurbs.degree_u), 3),
+            _ => panic!("Expected a nurbs surface"),
+        }
+    }
+}
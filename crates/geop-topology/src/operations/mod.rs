@@ -1 +1,8 @@
+mod blend;
+pub mod chamfer;
 pub mod extrude;
+pub mod fillet;
+pub mod loft;
+pub mod revolve;
+mod skin;
+pub mod sweep;
@@ -0,0 +1,466 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve, nurbs::Nurbs},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{
+        cone::Cone, cylinder::Cylinder, nurbs_surface::NurbsSurface, plane::Plane, sphere::Sphere,
+        surface::Surface, torus::Torus, SurfaceLike,
+    },
+    transforms::Transform,
+};
+
+use crate::topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume};
+
+// The axis of a revolution. The direction is normalized.
+pub(crate) struct Axis {
+    pub(crate) point: Point,
+    pub(crate) dir: Point,
+}
+
+impl Axis {
+    // Closest point on the axis.
+    fn project(&self, p: Point) -> Point {
+        self.point + self.dir * (p - self.point).dot(self.dir)
+    }
+
+    fn distance(&self, p: Point) -> EFloat64 {
+        (p - self.project(p)).norm()
+    }
+
+    // Circle traced by p, or None if p is on the axis. The circle runs in the direction of the rotation if forward is true.
+    fn circle(&self, p: Point, forward: bool) -> Option<Curve> {
+        let radius = self.distance(p);
+        if radius == 0.0 {
+            return None;
+        }
+        let normal = if forward { self.dir } else { -self.dir };
+        Some(Curve::Circle(Circle::new(self.project(p), normal, radius)))
+    }
+
+    // Direction in which p moves during the rotation.
+    fn rotation_dir(&self, p: Point) -> Point {
+        self.dir.cross(p - self.point)
+    }
+}
+
+// Exact surface of revolution of a nurbs profile. Every control point of the profile traces a circle around the axis, which is a rational quadratic nurbs, and the weights are multiplied.
+fn revolved_nurbs(profile: &Nurbs, axis: &Axis) -> NurbsSurface {
+    let around = Nurbs::from_curve(
+        &Curve::Circle(Circle::new(Point::zero(), Point::unit_z(), EFloat64::one())),
+        None,
+        None,
+    )
+    .unwrap();
+    let control_points = around
+        .control_points
+        .iter()
+        .map(|q| {
+            profile
+                .control_points
+                .iter()
+                .map(|p| {
+                    let center = axis.project(*p);
+                    let radial = *p - center;
+                    center + radial * q.x + axis.dir.cross(radial) * q.y
+                })
+                .collect()
+        })
+        .collect();
+    let weights = around
+        .weights
+        .iter()
+        .map(|v| profile.weights.iter().map(|w| *v * *w).collect())
+        .collect();
+    NurbsSurface::new(
+        around.degree,
+        profile.degree,
+        around.knots,
+        profile.knots.clone(),
+        weights,
+        control_points,
+    )
+    .unwrap()
+}
+
+// The surface that is swept by the curve. Returns None if the curve is on the axis.
+pub(crate) fn swept_surface(curve: &Curve, axis: &Axis, p: Point) -> Option<Surface> {
+    match curve {
+        Curve::Line(line) => {
+            let along = line.direction.dot(axis.dir);
+            if along == 0.0 {
+                let radial = line.direction;
+                return Some(Surface::Plane(Plane::new(
+                    line.basis,
+                    radial,
+                    axis.dir.cross(radial),
+                )));
+            }
+            if line.direction.cross(axis.dir).is_zero() {
+                let radius = axis.distance(line.basis);
+                if radius == 0.0 {
+                    return None;
+                }
+                return Some(Surface::Cylinder(Cylinder::new(
+                    axis.point, axis.dir, radius, true,
+                )));
+            }
+            // The line is in a plane with the axis, so it meets the axis at the apex.
+            let to_line = line.basis - axis.project(line.basis);
+            let radial = line.direction - axis.dir * along;
+            let apex =
+                line.basis - line.direction * (to_line.dot(radial) / radial.norm_sq()).unwrap();
+            let extend_dir = match (p - apex).dot(axis.dir) > 0.0 {
+                true => axis.dir,
+                false => -axis.dir,
+            };
+            let half_angle = along.abs().acos().unwrap();
+            Some(Surface::Cone(Cone::new(apex, extend_dir, half_angle, true)))
+        }
+        Curve::Circle(circle) => {
+            let radius = circle.radius.norm();
+            let major_radius = axis.distance(circle.basis);
+            if major_radius == 0.0 {
+                return Some(Surface::Sphere(Sphere::new(circle.basis, radius, true)));
+            }
+            Some(Surface::Torus(Torus::new(
+                axis.project(circle.basis),
+                axis.dir,
+                major_radius,
+                radius,
+                true,
+            )))
+        }
+        Curve::Ellipse(_) => {
+            let profile = Nurbs::from_curve(curve, None, None).unwrap();
+            Some(Surface::Nurbs(revolved_nurbs(&profile, axis)))
+        }
+        Curve::Helix(_) => panic!("Cannot revolve helix"),
+        Curve::Nurbs(profile) => Some(Surface::Nurbs(revolved_nurbs(profile, axis))),
+    }
+}
+
+// Sweeps a planar face around the axis through axis_point in direction axis_dir. The axis has to be in the plane of the face and the face on one side of it.
+// A full revolution has no start and end faces and no seams.
+pub fn revolve(face: Face, axis_point: Point, axis_dir: Point, angle: EFloat64) -> Volume {
+    let axis = Axis {
+        point: axis_point,
+        dir: axis_dir.normalize().unwrap(),
+    };
+    let Surface::Plane(plane) = &*face.surface else {
+        panic!("Only planar faces can be revolved");
+    };
+    let plane_normal = plane.u_slope.cross(plane.v_slope);
+    assert!(plane.on_surface(axis.point), "Axis must be in the plane");
+    assert!(
+        plane_normal.dot(axis.dir) == 0.0,
+        "Axis must be in the plane"
+    );
+    assert!(angle > 0.0, "Angle must be positive");
+    assert!(angle.lower_bound <= 2.0 * std::f64::consts::PI);
+    let full = angle == EFloat64::two_pi();
+
+    // The start face has to point against the rotation, so that it is on the outside of the volume.
+    let p = face
+        .all_edges()
+        .iter()
+        .map(|e| e.get_midpoint())
+        .find(|p| axis.distance(*p) > 0.0)
+        .expect("Face must not be on the axis");
+    let start_face = match plane_normal.dot(axis.rotation_dir(p)) > 0.0 {
+        true => face.flip(),
+        false => face,
+    };
+    let normal = start_face.surface.normal(p);
+    let rotation = Transform::from_axis_angle(axis.point, axis.dir, angle);
+
+    let mut faces = Vec::<Face>::new();
+    for edge in start_face.all_edges().iter() {
+        let p = edge.get_midpoint();
+        let Some(surface) = swept_surface(&edge.curve, &axis, p) else {
+            continue;
+        };
+        // The face is to the left of its boundary, so the swept face has to point to the right.
+        let outwards = edge.tangent(p).cross(normal);
+        let surface = match surface.normal(p).dot(outwards) > 0.0 {
+            true => surface,
+            false => surface.neg(),
+        };
+
+        // Circles traced by the ends of the edge. The one at the start runs along the rotation, the one at the end against it.
+        let contours = match full {
+            true => [(edge.start, true), (edge.end, false)]
+                .iter()
+                .filter_map(|(p, forward)| axis.circle((*p)?, *forward))
+                .map(|circle| Contour::new(vec![Edge::new(None, None, circle)]))
+                .collect(),
+            false => {
+                let end_edge = edge.transform(rotation);
+                match (edge.start, edge.end) {
+                    (Some(start), Some(end)) => {
+                        let mut edges = vec![edge.flip()];
+                        if let Some(circle) = axis.circle(start, true) {
+                            edges.push(Edge::new(Some(start), end_edge.start, circle));
+                        }
+                        edges.push(end_edge.clone());
+                        if let Some(circle) = axis.circle(end, false) {
+                            edges.push(Edge::new(end_edge.end, Some(end), circle));
+                        }
+                        vec![Contour::new(edges)]
+                    }
+                    _ => vec![
+                        Contour::new(vec![edge.flip()]),
+                        Contour::new(vec![end_edge]),
+                    ],
+                }
+            }
+        };
+        faces.push(Face::new(contours, Rc::new(surface)));
+    }
+
+    if !full {
+        faces.push(start_face.transform(rotation).flip());
+        faces.push(start_face);
+    }
+
+    Volume::new(Shell::new(faces), vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{
+        curves::{ellipse::Ellipse, line::Line},
+        efloat::EFloat64,
+    };
+
+    use crate::primitive_objects::edges::line::primitive_line;
+
+    use super::*;
+
+    // Rectangle in the xz-plane from x = 1 to x = 2 and z = 0 to z = 1.
+    fn rectangle() -> Face {
+        let points = [
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(2.0, 0.0, 1.0),
+            Point::from_f64(1.0, 0.0, 1.0),
+        ];
+        let edges = (0..4)
+            .map(|i| primitive_line(points[i], points[(i + 1) % 4]).unwrap())
+            .collect();
+        Face::new(
+            vec![Contour::new(edges)],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        )
+    }
+
+    fn count_surfaces(volume: &Volume) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for face in volume.boundary.faces.iter() {
+            match &*face.surface {
+                Surface::Plane(_) => counts[0] += 1,
+                Surface::Cylinder(_) => counts[1] += 1,
+                Surface::Cone(_) => counts[2] += 1,
+                Surface::Sphere(_) | Surface::Torus(_) => counts[3] += 1,
+                _ => panic!("Unexpected surface"),
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn test_revolve_rectangle() {
+        // A full revolution gives a tube with two annuli and two cylinders.
+        let tube = revolve(
+            rectangle(),
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::two_pi(),
+        );
+        assert_eq!(count_surfaces(&tube), [2, 2, 0, 0]);
+        for face in tube.boundary.faces.iter() {
+            // Each face is bounded by two full circles.
+            assert_eq!(face.boundaries.len(), 2);
+            // All faces point out of the tube.
+            let p = face.boundaries[0].edges[0].get_midpoint();
+            let outwards = match &*face.surface {
+                Surface::Cylinder(_) => {
+                    let radial = Point::new(p.x, p.y, EFloat64::zero());
+                    match radial.norm() > 1.5 {
+                        true => radial,
+                        false => -radial,
+                    }
+                }
+                _ => match p.z > 0.5 {
+                    true => Point::unit_z(),
+                    false => -Point::unit_z(),
+                },
+            };
+            assert!(face.surface.normal(p).dot(outwards) > 0.0);
+        }
+
+        // A quarter revolution also has the start and end faces.
+        let quarter = revolve(
+            rectangle(),
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::half_pi(),
+        );
+        assert_eq!(count_surfaces(&quarter), [4, 2, 0, 0]);
+    }
+
+    #[test]
+    fn test_revolve_cone_and_sphere() {
+        // A triangle with one side on the axis gives a cone and a disk.
+        let (a, b, c) = (
+            Point::zero(),
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::from_f64(0.0, 0.0, 1.0),
+        );
+        let triangle = Face::new(
+            vec![Contour::new(vec![
+                primitive_line(a, b).unwrap(),
+                primitive_line(b, c).unwrap(),
+                primitive_line(c, a).unwrap(),
+            ])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        );
+        let cone = revolve(triangle, Point::zero(), Point::unit_z(), EFloat64::two_pi());
+        assert_eq!(count_surfaces(&cone), [1, 0, 1, 0]);
+
+        // A half disk gives a sphere without any boundaries.
+        let top = Point::unit_z();
+        let bottom = -Point::unit_z();
+        let arc = Edge::new(
+            Some(bottom),
+            Some(top),
+            Curve::Circle(Circle::new(
+                Point::zero(),
+                -Point::unit_y(),
+                EFloat64::one(),
+            )),
+        );
+        let line = Edge::new(
+            Some(top),
+            Some(bottom),
+            Curve::Line(Line::new(top, -Point::unit_z()).unwrap()),
+        );
+        let half_disk = Face::new(
+            vec![Contour::new(vec![arc, line])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        );
+        let sphere = revolve(
+            half_disk,
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::two_pi(),
+        );
+        assert_eq!(count_surfaces(&sphere), [0, 0, 0, 1]);
+        let face = &sphere.boundary.faces[0];
+        assert!(face.boundaries.is_empty());
+        // The sphere points outwards.
+        let p = Point::from_f64(1.0, 0.0, 0.0);
+        assert_eq!(face.surface.normal(p), p);
+    }
+
+    #[test]
+    fn test_revolve_ellipse() {
+        // A half ellipse with semi axes 1 and 2 gives a spheroid, which is a nurbs.
+        let top = Point::from_f64(0.0, 0.0, 2.0);
+        let bottom = -top;
+        let arc = Edge::new(
+            Some(bottom),
+            Some(top),
+            Curve::Ellipse(Ellipse::new(
+                Point::zero(),
+                -Point::unit_y(),
+                Point::from_f64(0.0, 0.0, 2.0),
+                Point::unit_x(),
+            )),
+        );
+        let line = Edge::new(
+            Some(top),
+            Some(bottom),
+            Curve::Line(Line::new(top, -Point::unit_z()).unwrap()),
+        );
+        let half_ellipse = Face::new(
+            vec![Contour::new(vec![arc, line])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        );
+        let spheroid = revolve(
+            half_ellipse,
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::two_pi(),
+        );
+        assert_eq!(spheroid.boundary.faces.len(), 1);
+        let face = &spheroid.boundary.faces[0];
+        assert!(matches!(&*face.surface, Surface::Nurbs(_)));
+        assert!(face.boundaries.is_empty());
+        let half = 0.5f64.sqrt();
+        for p in [
+            Point::unit_y(),
+            Point::from_f64(-half, half, 0.0),
+            Point::from_f64(0.0, -half, 2.0f64.sqrt()),
+        ] {
+            assert!(face.surface.on_surface(p));
+        }
+        assert!(!face.surface.on_surface(Point::from_f64(0.0, 2.0, 0.0)));
+        // The spheroid points outwards.
+        let p = Point::unit_x();
+        assert!(face.surface.normal(p).dot(p) > 0.0);
+    }
+
+    #[test]
+    fn test_revolve_torus() {
+        // A disk next to the axis gives a torus.
+        let disk = Face::new(
+            vec![Contour::new(vec![Edge::new(
+                None,
+                None,
+                Curve::Circle(Circle::new(
+                    Point::from_f64(2.0, 0.0, 0.0),
+                    -Point::unit_y(),
+                    EFloat64::from(0.5),
+                )),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        );
+        let torus = revolve(
+            disk.clone(),
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::two_pi(),
+        );
+        assert_eq!(count_surfaces(&torus), [0, 0, 0, 1]);
+        assert!(torus.boundary.faces[0].boundaries.is_empty());
+        let p = Point::from_f64(2.5, 0.0, 0.0);
+        assert_eq!(torus.boundary.faces[0].surface.normal(p), Point::unit_x());
+
+        // Half of it is closed by two disks.
+        let half = revolve(disk, Point::zero(), Point::unit_z(), EFloat64::pi());
+        assert_eq!(count_surfaces(&half), [2, 0, 0, 1]);
+        assert_eq!(half.boundary.faces[0].boundaries.len(), 2);
+    }
+}
@@ -0,0 +1,106 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::{curve::Curve, nurbs::Nurbs},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{nurbs_surface::NurbsSurface, surface::Surface, SurfaceLike},
+};
+
+use crate::topology::{contour::Contour, edge::Edge, face::Face};
+
+// The same edge, represented by a nurbs curve.
+pub(crate) fn nurbs_edge(edge: &Edge) -> Edge {
+    let nurbs = Nurbs::from_curve(&edge.curve, edge.start, edge.end)
+        .expect("Edge cannot be represented as nurbs");
+    Edge::new(edge.start, edge.end, Curve::Nurbs(nurbs))
+}
+
+pub(crate) fn nurbs_contour(contour: &Contour) -> Contour {
+    Contour::new(contour.edges.iter().map(nurbs_edge).collect())
+}
+
+fn expect_nurbs(edge: &Edge) -> &Nurbs {
+    match &edge.curve {
+        Curve::Nurbs(nurbs) => nurbs,
+        _ => panic!("Expected a nurbs edge"),
+    }
+}
+
+// Knots 0, 0, 1, ..., n - 2, n - 1, n - 1 of a curve of degree 1 with n control points.
+fn section_knots(n: usize) -> Vec<EFloat64> {
+    let mut knots = vec![EFloat64::zero()];
+    knots.extend((0..n).map(|i| EFloat64::from(i as f64)));
+    knots.push(EFloat64::from((n - 1) as f64));
+    knots
+}
+
+// Polyline through the points, parameterized like the surface returned by skin.
+fn rail(points: Vec<Point>) -> Edge {
+    let n = points.len();
+    let nurbs = Nurbs::new(
+        1,
+        section_knots(n),
+        vec![EFloat64::one(); n],
+        points.clone(),
+    )
+    .unwrap();
+    Edge::new(Some(points[0]), Some(points[n - 1]), Curve::Nurbs(nurbs))
+}
+
+// Surface through the sections, where consecutive sections are connected by ruled surfaces. The sections need the same degree and knots.
+// The surface runs along the sections in u direction and from section to section in v direction.
+pub(crate) fn skin(sections: &[&Nurbs]) -> NurbsSurface {
+    let first = sections[0];
+    assert!(
+        sections
+            .iter()
+            .all(|s| s.degree == first.degree && s.knots.len() == first.knots.len()),
+        "Sections are not compatible"
+    );
+    let control_points = (0..first.control_points.len())
+        .map(|i| sections.iter().map(|s| s.control_points[i]).collect())
+        .collect();
+    let weights = (0..first.weights.len())
+        .map(|i| sections.iter().map(|s| s.weights[i]).collect())
+        .collect();
+    NurbsSurface::new(
+        first.degree,
+        1,
+        first.knots.clone(),
+        section_knots(sections.len()),
+        weights,
+        control_points,
+    )
+    .expect("Sections are not compatible")
+}
+
+// Face through the sections of an edge, ordered along the sweep. It is bounded by the first and last section and the rails through the ends of the sections.
+// The face is oriented such that it points outwards at the midpoint of the first section.
+pub(crate) fn skin_face(sections: &[Edge], outwards: Point) -> Face {
+    let surface = Surface::Nurbs(skin(&sections.iter().map(expect_nurbs).collect::<Vec<_>>()));
+    let first = &sections[0];
+    let last = &sections[sections.len() - 1];
+    let p = first.get_midpoint();
+    let surface = match surface.normal(p).dot(outwards) > 0.0 {
+        true => surface,
+        false => surface.neg(),
+    };
+    let contours = match (first.start, first.end) {
+        (Some(_), Some(_)) => {
+            let start_rail = rail(sections.iter().map(|s| s.start.unwrap()).collect());
+            let end_rail = rail(sections.iter().map(|s| s.end.unwrap()).collect());
+            vec![Contour::new(vec![
+                first.flip(),
+                start_rail,
+                last.clone(),
+                end_rail.flip(),
+            ])]
+        }
+        _ => vec![
+            Contour::new(vec![first.flip()]),
+            Contour::new(vec![last.clone()]),
+        ],
+    };
+    Face::new(contours, Rc::new(surface))
+}
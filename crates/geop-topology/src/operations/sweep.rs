@@ -0,0 +1,483 @@
+use geop_geometry::{efloat::EFloat64, point::Point, transforms::Transform};
+
+use crate::topology::{edge::Edge, face::Face, shell::Shell, volume::Volume};
+
+use super::skin::{nurbs_contour, skin_face};
+
+// Curved paths get this many sections per full turn of their tangent. Straight paths get sections only at their ends.
+const SECTIONS_PER_TURN: f64 = 32.0;
+// Samples used to measure how much the tangent of a path edge turns.
+const TURN_SAMPLES: usize = 64;
+// Joints of the path where the tangent turns by more than this get a mitre.
+const MAX_KINK: f64 = 1e-6;
+
+// A point on the path with its frame. The frame is orthonormal and rotates as little as possible along the path.
+#[derive(Clone, Copy)]
+struct Station {
+    point: Point,
+    tangent: Point,
+    normal: Point,
+}
+
+impl Station {
+    fn binormal(&self) -> Point {
+        self.tangent.cross(self.normal)
+    }
+
+    // Rotates the normal around the tangent.
+    fn twist(&self, angle: f64) -> Station {
+        let (sin, cos) = (EFloat64::from(angle.sin()), EFloat64::from(angle.cos()));
+        Station {
+            normal: (self.normal * cos + self.binormal() * sin).mid(),
+            ..*self
+        }
+    }
+}
+
+// Points and tangents of the edge, which are close enough that the sweep in between can be approximated by ruled surfaces.
+fn samples(edge: &Edge) -> Vec<(Point, Point)> {
+    let at = |t: f64| {
+        let p = edge.interpolate(t);
+        (p.mid(), edge.tangent(p).mid())
+    };
+    let mut turn = 0.0;
+    let mut previous = at(0.0).1;
+    for i in 1..=TURN_SAMPLES {
+        let tangent = at(i as f64 / TURN_SAMPLES as f64).1;
+        turn += previous.angle(tangent).unwrap().mid();
+        previous = tangent;
+    }
+    let n = (turn / (2.0 * std::f64::consts::PI) * SECTIONS_PER_TURN)
+        .ceil()
+        .max(1.0) as usize;
+    (0..=n).map(|i| at(i as f64 / n as f64)).collect()
+}
+
+// Moves the frame to the next point with the double reflection method of Wang et al.
+fn next_station(station: &Station, point: Point, tangent: Point) -> Station {
+    let reflect = |v: Point, axis: Point| {
+        let c = axis.norm_sq();
+        match c > 0.0 {
+            true => v - axis * (EFloat64::two() * axis.dot(v) / c).unwrap(),
+            false => v,
+        }
+    };
+    let v1 = point - station.point;
+    let normal = reflect(station.normal, v1);
+    let v2 = tangent - reflect(station.tangent, v1);
+    let normal = reflect(normal, v2);
+    // Remove the rounding errors, so that the frame stays orthonormal.
+    let normal = (normal - tangent * tangent.dot(normal))
+        .normalize()
+        .unwrap();
+    Station {
+        point,
+        tangent,
+        normal: normal.mid(),
+    }
+}
+
+// Turns the frame to a new tangent at the same point with the smallest rotation, which is the frame after a kink of the path.
+fn turn_station(station: &Station, tangent: Point) -> Station {
+    let axis = station.tangent.cross(tangent);
+    let rotate = |v: Point| {
+        let scale = (EFloat64::one() / (EFloat64::one() + station.tangent.dot(tangent))).unwrap();
+        v + axis.cross(v) + axis.cross(axis.cross(v)) * scale
+    };
+    let normal = rotate(station.normal);
+    let normal = (normal - tangent * tangent.dot(normal))
+        .normalize()
+        .unwrap();
+    Station {
+        point: station.point,
+        tangent,
+        normal: normal.mid(),
+    }
+}
+
+// Projects along the tangent of a section onto the plane that halves the kink between this tangent and the tangent on the other side. The sections of both sides of the kink meet in this plane.
+fn mitre_transform(point: Point, incoming: Point, outgoing: Point) -> Transform {
+    let normal = (incoming + outgoing).normalize().unwrap().mid();
+    let scale = (EFloat64::one() / incoming.dot(normal)).unwrap();
+    let (d, m) = (
+        [incoming.x, incoming.y, incoming.z],
+        [normal.x, normal.y, normal.z],
+    );
+    let mut projection = [[EFloat64::zero(); 4]; 4];
+    for i in 0..3 {
+        for j in 0..3 {
+            projection[i][j] = -d[i] * m[j] * scale;
+        }
+        projection[i][i] = projection[i][i] + EFloat64::one();
+    }
+    projection[3][3] = EFloat64::one();
+    Transform::from_translation(point)
+        * Transform { matrix: projection }
+        * Transform::from_translation(-point)
+}
+
+// Moves the profile from the first station to the given station.
+fn station_transform(first: &Station, station: &Station) -> Transform {
+    let from = [first.normal, first.binormal(), first.tangent];
+    let to = [station.normal, station.binormal(), station.tangent];
+    let mut rotation = [[EFloat64::zero(); 4]; 4];
+    for (f, t) in from.iter().zip(to.iter()) {
+        let (f, t) = ([f.x, f.y, f.z], [t.x, t.y, t.z]);
+        for i in 0..3 {
+            for j in 0..3 {
+                rotation[i][j] = rotation[i][j] + t[i] * f[j];
+            }
+        }
+    }
+    rotation[3][3] = EFloat64::one();
+    Transform::from_translation(station.point)
+        * Transform { matrix: rotation }
+        * Transform::from_translation(-first.point)
+}
+
+// Sweeps a planar face along a path of connected edges, e.g. the edges of a contour. The profile keeps its orientation relative to the path.
+// Curved parts of the path are approximated by ruled nurbs surfaces. Where the tangent of the path jumps, the sweep is mitred. Closed paths give a ring without start and end faces.
+pub fn sweep(face: Face, path: &[Edge]) -> Volume {
+    assert!(!path.is_empty(), "Path must not be empty");
+    for i in 1..path.len() {
+        assert!(path[i - 1].end == path[i].start, "Path must be connected");
+    }
+    let closed = path[0].start == path[path.len() - 1].end;
+
+    // Samples of each path edge. A single closed edge is split in two, so that all faces are bounded by four edges.
+    let mut segments: Vec<Vec<(Point, Point)>> = path.iter().map(samples).collect();
+    if segments.len() == 1 && path[0].start.is_none() {
+        let all = segments.pop().unwrap();
+        let n = all.len().max(3) / 2;
+        let all = match all.len() {
+            2 => vec![all[0], all[0], all[1]],
+            _ => all,
+        };
+        segments = vec![all[..=n].to_vec(), all[n..].to_vec()];
+    }
+    // The kinks at the start of each segment, given by the tangent before the kink. The start of a closed path is a joint as well.
+    let kinks: Vec<Option<Point>> = (0..segments.len())
+        .map(|i| {
+            let end = match i {
+                0 if !closed => return None,
+                0 => segments.last().unwrap().last().unwrap().1,
+                _ => segments[i - 1].last().unwrap().1,
+            };
+            let start = segments[i][0].1;
+            let angle = end.angle(start).unwrap().mid();
+            assert!(
+                angle < std::f64::consts::PI - MAX_KINK,
+                "Path must not turn back"
+            );
+            match angle > MAX_KINK {
+                true => Some(end),
+                false => None,
+            }
+        })
+        .collect();
+
+    // Frames along the whole path.
+    let (point, tangent) = segments[0][0];
+    let normal = match tangent.cross(Point::unit_x()).norm() > 0.5 {
+        true => tangent.cross(Point::unit_x()),
+        false => tangent.cross(Point::unit_y()),
+    };
+    let first = Station {
+        point,
+        tangent,
+        normal: normal.normalize().unwrap().mid(),
+    };
+    let mut stations = vec![vec![first]];
+    let mut previous = first;
+    for (i, segment) in segments.iter().enumerate() {
+        if i > 0 {
+            previous = turn_station(&previous, segment[0].1);
+            stations.push(vec![previous]);
+        }
+        for (point, tangent) in segment.iter().skip(1) {
+            previous = next_station(&previous, *point, *tangent);
+            stations[i].push(previous);
+        }
+    }
+
+    // A closed path has to end with the frame it started with. The difference is spread over the path by the arc length.
+    if closed {
+        let last = turn_station(stations.last().unwrap().last().unwrap(), first.tangent);
+        let twist = (last
+            .normal
+            .angle2(first.normal, first.tangent)
+            .unwrap_or(EFloat64::zero()))
+        .mid();
+        let mut lengths = Vec::new();
+        let mut length = 0.0;
+        let mut previous = first.point;
+        for station in stations.iter().flatten() {
+            length += (station.point - previous).norm().mid();
+            lengths.push(length);
+            previous = station.point;
+        }
+        for (station, l) in stations.iter_mut().flatten().zip(lengths) {
+            *station = station.twist(twist * l / length);
+        }
+    }
+
+    // The profile has to point against the path, so that it is on the outside of the volume.
+    let p = face.boundaries[0].edges[0].get_midpoint();
+    let face = match face.normal(p).dot(first.tangent) > 0.0 {
+        true => face.flip(),
+        false => face,
+    };
+    let start_face = Face::new(
+        face.boundaries.iter().map(nurbs_contour).collect(),
+        face.surface.clone(),
+    );
+    let profile = start_face.all_edges();
+    // The sections at a kink are projected along the tangent of their own side.
+    let mitre = |s: usize, incoming_side: bool| -> Transform {
+        let station = &stations[s][0];
+        match (kinks[s], incoming_side) {
+            (Some(incoming), true) => mitre_transform(station.point, incoming, station.tangent),
+            (Some(incoming), false) => mitre_transform(station.point, station.tangent, incoming),
+            (None, _) => Transform::from_translation(Point::zero()),
+        }
+    };
+    let first_sections: Vec<Edge> = profile
+        .iter()
+        .map(|e| e.transform(mitre(0, false)))
+        .collect();
+
+    let mut faces = Vec::<Face>::new();
+    let mut last_sections = first_sections.clone();
+    for (s, segment) in stations.iter().enumerate() {
+        let last_segment = s == stations.len() - 1;
+        let mut sections = vec![last_sections.clone()];
+        for (k, station) in segment.iter().enumerate().skip(1) {
+            // The end of a closed path is exactly the start again.
+            if closed && last_segment && k == segment.len() - 1 {
+                sections.push(first_sections.clone());
+                continue;
+            }
+            let mut transform = station_transform(&first, station);
+            if k == segment.len() - 1 && !last_segment {
+                transform = mitre(s + 1, true) * transform;
+            }
+            sections.push(profile.iter().map(|e| e.transform(transform)).collect());
+        }
+        for i in 0..profile.len() {
+            let edge_sections: Vec<Edge> = sections.iter().map(|s| s[i].clone()).collect();
+            let p = edge_sections[0].get_midpoint();
+            let outwards = edge_sections[0].tangent(p).cross(-segment[0].tangent);
+            faces.push(skin_face(&edge_sections, outwards));
+        }
+        last_sections = sections.pop().unwrap();
+    }
+
+    if !closed {
+        let last = stations.last().unwrap().last().unwrap();
+        faces.push(start_face.transform(station_transform(&first, last)).flip());
+        faces.push(start_face);
+    }
+
+    Volume::new(Shell::new(faces), vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        curves::{curve::Curve, helix::Helix},
+        surfaces::{plane::Plane, surface::Surface},
+    };
+
+    use crate::{
+        primitive_objects::{
+            edges::{arc::primitive_arc, circle::primitive_circle, line::primitive_line},
+            faces::rectangle::primitive_rectangle,
+        },
+        topology::contour::Contour,
+    };
+
+    use super::*;
+
+    fn count_nurbs(volume: &Volume) -> usize {
+        volume
+            .boundary
+            .faces
+            .iter()
+            .filter(|f| matches!(&*f.surface, Surface::Nurbs(_)))
+            .count()
+    }
+
+    #[test]
+    fn test_sweep_line() {
+        let square = primitive_rectangle(
+            Point::zero(),
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::from_f64(0.0, 0.5, 0.0),
+        );
+        let path = primitive_line(Point::zero(), Point::from_f64(0.0, 0.0, 2.0)).unwrap();
+        let volume = sweep(square, &[path]);
+        // Four sides and two caps.
+        assert_eq!(volume.boundary.faces.len(), 6);
+        assert_eq!(count_nurbs(&volume), 4);
+    }
+
+    #[test]
+    fn test_sweep_arc() {
+        // A quarter circle around (0, 0, 4) from the origin in x direction, followed by a line upwards.
+        let arc = primitive_arc(
+            Point::zero(),
+            Point::from_f64(4.0, 0.0, 4.0),
+            EFloat64::from(4.0),
+            -Point::unit_y(),
+        );
+        let line = primitive_line(
+            Point::from_f64(4.0, 0.0, 4.0),
+            Point::from_f64(4.0, 0.0, 6.0),
+        )
+        .unwrap();
+        let square = primitive_rectangle(
+            Point::zero(),
+            Point::from_f64(0.0, 0.5, 0.0),
+            Point::from_f64(0.0, 0.0, 0.5),
+        );
+        let volume = sweep(square, &[arc, line]);
+        // Each edge of the square gives one face along the arc and one along the line.
+        assert_eq!(volume.boundary.faces.len(), 10);
+        assert_eq!(count_nurbs(&volume), 8);
+        // The end face lies at the end of the line and points along it.
+        let end = &volume.boundary.faces[volume.boundary.faces.len() - 2];
+        let p = end.boundaries[0].edges[0].get_midpoint();
+        assert!((p.z.mid() - 6.0).abs() < 1e-9);
+        assert!((end.normal(p) - Point::unit_z()).norm().mid() < 1e-9);
+    }
+
+    #[test]
+    fn test_sweep_corner() {
+        // An L shaped path gives two straight pieces that meet in a mitre.
+        let square = primitive_rectangle(
+            Point::zero(),
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::from_f64(0.0, 0.5, 0.0),
+        );
+        let up = primitive_line(Point::zero(), Point::from_f64(0.0, 0.0, 2.0)).unwrap();
+        let side = primitive_line(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::from_f64(0.0, 2.0, 2.0),
+        )
+        .unwrap();
+        let volume = sweep(square, &[up, side]);
+        assert_eq!(volume.boundary.faces.len(), 10);
+        // The corner of the square that is on the outside of the bend is moved to the mitre plane.
+        let corner = Point::from_f64(0.5, -0.5, 2.5);
+        assert!(volume
+            .boundary
+            .faces
+            .iter()
+            .flat_map(|f| f.all_edges())
+            .any(|e| e.start == Some(corner) || e.end == Some(corner)));
+        // The end face is at the end of the path.
+        let end = &volume.boundary.faces[volume.boundary.faces.len() - 2];
+        let p = end.boundaries[0].edges[0].get_midpoint();
+        assert!((p.y.mid() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sweep_closed_corners() {
+        // A square around a square path gives a frame with four mitred corners.
+        let square = primitive_rectangle(
+            Point::zero(),
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::from_f64(0.0, 0.0, 0.5),
+        );
+        let corners = [
+            Point::zero(),
+            Point::from_f64(0.0, 4.0, 0.0),
+            Point::from_f64(-4.0, 4.0, 0.0),
+            Point::from_f64(-4.0, 0.0, 0.0),
+        ];
+        let path: Vec<Edge> = (0..4)
+            .map(|i| primitive_line(corners[i], corners[(i + 1) % 4]).unwrap())
+            .collect();
+        let volume = sweep(square, &path);
+        assert_eq!(volume.boundary.faces.len(), 16);
+        for face in volume.boundary.faces.iter() {
+            assert_eq!(face.boundaries.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_sweep_helix() {
+        // One turn of radius 4 and pitch 4 around the z axis, starting on the x axis.
+        let helix = Helix::new(
+            Point::zero(),
+            Point::from_f64(0.0, 0.0, 4.0),
+            Point::from_f64(4.0, 0.0, 0.0),
+            true,
+        );
+        let (start, end) = (
+            helix.point_at_pitch(EFloat64::zero()),
+            helix.point_at_pitch(EFloat64::one()),
+        );
+        let path = Edge::new(Some(start), Some(end), Curve::Helix(helix.clone()));
+        let tangent = path.tangent(start).normalize().unwrap();
+        // A rectangle across the path, which is longer in radial direction.
+        let radial = Point::unit_x();
+        let across = tangent.cross(radial).normalize().unwrap();
+        let rectangle = primitive_rectangle(
+            start,
+            radial * EFloat64::from(0.5),
+            across * EFloat64::from(0.25),
+        );
+        let volume = sweep(rectangle, &[path]);
+        assert_eq!(volume.boundary.faces.len(), 6);
+
+        // The end face is centered on the end of the helix and perpendicular to it.
+        let end_face = &volume.boundary.faces[volume.boundary.faces.len() - 2];
+        let midpoints: Vec<Point> = end_face.boundaries[0]
+            .edges
+            .iter()
+            .map(|e| e.get_midpoint())
+            .collect();
+        let center = ((midpoints[0] + midpoints[2]) / EFloat64::two()).unwrap();
+        assert!((center - end).norm().mid() < 1e-6);
+        let p = midpoints[0];
+        assert!((end_face.normal(p) - tangent).norm().mid() < 1e-6);
+
+        // The frame rotates as little as possible, so the profile turns against the radial direction by the torsion times the length of the helix.
+        let (r, c) = (4.0, 4.0 / (2.0 * std::f64::consts::PI));
+        let twist = -2.0 * std::f64::consts::PI * c / (r * r + c * c).sqrt();
+        let long = midpoints
+            .iter()
+            .map(|m| *m - end)
+            .find(|d| (d.norm().mid() - 0.5).abs() < 1e-6)
+            .unwrap();
+        let angle = radial.angle2(long, tangent).unwrap().mid();
+        let angle = (angle - twist).rem_euclid(std::f64::consts::PI);
+        assert!(angle.min(std::f64::consts::PI - angle) < 1e-3);
+    }
+
+    #[test]
+    fn test_sweep_closed() {
+        // A disk around a circle gives a ring without caps.
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::from_f64(3.0, 0.0, 0.0),
+                Point::unit_y(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::from_f64(3.0, 0.0, 0.0),
+                Point::unit_z(),
+                Point::unit_x(),
+            ))),
+        );
+        let path = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::from(3.0));
+        let volume = sweep(disk, &[path]);
+        assert_eq!(count_nurbs(&volume), volume.boundary.faces.len());
+        for face in volume.boundary.faces.iter() {
+            assert_eq!(face.boundaries.len(), 2);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{cone::Cone, surface::Surface},
+};
+
+use crate::topology::face::Face;
+
+pub fn primitive_cone(basis: Point, extend_dir: Point, half_angle: EFloat64) -> Face {
+    Face::new(
+        vec![],
+        Rc::new(Surface::Cone(Cone::new(
+            basis, extend_dir, half_angle, true,
+        ))),
+    )
+}
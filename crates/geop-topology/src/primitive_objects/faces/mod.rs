@@ -1,4 +1,6 @@
+pub mod cone;
 pub mod cylinder;
 pub mod plane;
 pub mod rectangle;
 pub mod sphere;
+pub mod torus;
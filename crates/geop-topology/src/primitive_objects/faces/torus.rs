@@ -0,0 +1,27 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{surface::Surface, torus::Torus},
+};
+
+use crate::topology::face::Face;
+
+pub fn primitive_torus(
+    basis: Point,
+    normal: Point,
+    major_radius: EFloat64,
+    minor_radius: EFloat64,
+) -> Face {
+    Face::new(
+        vec![],
+        Rc::new(Surface::Torus(Torus::new(
+            basis,
+            normal,
+            major_radius,
+            minor_radius,
+            true,
+        ))),
+    )
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display};
 
 use geop_geometry::{curves::CurveLike, point::Point, transforms::Transform};
 
@@ -43,10 +43,7 @@ impl ContourTangent {
         let curve_dir = -curve_dir.normalize().unwrap();
         let tangent1 = -tangent1.normalize().unwrap();
         let tangent2 = tangent2.normalize().unwrap();
-        let det = (tangent1 - curve_dir)
-            .cross(tangent2 - curve_dir)
-            .dot(normal - curve_dir);
-        det > 0.0
+        Point::orient3d(tangent1, tangent2, normal, curve_dir) == Ordering::Greater
     }
 }
 
@@ -191,37 +188,31 @@ impl Contour {
         result
     }
 
+    // Gets the whole contour as a path that starts and ends at the given point.
     pub fn get_subcurve_single_point(&self, point: Point) -> Vec<Edge> {
-        let i = match self.get_edge_index(point) {
-            EdgeIndex::OnEdge(i) => i,
-            EdgeIndex::OnCorner(i, _) => i,
-        };
+        let n = self.edges.len();
         let mut result = Vec::<Edge>::new();
-        if Some(point) != self.edges[i].end {
-            result.push(Edge::new(
-                Some(point.clone()),
-                self.edges[i].end.clone(),
-                self.edges[i].curve.clone(),
-            ));
-        }
-        for j in 1..(self.edges.len() - 1) {
-            let edge = self.edges[(i + j) % self.edges.len()].clone();
-            if edge.end == Some(point) {
-                result.push(edge);
-                break;
+        match self.get_edge_index(point) {
+            EdgeIndex::OnEdge(i) => {
+                result.push(Edge::new(
+                    Some(point.clone()),
+                    self.edges[i].end.clone(),
+                    self.edges[i].curve.clone(),
+                ));
+                for j in 1..n {
+                    result.push(self.edges[(i + j) % n].clone());
+                }
+                result.push(Edge::new(
+                    self.edges[i].start.clone(),
+                    Some(point.clone()),
+                    self.edges[i].curve.clone(),
+                ));
+            }
+            EdgeIndex::OnCorner(i, _) => {
+                for j in 1..=n {
+                    result.push(self.edges[(i + j) % n].clone());
+                }
             }
-            result.push(edge);
-        }
-        if Some(point) != self.edges[(i + self.edges.len() - 1) % self.edges.len()].start {
-            result.push(Edge::new(
-                self.edges[(i + self.edges.len() - 1) % self.edges.len()]
-                    .start
-                    .clone(),
-                Some(point.clone()),
-                self.edges[(i + self.edges.len() - 1) % self.edges.len()]
-                    .curve
-                    .clone(),
-            ));
         }
         result
     }
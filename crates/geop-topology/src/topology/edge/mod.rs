@@ -100,12 +100,81 @@ impl Edge {
         self.curve.interpolate(self.start, self.end, t).unwrap()
     }
 
+    // The arc length of the edge. Closed curves without bounds run once around, other unbounded edges have no length.
     pub fn length(&self) -> Option<EFloat64> {
-        match (self.start, self.end) {
-            (Some(start), Some(end)) => Some(self.curve.distance(start, end).unwrap()),
+        match (self.start, self.end, &self.curve) {
+            (Some(start), Some(end), _) => Some(self.curve.length_between(start, end).unwrap()),
+            (None, None, Curve::Circle(_) | Curve::Ellipse(_)) => {
+                let start = self.interpolate(0.0);
+                let mid = self.interpolate(0.5);
+                Some(
+                    self.curve.length_between(start, mid).unwrap()
+                        + self.curve.length_between(mid, start).unwrap(),
+                )
+            }
+            (_, _, Curve::Nurbs(_)) => Some(
+                self.curve
+                    .length_between(self.interpolate(0.0), self.interpolate(1.0))
+                    .unwrap(),
+            ),
             _ => None,
         }
     }
+
+    // The point at the given arc length from the start of the edge.
+    pub fn point_at_length(&self, length: EFloat64) -> Point {
+        let start = self.start.unwrap_or_else(|| self.interpolate(0.0));
+        self.curve.point_at_length(start, length).unwrap()
+    }
+
+    // n points that are evenly spaced by arc length, including the start and the end.
+    // Unbounded lines and helices are sampled up to the horizon, where interpolate is already uniform.
+    pub fn sample_uniform(&self, n: usize) -> Vec<Point> {
+        assert!(n >= 2);
+        let length = match self.length() {
+            Some(length) => length,
+            None => {
+                return (0..n)
+                    .map(|i| self.interpolate(i as f64 / (n - 1) as f64))
+                    .collect();
+            }
+        };
+        (0..n)
+            .map(|i| match i {
+                0 => self.start.unwrap_or_else(|| self.interpolate(0.0)),
+                _ if i == n - 1 => self.end.unwrap_or_else(|| self.interpolate(1.0)),
+                _ => self.point_at_length(length * EFloat64::from(i as f64 / (n - 1) as f64)),
+            })
+            .collect()
+    }
+
+    // Evenly spaced points, such that the curve deviates from the chords between them by at most the tolerance.
+    pub fn sample_by_chord_tolerance(&self, tolerance: f64) -> Vec<Point> {
+        const MAX_SEGMENTS: usize = 1 << 16;
+        let mut segments = 1;
+        loop {
+            // Every other point is the middle of a chord.
+            let points = self.sample_uniform(2 * segments + 1);
+            let deviation = (0..segments)
+                .map(|i| chord_deviation(points[2 * i], points[2 * i + 2], points[2 * i + 1]))
+                .fold(0.0, f64::max);
+            if deviation <= tolerance || segments >= MAX_SEGMENTS {
+                return points.into_iter().step_by(2).collect();
+            }
+            segments *= 2;
+        }
+    }
+}
+
+// Distance of m from the chord between a and b.
+fn chord_deviation(a: Point, b: Point, m: Point) -> f64 {
+    let chord = b - a;
+    let length_sq = chord.norm_sq().mid();
+    if length_sq == 0.0 {
+        return (m - a).norm().mid();
+    }
+    let t = ((m - a).dot(chord).mid() / length_sq).clamp(0.0, 1.0);
+    (a + chord * EFloat64::from(t) - m).norm().mid()
 }
 
 impl PartialEq for Edge {
@@ -131,6 +200,44 @@ impl Display for Edge {
             ),
             Curve::Ellipse(_) => write!(f, "Ellipse {:?} - {:?}", self.start, self.end),
             Curve::Helix(_) => write!(f, "Helix {:?} - {:?}", self.start, self.end),
+            Curve::Nurbs(_) => write!(f, "Nurbs {:?} - {:?}", self.start, self.end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitive_objects::edges::{
+        circle::primitive_circle, ellipse::primitive_ellipse, line::primitive_line,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_edge_sampling() {
+        let ellipse = primitive_ellipse(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        let length = ellipse.length().unwrap();
+        assert!(length == 9.688448220547675);
+
+        // The samples of the closed ellipse are evenly spaced and return to the start.
+        let points = ellipse.sample_uniform(9);
+        assert!((points[0] - points[8]).norm() < 1e-12);
+        for pair in points.windows(2) {
+            let arc = ellipse.curve.length_between(pair[0], pair[1]).unwrap();
+            assert!((arc - length * EFloat64::from(0.125)).abs() < 1e-8);
         }
+
+        let line = primitive_line(Point::zero(), Point::from_f64(3.0, 0.0, 0.0)).unwrap();
+        assert_eq!(line.sample_by_chord_tolerance(0.01).len(), 2);
+        assert_eq!(line.sample_uniform(4)[1], Point::unit_x());
+
+        // A circle of radius 1 deviates by 1 - cos(pi / n) from n chords, which is below 0.01 from 23 chords on.
+        let circle = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::one());
+        assert_eq!(circle.sample_by_chord_tolerance(0.01).len(), 33);
     }
 }
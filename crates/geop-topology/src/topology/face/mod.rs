@@ -19,6 +19,8 @@ use super::{
     {contour::Contour, edge::Edge},
 };
 
+pub mod pcurve;
+
 #[derive(Clone, Debug)]
 pub struct Face {
     pub boundaries: Vec<Contour>, // Outer boundary is Coutner-clockwise, inner ones are Clockwise. However, each of theh inner ones can also be the outside. So the only condition that applies to them is that they are not self-intersecting.
@@ -216,6 +218,62 @@ impl std::fmt::Display for Face {
                     }
                 }
             }
+            Surface::Cone(c) => {
+                writeln!(f, "Cone at apex = {:?} with extend_dir = {:?}, half_angle = {:?} and normal direction = {:?}", c.basis, c.extend_dir, c.half_angle, c.normal_outwards)?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
+            Surface::Torus(t) => {
+                writeln!(f, "Torus at basis = {:?} with normal = {:?}, major_radius = {:?}, minor_radius = {:?} and normal direction = {:?}", t.basis, t.normal, t.major_radius, t.minor_radius, t.normal_outwards)?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
+            Surface::Ellipsoid(e) => {
+                writeln!(
+                    f,
+                    "Ellipsoid at basis = {:?} with radii = {:?} and normal direction = {:?}",
+                    e.basis, e.radii, e.normal_outwards
+                )?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
+            Surface::EllipticCylinder(c) => {
+                writeln!(f, "Elliptic cylinder at basis = {:?} with extend_dir = {:?}, major_radius = {:?}, minor_radius = {:?} and normal direction = {:?}", c.basis, c.extend_dir, c.major_radius, c.minor_radius, c.normal_outwards)?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
+            Surface::Nurbs(n) => {
+                writeln!(
+                    f,
+                    "Nurbs surface of degree ({}, {}) with {}x{} control points",
+                    n.degree_u,
+                    n.degree_v,
+                    n.control_points.len(),
+                    n.control_points[0].len()
+                )?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
         };
         Ok(())
     }
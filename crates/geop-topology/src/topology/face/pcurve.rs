@@ -0,0 +1,107 @@
+use geop_geometry::{
+    curves::nurbs::Nurbs,
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{parameter_space::SurfaceParameterSpace, SurfaceLike},
+};
+
+use crate::topology::edge::Edge;
+
+use super::Face;
+
+// Shifts x by multiples of the period, such that it is at most half a period away from previous.
+fn continue_periodic(x: EFloat64, previous: EFloat64, period: Option<EFloat64>) -> EFloat64 {
+    match period {
+        Some(period) => {
+            let turns = ((x.mid() - previous.mid()) / period.mid()).round();
+            x - period * EFloat64::from(turns)
+        }
+        None => x,
+    }
+}
+
+impl Face {
+    // The pcurve of an edge on this face, i.e. the image of the edge in the (u, v) parameter space of the surface, as a polyline through the points (u, v, 0).
+    // The edge is sampled evenly, such that its chords deviate by at most the tolerance.
+    // Periodic parameters are continued across the seam, so the pcurve may leave the range of uv. At singular points, u is taken from the neighbouring points.
+    pub fn pcurve(&self, edge: &Edge, tolerance: f64) -> Nurbs {
+        let points = edge.sample_by_chord_tolerance(tolerance);
+        assert!(points.iter().all(|p| self.surface.on_surface(*p)));
+
+        let mut uv: Vec<(EFloat64, EFloat64)> =
+            points.iter().map(|p| self.surface.uv(*p)).collect();
+        let singular: Vec<bool> = points
+            .iter()
+            .map(|p| self.surface.is_uv_singular(*p))
+            .collect();
+        for i in 0..uv.len() {
+            if !singular[i] {
+                continue;
+            }
+            let neighbour = (0..i).rev().chain(i + 1..uv.len()).find(|j| !singular[*j]);
+            if let Some(j) = neighbour {
+                uv[i].0 = uv[j].0;
+            }
+        }
+
+        let (u_period, v_period) = self.surface.uv_periods();
+        for i in 1..uv.len() {
+            uv[i] = (
+                continue_periodic(uv[i].0, uv[i - 1].0, u_period),
+                continue_periodic(uv[i].1, uv[i - 1].1, v_period),
+            );
+        }
+
+        Nurbs::polyline(
+            uv.into_iter()
+                .map(|(u, v)| Point::new(u, v, EFloat64::zero()))
+                .collect(),
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::curves::CurveLike;
+
+    use crate::primitive_objects::{
+        edges::{circle::primitive_circle, line::primitive_line},
+        faces::{cylinder::primitive_cylinder, rectangle::primitive_rectangle},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_pcurve() {
+        // A line on a plane stays a line.
+        let face = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
+        let edge = primitive_line(
+            Point::from_f64(-1.0, 0.5, 0.0),
+            Point::from_f64(1.0, -0.5, 0.0),
+        )
+        .unwrap();
+        let pcurve = face.pcurve(&edge, 0.01);
+        assert_eq!(pcurve.control_points.len(), 2);
+        assert_eq!(pcurve.control_points[0], Point::from_f64(-1.0, 0.5, 0.0));
+
+        // A circle around a cylinder runs once through the seam, increasing u by a full turn at constant v.
+        let face = primitive_cylinder(Point::zero(), Point::unit_z(), EFloat64::one());
+        let edge = primitive_circle(Point::unit_z(), Point::unit_z(), EFloat64::one());
+        let pcurve = face.pcurve(&edge, 0.01);
+        let points = &pcurve.control_points;
+        for pair in points.windows(2) {
+            let du = pair[1].x - pair[0].x;
+            assert!(du > 0.0 && du < 1.0);
+            assert_eq!(pair[1].y, EFloat64::one());
+        }
+        let turn = points[points.len() - 1].x - points[0].x;
+        assert!((turn - EFloat64::two_pi()).abs() < 1e-9);
+        // The start of the pcurve maps back to the start of the circle.
+        let start = pcurve.interpolate(None, None, 0.0).unwrap();
+        assert_eq!(
+            face.surface.point_at_uv(start.x, start.y),
+            edge.interpolate(0.0)
+        );
+    }
+}
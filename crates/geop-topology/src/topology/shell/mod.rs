@@ -59,6 +59,12 @@ impl Shell {
         }
     }
 
+    pub fn flip(&self) -> Shell {
+        Shell {
+            faces: self.faces.iter().map(|f| f.flip()).collect(),
+        }
+    }
+
     pub fn normal(&self, point: Point) -> ShellNormal {
         let mut relevant_faces = Vec::<&Face>::new();
         for face in self.faces.iter() {
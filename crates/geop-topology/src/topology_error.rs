@@ -17,7 +17,7 @@ pub enum TopologyErrorRoot {
 pub enum TopologyError {
     Context {
         message: String,
-        error_scene: Option<TopologyScene>,
+        error_scene: Option<Box<TopologyScene>>,
         inner_error: Box<TopologyError>,
     },
     Root(TopologyErrorRoot),
@@ -40,7 +40,7 @@ impl TopologyError {
     pub fn with_context_scene(self, message: String, error_scene: TopologyScene) -> TopologyError {
         TopologyError::Context {
             message,
-            error_scene: Some(error_scene),
+            error_scene: Some(Box::new(error_scene)),
             inner_error: Box::new(self),
         }
     }
@@ -1,8 +1,11 @@
 #[cfg(test)]
 mod tests {
     use geop_booleans::{
+        difference::volume_volume::volume_volume_difference,
+        intersections::volume_volume::volume_volume_intersection,
         remesh::volume::{volume_split, volume_split_edges, VolumeSplit},
         split_if_necessary::edge_split_face::split_faces_by_edges_if_necessary,
+        union::volume::volume_volume_union,
     };
     use geop_geometry::{efloat::EFloat64, point::Point, transforms::Transform};
     use geop_topology::{
@@ -64,6 +67,22 @@ mod tests {
         (v1, v2)
     }
 
+    fn generate_secene_4() -> (Volume, Volume) {
+        let v1 = primitive_cube(
+            EFloat64::from(1.0),
+            EFloat64::from(1.0),
+            EFloat64::from(1.0),
+        );
+        let v2 = primitive_cube(
+            EFloat64::from(1.0),
+            EFloat64::from(1.0),
+            EFloat64::from(1.0),
+        )
+        .transform(Transform::from_translation(Point::from_f64(0.5, 0.5, 0.5)));
+
+        (v1, v2)
+    }
+
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
@@ -300,4 +319,73 @@ mod tests {
             )
             .await;
     }
+
+    #[rstest]
+    async fn test_volume_union(#[future] renderer: Box<HeadlessRenderer>) {
+        let (volume1, volume2) = generate_secene_4();
+        let volumes = volume_volume_union(&volume1, &volume2);
+        assert!(volumes.len() == 1);
+
+        let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
+        for v in volumes {
+            scene.volumes.push((v, Color::white()));
+        }
+
+        let mut renderer = renderer.await;
+        renderer
+            .render_to_file(
+                &scene,
+                false,
+                false,
+                Point::from_f64(2.0, -4.0, 2.0),
+                std::path::Path::new("src/generated_images/booleans/volume_union.png"),
+            )
+            .await;
+    }
+
+    #[rstest]
+    async fn test_volume_intersection(#[future] renderer: Box<HeadlessRenderer>) {
+        let (volume1, volume2) = generate_secene_4();
+        let volumes = volume_volume_intersection(&volume1, &volume2);
+        assert!(volumes.len() == 1);
+
+        let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
+        for v in volumes {
+            scene.volumes.push((v, Color::white()));
+        }
+
+        let mut renderer = renderer.await;
+        renderer
+            .render_to_file(
+                &scene,
+                false,
+                false,
+                Point::from_f64(2.0, -4.0, 2.0),
+                std::path::Path::new("src/generated_images/booleans/volume_intersection.png"),
+            )
+            .await;
+    }
+
+    #[rstest]
+    async fn test_volume_difference(#[future] renderer: Box<HeadlessRenderer>) {
+        let (volume1, volume2) = generate_secene_4();
+        let volumes = volume_volume_difference(&volume1, &volume2);
+        assert!(volumes.len() == 1);
+
+        let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
+        for v in volumes {
+            scene.volumes.push((v, Color::white()));
+        }
+
+        let mut renderer = renderer.await;
+        renderer
+            .render_to_file(
+                &scene,
+                false,
+                false,
+                Point::from_f64(2.0, -4.0, 2.0),
+                std::path::Path::new("src/generated_images/booleans/volume_difference.png"),
+            )
+            .await;
+    }
 }
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{efloat::EFloat64, point::Point};
+    use geop_rasterize::curvature::rasterize_curvature_comb_into_edges;
+    use geop_topology::{
+        primitive_objects::edges::ellipse::primitive_ellipse,
+        topology::scene::{Color, Scene},
+    };
+    use geop_wgpu::headless_renderer::HeadlessRenderer;
+    use rstest::rstest;
+
+    use crate::tests::renderer;
+
+    #[rstest]
+    async fn test_curvature_comb(#[future] renderer: Box<HeadlessRenderer>) {
+        let ellipse = primitive_ellipse(
+            Point::zero(),
+            Point::unit_y(),
+            Point::unit_x() * EFloat64::from(1.5),
+            Point::unit_z() * EFloat64::from(0.5),
+        );
+
+        let mut scene = Scene::new(
+            vec![],
+            vec![],
+            vec![(ellipse.clone(), Color::white())],
+            vec![],
+        );
+        scene.edges.extend(
+            rasterize_curvature_comb_into_edges(&ellipse, 0.1, 64)
+                .into_iter()
+                .map(|e| (e, Color::red())),
+        );
+
+        renderer
+            .await
+            .render_to_file(
+                &scene,
+                false,
+                false,
+                Point::from_f64(0.0, -3.0, 0.0),
+                std::path::Path::new("src/generated_images/geometry/curvature_comb.png"),
+            )
+            .await;
+    }
+}
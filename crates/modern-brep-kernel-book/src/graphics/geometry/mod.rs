@@ -1,4 +1,5 @@
 pub mod boundingsbox;
+pub mod curvature;
 pub mod geodesics;
 pub mod primitive_curve_intersections;
 pub mod primitive_curves;